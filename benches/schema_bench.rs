@@ -0,0 +1,68 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Performance baselines for NIA schema construction, identification and
+//! encoding.
+
+use aluvm::isa::Instr;
+use aluvm::library::Lib;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rgbstd::vm::RgbIsa;
+use schemata::{nia_lib, nia_schema, NonInflatableAsset};
+use strict_encoding::StrictSerialize;
+
+fn assembly(c: &mut Criterion) {
+    // `nia_lib` caches the assembled library, thus we assemble its instructions
+    // directly
+    let code = nia_lib()
+        .expect("invalid NIA library")
+        .disassemble::<Instr<RgbIsa>>()
+        .expect("invalid NIA library bytecode");
+    c.bench_function("nia_lib", |b| {
+        b.iter(|| Lib::assemble(black_box(&code)).expect("invalid NIA library code"))
+    });
+}
+
+fn construction(c: &mut Criterion) {
+    c.bench_function("nia_schema", |b| b.iter(|| nia_schema().expect("invalid NIA schema")));
+    c.bench_function("nia_bundle", |b| {
+        b.iter(|| NonInflatableAsset::bundle().expect("invalid NIA schema"))
+    });
+}
+
+fn identification(c: &mut Criterion) {
+    let schema = nia_schema().expect("invalid NIA schema");
+    c.bench_function("nia_schema_id", |b| b.iter(|| black_box(&schema).schema_id()));
+}
+
+fn encoding(c: &mut Criterion) {
+    let schema = nia_schema().expect("invalid NIA schema");
+    c.bench_function("nia_schema_strict_encode", |b| {
+        b.iter(|| {
+            black_box(&schema)
+                .to_strict_serialized::<{ u16::MAX as usize }>()
+                .expect("schema exceeds serialization limit")
+        })
+    });
+}
+
+criterion_group!(benches, assembly, construction, identification, encoding);
+criterion_main!(benches);
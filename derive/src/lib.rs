@@ -0,0 +1,142 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derive macros for the RGB schemata.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Error, Expr, Path, Result};
+
+/// Derives `ifaces::IssuerWrapper` implementation from the functions
+/// constructing the schema, its interface implementation and AluVM library.
+///
+/// ```ignore
+/// #[derive(IssuerWrapper)]
+/// #[issuer_wrapper(
+///     iface = Rgb20,
+///     features = rgb20::Features::FIXED,
+///     schema = nia_schema,
+///     iface_impl = nia_rgb20,
+///     lib = nia_lib
+/// )]
+/// pub struct NonInflatableAsset;
+/// ```
+///
+/// The type system defaults to the standard types extended with the interface
+/// library; it can be overridden with a `types = <fn>` argument.
+#[proc_macro_derive(IssuerWrapper, attributes(issuer_wrapper))]
+pub fn derive_issuer_wrapper(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    issuer_wrapper_inner(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Attrs {
+    iface: Option<Path>,
+    features: Option<Expr>,
+    schema: Option<Path>,
+    iface_impl: Option<Path>,
+    lib: Option<Path>,
+    types: Option<Path>,
+}
+
+impl Attrs {
+    fn parse(input: &DeriveInput) -> Result<Self> {
+        let mut attrs = Attrs::default();
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("issuer_wrapper"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let value = meta.value()?;
+                let slot = match &meta.path {
+                    p if p.is_ident("features") => {
+                        attrs.features = Some(value.parse()?);
+                        return Ok(());
+                    }
+                    p if p.is_ident("iface") => &mut attrs.iface,
+                    p if p.is_ident("schema") => &mut attrs.schema,
+                    p if p.is_ident("iface_impl") => &mut attrs.iface_impl,
+                    p if p.is_ident("lib") => &mut attrs.lib,
+                    p if p.is_ident("types") => &mut attrs.types,
+                    _ => return Err(meta.error("unknown issuer_wrapper argument")),
+                };
+                *slot = Some(value.parse()?);
+                Ok(())
+            })?;
+        }
+        Ok(attrs)
+    }
+}
+
+fn required<T>(value: Option<T>, input: &DeriveInput, name: &str) -> Result<T> {
+    value.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            format!("missing `{name}` argument in `#[issuer_wrapper(...)]` attribute"),
+        )
+    })
+}
+
+fn issuer_wrapper_inner(input: DeriveInput) -> Result<TokenStream2> {
+    let attrs = Attrs::parse(&input)?;
+    let iface = required(attrs.iface, &input, "iface")?;
+    let features = required(attrs.features, &input, "features")?;
+    let schema = required(attrs.schema, &input, "schema")?;
+    let iface_impl = required(attrs.iface_impl, &input, "iface_impl")?;
+    let lib = required(attrs.lib, &input, "lib")?;
+    let types = match attrs.types {
+        Some(types) => quote! { #types() },
+        None => quote! {
+            ::rgbstd::stl::StandardTypes::with(
+                <#iface as ::rgbstd::interface::IfaceClass>::stl()
+            ).type_system()
+        },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::ifaces::IssuerWrapper for #ident #ty_generics #where_clause {
+            const FEATURES: <#iface as ::rgbstd::interface::IfaceClass>::Features = #features;
+            type IssuingIface = #iface;
+
+            fn schema() -> ::rgbstd::schema::Schema { #schema() }
+            fn issue_impl() -> ::rgbstd::interface::IfaceImpl { #iface_impl() }
+
+            fn types() -> ::strict_types::TypeSystem { #types }
+
+            fn scripts() -> ::rgbstd::validation::Scripts {
+                let lib = #lib();
+                ::amplify::confinement::Confined::try_from(
+                    ::std::collections::BTreeMap::from([(lib.id(), lib)])
+                )
+                .expect("invalid confinement range")
+            }
+        }
+    })
+}
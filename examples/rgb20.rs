@@ -17,7 +17,7 @@ fn main() {
 
     let contract = Rgb20::testnet::<NonInflatableAsset>("ssi:anonymous","TEST", "Test asset", None, Precision::CentiMicro)
         .expect("invalid contract data")
-        .allocate(Method::TapretFirst, beneficiary, 1_000_000_000_00u64)
+        .allocate(Method::TapretFirst, beneficiary, 100_000_000_000u64)
         .expect("invalid allocations")
         .issue_contract()
         .expect("invalid contract data");
@@ -37,7 +37,6 @@ fn main() {
 
     // Reading contract state through the interface from the stock:
     let contract = stock.contract_iface_class::<Rgb20>(contract_id).unwrap();
-    let contract = Rgb20::from(contract);
     let allocations = contract.fungible("assetOwner", &FilterIncludeAll).unwrap();
     eprintln!("\nThe issued contract data:");
     eprintln!("{}", serde_json::to_string(&contract.spec()).unwrap());
@@ -78,6 +78,5 @@ fn main() {
 
     // Reading contract state through the interface from the stock:
     let contract = stock.contract_iface_class::<Rgb21>(contract_id).unwrap();
-    let contract = Rgb21::from(contract);
     eprintln!("{}", serde_json::to_string(&contract.spec()).unwrap());
 }
@@ -23,7 +23,7 @@ fn main() {
 
     let contract = Rgb25::testnet::<CollectibleFungibleAsset>("ssi:anonymous", "Test asset", Precision::CentiMicro)
         .expect("invalid contract data")
-        .allocate(Method::TapretFirst, beneficiary, 1_000_000_000_00u64.into())
+        .allocate(Method::TapretFirst, beneficiary, 100_000_000_000u64.into())
         .expect("invalid allocations")
         .issue_contract()
         .expect("invalid contract data");
@@ -38,7 +38,6 @@ fn main() {
 
     // Reading contract state through the interface from the stock:
     let contract = stock.contract_iface_class::<Rgb25>(contract_id).unwrap();
-    let contract = Rgb25::from(contract);
     let allocations = contract.fungible("assetOwner", &FilterIncludeAll).unwrap();
     eprintln!("\nThe issued contract data:");
     eprintln!("{}", contract.name());
@@ -0,0 +1,53 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Fuzzes strict decoding of AluVM libraries, checking that any decoded library round-trips:
+//! its strict encoding matches the consumed input and it has the same id once decoded again.
+
+#![no_main]
+
+use aluvm::library::Lib;
+use libfuzzer_sys::fuzz_target;
+use strict_encoding::{StreamReader, StrictDecode, StrictEncode, StrictReader, StrictWriter};
+
+const MAX_LIB_SIZE: usize = u16::MAX as usize * 4;
+
+fuzz_target!(|data: &[u8]| {
+    let mut reader = StrictReader::in_memory::<MAX_LIB_SIZE>(data);
+    let Ok(lib) = Lib::strict_decode(&mut reader) else {
+        return;
+    };
+    let consumed = reader.into_cursor().position() as usize;
+
+    let writer = StrictWriter::in_memory::<MAX_LIB_SIZE>();
+    let encoded = lib
+        .strict_encode(writer)
+        .expect("decoded library can't be encoded")
+        .unbox()
+        .unconfine();
+    assert_eq!(encoded, &data[..consumed]);
+
+    let stream = StreamReader::in_memory::<MAX_LIB_SIZE>(encoded.as_slice());
+    let mut reader = StrictReader::with(stream);
+    let decoded = Lib::strict_decode(&mut reader).expect("encoded library can't be decoded");
+    assert_eq!(decoded.id(), lib.id());
+});
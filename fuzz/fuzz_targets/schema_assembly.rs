@@ -0,0 +1,76 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Fuzzes AluVM assembler used by `nia_lib` and other schema libraries with arbitrary
+//! bytecode.
+//!
+//! The input is decoded into RGB ISA instructions, as if they were produced by `rgbasm!`, which
+//! are then assembled with `Lib::assemble`. Assembling must either succeed or fail with one of
+//! the documented `AssemblerError` variants and never panic; successfully assembled code must
+//! disassemble back into the same instructions.
+//!
+//! `Lib::disassemble` doesn't terminate on bytecode with instructions which don't move the
+//! cursor forward (like a single `0x9C` byte), thus the target decodes instructions itself and
+//! stops at the first such instruction.
+//!
+//! Known finding: with aluvm v0.11.0-beta.6 the bytecode `3D 20 3D` (`ins` byte string
+//! instruction) is assembled back as `3E 20 3D`, which can't be decoded anymore.
+
+#![no_main]
+
+use aluvm::isa::{Bytecode, Instr, InstructionSet};
+use aluvm::library::{AssemblerError, Cursor, Lib, LibSeg, Read};
+use libfuzzer_sys::fuzz_target;
+use rgbstd::vm::RgbIsa;
+
+/// Decodes instructions from the library bytecode, failing on instructions which can't be decoded
+/// or don't move the cursor forward.
+fn disassemble(lib: &Lib) -> Option<Vec<Instr<RgbIsa>>> {
+    let mut instrs = Vec::new();
+    let mut reader = Cursor::with(&lib.code, &lib.data, &lib.libs);
+    while !reader.is_eof() {
+        let pos = reader.pos();
+        instrs.push(Instr::<RgbIsa>::decode(&mut reader).ok()?);
+        if reader.pos() <= pos {
+            return None;
+        }
+    }
+    Some(instrs)
+}
+
+fuzz_target!(|code: &[u8]| {
+    let isae = Instr::<RgbIsa>::isa_ids().to_string();
+    let Ok(lib) = Lib::with(&isae, code.to_vec(), vec![], LibSeg::default()) else {
+        return;
+    };
+    let Some(instrs) = disassemble(&lib) else {
+        return;
+    };
+    match Lib::assemble(&instrs) {
+        Ok(assembled) => {
+            let reassembled =
+                disassemble(&assembled).expect("assembled library can't be disassembled");
+            assert_eq!(reassembled, instrs);
+        }
+        Err(AssemblerError::Bytecode(_)) | Err(AssemblerError::LibSegOverflow(_)) => {}
+    }
+});
@@ -0,0 +1,418 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Machine-readable summary of the operations permitted by a schema, used by
+//! security review tooling.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+use aluvm::isa::{Bytecode, ControlFlowOp, Instr};
+use aluvm::library::{Cursor, Lib, LibId, LibSite, Read};
+use rgbstd::interface::IfaceImpl;
+use rgbstd::schema::{Occurrences, OwnedStateSchema, Schema};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{AssignmentType, GlobalStateType, SchemaId, TransitionType};
+use strict_encoding::FieldName;
+use strict_types::SemId;
+
+/// Audit summary of a schema produced by [`audit_schema`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SchemaAuditReport {
+    pub schema_id: SchemaId,
+    pub global_state_fields: Vec<GlobalStateField>,
+    pub owned_states: Vec<OwnedStateField>,
+    pub transitions: Vec<TransitionAuditEntry>,
+    /// Ids of AluVM libraries with the validation scripts referenced by the
+    /// schema.
+    pub script_library_ids: Vec<LibId>,
+}
+
+/// Global state type defined by a schema.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GlobalStateField {
+    pub id: GlobalStateType,
+    /// Name of the state in the interface implementation, if any.
+    pub name: Option<FieldName>,
+    pub sem_id: SemId,
+    pub max_items: u16,
+    /// Whether the state can be appended after genesis by state transitions or
+    /// extensions.
+    pub mutable: bool,
+}
+
+/// Owned state type defined by a schema.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OwnedStateField {
+    pub id: AssignmentType,
+    /// Name of the state in the interface implementation, if any.
+    pub name: Option<FieldName>,
+    /// State kind: declarative right, fungible, structured or attachment.
+    pub state: OwnedStateSchema,
+}
+
+/// State transition defined by a schema.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TransitionAuditEntry {
+    pub id: TransitionType,
+    /// Name of the transition in the interface implementation, if any.
+    pub name: Option<FieldName>,
+    pub inputs: BTreeMap<AssignmentType, Occurrences>,
+    pub outputs: BTreeMap<AssignmentType, Occurrences>,
+    /// Declarative rights which must be spent by the transition, granting the
+    /// authority to perform it to their holders.
+    pub authority_required: Vec<AssignmentType>,
+    /// Entry point of the validation script, if the transition is validated.
+    pub validator: Option<LibSite>,
+}
+
+/// Summarizes operations permitted by the `schema`, naming state and
+/// transitions with the `iface_impl`.
+pub fn audit_schema(schema: &Schema, iface_impl: &IfaceImpl) -> SchemaAuditReport {
+    let appended = schema
+        .transitions
+        .values()
+        .flat_map(|ts| ts.globals.keys())
+        .chain(
+            schema
+                .extensions
+                .values()
+                .flat_map(|ext| ext.globals.keys()),
+        )
+        .collect::<BTreeSet<_>>();
+
+    let global_state_fields = schema
+        .global_types
+        .iter()
+        .map(|(id, state)| GlobalStateField {
+            id: *id,
+            name: iface_impl.global_name(*id).cloned(),
+            sem_id: state.sem_id,
+            max_items: state.max_items,
+            mutable: appended.contains(id),
+        })
+        .collect();
+
+    let owned_states = schema
+        .owned_types
+        .iter()
+        .map(|(id, state)| OwnedStateField {
+            id: *id,
+            name: iface_impl.assignment_name(*id).cloned(),
+            state: *state,
+        })
+        .collect();
+
+    let transitions = schema
+        .transitions
+        .iter()
+        .map(|(id, ts)| TransitionAuditEntry {
+            id: *id,
+            name: iface_impl.transition_name(*id).cloned(),
+            inputs: ts
+                .inputs
+                .iter()
+                .map(|(ty, occ)| (*ty, occ.clone()))
+                .collect(),
+            outputs: ts
+                .assignments
+                .iter()
+                .map(|(ty, occ)| (*ty, occ.clone()))
+                .collect(),
+            authority_required: ts
+                .inputs
+                .iter()
+                .filter(|(ty, occ)| {
+                    occ.min_value() > 0 &&
+                        schema.owned_types.get(*ty) == Some(&OwnedStateSchema::Declarative)
+                })
+                .map(|(ty, _)| *ty)
+                .collect(),
+            validator: ts.validator,
+        })
+        .collect();
+
+    let script_library_ids = schema
+        .transitions
+        .values()
+        .map(|ts| &ts.validator)
+        .chain(schema.extensions.values().map(|ext| &ext.validator))
+        .chain([&schema.genesis.validator])
+        .flatten()
+        .map(|site| site.lib)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    SchemaAuditReport {
+        schema_id: schema.schema_id(),
+        global_state_fields,
+        owned_states,
+        transitions,
+        script_library_ids,
+    }
+}
+
+/// Subroutine entry point holding an opcode different from the expected one.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct OpcodeMismatch {
+    pub offset: u16,
+    pub expected: u8,
+    pub found: u8,
+}
+
+/// Coverage of AluVM library code by its subroutines produced by
+/// [`script_coverage_analysis`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CoverageReport {
+    /// Offsets of the decoded instructions mapped to their opcodes.
+    pub instructions: BTreeMap<u16, u8>,
+    /// Subroutine entry points holding an unexpected opcode.
+    pub opcode_mismatches: Vec<OpcodeMismatch>,
+    /// Subroutine entry points and jump targets pointing into the middle of a
+    /// multi-byte instruction or beyond the end of the code.
+    pub misaligned: Vec<u16>,
+    /// Code ranges not reachable from any of the subroutine entry points.
+    pub dead_code: Vec<Range<u16>>,
+    /// Offset of the first instruction which can't be decoded; the code
+    /// following it is not analyzed.
+    pub undecodable: Option<u16>,
+}
+
+impl CoverageReport {
+    /// Checks that all subroutines point to the expected instructions and that
+    /// there is no dead or undecodable code.
+    pub fn is_complete(&self) -> bool {
+        self.opcode_mismatches.is_empty() &&
+            self.misaligned.is_empty() &&
+            self.dead_code.is_empty() &&
+            self.undecodable.is_none()
+    }
+}
+
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} instructions decoded", self.instructions.len())?;
+        for mismatch in &self.opcode_mismatches {
+            writeln!(
+                f,
+                "opcode mismatch at 0x{:04X}: expected 0x{:02X}, found 0x{:02X}",
+                mismatch.offset, mismatch.expected, mismatch.found
+            )?;
+        }
+        for offset in &self.misaligned {
+            writeln!(f, "misaligned offset 0x{offset:04X}")?;
+        }
+        for range in &self.dead_code {
+            writeln!(f, "dead code 0x{:04X}..0x{:04X}", range.start, range.end)?;
+        }
+        if let Some(offset) = self.undecodable {
+            writeln!(f, "undecodable code at 0x{offset:04X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Analyzes which parts of the `lib` code are covered by the subroutines
+/// starting at the offsets of `expected_subroutines`, each of which must start
+/// with the given opcode.
+///
+/// The code is decoded instruction by instruction, following jumps within the
+/// library from each of the subroutines to detect the code which is never
+/// executed.
+pub fn script_coverage_analysis(lib: &Lib, expected_subroutines: &[(u16, u8)]) -> CoverageReport {
+    let lib_id = lib.id();
+    let end = lib.code.len() as u16;
+    let mut report = CoverageReport::default();
+
+    let mut flow = BTreeMap::<u16, Vec<u16>>::new();
+    let mut cursor = Cursor::with(&lib.code, &lib.data, &lib.libs);
+    while !cursor.is_eof() {
+        let pos = cursor.offset().0;
+        let Ok(instr) = Instr::<RgbIsa>::decode(&mut cursor) else {
+            report.undecodable = Some(pos);
+            break;
+        };
+        let next = cursor.offset().0;
+        let local = |site: LibSite| Some(site.pos).filter(|_| site.lib == lib_id);
+        let successors = match instr {
+            Instr::ControlFlow(ControlFlowOp::Fail | ControlFlowOp::Ret) => vec![],
+            Instr::ControlFlow(ControlFlowOp::Jmp(to)) => vec![to],
+            Instr::ControlFlow(ControlFlowOp::Jif(to) | ControlFlowOp::Routine(to)) => {
+                vec![to, next]
+            }
+            Instr::ControlFlow(ControlFlowOp::Call(site)) => {
+                local(site).into_iter().chain([next]).collect()
+            }
+            Instr::ControlFlow(ControlFlowOp::Exec(site)) => local(site).into_iter().collect(),
+            _ => vec![next],
+        };
+        report.instructions.insert(pos, instr.instr_byte());
+        flow.insert(pos, successors);
+    }
+
+    for (offset, expected) in expected_subroutines {
+        if let Some(found) = report.instructions.get(offset) {
+            if found != expected {
+                report.opcode_mismatches.push(OpcodeMismatch {
+                    offset: *offset,
+                    expected: *expected,
+                    found: *found,
+                });
+            }
+        }
+    }
+
+    // Reaching the end of the code completes the execution
+    let mut reachable = BTreeSet::new();
+    let mut queue = expected_subroutines
+        .iter()
+        .map(|(offset, _)| *offset)
+        .collect::<Vec<_>>();
+    while let Some(pos) = queue.pop() {
+        match flow.get(&pos) {
+            Some(successors) if reachable.insert(pos) => {
+                queue.extend(successors.iter().filter(|next| **next != end))
+            }
+            Some(_) => {}
+            None => report.misaligned.push(pos),
+        }
+    }
+    report.misaligned.sort_unstable();
+    report.misaligned.dedup();
+
+    let mut dead = None;
+    for pos in report.instructions.keys().copied().chain([end]) {
+        match (reachable.contains(&pos) || pos == end, dead) {
+            (false, None) => dead = Some(pos),
+            (true, Some(start)) => {
+                report.dead_code.push(start..pos);
+                dead = None;
+            }
+            _ => {}
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use aluvm::isa::opcodes::INSTR_PUTA;
+    use ifaces::IssuerWrapper;
+    use rgbstd::vm::opcodes::INSTR_LDG;
+
+    use super::*;
+    use crate::nia::{
+        FN_CFA_GENESIS_OFFSET, FN_NIA_BURN_OFFSET, FN_NIA_FREEZE_OFFSET, FN_NIA_GENESIS_OFFSET,
+        FN_NIA_RENAME_OFFSET, FN_NIA_UNFREEZE_OFFSET,
+    };
+    use crate::{
+        nia_base_lib, nia_iface_impl_with_features, nia_lib, nia_schema_with_features, NiaFeatures,
+        NonInflatableAsset, FN_ZERO_AMOUNT_GUARD_OFFSET, GS_ISSUED_SUPPLY, GS_RENAME_HISTORY,
+        OS_ASSET, OS_FREEZE_RIGHT, TS_FREEZE, TS_TRANSFER,
+    };
+
+    #[test]
+    fn nia_audit() {
+        let report = audit_schema(&NonInflatableAsset::schema(), &NonInflatableAsset::issue_impl());
+        assert_eq!(report.script_library_ids, vec![nia_base_lib().unwrap().id()]);
+        assert!(report
+            .global_state_fields
+            .iter()
+            .all(|field| !field.mutable));
+        let transfer = report
+            .transitions
+            .iter()
+            .find(|ts| ts.id == TS_TRANSFER)
+            .unwrap();
+        assert_eq!(transfer.name, Some(fname!("transfer")));
+        assert_eq!(transfer.inputs.get(&OS_ASSET), Some(&Occurrences::OnceOrMore));
+        assert!(transfer.authority_required.is_empty());
+        let asset = report
+            .owned_states
+            .iter()
+            .find(|state| state.id == OS_ASSET)
+            .unwrap();
+        assert_eq!(asset.name, Some(fname!("assetOwner")));
+    }
+
+    #[test]
+    fn privileged_operations() {
+        let features = NiaFeatures::ALL;
+        let schema = nia_schema_with_features(features).unwrap();
+        let report = audit_schema(&schema, &nia_iface_impl_with_features(features).unwrap());
+        let freeze = report
+            .transitions
+            .iter()
+            .find(|ts| ts.id == TS_FREEZE)
+            .unwrap();
+        assert_eq!(freeze.authority_required, vec![OS_FREEZE_RIGHT]);
+        let global = |id| {
+            report
+                .global_state_fields
+                .iter()
+                .find(|field| field.id == id)
+                .unwrap()
+        };
+        assert!(global(GS_RENAME_HISTORY).mutable);
+        assert!(!global(GS_ISSUED_SUPPLY).mutable);
+    }
+
+    #[test]
+    fn nia_script_coverage() {
+        let report = script_coverage_analysis(&nia_lib().unwrap(), &[
+            (FN_ZERO_AMOUNT_GUARD_OFFSET, INSTR_PUTA),
+            (FN_NIA_BURN_OFFSET, INSTR_PUTA),
+            (FN_NIA_GENESIS_OFFSET, INSTR_PUTA),
+            (FN_NIA_FREEZE_OFFSET, INSTR_PUTA),
+            (FN_NIA_UNFREEZE_OFFSET, INSTR_PUTA),
+            (FN_NIA_RENAME_OFFSET, INSTR_PUTA),
+            (FN_CFA_GENESIS_OFFSET, INSTR_PUTA),
+        ]);
+        assert!(report.is_complete(), "{report}");
+    }
+
+    #[test]
+    fn script_coverage_gaps() {
+        let lib = nia_lib().unwrap();
+        let report = script_coverage_analysis(&lib, &[
+            (FN_ZERO_AMOUNT_GUARD_OFFSET + 1, INSTR_PUTA),
+            (FN_NIA_BURN_OFFSET, INSTR_LDG),
+            (FN_NIA_GENESIS_OFFSET, INSTR_PUTA),
+        ]);
+        assert!(!report.is_complete());
+        assert_eq!(report.misaligned, vec![FN_ZERO_AMOUNT_GUARD_OFFSET + 1]);
+        assert_eq!(report.opcode_mismatches, vec![OpcodeMismatch {
+            offset: FN_NIA_BURN_OFFSET,
+            expected: INSTR_LDG,
+            found: INSTR_PUTA,
+        }]);
+        assert_eq!(
+            report.dead_code.first(),
+            Some(&(FN_ZERO_AMOUNT_GUARD_OFFSET..FN_NIA_BURN_OFFSET))
+        );
+        assert_eq!(report.dead_code.last(), Some(&(FN_NIA_FREEZE_OFFSET..lib.code.len() as u16)));
+        assert!(report.undecodable.is_none());
+        assert!(report.to_string().contains("misaligned offset 0x0001"));
+    }
+}
@@ -0,0 +1,589 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Basket token schema implementing RGB20 fungible assets interface for index
+//! tokens backed by multiple RGB assets.
+//!
+//! The genesis defines the composition of the basket
+//! ([`GS_BASKET_COMPOSITION`]): the list of underlying RGB contracts with their
+//! weights in basis points, which must sum up to 10000. The custodian holding
+//! the mint right ([`OS_BASKET_CUSTODIAN`]) locks the underlying assets on its
+//! UTXOs and issues basket shares ([`OS_BASKET_SHARE`]) with
+//! [`TS_MINT_BASKET`], while holders burn their shares with
+//! [`TS_REDEEM_BASKET`] to get the underlying assets unlocked. Both operations
+//! list the underlying amounts ([`GS_UNDERLYING_AMOUNT`]), and each of them
+//! must be the weight-proportional part of the minted or redeemed shares
+//! ([`BasketComponent::amount`]).
+//!
+//! RGB consensus can't validate the state of other contracts, thus wallets must
+//! check the transfers of the underlying assets in their own contracts. Each
+//! operation also repeats the composition in the underlying amounts, and
+//! wallets must check that it matches the genesis composition (see [consensus
+//! limitations](crate#consensus-limitations)).
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_JMP, INSTR_PUTA};
+use aluvm::isa::{Instr, MoveOp};
+use aluvm::library::{Lib, LibSite};
+use aluvm::reg::{Reg32, RegA};
+use amplify::{ByteArray, Wrapper};
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_CNG, INSTR_LDG, INSTR_PCAS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, ContractId, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    BasisPoints, ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL,
+    GS_TERMS, TS_TRANSFER,
+};
+
+/// Underlying assets of the basket with their weights ([`BasketComponent`]).
+pub const GS_BASKET_COMPOSITION: GlobalStateType = GlobalStateType::with(3800);
+/// Number of basket shares issued by a mint operation.
+pub const GS_MINTED_SHARES: GlobalStateType = GlobalStateType::with(3801);
+/// Number of basket shares burned by a redemption.
+pub const GS_REDEEMED_SHARES: GlobalStateType = GlobalStateType::with(3802);
+/// Amount of an underlying asset locked by a mint or unlocked by a redemption
+/// ([`BasketAllocation`]).
+pub const GS_UNDERLYING_AMOUNT: GlobalStateType = GlobalStateType::with(3803);
+
+/// Fungible allocation of the basket shares (RGB20 `assetOwner`).
+pub const OS_BASKET_SHARE: AssignmentType = AssignmentType::with(5700);
+/// Right of the custodian of the underlying assets to mint basket shares.
+pub const OS_BASKET_CUSTODIAN: AssignmentType = AssignmentType::with(5701);
+
+pub const TS_MINT_BASKET: TransitionType = TransitionType::with(11800);
+pub const TS_REDEEM_BASKET: TransitionType = TransitionType::with(11801);
+
+/// Error raised when the weights of the basket don't sum up to 10000 basis
+/// points, or the underlying amounts aren't proportional to the minted or
+/// redeemed shares.
+pub const ERRNO_INVALID_BASKET: u8 = 50;
+
+pub(crate) const FN_BASKET_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_BASKET_GENESIS_OFFSET: u16 = FN_BASKET_TRANSFER_OFFSET + 4 + 3 + 1 + 1;
+/// Part of the genesis validation checking that the composition weights sum up
+/// to 10000.
+pub(crate) const FN_COMPOSITION_OFFSET: u16 = FN_BASKET_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1;
+pub(crate) const FN_COMPOSITION_LOOP_OFFSET: u16 = FN_COMPOSITION_OFFSET + 4 + 4 + 4 + 4;
+pub(crate) const FN_COMPOSITION_END_OFFSET: u16 =
+    FN_COMPOSITION_LOOP_OFFSET + 3 + 3 + 4 + 3 + 3 + 3 + 3;
+pub const FN_MINT_BASKET_OFFSET: u16 = FN_COMPOSITION_END_OFFSET + 4 + 3 + 1 + 1;
+pub const FN_REDEEM_BASKET_OFFSET: u16 = FN_MINT_BASKET_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1 + 3;
+pub(crate) const FN_PROPORTION_OFFSET: u16 = FN_REDEEM_BASKET_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1;
+pub(crate) const FN_PROPORTION_LOOP_OFFSET: u16 = FN_PROPORTION_OFFSET + 4 + 4 + 4 + 4 + 4;
+
+/// Underlying asset of a basket.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct BasketComponent {
+    /// Id of the RGB contract of the underlying asset.
+    pub contract_id: [u8; 32],
+    /// Part of the basket taken by the asset.
+    pub weight: BasisPoints,
+}
+impl StrictSerialize for BasketComponent {}
+impl StrictDeserialize for BasketComponent {}
+
+impl BasketComponent {
+    fn dumb() -> Self {
+        Self {
+            contract_id: [0u8; 32],
+            weight: BasisPoints::default(),
+        }
+    }
+
+    /// Constructs the component for the asset of `contract_id` contract.
+    pub fn with(contract_id: ContractId, weight: BasisPoints) -> Self {
+        Self {
+            contract_id: contract_id.to_byte_array(),
+            weight,
+        }
+    }
+
+    /// Returns id of the contract of the underlying asset.
+    pub fn contract_id(&self) -> ContractId { ContractId::from_byte_array(self.contract_id) }
+
+    /// Computes the amount of the underlying asset backing a number of basket
+    /// `shares`, which is `shares * weight / 10000`. Returns `None` if the
+    /// amount isn't integer.
+    pub fn amount(&self, shares: u64) -> Option<BasketAllocation> {
+        let value = (shares as u128) * (self.weight.to_inner() as u128);
+        if value % 10000 != 0 {
+            return None;
+        }
+        let amount = u64::try_from(value / 10000).ok()?;
+        Some(BasketAllocation {
+            component: *self,
+            amount,
+        })
+    }
+}
+
+/// Amount of an underlying asset locked or unlocked by a basket operation.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct BasketAllocation {
+    /// Underlying asset, repeating its basket weight.
+    pub component: BasketComponent,
+    /// Amount of the underlying asset.
+    pub amount: u64,
+}
+impl StrictSerialize for BasketAllocation {}
+impl StrictDeserialize for BasketAllocation {}
+
+impl BasketAllocation {
+    fn dumb() -> Self {
+        Self {
+            component: BasketComponent::dumb(),
+            amount: 0,
+        }
+    }
+}
+
+/// Assembles AluVM library with validation scripts used by the basket token
+/// schema.
+// `add` and `mul` macro arms for float registers panic on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn basket_lib() -> Lib {
+    let mut code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_BASKET_SHARE;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_BASKET_SHARE;
+        test;
+
+        // Summing up composition weights into a16[2]
+        put     a8[0],ERRNO_INVALID_BASKET;
+        put     a16[1],32;
+        put     a16[2],0;
+        cng     GS_BASKET_COMPOSITION,a8[2];
+        eq.n    a8[1],a8[2];
+        jif     FN_COMPOSITION_END_OFFSET;
+        ldg     GS_BASKET_COMPOSITION,a8[1],s16[1];
+        // Extract the weight following the 32-byte contract id into a16[3]
+        extr    s16[1],a16[3],a16[1];
+        add.uc  a16[3],a16[2];
+        add     a8[1],1;
+        jmp     FN_COMPOSITION_LOOP_OFFSET;
+        // Checking that the weights sum up to 10000 basis points
+        put     a16[3],10000;
+        eq.n    a16[2],a16[3];
+        test;
+        ret;
+
+        // SUBROUTINE Mint validation
+        // Checking that the issued shares match the minted amount
+        put     a8[0],ERRNO_INVALID_BASKET;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_MINTED_SHARES,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_BASKET_SHARE;
+        test;
+        jmp     FN_PROPORTION_OFFSET;
+
+        // SUBROUTINE Redemption validation
+        // Checking that the burned shares match the redeemed amount
+        put     a8[0],ERRNO_INVALID_BASKET;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_REDEEMED_SHARES,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_BASKET_SHARE;
+        test;
+
+        // SUBROUTINE Proportion check
+        // Checking that each underlying amount multiplied by 10000 is equal to the number of
+        // shares in a64[0] multiplied by the weight, summing up the weights into a16[2]
+        put     a16[1],32;
+        put     a16[2],0;
+        put     a16[4],34;
+        put     a64[3],10000;
+        cng     GS_UNDERLYING_AMOUNT,a8[2];
+        eq.n    a8[1],a8[2];
+        jif     FN_COMPOSITION_END_OFFSET;
+        ldg     GS_UNDERLYING_AMOUNT,a8[1],s16[1];
+        extr    s16[1],a16[3],a16[1];
+        add.uc  a16[3],a16[2];
+        // Extract the amount following the weight into a64[1]
+        extr    s16[1],a64[1],a16[4];
+    };
+    // NB: `rgbasm!` can't copy between registers of different size, thus the
+    // instruction is constructed directly
+    code.push(Instr::Move(MoveOp::CpyA(RegA::A16, Reg32::Reg3, RegA::A64, Reg32::Reg2)));
+    code.extend(rgbasm! {
+        mul.uc  a64[0],a64[2];
+        mul.uc  a64[3],a64[1];
+        eq.n    a64[1],a64[2];
+        test;
+        add     a8[1],1;
+        jmp     FN_PROPORTION_LOOP_OFFSET;
+    });
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong basket token script")
+}
+
+fn basket_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = basket_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_BASKET_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_BASKET_TRANSFER_OFFSET + 4), OS_BASKET_SHARE.to_inner());
+    assert_eq!(code[FN_BASKET_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_BASKET_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_BASKET_GENESIS_OFFSET as usize + 19], INSTR_PCAS);
+    assert_eq!(code[FN_COMPOSITION_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_COMPOSITION_LOOP_OFFSET as usize - 4], INSTR_CNG);
+    assert_eq!(code[FN_COMPOSITION_LOOP_OFFSET as usize + 6], INSTR_LDG);
+    assert_eq!(state_arg(FN_COMPOSITION_LOOP_OFFSET + 6), GS_BASKET_COMPOSITION.to_inner());
+    assert_eq!(code[FN_COMPOSITION_END_OFFSET as usize - 3], INSTR_JMP);
+    assert_eq!(code[FN_COMPOSITION_END_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_MINT_BASKET_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_MINT_BASKET_OFFSET + 12), GS_MINTED_SHARES.to_inner());
+    assert_eq!(code[FN_REDEEM_BASKET_OFFSET as usize - 3], INSTR_JMP);
+    assert_eq!(code[FN_REDEEM_BASKET_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_REDEEM_BASKET_OFFSET + 12), GS_REDEEMED_SHARES.to_inner());
+    assert_eq!(code[FN_REDEEM_BASKET_OFFSET as usize + 19], INSTR_PCPS);
+    assert_eq!(code[FN_PROPORTION_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_PROPORTION_LOOP_OFFSET as usize + 6], INSTR_LDG);
+    assert_eq!(state_arg(FN_PROPORTION_LOOP_OFFSET + 6), GS_UNDERLYING_AMOUNT.to_inner());
+    assert_eq!(code[FN_PROPORTION_LOOP_OFFSET as usize + 16], INSTR_EXTR);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("BasketToken"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_BASKET_COMPOSITION =>
+                GlobalStateSchema::many(types.get("RGBSchemata.BasketComponent")),
+            GS_MINTED_SHARES => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+            GS_REDEEMED_SHARES => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+            GS_UNDERLYING_AMOUNT =>
+                GlobalStateSchema::many(types.get("RGBSchemata.BasketAllocation")),
+        },
+        owned_types: tiny_bmap! {
+            OS_BASKET_SHARE => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_BASKET_CUSTODIAN => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_BASKET_COMPOSITION => Occurrences::OnceOrMore,
+            },
+            assignments: tiny_bmap! {
+                OS_BASKET_SHARE => Occurrences::OnceOrMore,
+                OS_BASKET_CUSTODIAN => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_BASKET_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_BASKET_SHARE => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_BASKET_SHARE => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_BASKET_TRANSFER_OFFSET, alu_id))
+            },
+            TS_MINT_BASKET => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_MINTED_SHARES => Occurrences::Once,
+                    GS_UNDERLYING_AMOUNT => Occurrences::OnceOrMore,
+                },
+                inputs: tiny_bmap! {
+                    OS_BASKET_CUSTODIAN => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_BASKET_SHARE => Occurrences::OnceOrMore,
+                    OS_BASKET_CUSTODIAN => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_MINT_BASKET_OFFSET, alu_id))
+            },
+            TS_REDEEM_BASKET => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_REDEEMED_SHARES => Occurrences::Once,
+                    GS_UNDERLYING_AMOUNT => Occurrences::OnceOrMore,
+                },
+                inputs: tiny_bmap! {
+                    OS_BASKET_SHARE => Occurrences::OnceOrMore
+                },
+                assignments: none!(),
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_REDEEM_BASKET_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn basket_rgb20() -> IfaceImpl {
+    let schema = basket_schema();
+    let iface = Rgb20::iface(BasketTokenIssuer::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_BASKET_COMPOSITION, fname!("basketComposition")),
+            NamedField::with(GS_MINTED_SHARES, fname!("mintedShares")),
+            NamedField::with(GS_REDEEMED_SHARES, fname!("redeemedShares")),
+            NamedField::with(GS_UNDERLYING_AMOUNT, fname!("underlyingAmount")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_BASKET_SHARE, fname!("assetOwner")),
+            NamedField::with(OS_BASKET_CUSTODIAN, fname!("custodianRight")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_MINT_BASKET, fname!("mintBasket")),
+            NamedField::with(TS_REDEEM_BASKET, fname!("redeemBasket")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Index token representing proportional ownership of a basket of RGB assets.
+pub struct BasketTokenIssuer;
+
+impl IssuerWrapper for BasketTokenIssuer {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { basket_schema() }
+    fn issue_impl() -> IfaceImpl { basket_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = basket_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::Amount;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+
+    fn component(id: u8, weight: u16) -> BasketComponent {
+        BasketComponent::with(ContractId::from_byte_array([id; 32]), BasisPoints::from(weight))
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(BasketTokenIssuer::FEATURES);
+        if let Err(err) = basket_rgb20().check(&iface, &basket_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid basket token RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn proportional_amounts() {
+        let btc = component(1, 6000);
+        assert_eq!(btc.contract_id(), ContractId::from_byte_array([1u8; 32]));
+        assert_eq!(btc.amount(500).unwrap().amount, 300);
+        assert_eq!(component(2, 4000).amount(500).unwrap().amount, 200);
+        assert_eq!(btc.amount(1), None);
+        assert_eq!(component(3, 10000).amount(u64::MAX).unwrap().amount, u64::MAX);
+    }
+
+    #[test]
+    fn allocation_layout() {
+        // Scripts read the weight at offset 32 and the amount at offset 34
+        let data = component(1, 6000)
+            .amount(500)
+            .unwrap()
+            .to_strict_serialized::<42>()
+            .unwrap();
+        assert_eq!(data.len(), 42);
+        assert_eq!(&data[32..34], &6000u16.to_le_bytes());
+        assert_eq!(&data[34..], &300u64.to_le_bytes());
+    }
+
+    /// Issues 1000 shares of a basket with the composition of `weights`.
+    fn contract(weights: &[u16]) -> Option<Contract> {
+        let builder = issuer::<BasketTokenIssuer>();
+        let mut builder = fungible_genesis(builder, spec("IDX", "Index"), 1000);
+        for (id, weight) in (1u8..).zip(weights) {
+            builder = builder
+                .add_global_state("basketComposition", component(id, *weight))
+                .unwrap();
+        }
+        let contract = builder
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_rights("custodianRight", seal(1))
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn basket_genesis() {
+        assert!(contract(&[10000]).is_some());
+        assert!(contract(&[6000, 3000, 1000]).is_some());
+        assert!(contract(&[6000, 3000]).is_none());
+        assert!(contract(&[6000, 5000]).is_none());
+    }
+
+    #[test]
+    fn transfer() {
+        let contract = contract(&[6000, 4000]).unwrap();
+        let transfer = |amount: u64| {
+            let transition = spend_genesis::<BasketTokenIssuer>(&contract, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &BasketTokenIssuer::schema(), &transition)
+        };
+
+        let status = transfer(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn basket_mint() {
+        let contract = contract(&[6000, 4000]).unwrap();
+        let mint = |shares: u64, issued: u64, allocations: &[BasketAllocation]| {
+            let mut builder = spend_genesis::<BasketTokenIssuer>(&contract, "mintBasket")
+                .add_global_state("mintedShares", Amount::from(shares))
+                .unwrap();
+            for allocation in allocations {
+                builder = builder
+                    .add_global_state("underlyingAmount", *allocation)
+                    .unwrap();
+            }
+            let transition = builder
+                .add_fungible_state("assetOwner", graph_seal(0), issued)
+                .unwrap()
+                .add_rights("custodianRight", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &BasketTokenIssuer::schema(), &transition)
+        };
+
+        let (btc, eth) = (component(1, 6000), component(2, 4000));
+        let allocations = [btc.amount(500).unwrap(), eth.amount(500).unwrap()];
+        let status = mint(500, 500, &allocations);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(mint(500, 500, &allocations[..1])), Some(ERRNO_INVALID_BASKET));
+        assert_eq!(script_errno(mint(500, 600, &allocations)), Some(ERRNO_INVALID_BASKET));
+        assert_eq!(script_errno(mint(600, 600, &allocations)), Some(ERRNO_INVALID_BASKET));
+        let skewed = BasketAllocation {
+            component: btc,
+            amount: 301,
+        };
+        let status = mint(500, 500, &[skewed, allocations[1]]);
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_BASKET));
+    }
+
+    #[test]
+    fn basket_redeem() {
+        let contract = contract(&[6000, 4000]).unwrap();
+        let redeem = |shares: u64, allocations: &[BasketAllocation]| {
+            let mut builder = spend_genesis::<BasketTokenIssuer>(&contract, "redeemBasket")
+                .add_global_state("redeemedShares", Amount::from(shares))
+                .unwrap();
+            for allocation in allocations {
+                builder = builder
+                    .add_global_state("underlyingAmount", *allocation)
+                    .unwrap();
+            }
+            let transition = builder.complete_transition().unwrap();
+            validate_transition(&contract, &BasketTokenIssuer::schema(), &transition)
+        };
+
+        let (btc, eth) = (component(1, 6000), component(2, 4000));
+        let allocations = [btc.amount(1000).unwrap(), eth.amount(1000).unwrap()];
+        let status = redeem(1000, &allocations);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(redeem(900, &allocations)), Some(ERRNO_INVALID_BASKET));
+        let allocations = [btc.amount(1000).unwrap(), eth.amount(500).unwrap()];
+        assert_eq!(script_errno(redeem(1000, &allocations)), Some(ERRNO_INVALID_BASKET));
+    }
+}
@@ -0,0 +1,255 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prints human-readable details of a schema, read from a file or looked up by
+//! its id among the schemata provided by this crate.
+//!
+//! Usage: `rgb-schema-info <FILE | SCHEMA_ID>`, where the file may be a kit or
+//! a strict-encoded schema, and the schema id may be given in hex, Baid64 or
+//! Bech32m form.
+
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use amplify::num::u24;
+use amplify::{ByteArray, Wrapper};
+use ifaces::{Rgb20, Rgb21, Rgb25};
+use rgbstd::containers::{FileContent, Kit};
+use rgbstd::interface::{IfaceClass, IfaceImpl};
+use rgbstd::schema::{Occurrences, OwnedStateSchema, Schema, SchemaId};
+use rgbstd::stl::{bp_tx_stl, rgb_contract_stl};
+use schemata::{
+    rgb_schemata_stl, AssignmentTypeRegistry, CompactSchemaId, SchemaRegistry,
+    TransitionTypeRegistry,
+};
+use strict_encoding::StrictDeserialize;
+use strict_types::stl::std_stl;
+use strict_types::{SemId, SymbolicSys, SystemBuilder};
+
+const MAX_SCHEMA_LEN: usize = u24::MAX.into_usize();
+
+fn main() -> ExitCode {
+    let Some(arg) = std::env::args().nth(1) else {
+        eprintln!("Usage: rgb-schema-info <FILE | SCHEMA_ID>");
+        return ExitCode::FAILURE;
+    };
+    let schemata = if Path::new(&arg).is_file() {
+        load_file(&arg)
+    } else {
+        lookup(&arg)
+    };
+    match schemata {
+        Ok(schemata) => {
+            let types = known_types();
+            for (schema, iimpl) in schemata {
+                print_schema(&schema, iimpl.as_ref(), &types);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn load_file(path: &str) -> Result<Vec<(Schema, Option<IfaceImpl>)>, String> {
+    if path.ends_with(".rgba") {
+        // NB: rgb-std 0.11.0-beta.6 fails to parse multi-line headers of the armored
+        // kits it produces, thus binary kits should be used instead
+        return Kit::load_armored(path)
+            .map(kit_schemata)
+            .map_err(|err| format!("invalid armored kit '{path}': {err}"));
+    }
+    if let Ok(kit) = Kit::load_file(path) {
+        return Ok(kit_schemata(kit));
+    }
+    Schema::strict_deserialize_from_file::<MAX_SCHEMA_LEN>(path)
+        .map(|schema| vec![(schema, None)])
+        .map_err(|err| format!("'{path}' is neither a kit nor a strict-encoded schema: {err}"))
+}
+
+fn kit_schemata(kit: Kit) -> Vec<(Schema, Option<IfaceImpl>)> {
+    kit.schemata
+        .iter()
+        .map(|schema| {
+            let id = schema.schema_id();
+            let iimpl = kit
+                .iimpls
+                .iter()
+                .find(|iimpl| iimpl.schema_id == id)
+                .cloned();
+            (schema.clone(), iimpl)
+        })
+        .collect()
+}
+
+fn lookup(id: &str) -> Result<Vec<(Schema, Option<IfaceImpl>)>, String> {
+    let id =
+        parse_schema_id(id).ok_or_else(|| format!("'{id}' is neither a file nor a schema id"))?;
+    let registry = SchemaRegistry::with_builtin_schemata();
+    let wrapper = registry
+        .lookup(id)
+        .ok_or_else(|| format!("schema {id} is not provided by this crate"))?;
+    Ok(vec![(wrapper.erased_schema(), Some(wrapper.erased_issue_impl()))])
+}
+
+fn parse_schema_id(s: &str) -> Option<SchemaId> {
+    if s.len() == 64 {
+        let mut id = [0u8; 32];
+        for (pos, byte) in id.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(s.get(pos * 2..pos * 2 + 2)?, 16).ok()?;
+        }
+        return Some(SchemaId::from_byte_array(id));
+    }
+    SchemaId::from_str(s)
+        .ok()
+        .or_else(|| CompactSchemaId::from_str(s).ok().map(SchemaId::from))
+}
+
+/// Type system with the names of all types used by the schemata of this crate.
+fn known_types() -> SymbolicSys {
+    let mut builder = SystemBuilder::new();
+    for lib in [
+        std_stl(),
+        bp_tx_stl(),
+        rgb_contract_stl(),
+        Rgb20::stl(),
+        Rgb21::stl(),
+        Rgb25::stl(),
+        rgb_schemata_stl(),
+    ] {
+        builder = builder.import(lib).expect("invalid standard type library");
+    }
+    builder.finalize().expect("invalid standard type system")
+}
+
+fn type_name(types: &SymbolicSys, sem_id: SemId) -> String {
+    types
+        .lookup(sem_id)
+        .map(|fqn| fqn.to_string())
+        .unwrap_or_else(|| sem_id.to_string())
+}
+
+fn occurrences(occ: &Occurrences) -> String {
+    match (occ.min_value(), occ.max_value()) {
+        (min, u16::MAX) => format!("{min}..*"),
+        (min, max) if min == max => format!("{min}"),
+        (min, max) => format!("{min}..{max}"),
+    }
+}
+
+fn print_schema(schema: &Schema, iimpl: Option<&IfaceImpl>, types: &SymbolicSys) {
+    let id = schema.schema_id();
+    let global = |id| {
+        iimpl
+            .and_then(|iimpl| iimpl.global_name(id))
+            .map(|name| format!("{} {name}", id.to_inner()))
+            .unwrap_or_else(|| id.to_inner().to_string())
+    };
+    // Schemata without interface implementation still get names for the well-known
+    // types
+    let assignments = AssignmentTypeRegistry::with_builtin_types();
+    let transitions = TransitionTypeRegistry::with_builtin_types();
+    let owned = |id| {
+        iimpl
+            .and_then(|iimpl| iimpl.assignment_name(id))
+            .map(|name| name.to_string())
+            .or_else(|| assignments.name(id).map(str::to_owned))
+            .map(|name| format!("{} {name}", id.to_inner()))
+            .unwrap_or_else(|| id.to_inner().to_string())
+    };
+    let transition = |id| {
+        iimpl
+            .and_then(|iimpl| iimpl.transition_name(id))
+            .map(|name| name.to_string())
+            .or_else(|| transitions.name(id).map(str::to_owned))
+            .map(|name| format!("{} {name}", id.to_inner()))
+            .unwrap_or_else(|| id.to_inner().to_string())
+    };
+
+    println!("Schema:       {}", schema.name);
+    println!("Id:           {id}");
+    println!("Hex id:       {id:x}");
+    println!("Compact id:   {}", CompactSchemaId::from(id));
+    println!("Developer:    {}", schema.developer);
+
+    println!("Global state:");
+    for (id, state) in &schema.global_types {
+        let genesis = schema.genesis.globals.get(id).map(occurrences);
+        println!(
+            "  {}: {}, up to {} item(s), {} in genesis",
+            global(*id),
+            type_name(types, state.sem_id),
+            state.max_items,
+            genesis.as_deref().unwrap_or("0")
+        );
+    }
+
+    println!("Owned state:");
+    for (id, state) in &schema.owned_types {
+        let kind = match state {
+            OwnedStateSchema::Declarative => "declarative right".to_owned(),
+            OwnedStateSchema::Fungible(ty) => format!("fungible, {ty}"),
+            OwnedStateSchema::Structured(sem_id) => {
+                format!("structured, {}", type_name(types, *sem_id))
+            }
+            OwnedStateSchema::Attachment(media) => format!("attachment, {media}"),
+        };
+        println!("  {}: {kind}", owned(*id));
+    }
+
+    println!("Transitions:");
+    for (id, ts) in &schema.transitions {
+        println!("  {}:", transition(*id));
+        for (ty, occ) in &ts.inputs {
+            println!("    input   {}: {}", owned(*ty), occurrences(occ));
+        }
+        for (ty, occ) in &ts.assignments {
+            println!("    output  {}: {}", owned(*ty), occurrences(occ));
+        }
+        for (ty, occ) in &ts.globals {
+            println!("    global  {}: {}", global(*ty), occurrences(occ));
+        }
+        match &ts.validator {
+            Some(site) => println!("    validator at {:#06X}", site.pos),
+            None => println!("    not validated"),
+        }
+    }
+
+    println!("Libraries:");
+    let mut libs = schema
+        .transitions
+        .values()
+        .map(|ts| &ts.validator)
+        .chain(schema.extensions.values().map(|ext| &ext.validator))
+        .chain([&schema.genesis.validator])
+        .flatten()
+        .map(|site| site.lib)
+        .collect::<Vec<_>>();
+    libs.sort();
+    libs.dedup();
+    for lib in libs {
+        println!("  {lib}");
+    }
+    println!();
+}
@@ -0,0 +1,563 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bond schema implementing RGB20 fungible assets interface for tokenized
+//! fixed-income instruments with a coupon schedule.
+//!
+//! Each bond is issued as a structured bond note ([`BondNote`]) carrying its
+//! denomination, coupon rate, the number of coupons till maturity and the
+//! number of already paid coupons. The fungible part of the contract is the
+//! settlement asset reserve of the issuer, which is used to pay coupons and
+//! redeem the principal.
+//!
+//! The coupon schedule (coupon interval and maturity height) is committed to
+//! the genesis global state, but the scripts can't check it against block
+//! heights (see [consensus limitations](crate#consensus-limitations)). Instead,
+//! they guarantee that coupons are paid one by one, no more coupons than
+//! scheduled are paid, and a bond is redeemed only once all of its coupons are
+//! paid. Wallets are expected to check the mining height of the payment witness
+//! against the schedule.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_JIF, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use aluvm::reg::Reg32;
+use amplify::Wrapper;
+use bp::Outpoint;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_CNS, INSTR_LDG, INSTR_LDP, INSTR_LDS, INSTR_PCVS};
+use rgbstd::vm::{ContractOp, RgbIsa};
+use rgbstd::{rgbasm, Amount, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, BasisPoints, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PRECISION,
+    GS_TERMS, OS_ASSET, TS_TRANSFER,
+};
+
+/// Principal amount of each bond note.
+pub const GS_FACE_VALUE: GlobalStateType = GlobalStateType::with(2500);
+/// Coupon rate, in basis points of the face value per coupon interval.
+pub const GS_COUPON_RATE: GlobalStateType = GlobalStateType::with(2501);
+/// Block height at which the bonds mature.
+pub const GS_MATURITY_HEIGHT: GlobalStateType = GlobalStateType::with(2502);
+/// Number of blocks between coupon payments.
+pub const GS_COUPON_INTERVAL: GlobalStateType = GlobalStateType::with(2503);
+
+/// Bond note ([`BondNote`]).
+pub const OS_BOND: AssignmentType = AssignmentType::with(4500);
+
+pub const TS_COUPON_PAYMENT: TransitionType = TransitionType::with(10500);
+pub const TS_MATURITY_REDEMPTION: TransitionType = TransitionType::with(10501);
+
+/// Bond note doesn't match the bond terms or the coupon schedule. Not a part of
+/// RGB20 interface errors.
+pub const ERRNO_INVALID_BOND: u8 = 41;
+
+pub(crate) const FN_BOND_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_BOND_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+const FN_BOND_GENESIS_LOOP_OFFSET: u16 =
+    FN_BOND_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1 + 4 + 4 + 3 + 4 + 3 + 4 + 4 + 4 + 4 + 4;
+pub const FN_COUPON_PAYMENT_OFFSET: u16 =
+    FN_BOND_GENESIS_LOOP_OFFSET + 4 + 3 + 4 + 3 + 4 + 3 + 3 + 3 + 3 + 3 + 2;
+pub const FN_MATURITY_REDEMPTION_OFFSET: u16 = FN_COUPON_PAYMENT_OFFSET +
+    4 +
+    3 +
+    1 +
+    4 * 8 +
+    4 +
+    4 +
+    (3 + 3 + 3 + 1) * 2 +
+    3 +
+    3 +
+    3 +
+    3 +
+    1 +
+    3 +
+    3 +
+    1 +
+    1 +
+    1;
+
+/// Bond note, kept as [`OS_BOND`] owned state.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct BondNote {
+    /// Principal amount, equal to [`GS_FACE_VALUE`].
+    pub denomination: Amount,
+    /// Coupon rate, equal to [`GS_COUPON_RATE`].
+    pub coupon_rate: BasisPoints,
+    /// Total number of coupons till maturity.
+    pub coupons: u16,
+    /// Number of coupons already paid.
+    pub paid_coupons: u16,
+    /// Seal of the registered bond holder receiving the payments.
+    pub holder: Outpoint,
+}
+impl StrictSerialize for BondNote {}
+impl StrictDeserialize for BondNote {}
+
+impl BondNote {
+    /// Amount of a single coupon payment.
+    pub fn coupon_amount(&self) -> Amount {
+        Amount::from(
+            (self.denomination.value() as u128 * self.coupon_rate.to_inner() as u128 / 10_000)
+                as u64,
+        )
+    }
+}
+
+/// Assembles AluVM library with validation scripts used by the bond schema.
+// `add` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn bond_lib() -> Lib {
+    let mut code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the issued supply matches the settlement asset allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_ASSET;
+        test;
+        // Checking that all bond notes match the face value, the coupon rate and have no paid
+        // coupons.
+        put     a8[0],ERRNO_INVALID_BOND;
+        // Read face value into a64[0]
+        ldg     GS_FACE_VALUE,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        // Read coupon rate into a16[1]
+        ldg     GS_COUPON_RATE,a8[1],s16[1];
+        extr    s16[1],a16[1],a16[0];
+        put     a16[3],1;
+        // Offsets of the coupon rate and paid coupons within the bond note
+        put     a16[4],8;
+        put     a16[5],12;
+        // Bond note index
+        put     a16[6],0;
+    };
+    // `rgbasm!` has no mnemonic for counting owned state assignments; put their
+    // number to a16[2]
+    code.push(Instr::ExtensionCodes(RgbIsa::Contract(ContractOp::CnS(OS_BOND, Reg32::Reg2))));
+    code.extend(rgbasm! {
+        lds     OS_BOND,a16[6],s16[2];
+        extr    s16[2],a64[1],a16[0];
+        eq.n    a64[0],a64[1];
+        test;
+        extr    s16[2],a16[7],a16[4];
+        eq.n    a16[1],a16[7];
+        test;
+        extr    s16[2],a16[7],a16[5];
+        ifz     a16[7];
+        test;
+        // Move to the next bond note, if any
+        add.uc  a16[3],a16[6];
+        lt.u    a16[6],a16[2];
+        jif     FN_BOND_GENESIS_LOOP_OFFSET;
+        // All bond notes are checked; reset the failed comparison
+        inv     st0;
+        ret;
+
+        // SUBROUTINE Coupon payment validation
+        // Checking that the settlement asset sum is preserved, and the bond note is re-issued
+        // with the same terms and exactly one more paid coupon, not exceeding the scheduled
+        // number of coupons.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_ASSET;
+        test;
+        put     a8[0],ERRNO_INVALID_BOND;
+        put     a16[0],0;
+        // Offsets of the coupon rate, number of coupons and paid coupons within the bond note
+        put     a16[1],8;
+        put     a16[4],10;
+        put     a16[2],12;
+        put     a16[3],1;
+        put     a16[5],0;
+        put     a16[6],0;
+        ldp     OS_BOND,a16[0],s16[0];
+        lds     OS_BOND,a16[0],s16[1];
+        // Compare denominations
+        extr    s16[0],a64[0],a16[0];
+        extr    s16[1],a64[1],a16[0];
+        eq.n    a64[0],a64[1];
+        test;
+        // Compare coupon rates and numbers of coupons
+        extr    s16[0],a32[0],a16[1];
+        extr    s16[1],a32[1],a16[1];
+        eq.n    a32[0],a32[1];
+        test;
+        // Check that only a single coupon is paid
+        extr    s16[0],a16[5],a16[2];
+        extr    s16[1],a16[6],a16[2];
+        add.uc  a16[3],a16[5];
+        eq.n    a16[5],a16[6];
+        test;
+        // Check that the number of paid coupons doesn't exceed the scheduled number
+        extr    s16[1],a16[7],a16[4];
+        gt.u    a16[6],a16[7];
+        inv     st0;
+        test;
+        ret;
+
+        // SUBROUTINE Maturity redemption validation
+        // Checking that the settlement asset sum is preserved, and the redeemed bond note has all
+        // its coupons paid.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_ASSET;
+        test;
+        put     a8[0],ERRNO_INVALID_BOND;
+        put     a16[0],0;
+        put     a16[1],10;
+        put     a16[2],12;
+        ldp     OS_BOND,a16[0],s16[0];
+        extr    s16[0],a16[3],a16[1];
+        extr    s16[0],a16[4],a16[2];
+        eq.n    a16[3],a16[4];
+        test;
+        ret;
+    });
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong bond script")
+}
+
+fn bond_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = bond_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_BOND_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_BOND_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_BOND_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_BOND_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_BOND_GENESIS_OFFSET as usize + 27], INSTR_LDG);
+    assert_eq!(state_arg(FN_BOND_GENESIS_OFFSET + 27), GS_FACE_VALUE.to_inner());
+    assert_eq!(code[FN_BOND_GENESIS_LOOP_OFFSET as usize - 4], INSTR_CNS);
+    assert_eq!(state_arg(FN_BOND_GENESIS_LOOP_OFFSET - 4), OS_BOND.to_inner());
+    assert_eq!(code[FN_BOND_GENESIS_LOOP_OFFSET as usize], INSTR_LDS);
+    assert_eq!(code[FN_BOND_GENESIS_LOOP_OFFSET as usize + 30], INSTR_JIF);
+    assert_eq!(code[FN_COUPON_PAYMENT_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_COUPON_PAYMENT_OFFSET as usize + 40], INSTR_LDP);
+    assert_eq!(state_arg(FN_COUPON_PAYMENT_OFFSET + 40), OS_BOND.to_inner());
+    assert_eq!(code[FN_COUPON_PAYMENT_OFFSET as usize + 48], INSTR_EXTR);
+    assert_eq!(code[FN_MATURITY_REDEMPTION_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_MATURITY_REDEMPTION_OFFSET as usize + 24], INSTR_LDP);
+    assert_eq!(state_arg(FN_MATURITY_REDEMPTION_OFFSET + 24), OS_BOND.to_inner());
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("BondSchema"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_FACE_VALUE => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_COUPON_RATE => GlobalStateSchema::once(types.get("RGBSchemata.BasisPoints")),
+            GS_MATURITY_HEIGHT => GlobalStateSchema::once(types.get("RGBSchemata.BlockHeight")),
+            GS_COUPON_INTERVAL => GlobalStateSchema::once(types.get("RGBSchemata.BlockCount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_BOND => OwnedStateSchema::Structured(types.get("RGBSchemata.BondNote")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_FACE_VALUE => Occurrences::Once,
+                GS_COUPON_RATE => Occurrences::Once,
+                GS_MATURITY_HEIGHT => Occurrences::Once,
+                GS_COUPON_INTERVAL => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_BOND => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_BOND_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_BOND_TRANSFER_OFFSET, alu_id))
+            },
+            TS_COUPON_PAYMENT => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_BOND => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_BOND => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_COUPON_PAYMENT_OFFSET, alu_id))
+            },
+            TS_MATURITY_REDEMPTION => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_BOND => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_MATURITY_REDEMPTION_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn bond_rgb20() -> IfaceImpl {
+    let schema = bond_schema();
+    let iface = Rgb20::iface(BondIssuer::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_FACE_VALUE, fname!("faceValue")),
+            NamedField::with(GS_COUPON_RATE, fname!("couponRate")),
+            NamedField::with(GS_MATURITY_HEIGHT, fname!("maturityHeight")),
+            NamedField::with(GS_COUPON_INTERVAL, fname!("couponInterval")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+            NamedField::with(OS_BOND, fname!("bond")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_COUPON_PAYMENT, fname!("couponPayment")),
+            NamedField::with(TS_MATURITY_REDEMPTION, fname!("maturityRedemption")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Bond issuer.
+///
+/// RGB20 has no dedicated fixed-income features, thus bonds are issued using
+/// the fixed supply RGB20 interface variant for the settlement asset.
+pub struct BondIssuer;
+
+impl IssuerWrapper for BondIssuer {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { bond_schema() }
+    fn issue_impl() -> IfaceImpl { bond_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = bond_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::validation;
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::stl::{BlockCount, BlockHeight};
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(BondIssuer::FEATURES);
+        if let Err(err) = bond_rgb20().check(&iface, &bond_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid bond RGB20 interface implementation");
+        }
+    }
+
+    fn note(denomination: u64, paid_coupons: u16) -> BondNote {
+        BondNote {
+            denomination: Amount::from(denomination),
+            coupon_rate: BasisPoints::from(250),
+            coupons: 8,
+            paid_coupons,
+            holder: Outpoint::strict_dumb(),
+        }
+    }
+
+    /// Bond note which has no more coupons to be paid.
+    fn matured_note() -> BondNote {
+        BondNote {
+            coupons: 0,
+            ..note(1000, 0)
+        }
+    }
+
+    #[test]
+    fn note_layout() {
+        // Scripts read the coupon rate at offset 8, the number of coupons at offset 10
+        // and the number of paid coupons at offset 12
+        let data = note(1000, 3).to_strict_serialized::<64>().unwrap();
+        assert_eq!(&data[..14], &[0xE8, 0x03, 0, 0, 0, 0, 0, 0, 250, 0, 8, 0, 3, 0]);
+        assert_eq!(note(1000, 0).coupon_amount(), Amount::from(25u64));
+    }
+
+    /// Issues bonds with the 1000 face value and the reserve of 10000 units of
+    /// the settlement asset.
+    fn contract(notes: &[BondNote]) -> Option<Contract> {
+        let builder = issuer::<BondIssuer>();
+        let mut builder = fungible_genesis(builder, spec("BOND", "Tokenized bond"), 10_000)
+            .add_global_state("faceValue", Amount::from(1000u64))
+            .unwrap()
+            .add_global_state("couponRate", BasisPoints::from(250))
+            .unwrap()
+            .add_global_state("maturityHeight", BlockHeight::from(1_000_000))
+            .unwrap()
+            .add_global_state("couponInterval", BlockCount::from(26_280))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 10_000u64)
+            .unwrap();
+        for (vout, note) in notes.iter().enumerate() {
+            builder = builder
+                .add_data("bond", seal(vout as u32 + 1), note.clone())
+                .unwrap();
+        }
+        let contract = builder.issue_contract().ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn bond_genesis() {
+        assert!(contract(&[note(1000, 0), note(1000, 0)]).is_some());
+        assert!(contract(&[note(1000, 0), note(500, 0)]).is_none());
+        assert!(contract(&[note(1000, 0), note(1000, 1)]).is_none());
+    }
+
+    /// Pays a coupon of the single bond `note` of the genesis, re-issuing it as
+    /// the `paid` note.
+    fn pay_coupon(note: BondNote, reserve: u64, paid: BondNote) -> validation::Status {
+        let contract = contract(&[note]).unwrap();
+        let transition = spend_genesis::<BondIssuer>(&contract, "couponPayment")
+            .add_fungible_state("assetOwner", graph_seal(0), reserve)
+            .unwrap()
+            .add_data("bond", graph_seal(1), paid)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        validate_transition(&contract, &BondIssuer::schema(), &transition)
+    }
+
+    #[test]
+    fn coupon_payment() {
+        let status = pay_coupon(note(1000, 0), 10_000, note(1000, 1));
+        assert!(status.failures.is_empty(), "{status}");
+
+        let status = pay_coupon(note(1000, 0), 9_000, note(1000, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+        let status = pay_coupon(note(1000, 0), 10_000, note(1000, 2));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_BOND));
+        let status = pay_coupon(note(1000, 0), 10_000, note(500, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_BOND));
+        let paid = BondNote {
+            paid_coupons: 1,
+            ..matured_note()
+        };
+        let status = pay_coupon(matured_note(), 10_000, paid);
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_BOND));
+    }
+
+    #[test]
+    fn maturity_redemption() {
+        let redeem = |note: BondNote, reserve: u64| {
+            let contract = contract(&[note]).unwrap();
+            let transition = spend_genesis::<BondIssuer>(&contract, "maturityRedemption")
+                .add_fungible_state("assetOwner", graph_seal(0), reserve)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &BondIssuer::schema(), &transition)
+        };
+
+        let status = redeem(matured_note(), 10_000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(redeem(matured_note(), 9_000)), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(redeem(note(1000, 0), 10_000)), Some(ERRNO_INVALID_BOND));
+    }
+}
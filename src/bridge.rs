@@ -0,0 +1,543 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token bridge schema implementing RGB20 fungible assets interface for wrapped
+//! assets issued against assets locked on another chain.
+//!
+//! The genesis names the source chain ([`GS_SOURCE_CHAIN`]) and commits to the
+//! public key of the bridge custodian ([`GS_BRIDGE_CUSTODIAN_KEY`]). Once
+//! assets are locked on the source chain, the custodian spends its bridge right
+//! ([`OS_BRIDGE_CUSTODIAN`]) to issue the same amount of wrapped assets
+//! ([`OS_WRAPPED_ASSET`]) with [`TS_WRAP`], providing the proof of the lock
+//! transaction ([`GS_LOCK_TX_PROOF`]). Holders burn wrapped assets with
+//! [`TS_UNWRAP`], which emits a redemption receipt ([`OS_REDEMPTION_RECEIPT`])
+//! for the custodian to release the locked assets on the source chain.
+//!
+//! The script only checks that the wrapped amount matches the lock proof:
+//! neither the custodian signature nor the source chain transactions are
+//! visible to it (see [consensus limitations](crate#consensus-limitations)), so
+//! wallets must check the custodian signature over the proof with
+//! [`LockTxProof::verify`].
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::ascii::AsciiString;
+use amplify::confinement::Confined;
+use amplify::Wrapper;
+use bp::secp256k1::{schnorr, Message, XOnlyPublicKey, SECP256K1};
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDS, INSTR_PCAS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, TransitionType};
+use sha2::{Digest, Sha256};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    IssuerKey, ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL,
+    GS_TERMS, TS_TRANSFER,
+};
+
+/// Identifier of the chain of the locked assets, like "ethereum"
+/// ([`SourceChain`]).
+pub const GS_SOURCE_CHAIN: GlobalStateType = GlobalStateType::with(4300);
+/// Public key of the bridge custodian ([`IssuerKey`]).
+pub const GS_BRIDGE_CUSTODIAN_KEY: GlobalStateType = GlobalStateType::with(4301);
+/// Proof of the source chain transaction locking wrapped assets
+/// ([`LockTxProof`]).
+pub const GS_LOCK_TX_PROOF: GlobalStateType = GlobalStateType::with(4302);
+
+/// Fungible allocation of the wrapped asset (RGB20 `assetOwner`).
+pub const OS_WRAPPED_ASSET: AssignmentType = AssignmentType::with(6200);
+/// Right of the bridge custodian to issue wrapped assets.
+pub const OS_BRIDGE_CUSTODIAN: AssignmentType = AssignmentType::with(6201);
+/// Receipt of the burned wrapped assets to be released on the source chain
+/// ([`RedemptionRequest`]).
+pub const OS_REDEMPTION_RECEIPT: AssignmentType = AssignmentType::with(6202);
+
+pub const TS_WRAP: TransitionType = TransitionType::with(12200);
+pub const TS_UNWRAP: TransitionType = TransitionType::with(12201);
+
+/// Error raised when the wrapped or unwrapped amount is zero or doesn't match
+/// the lock proof or the redemption receipt.
+pub const ERRNO_INVALID_BRIDGE_AMOUNT: u8 = 55;
+
+pub(crate) const FN_BRIDGE_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_BRIDGE_GENESIS_OFFSET: u16 = FN_BRIDGE_TRANSFER_OFFSET + 4 + 3 + 1 + 1;
+pub const FN_WRAP_OFFSET: u16 = FN_BRIDGE_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1 + 1;
+pub const FN_UNWRAP_OFFSET: u16 = FN_WRAP_OFFSET + 4 + 4 + 4 + 4 + 3 + 2 + 1 + 1 + 3 + 1 + 1;
+
+/// Identifier of the source chain of a bridge, consisting of 1 to 32 ASCII
+/// characters.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct SourceChain(Confined<AsciiString, 1, 32>);
+impl StrictSerialize for SourceChain {}
+impl StrictDeserialize for SourceChain {}
+
+impl SourceChain {
+    fn dumb() -> Self { Self::from_str("bitcoin").expect("valid chain identifier") }
+}
+
+/// Error parsing [`SourceChain`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display("invalid source chain identifier '{0}', which must consist of 1 to 32 ASCII characters")]
+pub struct InvalidSourceChain(pub String);
+
+impl FromStr for SourceChain {
+    type Err = InvalidSourceChain;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ascii = AsciiString::from_ascii(s).map_err(|_| InvalidSourceChain(s.to_owned()))?;
+        Confined::try_from(ascii)
+            .map(Self)
+            .map_err(|_| InvalidSourceChain(s.to_owned()))
+    }
+}
+
+impl Display for SourceChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(self.0.as_str()) }
+}
+
+/// Custodian-signed proof of the source chain transaction locking the wrapped
+/// assets.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct LockTxProof {
+    /// Amount of the locked assets, which is the amount of the issued wrapped
+    /// assets.
+    pub amount: u64,
+    /// Hash of the lock transaction on the source chain.
+    pub tx_hash: [u8; 32],
+    /// BIP-340 signature of the custodian key over [`LockTxProof::message`].
+    pub signature: [u8; 64],
+}
+impl StrictSerialize for LockTxProof {}
+impl StrictDeserialize for LockTxProof {}
+
+impl LockTxProof {
+    fn dumb() -> Self {
+        Self {
+            amount: 0,
+            tx_hash: [0u8; 32],
+            signature: [0u8; 64],
+        }
+    }
+
+    /// Computes the message signed by the custodian, which is SHA256 hash of
+    /// the strict-encoded proof without the signature.
+    pub fn message(&self) -> [u8; 32] {
+        let data = self
+            .to_strict_serialized::<104>()
+            .expect("fixed-size proof");
+        Sha256::digest(&data[..data.len() - 64]).into()
+    }
+
+    /// Verifies the signature of the `custodian` key over the proof.
+    pub fn verify(&self, custodian: &IssuerKey) -> bool {
+        let Ok(key) = XOnlyPublicKey::from_slice(&custodian[1..]) else {
+            return false;
+        };
+        let Ok(signature) = schnorr::Signature::from_slice(&self.signature) else {
+            return false;
+        };
+        let message = Message::from_digest(self.message());
+        SECP256K1.verify_schnorr(&signature, &message, &key).is_ok()
+    }
+}
+
+/// Request to release the assets on the source chain, kept as
+/// [`OS_REDEMPTION_RECEIPT`] owned state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct RedemptionRequest {
+    /// Amount of the burned wrapped assets.
+    pub amount: u64,
+    /// Hash of the source chain address receiving the released assets.
+    pub destination: [u8; 32],
+}
+impl StrictSerialize for RedemptionRequest {}
+impl StrictDeserialize for RedemptionRequest {}
+
+/// Assembles AluVM library with validation scripts used by the token bridge
+/// schema.
+pub fn bridge_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_WRAPPED_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_WRAPPED_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Wrap validation
+        // Checking that the issued wrapped assets match the non-zero locked amount
+        put     a8[0],ERRNO_INVALID_BRIDGE_AMOUNT;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_LOCK_TX_PROOF,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        ifz     a64[0];
+        inv     st0;
+        test;
+        pcas    OS_WRAPPED_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Unwrap validation
+        // Checking that the burned wrapped assets match the non-zero amount of the receipt
+        put     a8[0],ERRNO_INVALID_BRIDGE_AMOUNT;
+        put     a16[0],0;
+        lds     OS_REDEMPTION_RECEIPT,a16[0],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        ifz     a64[0];
+        inv     st0;
+        test;
+        pcps    OS_WRAPPED_ASSET;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong token bridge script")
+}
+
+fn bridge_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = bridge_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_BRIDGE_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_BRIDGE_TRANSFER_OFFSET + 4), OS_WRAPPED_ASSET.to_inner());
+    assert_eq!(code[FN_BRIDGE_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_BRIDGE_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_WRAP_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_WRAP_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_WRAP_OFFSET + 12), GS_LOCK_TX_PROOF.to_inner());
+    assert_eq!(code[FN_WRAP_OFFSET as usize + 23], INSTR_PCAS);
+    assert_eq!(code[FN_UNWRAP_OFFSET as usize + 8], INSTR_LDS);
+    assert_eq!(state_arg(FN_UNWRAP_OFFSET + 8), OS_REDEMPTION_RECEIPT.to_inner());
+    assert_eq!(code[FN_UNWRAP_OFFSET as usize + 12], INSTR_EXTR);
+    assert_eq!(code[FN_UNWRAP_OFFSET as usize + 19], INSTR_PCPS);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("TokenBridge"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_SOURCE_CHAIN => GlobalStateSchema::once(types.get("RGBSchemata.SourceChain")),
+            GS_BRIDGE_CUSTODIAN_KEY => GlobalStateSchema::once(types.get("RGBSchemata.IssuerKey")),
+            GS_LOCK_TX_PROOF => GlobalStateSchema::many(types.get("RGBSchemata.LockTxProof")),
+        },
+        owned_types: tiny_bmap! {
+            OS_WRAPPED_ASSET => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_BRIDGE_CUSTODIAN => OwnedStateSchema::Declarative,
+            OS_REDEMPTION_RECEIPT =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.RedemptionRequest")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_SOURCE_CHAIN => Occurrences::Once,
+                GS_BRIDGE_CUSTODIAN_KEY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_WRAPPED_ASSET => Occurrences::OnceOrMore,
+                OS_BRIDGE_CUSTODIAN => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_BRIDGE_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_WRAPPED_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_WRAPPED_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_BRIDGE_TRANSFER_OFFSET, alu_id))
+            },
+            TS_WRAP => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_LOCK_TX_PROOF => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_BRIDGE_CUSTODIAN => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_WRAPPED_ASSET => Occurrences::OnceOrMore,
+                    OS_BRIDGE_CUSTODIAN => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_WRAP_OFFSET, alu_id))
+            },
+            TS_UNWRAP => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_WRAPPED_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_REDEMPTION_RECEIPT => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_UNWRAP_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn bridge_rgb20() -> IfaceImpl {
+    let schema = bridge_schema();
+    let iface = Rgb20::iface(TokenBridgeSchema::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_SOURCE_CHAIN, fname!("sourceChain")),
+            NamedField::with(GS_BRIDGE_CUSTODIAN_KEY, fname!("bridgeCustodianKey")),
+            NamedField::with(GS_LOCK_TX_PROOF, fname!("lockTxProof")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_WRAPPED_ASSET, fname!("assetOwner")),
+            NamedField::with(OS_BRIDGE_CUSTODIAN, fname!("custodianRight")),
+            NamedField::with(OS_REDEMPTION_RECEIPT, fname!("redemptionReceipt")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_WRAP, fname!("wrap")),
+            NamedField::with(TS_UNWRAP, fname!("unwrap")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Wrapped asset issued by a bridge custodian against assets locked on another
+/// chain.
+pub struct TokenBridgeSchema;
+
+impl IssuerWrapper for TokenBridgeSchema {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { bridge_schema() }
+    fn issue_impl() -> IfaceImpl { bridge_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = bridge_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bp::secp256k1::Keypair;
+    use rgbstd::containers::Contract;
+    use rgbstd::invoice::Precision;
+    use rgbstd::stl::AssetSpec;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spend_genesis,
+        validate_transition,
+    };
+
+    fn proof(amount: u64) -> LockTxProof {
+        LockTxProof {
+            amount,
+            tx_hash: [7u8; 32],
+            signature: [0u8; 64],
+        }
+    }
+
+    /// Issues 1000 wrapped assets together with the custodian right.
+    fn contract() -> Contract {
+        let builder = issuer::<TokenBridgeSchema>();
+        fungible_genesis(builder, AssetSpec::new("WETH", "Wrapped Ether", Precision::Milli), 1000)
+            .add_global_state("sourceChain", SourceChain::from_str("ethereum").unwrap())
+            .unwrap()
+            .add_global_state("bridgeCustodianKey", IssuerKey::from([2u8; 33]))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_rights("custodianRight", seal(1))
+            .unwrap()
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(TokenBridgeSchema::FEATURES);
+        if let Err(err) = bridge_rgb20().check(&iface, &bridge_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid token bridge RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn lock_proof_signature() {
+        let keypair = Keypair::from_seckey_slice(SECP256K1, &[1u8; 32]).unwrap();
+        let custodian = IssuerKey::from(keypair.public_key().serialize());
+        let mut proof = proof(1000);
+        let message = Message::from_digest(proof.message());
+        proof.signature = *SECP256K1
+            .sign_schnorr_no_aux_rand(&message, &keypair)
+            .as_ref();
+        assert!(proof.verify(&custodian));
+
+        let other = Keypair::from_seckey_slice(SECP256K1, &[2u8; 32]).unwrap();
+        assert!(!proof.verify(&IssuerKey::from(other.public_key().serialize())));
+        proof.amount = 2000;
+        assert!(!proof.verify(&custodian));
+    }
+
+    #[test]
+    fn source_chain() {
+        assert_eq!(SourceChain::from_str("ethereum").unwrap().to_string(), "ethereum");
+        assert!(SourceChain::from_str("").is_err());
+        assert!(SourceChain::from_str(&"x".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn bridge_genesis() { contract(); }
+
+    #[test]
+    fn transfer() {
+        let contract = contract();
+        let transfer = |amount: u64| {
+            let transition = spend_genesis::<TokenBridgeSchema>(&contract, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &TokenBridgeSchema::schema(), &transition)
+        };
+
+        let status = transfer(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn bridge_wrap() {
+        let contract = contract();
+        let wrap = |locked: u64, wrapped: u64| {
+            let transition = spend_genesis::<TokenBridgeSchema>(&contract, "wrap")
+                .add_global_state("lockTxProof", proof(locked))
+                .unwrap()
+                .add_fungible_state("assetOwner", graph_seal(0), wrapped)
+                .unwrap()
+                .add_rights("custodianRight", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &TokenBridgeSchema::schema(), &transition)
+        };
+
+        let status = wrap(500, 500);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(wrap(500, 499)), Some(ERRNO_INVALID_BRIDGE_AMOUNT));
+        assert_eq!(script_errno(wrap(0, 0)), Some(ERRNO_INVALID_BRIDGE_AMOUNT));
+    }
+
+    #[test]
+    fn bridge_unwrap() {
+        let contract = contract();
+        let unwrap = |amount: u64| {
+            let receipt = RedemptionRequest {
+                amount,
+                destination: [9u8; 32],
+            };
+            let transition = spend_genesis::<TokenBridgeSchema>(&contract, "unwrap")
+                .add_data("redemptionReceipt", graph_seal(0), receipt)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &TokenBridgeSchema::schema(), &transition)
+        };
+
+        let status = unwrap(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(unwrap(999)), Some(ERRNO_INVALID_BRIDGE_AMOUNT));
+        assert_eq!(script_errno(unwrap(0)), Some(ERRNO_INVALID_BRIDGE_AMOUNT));
+    }
+}
@@ -0,0 +1,451 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Schema-specific construction of contract genesis, validating the schema
+//! invariants before the genesis is issued.
+
+use ifaces::rgb21::TokenData;
+use ifaces::{IssuerWrapper, Rgb20, Rgb21};
+use rgbstd::containers::{BuilderSeal, ValidContract};
+use rgbstd::interface::{BuilderError, ContractBuilder, IfaceClass};
+use rgbstd::stl::{AssetSpec, ContractTerms};
+use rgbstd::{Allocation, Amount, GenesisSeal, Identity};
+
+use crate::{
+    check_allocation_sum, ContractInflatableAsset, IssuerKey, NiaGenesisError, NiaIssuanceParams,
+    NiaSchemaError, OverflowError, UniqueDigitalAsset,
+};
+
+/// Seal receiving a genesis allocation, which may be concealed.
+pub type BlindedSeal = BuilderSeal<GenesisSeal>;
+
+/// Errors building contract genesis with [`GenesisBuilder::build`].
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum GenesisBuilderError {
+    /// asset specification is not provided.
+    MissingNominal,
+
+    /// contract terms are not provided.
+    MissingTerms,
+
+    /// issuer public key is not provided.
+    MissingIssuerKey,
+
+    /// genesis has no asset allocations.
+    NoAllocations,
+
+    /// {0}
+    #[from]
+    Overflow(OverflowError),
+
+    /// asset allocations sum up to {allocated} while the issued supply is
+    /// {issued}.
+    SupplyMismatch { issued: u64, allocated: u64 },
+
+    /// issued supply {issued} exceeds the maximum supply {max}.
+    ExceedsMaxSupply { issued: u64, max: u64 },
+
+    /// unique token must be allocated to a single seal with the amount of 1.
+    InvalidTokenAllocation,
+
+    /// {0}
+    #[from]
+    Builder(BuilderError),
+
+    /// {0}
+    #[from]
+    Schema(NiaSchemaError),
+}
+
+impl From<NiaGenesisError> for GenesisBuilderError {
+    fn from(err: NiaGenesisError) -> Self {
+        match err {
+            NiaGenesisError::Overflow(err) => Self::Overflow(err),
+            NiaGenesisError::SupplyMismatch { issued, allocated } => {
+                Self::SupplyMismatch { issued, allocated }
+            }
+            NiaGenesisError::Builder(err) => Self::Builder(err),
+            NiaGenesisError::Schema(err) => Self::Schema(err),
+        }
+    }
+}
+
+/// Contract genesis produced by a [`GenesisBuilder`], which passed the
+/// schema-specific checks.
+#[derive(Clone, Debug)]
+pub struct GenesisData(ContractBuilder);
+
+impl GenesisData {
+    /// Returns contract builder with the genesis state, which can be extended
+    /// with data not covered by the genesis builder.
+    pub fn into_builder(self) -> ContractBuilder { self.0 }
+
+    /// Issues the contract.
+    // Error type is the same as returned by the `ContractBuilder` methods
+    #[allow(clippy::result_large_err)]
+    pub fn issue_contract(self) -> Result<ValidContract, BuilderError> { self.0.issue_contract() }
+}
+
+/// Builder of contract genesis parameters for a specific schema.
+pub trait GenesisBuilder: Sized {
+    /// Sets asset specification (RGB20 and RGB21 `spec`).
+    fn with_nominal(self, spec: AssetSpec) -> Self;
+
+    /// Sets contract terms (`terms`).
+    fn with_terms(self, terms: ContractTerms) -> Self;
+
+    /// Adds asset allocation to the `seal`.
+    fn with_allocation(self, seal: BlindedSeal, amount: u64) -> Self;
+
+    /// Checks the schema invariants and constructs the genesis.
+    // Error type wraps the one returned by the `ContractBuilder` methods
+    #[allow(clippy::result_large_err)]
+    fn build(self) -> Result<GenesisData, GenesisBuilderError>;
+}
+
+/// Genesis builder for [`crate::NonInflatableAsset`] contracts.
+///
+/// Checks that the allocations sum up to the issued supply.
+#[derive(Clone, Debug)]
+pub struct NiaGenesisBuilder {
+    issuer: Identity,
+    spec: Option<AssetSpec>,
+    terms: Option<ContractTerms>,
+    issued_supply: u64,
+    allocations: Vec<(BlindedSeal, u64)>,
+}
+
+impl NiaGenesisBuilder {
+    pub fn new(issuer: Identity, issued_supply: u64) -> Self {
+        Self {
+            issuer,
+            spec: None,
+            terms: None,
+            issued_supply,
+            allocations: vec![],
+        }
+    }
+}
+
+impl GenesisBuilder for NiaGenesisBuilder {
+    fn with_nominal(mut self, spec: AssetSpec) -> Self {
+        self.spec = Some(spec);
+        self
+    }
+
+    fn with_terms(mut self, terms: ContractTerms) -> Self {
+        self.terms = Some(terms);
+        self
+    }
+
+    fn with_allocation(mut self, seal: BlindedSeal, amount: u64) -> Self {
+        self.allocations.push((seal, amount));
+        self
+    }
+
+    fn build(self) -> Result<GenesisData, GenesisBuilderError> {
+        if self.allocations.is_empty() {
+            return Err(GenesisBuilderError::NoAllocations);
+        }
+        let params = NiaIssuanceParams {
+            spec: self.spec.ok_or(GenesisBuilderError::MissingNominal)?,
+            terms: self.terms.ok_or(GenesisBuilderError::MissingTerms)?,
+            issued_supply: Amount::from(self.issued_supply),
+            max_supply: None,
+            website: None,
+            description: None,
+        };
+        let builder = params.genesis_builder(self.issuer, &self.allocations)?;
+        Ok(GenesisData(builder))
+    }
+}
+
+/// Genesis builder for [`ContractInflatableAsset`] contracts.
+///
+/// Checks that the allocations sum up to the issued supply, which doesn't
+/// exceed the maximum supply.
+#[derive(Clone, Debug)]
+pub struct CiaGenesisBuilder {
+    issuer: Identity,
+    spec: Option<AssetSpec>,
+    terms: Option<ContractTerms>,
+    issuer_key: Option<IssuerKey>,
+    issued_supply: u64,
+    max_supply: u64,
+    allocations: Vec<(BlindedSeal, u64)>,
+}
+
+impl CiaGenesisBuilder {
+    pub fn new(issuer: Identity, issued_supply: u64, max_supply: u64) -> Self {
+        Self {
+            issuer,
+            spec: None,
+            terms: None,
+            issuer_key: None,
+            issued_supply,
+            max_supply,
+            allocations: vec![],
+        }
+    }
+
+    /// Sets the public key of the issuer, authorized to issue more assets.
+    pub fn with_issuer_key(mut self, key: IssuerKey) -> Self {
+        self.issuer_key = Some(key);
+        self
+    }
+}
+
+impl GenesisBuilder for CiaGenesisBuilder {
+    fn with_nominal(mut self, spec: AssetSpec) -> Self {
+        self.spec = Some(spec);
+        self
+    }
+
+    fn with_terms(mut self, terms: ContractTerms) -> Self {
+        self.terms = Some(terms);
+        self
+    }
+
+    fn with_allocation(mut self, seal: BlindedSeal, amount: u64) -> Self {
+        self.allocations.push((seal, amount));
+        self
+    }
+
+    fn build(self) -> Result<GenesisData, GenesisBuilderError> {
+        let spec = self.spec.ok_or(GenesisBuilderError::MissingNominal)?;
+        let terms = self.terms.ok_or(GenesisBuilderError::MissingTerms)?;
+        let issuer_key = self
+            .issuer_key
+            .ok_or(GenesisBuilderError::MissingIssuerKey)?;
+        let issued = self.issued_supply;
+        let max = self.max_supply;
+        if issued > max {
+            return Err(GenesisBuilderError::ExceedsMaxSupply { issued, max });
+        }
+        let allocated = check_allocation_sum(&self.allocations)?;
+        if allocated != issued {
+            return Err(GenesisBuilderError::SupplyMismatch { issued, allocated });
+        }
+
+        let mut builder = ContractBuilder::with(
+            self.issuer,
+            Rgb20::iface(ContractInflatableAsset::FEATURES),
+            ContractInflatableAsset::schema(),
+            ContractInflatableAsset::issue_impl(),
+            ContractInflatableAsset::types(),
+            ContractInflatableAsset::scripts(),
+        )
+        .add_global_state("spec", spec)?
+        .add_global_state("terms", terms)?
+        .add_global_state("issuedSupply", Amount::from(issued))?
+        .add_global_state("maxSupply", Amount::from(max))?
+        .add_global_state("issuerKey", issuer_key)?;
+        for (seal, amount) in self.allocations {
+            builder = builder.add_fungible_state("assetOwner", seal, amount)?;
+        }
+        Ok(GenesisData(builder))
+    }
+}
+
+/// Genesis builder for [`UniqueDigitalAsset`] contracts.
+///
+/// Checks that the token is allocated to a single seal as a whole.
+#[derive(Clone, Debug)]
+pub struct UdaGenesisBuilder {
+    issuer: Identity,
+    spec: Option<AssetSpec>,
+    terms: Option<ContractTerms>,
+    token: TokenData,
+    allocations: Vec<(BlindedSeal, u64)>,
+}
+
+impl UdaGenesisBuilder {
+    pub fn new(issuer: Identity, token: TokenData) -> Self {
+        Self {
+            issuer,
+            spec: None,
+            terms: None,
+            token,
+            allocations: vec![],
+        }
+    }
+}
+
+impl GenesisBuilder for UdaGenesisBuilder {
+    fn with_nominal(mut self, spec: AssetSpec) -> Self {
+        self.spec = Some(spec);
+        self
+    }
+
+    fn with_terms(mut self, terms: ContractTerms) -> Self {
+        self.terms = Some(terms);
+        self
+    }
+
+    fn with_allocation(mut self, seal: BlindedSeal, amount: u64) -> Self {
+        self.allocations.push((seal, amount));
+        self
+    }
+
+    fn build(self) -> Result<GenesisData, GenesisBuilderError> {
+        let spec = self.spec.ok_or(GenesisBuilderError::MissingNominal)?;
+        let terms = self.terms.ok_or(GenesisBuilderError::MissingTerms)?;
+        let seal = match self.allocations.as_slice() {
+            [] => return Err(GenesisBuilderError::NoAllocations),
+            [(seal, 1)] => *seal,
+            _ => return Err(GenesisBuilderError::InvalidTokenAllocation),
+        };
+
+        let index = self.token.index;
+        let builder = ContractBuilder::with(
+            self.issuer,
+            Rgb21::iface(UniqueDigitalAsset::FEATURES),
+            UniqueDigitalAsset::schema(),
+            UniqueDigitalAsset::issue_impl(),
+            UniqueDigitalAsset::types(),
+            UniqueDigitalAsset::scripts(),
+        )
+        .add_global_state("spec", spec)?
+        .add_global_state("terms", terms)?
+        .add_global_state("tokens", self.token)?
+        .add_data("assetOwner", seal, Allocation::with(index, 1))?;
+        Ok(GenesisData(builder))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use amplify::Wrapper;
+    use bp::dbc::Method;
+    use bp::Txid;
+    use rgbstd::invoice::Precision;
+    use rgbstd::stl::RicardianContract;
+    use rgbstd::{TokenIndex, XChain};
+
+    use super::*;
+
+    fn seal(vout: u32) -> BlindedSeal {
+        let txid =
+            Txid::from_str("14295d5bb1a191cdb6286dc0944df938421e3dfcbf0811353ccac4100c2068c5")
+                .unwrap();
+        BuilderSeal::from(XChain::Bitcoin(GenesisSeal::new_random(Method::TapretFirst, txid, vout)))
+    }
+
+    fn with_params<B: GenesisBuilder>(builder: B) -> B {
+        builder
+            .with_nominal(AssetSpec::new("TEST", "Test asset", Precision::Indivisible))
+            .with_terms(ContractTerms {
+                text: RicardianContract::default(),
+                media: None,
+            })
+    }
+
+    #[test]
+    fn nia_genesis() {
+        let builder = || with_params(NiaGenesisBuilder::new(Identity::default(), 1_000));
+        builder()
+            .with_allocation(seal(0), 400)
+            .with_allocation(seal(1), 600)
+            .build()
+            .unwrap()
+            .issue_contract()
+            .unwrap();
+        assert!(matches!(
+            builder().with_allocation(seal(0), 999).build(),
+            Err(GenesisBuilderError::SupplyMismatch {
+                issued: 1_000,
+                allocated: 999
+            })
+        ));
+        assert!(matches!(builder().build(), Err(GenesisBuilderError::NoAllocations)));
+        assert!(matches!(
+            NiaGenesisBuilder::new(Identity::default(), 1_000)
+                .with_allocation(seal(0), 1_000)
+                .build(),
+            Err(GenesisBuilderError::MissingNominal)
+        ));
+    }
+
+    #[test]
+    fn cia_genesis() {
+        let builder = |issued, max| {
+            with_params(CiaGenesisBuilder::new(Identity::default(), issued, max))
+                .with_issuer_key(IssuerKey::from([2u8; 33]))
+        };
+        builder(1_000, 10_000)
+            .with_allocation(seal(0), 1_000)
+            .build()
+            .unwrap()
+            .issue_contract()
+            .unwrap();
+        assert!(matches!(
+            builder(1_000, 10_000).with_allocation(seal(0), 999).build(),
+            Err(GenesisBuilderError::SupplyMismatch {
+                issued: 1_000,
+                allocated: 999
+            })
+        ));
+        assert!(matches!(
+            with_params(CiaGenesisBuilder::new(Identity::default(), 1_000, 10_000))
+                .with_allocation(seal(0), 1_000)
+                .build(),
+            Err(GenesisBuilderError::MissingIssuerKey)
+        ));
+        assert!(matches!(
+            builder(20_000, 10_000)
+                .with_allocation(seal(0), 20_000)
+                .build(),
+            Err(GenesisBuilderError::ExceedsMaxSupply {
+                issued: 20_000,
+                max: 10_000
+            })
+        ));
+    }
+
+    #[test]
+    fn uda_genesis() {
+        let token = TokenData {
+            index: TokenIndex::from_inner(1),
+            ..Default::default()
+        };
+        let builder = || with_params(UdaGenesisBuilder::new(Identity::default(), token.clone()));
+        builder()
+            .with_allocation(seal(0), 1)
+            .build()
+            .unwrap()
+            .issue_contract()
+            .unwrap();
+        assert!(matches!(
+            builder().with_allocation(seal(0), 2).build(),
+            Err(GenesisBuilderError::InvalidTokenAllocation)
+        ));
+        assert!(matches!(
+            builder()
+                .with_allocation(seal(0), 1)
+                .with_allocation(seal(1), 1)
+                .build(),
+            Err(GenesisBuilderError::InvalidTokenAllocation)
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bundle of the schema artifacts published to LNP/BP nodes as a single unit.
+
+use std::path::Path;
+use std::{fs, io};
+
+use amplify::confinement::{Confined, U24};
+use rgbstd::interface::IfaceImpl;
+use rgbstd::schema::Schema;
+use rgbstd::validation::Scripts;
+use strict_encoding::{DeserializeError, StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::LIB_NAME_RGB_SCHEMATA;
+
+/// Errors loading [`SchemaPublicationBundle`] from a file.
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum BundleLoadError {
+    /// unable to read schema publication bundle. Details: {0}
+    #[from]
+    Io(io::Error),
+
+    /// schema publication bundle exceeds the maximal size of {0} bytes.
+    TooLarge(usize),
+
+    /// invalid schema publication bundle. Details: {0}
+    #[from]
+    Decode(DeserializeError),
+}
+
+/// Schema together with its interface implementation, types and validation
+/// scripts, transmitted to LNP/BP nodes as a single strict-encoded bundle.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct SchemaPublicationBundle {
+    pub schema: Schema,
+    pub iface_impl: IfaceImpl,
+    pub type_system: TypeSystem,
+    pub scripts: Scripts,
+}
+
+impl StrictSerialize for SchemaPublicationBundle {}
+impl StrictDeserialize for SchemaPublicationBundle {}
+
+impl SchemaPublicationBundle {
+    /// Maximal size of a strict-encoded bundle.
+    pub const MAX_SIZE: usize = U24;
+
+    /// Writes strict-encoded bundle into the file at `path`.
+    pub fn to_file(&self, path: &Path) -> io::Result<()> {
+        let data = self
+            .to_strict_serialized::<{ Self::MAX_SIZE }>()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::write(path, data.as_slice())
+    }
+
+    /// Reads strict-encoded bundle from the file at `path`.
+    pub fn from_file(path: &Path) -> Result<Self, BundleLoadError> {
+        let data = fs::read(path)?;
+        let len = data.len();
+        let data = Confined::try_from(data).map_err(|_| BundleLoadError::TooLarge(len))?;
+        Ok(Self::from_strict_serialized::<{ Self::MAX_SIZE }>(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NonInflatableAsset;
+
+    #[test]
+    fn file_roundtrip() {
+        let bundle = NonInflatableAsset::publication_bundle();
+        let path = std::env::temp_dir().join("rgb-schemata-publication-bundle.strict");
+        bundle.to_file(&path).unwrap();
+        assert_eq!(SchemaPublicationBundle::from_file(&path).unwrap(), bundle);
+
+        fs::write(&path, b"garbage").unwrap();
+        assert!(matches!(
+            SchemaPublicationBundle::from_file(&path),
+            Err(BundleLoadError::Decode(_))
+        ));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(SchemaPublicationBundle::from_file(&path), Err(BundleLoadError::Io(_))));
+    }
+}
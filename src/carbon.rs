@@ -0,0 +1,380 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Carbon credit schema implementing RGB20 fungible assets interface with
+//! on-chain retirement records.
+//!
+//! Each unit of the asset represents a carbon credit issued by the carbon
+//! registry ([`GS_REGISTRY_NAME`]) for the project ([`GS_PROJECT_ID`]) and the
+//! vintage year ([`GS_VINTAGE_YEAR`]). Retiring credits burns them and emits a
+//! retirement receipt ([`RetirementReceipt`]) serving as the proof of the
+//! offset use.
+//!
+//! The scripts can't keep a running total of the retired supply (see [consensus
+//! limitations](crate#consensus-limitations)). Instead, each retirement adds a
+//! [`GS_RETIRED_SUPPLY`] entry with the amount it retires, which is checked
+//! against the burned credits, and the retired supply is the sum of all these
+//! entries. The receipt timestamp is not verified by the consensus.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::{Details, StandardTypes};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, Amount, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PRECISION,
+    GS_TERMS, OS_ASSET, TS_TRANSFER,
+};
+
+/// Identifier of the project in the carbon registry.
+pub const GS_PROJECT_ID: GlobalStateType = GlobalStateType::with(2900);
+/// Year of the emission reductions represented by the credits
+/// ([`VintageYear`]).
+pub const GS_VINTAGE_YEAR: GlobalStateType = GlobalStateType::with(2901);
+/// Name of the carbon registry which has certified the project.
+pub const GS_REGISTRY_NAME: GlobalStateType = GlobalStateType::with(2902);
+/// Amount of credits retired by an operation.
+pub const GS_RETIRED_SUPPLY: GlobalStateType = GlobalStateType::with(2903);
+
+/// Receipt for retired credits ([`RetirementReceipt`]).
+pub const OS_RETIREMENT_RECEIPT: AssignmentType = AssignmentType::with(4900);
+
+pub const TS_RETIRE: TransitionType = TransitionType::with(10900);
+
+pub(crate) const FN_CARBON_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_CARBON_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+pub const FN_RETIRE_OFFSET: u16 = FN_CARBON_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 2;
+
+/// Year of the emission reductions, kept in [`GS_VINTAGE_YEAR`] global state.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct VintageYear(u16);
+impl StrictSerialize for VintageYear {}
+impl StrictDeserialize for VintageYear {}
+
+/// Receipt for retired carbon credits, kept as [`OS_RETIREMENT_RECEIPT`] owned
+/// state.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct RetirementReceipt {
+    /// Amount of retired credits.
+    pub amount: Amount,
+    /// Unix timestamp of the retirement.
+    pub timestamp: i64,
+    /// Identity of the party on whose behalf the credits are retired.
+    pub beneficiary: Details,
+}
+impl StrictSerialize for RetirementReceipt {}
+impl StrictDeserialize for RetirementReceipt {}
+
+/// Assembles AluVM library with validation scripts used by the carbon credit
+/// schema.
+pub fn carbon_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the issued supply matches the sum of allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Retirement validation
+        // Checking that the retired supply matches the sum of burned credits.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_RETIRED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_ASSET;
+        test;
+        // Checking that the receipt amount matches the retired supply.
+        lds     OS_RETIREMENT_RECEIPT,a16[0],s16[1];
+        extr    s16[1],a64[1],a16[0];
+        eq.n    a64[0],a64[1];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong carbon credit script")
+}
+
+fn carbon_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = carbon_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_CARBON_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_CARBON_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_CARBON_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_CARBON_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_RETIRE_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_RETIRE_OFFSET + 12), GS_RETIRED_SUPPLY.to_inner());
+    assert_eq!(code[FN_RETIRE_OFFSET as usize + 19], INSTR_PCPS);
+    assert_eq!(code[FN_RETIRE_OFFSET as usize + 23], INSTR_LDS);
+    assert_eq!(state_arg(FN_RETIRE_OFFSET + 23), OS_RETIREMENT_RECEIPT.to_inner());
+    assert_eq!(code[FN_RETIRE_OFFSET as usize + 27], INSTR_EXTR);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("CarbonCreditSchema"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_PROJECT_ID => GlobalStateSchema::once(types.get("RGBContract.Details")),
+            GS_VINTAGE_YEAR => GlobalStateSchema::once(types.get("RGBSchemata.VintageYear")),
+            GS_REGISTRY_NAME => GlobalStateSchema::once(types.get("RGBContract.Name")),
+            GS_RETIRED_SUPPLY => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_RETIREMENT_RECEIPT =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.RetirementReceipt")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_PROJECT_ID => Occurrences::Once,
+                GS_VINTAGE_YEAR => Occurrences::Once,
+                GS_REGISTRY_NAME => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_CARBON_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_CARBON_TRANSFER_OFFSET, alu_id))
+            },
+            TS_RETIRE => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_RETIRED_SUPPLY => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_RETIREMENT_RECEIPT => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_RETIRE_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn carbon_rgb20() -> IfaceImpl {
+    let schema = carbon_schema();
+    let iface = Rgb20::iface(CarbonCredit::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_PROJECT_ID, fname!("projectId")),
+            NamedField::with(GS_VINTAGE_YEAR, fname!("vintageYear")),
+            NamedField::with(GS_REGISTRY_NAME, fname!("registryName")),
+            NamedField::with(GS_RETIRED_SUPPLY, fname!("retiredSupply")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+            NamedField::with(OS_RETIREMENT_RECEIPT, fname!("retirementReceipt")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_RETIRE, fname!("retire")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Carbon credits certified by a carbon registry.
+///
+/// Credits are retired in full: a retirement spends the credit allocations
+/// without any change, so the holder should first transfer the exact amount to
+/// retire onto a separate seal.
+pub struct CarbonCredit;
+
+impl IssuerWrapper for CarbonCredit {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { carbon_schema() }
+    fn issue_impl() -> IfaceImpl { carbon_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = carbon_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::stl::Name;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(CarbonCredit::FEATURES);
+        if let Err(err) = carbon_rgb20().check(&iface, &carbon_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid carbon credit RGB20 interface implementation");
+        }
+    }
+
+    fn receipt(amount: u64) -> RetirementReceipt {
+        RetirementReceipt {
+            amount: Amount::from(amount),
+            timestamp: 1713343888,
+            beneficiary: Details::from("ACME Corp."),
+        }
+    }
+
+    #[test]
+    fn receipt_layout() {
+        // Retirement script reads the retired amount at offset 0 of the receipt
+        let data = receipt(250).to_strict_serialized::<128>().unwrap();
+        assert_eq!(&data[..8], &250u64.to_le_bytes());
+        assert_eq!(&data[8..16], &1713343888i64.to_le_bytes());
+    }
+
+    /// Issues 1000 carbon credits allocated to two seals.
+    fn contract(issued: u64) -> Option<Contract> {
+        let builder = issuer::<CarbonCredit>();
+        let contract = fungible_genesis(builder, spec("VCU", "Verified carbon units"), issued)
+            .add_global_state("projectId", Details::from("VCS-1234"))
+            .unwrap()
+            .add_global_state("vintageYear", VintageYear::from(2023))
+            .unwrap()
+            .add_global_state("registryName", Name::from("Verra"))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 600u64)
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(1), 400u64)
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn carbon_genesis() {
+        assert!(contract(1000).is_some());
+        assert!(contract(600).is_none());
+    }
+
+    #[test]
+    fn retire() {
+        let contract = contract(1000).unwrap();
+        let retire = |retired: u64, receipt: RetirementReceipt| {
+            let transition = spend_genesis::<CarbonCredit>(&contract, "retire")
+                .add_global_state("retiredSupply", Amount::from(retired))
+                .unwrap()
+                .add_data("retirementReceipt", graph_seal(0), receipt)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &CarbonCredit::schema(), &transition)
+        };
+
+        let status = retire(1000, receipt(1000));
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(retire(900, receipt(900))), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(retire(1000, receipt(900))), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+}
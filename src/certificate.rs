@@ -0,0 +1,509 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Certificate schema implementing RGB21 NFT interface for revocable
+//! credentials issued by universities, certification bodies or governments.
+//!
+//! Each certificate is a unique allocation of certificate data
+//! ([`Certificate`]) naming its holder, the credential type and the validity
+//! period. The issuer, identified by its decentralized identifier
+//! ([`GS_ISSUER_DID`]) and public key ([`GS_ISSUER_KEY`]), holds the
+//! issuer authority ([`OS_CERTIFIER_AUTHORITY`]), which it spends to issue
+//! certificates with [`TS_ISSUE_CERT`], to revoke them with [`TS_REVOKE_CERT`],
+//! leaving a revoked certificate tombstone ([`OS_REVOKED_CERT`]), and to extend
+//! their expiry with [`TS_RENEW_CERT`].
+//!
+//! Certificates can't be transferred peer-to-peer: the RGB21 transfer requires
+//! the issuer authority too, so the issuer may only move a certificate to a new
+//! seal of the same holder. The issuer signature over the renewal is
+//! represented by spending the issuer authority, while checking that
+//! a certificate hasn't expired is left to the verifier (see [consensus
+//! limitations](crate#consensus-limitations)).
+
+use aluvm::isa::opcodes::{INSTR_JMP, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb21, IssuerWrapper, Rgb21, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDP, INSTR_LDS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, BlockHeight, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_NON_EQUAL_IN_OUT, GS_ATTACH, GS_ISSUER_KEY, GS_NOMINAL, GS_TERMS, GS_TOKENS, TS_TRANSFER,
+};
+
+/// Decentralized identifier of the certificate issuer ([`crate::Did`]).
+pub const GS_ISSUER_DID: GlobalStateType = GlobalStateType::with(4000);
+
+/// Allocation of a single certificate ([`Certificate`]).
+pub const OS_CERTIFICATE: AssignmentType = AssignmentType::with(5900);
+/// Tombstone of a revoked certificate, keeping its data ([`Certificate`]).
+pub const OS_REVOKED_CERT: AssignmentType = AssignmentType::with(5901);
+/// Right of the issuer to issue, revoke, renew and move certificates.
+pub const OS_CERTIFIER_AUTHORITY: AssignmentType = AssignmentType::with(5902);
+
+pub const TS_ISSUE_CERT: TransitionType = TransitionType::with(12000);
+pub const TS_REVOKE_CERT: TransitionType = TransitionType::with(12001);
+pub const TS_RENEW_CERT: TransitionType = TransitionType::with(12002);
+
+/// Error raised when a renewal changes anything but the expiry height of the
+/// certificate, or doesn't extend it.
+pub const ERRNO_INVALID_CERTIFICATE: u8 = 52;
+
+pub(crate) const FN_CERT_TRANSFER_OFFSET: u16 = 0;
+pub const FN_REVOKE_CERT_OFFSET: u16 = FN_CERT_TRANSFER_OFFSET + 4 + 4 + 4 + 3;
+pub(crate) const FN_CERT_COMPARE_OFFSET: u16 = FN_REVOKE_CERT_OFFSET + 4 + 4 + 4;
+pub const FN_RENEW_CERT_OFFSET: u16 = FN_CERT_COMPARE_OFFSET + 4 + 2 + 1 + 1;
+
+/// Data of a single certificate, kept as [`OS_CERTIFICATE`] and
+/// [`OS_REVOKED_CERT`] owned state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct Certificate {
+    /// Decentralized identifier of the holder, zero-padded to 64 bytes.
+    pub holder_did: [u8; 64],
+    /// Type of the credential, defined by the issuer.
+    pub credential_type: u8,
+    /// Height of the block at which the certificate was issued.
+    pub issuance_height: BlockHeight,
+    /// Height of the last block at which the certificate is valid.
+    pub expiry_height: BlockHeight,
+}
+impl StrictSerialize for Certificate {}
+impl StrictDeserialize for Certificate {}
+
+impl Certificate {
+    fn dumb() -> Self {
+        Self {
+            holder_did: [0u8; 64],
+            credential_type: 0,
+            issuance_height: BlockHeight::default(),
+            expiry_height: BlockHeight::default(),
+        }
+    }
+}
+
+/// Assembles AluVM library with validation scripts used by the certificate
+/// schema.
+pub fn certificate_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Checking that the certificate data are kept unchanged.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        lds     OS_CERTIFICATE,a16[0],s16[1];
+        jmp     FN_CERT_COMPARE_OFFSET;
+
+        // SUBROUTINE Revocation validation
+        // Checking that the tombstone keeps the data of the revoked certificate.
+        put     a8[0],ERRNO_INVALID_CERTIFICATE;
+        put     a16[0],0;
+        lds     OS_REVOKED_CERT,a16[0],s16[1];
+
+        // SUBROUTINE Comparing the spent certificate with s16[1]
+        ldp     OS_CERTIFICATE,a16[0],s16[0];
+        eq      s16[0],s16[1];
+        test;
+        ret;
+
+        // SUBROUTINE Renewal validation
+        put     a8[0],ERRNO_INVALID_CERTIFICATE;
+        put     a16[0],0;
+        ldp     OS_CERTIFICATE,a16[0],s16[0];
+        lds     OS_CERTIFICATE,a16[0],s16[1];
+        // Checking that the holder is kept unchanged.
+        extr    s16[0],r512[0],a16[0];
+        extr    s16[1],r512[1],a16[0];
+        eq.n    r512[0],r512[1];
+        test;
+        // Checking that the credential type is kept unchanged.
+        put     a16[1],64;
+        extr    s16[0],a8[1],a16[1];
+        extr    s16[1],a8[2],a16[1];
+        eq.n    a8[1],a8[2];
+        test;
+        // Checking that the issuance height is kept unchanged.
+        put     a16[1],65;
+        extr    s16[0],a32[0],a16[1];
+        extr    s16[1],a32[1],a16[1];
+        eq.n    a32[0],a32[1];
+        test;
+        // Checking that the new expiry height is greater than the old one.
+        put     a16[1],69;
+        extr    s16[0],a32[0],a16[1];
+        extr    s16[1],a32[1],a16[1];
+        gt.u    a32[1],a32[0];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong certificate script")
+}
+
+fn certificate_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+    let rgb21_types = StandardTypes::with(Rgb21::stl());
+
+    let alu_lib = certificate_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_CERT_TRANSFER_OFFSET as usize + 8], INSTR_LDS);
+    assert_eq!(state_arg(FN_CERT_TRANSFER_OFFSET + 8), OS_CERTIFICATE.to_inner());
+    assert_eq!(code[FN_REVOKE_CERT_OFFSET as usize - 3], INSTR_JMP);
+    assert_eq!(code[FN_REVOKE_CERT_OFFSET as usize + 8], INSTR_LDS);
+    assert_eq!(state_arg(FN_REVOKE_CERT_OFFSET + 8), OS_REVOKED_CERT.to_inner());
+    assert_eq!(code[FN_CERT_COMPARE_OFFSET as usize], INSTR_LDP);
+    assert_eq!(code[FN_RENEW_CERT_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_RENEW_CERT_OFFSET as usize + 8], INSTR_LDP);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("Certificate"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_TOKENS => GlobalStateSchema::once(rgb21_types.get("RGB21.TokenData")),
+            GS_ATTACH => GlobalStateSchema::once(rgb21_types.get("RGB21.AttachmentType")),
+            GS_ISSUER_DID => GlobalStateSchema::once(types.get("RGBSchemata.Did")),
+            GS_ISSUER_KEY => GlobalStateSchema::once(types.get("RGBSchemata.IssuerKey")),
+        },
+        owned_types: tiny_bmap! {
+            OS_CERTIFICATE => OwnedStateSchema::Structured(types.get("RGBSchemata.Certificate")),
+            OS_REVOKED_CERT => OwnedStateSchema::Structured(types.get("RGBSchemata.Certificate")),
+            OS_CERTIFIER_AUTHORITY => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::NoneOrOnce,
+                GS_ATTACH => Occurrences::NoneOrOnce,
+                GS_ISSUER_DID => Occurrences::Once,
+                GS_ISSUER_KEY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_CERTIFICATE => Occurrences::NoneOrMore,
+                OS_CERTIFIER_AUTHORITY => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: None,
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_CERTIFICATE => Occurrences::Once,
+                    OS_CERTIFIER_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_CERTIFICATE => Occurrences::Once,
+                    OS_CERTIFIER_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_CERT_TRANSFER_OFFSET, alu_id))
+            },
+            TS_ISSUE_CERT => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_CERTIFIER_AUTHORITY => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_CERTIFICATE => Occurrences::OnceOrMore,
+                    OS_CERTIFIER_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: None
+            },
+            TS_REVOKE_CERT => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_CERTIFICATE => Occurrences::Once,
+                    OS_CERTIFIER_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_REVOKED_CERT => Occurrences::Once,
+                    OS_CERTIFIER_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_REVOKE_CERT_OFFSET, alu_id))
+            },
+            TS_RENEW_CERT => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_CERTIFICATE => Occurrences::Once,
+                    OS_CERTIFIER_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_CERTIFICATE => Occurrences::Once,
+                    OS_CERTIFIER_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_RENEW_CERT_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn certificate_rgb21() -> IfaceImpl {
+    let schema = certificate_schema();
+    let iface = Rgb21::iface(CertificateSchema::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_TOKENS, fname!("tokens")),
+            NamedField::with(GS_ATTACH, fname!("attachmentTypes")),
+            NamedField::with(GS_ISSUER_DID, fname!("issuerDid")),
+            NamedField::with(GS_ISSUER_KEY, fname!("issuerKey")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_CERTIFICATE, fname!("assetOwner")),
+            NamedField::with(OS_REVOKED_CERT, fname!("revokedCertificate")),
+            NamedField::with(OS_CERTIFIER_AUTHORITY, fname!("issuerAuthority")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_ISSUE_CERT, fname!("issueCertificate")),
+            NamedField::with(TS_REVOKE_CERT, fname!("revokeCertificate")),
+            NamedField::with(TS_RENEW_CERT, fname!("renewCertificate")),
+        },
+        extensions: none!(),
+        errors: tiny_bset! {
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualValues")),
+        },
+    }
+}
+
+/// Revocable non-transferable credentials issued by a certification body.
+pub struct CertificateSchema;
+
+impl IssuerWrapper for CertificateSchema {
+    const FEATURES: rgb21::Features = rgb21::Features::NONE;
+    type IssuingIface = Rgb21;
+
+    fn schema() -> Schema { certificate_schema() }
+    fn issue_impl() -> IfaceImpl { certificate_rgb21() }
+
+    fn types() -> TypeSystem {
+        let mut types = StandardTypes::with(rgb_schemata_stl()).type_system();
+        types
+            .extend(StandardTypes::with(Rgb21::stl()).type_system())
+            .expect("certificate type system is too large");
+        types
+    }
+
+    fn scripts() -> Scripts {
+        let lib = certificate_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use rgbstd::containers::Contract;
+
+    use super::*;
+    use crate::test_helpers::{
+        asset_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+    use crate::{Did, IssuerKey};
+
+    fn certificate() -> Certificate {
+        let mut holder_did = [0u8; 64];
+        holder_did[..13].copy_from_slice(b"did:rgb:alice");
+        Certificate {
+            holder_did,
+            credential_type: 3,
+            issuance_height: BlockHeight::from(840_000),
+            expiry_height: BlockHeight::from(850_000),
+        }
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb21::iface(CertificateSchema::FEATURES);
+        if let Err(err) = certificate_rgb21().check(&iface, &certificate_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid certificate RGB21 interface implementation");
+        }
+    }
+
+    #[test]
+    fn issuer_gated_operations() {
+        let schema = certificate_schema();
+        for transition in schema.transitions.values() {
+            assert_eq!(transition.inputs.get(&OS_CERTIFIER_AUTHORITY), Some(&Occurrences::Once));
+        }
+    }
+
+    #[test]
+    fn certificate_layout() {
+        // Renewal script reads the credential type at offset 64, the issuance height at
+        // offset 65 and the expiry height at offset 69
+        let data = certificate().to_strict_serialized::<73>().unwrap();
+        assert_eq!(data.len(), 73);
+        assert_eq!(data[64], 3);
+        assert_eq!(&data[65..69], &840_000u32.to_le_bytes());
+        assert_eq!(&data[69..], &850_000u32.to_le_bytes());
+    }
+
+    /// Issues the diploma of alice together with the issuer authority.
+    fn contract() -> Contract {
+        asset_genesis(issuer::<CertificateSchema>(), spec("CERT", "Diploma"))
+            .add_global_state("issuerDid", Did::from_str("did:rgb:university").unwrap())
+            .unwrap()
+            .add_global_state("issuerKey", IssuerKey::from([2u8; 33]))
+            .unwrap()
+            .add_data("assetOwner", seal(0), certificate())
+            .unwrap()
+            .add_rights("issuerAuthority", seal(1))
+            .unwrap()
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    #[test]
+    fn certificate_genesis() { contract(); }
+
+    #[test]
+    fn transfer() {
+        let contract = contract();
+        let transfer = |certificate: Certificate| {
+            let transition = spend_genesis::<CertificateSchema>(&contract, "transfer")
+                .add_data("assetOwner", graph_seal(0), certificate)
+                .unwrap()
+                .add_rights("issuerAuthority", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &CertificateSchema::schema(), &transition)
+        };
+
+        let status = transfer(certificate());
+        assert!(status.failures.is_empty(), "{status}");
+        let upgraded = Certificate {
+            credential_type: 4,
+            ..certificate()
+        };
+        assert_eq!(script_errno(transfer(upgraded)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn revoke_certificate() {
+        let contract = contract();
+        let revoke = |tombstone: Certificate| {
+            let transition = spend_genesis::<CertificateSchema>(&contract, "revokeCertificate")
+                .add_data("revokedCertificate", graph_seal(0), tombstone)
+                .unwrap()
+                .add_rights("issuerAuthority", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &CertificateSchema::schema(), &transition)
+        };
+
+        let status = revoke(certificate());
+        assert!(status.failures.is_empty(), "{status}");
+        let backdated = Certificate {
+            expiry_height: BlockHeight::from(845_000),
+            ..certificate()
+        };
+        assert_eq!(script_errno(revoke(backdated)), Some(ERRNO_INVALID_CERTIFICATE));
+    }
+
+    #[test]
+    fn renew_certificate() {
+        let contract = contract();
+        let renew = |renewed: Certificate| {
+            let transition = spend_genesis::<CertificateSchema>(&contract, "renewCertificate")
+                .add_data("assetOwner", graph_seal(0), renewed)
+                .unwrap()
+                .add_rights("issuerAuthority", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &CertificateSchema::schema(), &transition)
+        };
+        let renewed = |expiry_height: u32| Certificate {
+            expiry_height: BlockHeight::from(expiry_height),
+            ..certificate()
+        };
+
+        let status = renew(renewed(900_000));
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(renew(renewed(850_000))), Some(ERRNO_INVALID_CERTIFICATE));
+        assert_eq!(script_errno(renew(renewed(849_000))), Some(ERRNO_INVALID_CERTIFICATE));
+        let mut holder_did = [0u8; 64];
+        holder_did[..11].copy_from_slice(b"did:rgb:bob");
+        let reassigned = Certificate {
+            holder_did,
+            ..renewed(900_000)
+        };
+        assert_eq!(script_errno(renew(reassigned)), Some(ERRNO_INVALID_CERTIFICATE));
+        let upgraded = Certificate {
+            credential_type: 4,
+            ..renewed(900_000)
+        };
+        assert_eq!(script_errno(renew(upgraded)), Some(ERRNO_INVALID_CERTIFICATE));
+        let reissued = Certificate {
+            issuance_height: BlockHeight::from(845_000),
+            ..renewed(900_000)
+        };
+        assert_eq!(script_errno(renew(reissued)), Some(ERRNO_INVALID_CERTIFICATE));
+    }
+}
@@ -22,7 +22,7 @@
 //! Collectible Fungible Assets (CFA) schema implementing RGB25 fungible assets
 //! interface.
 
-use aluvm::library::LibSite;
+use aluvm::library::{Lib, LibSite};
 use ifaces::rgb25::Rgb25;
 use ifaces::{rgb25, IssuerWrapper, LNPBP_IDENTITY};
 use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
@@ -31,10 +31,10 @@ use rgbstd::schema::{
 };
 use rgbstd::stl::StandardTypes;
 use rgbstd::validation::Scripts;
-use rgbstd::{GlobalStateType, Identity, OwnedStateSchema};
+use rgbstd::{AssignmentType, GlobalStateType, Identity, OwnedStateSchema};
 use strict_types::TypeSystem;
 
-use crate::nia::{nia_lib, FN_NIA_GENESIS_OFFSET, FN_NIA_TRANSFER_OFFSET};
+use crate::nia::{nia_lib, FN_CFA_GENESIS_OFFSET, FN_NIA_TRANSFER_OFFSET};
 use crate::{
     ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_TERMS, OS_ASSET,
     TS_TRANSFER,
@@ -45,10 +45,24 @@ const GS_NAME: GlobalStateType = GlobalStateType::with(3001);
 const GS_DETAILS: GlobalStateType = GlobalStateType::with(3004);
 const GS_PRECISION: GlobalStateType = GlobalStateType::with(3005);
 
+/// Collection-wide data describing the collectible series (RGB25 `details`).
+pub const GS_COLLECTION_DATA: GlobalStateType = GS_DETAILS;
+/// Number of collectible items in the edition (RGB25 `issuedSupply`).
+pub const GS_EDITION_SIZE: GlobalStateType = GS_ISSUED_SUPPLY;
+/// Fungible allocation of collection items (RGB25 `assetOwner`).
+pub const OS_COLLECTION_ITEM: AssignmentType = OS_ASSET;
+
+/// AluVM library validating CFA operations.
+///
+/// CFA shares its validation logic with NIA: transfers must preserve the sum
+/// of Pedersen-committed amounts, and genesis allocations must sum up to the
+/// edition size ([`GS_EDITION_SIZE`]).
+pub fn cfa_lib() -> Lib { nia_lib().expect("invalid NIA validation library") }
+
 pub fn cfa_schema() -> Schema {
     let types = StandardTypes::with(Rgb25::stl());
 
-    let nia_id = nia_lib().id();
+    let nia_id = cfa_lib().id();
 
     Schema {
         ffv: zero!(),
@@ -83,7 +97,7 @@ pub fn cfa_schema() -> Schema {
                 OS_ASSET => Occurrences::OnceOrMore,
             },
             valencies: none!(),
-            validator: Some(LibSite::with(FN_NIA_GENESIS_OFFSET, nia_id)),
+            validator: Some(LibSite::with(FN_CFA_GENESIS_OFFSET, nia_id)),
         },
         extensions: none!(),
         transitions: tiny_bmap! {
@@ -150,13 +164,23 @@ impl IssuerWrapper for CollectibleFungibleAsset {
     fn types() -> TypeSystem { StandardTypes::with(Rgb25::stl()).type_system() }
 
     fn scripts() -> Scripts {
-        let lib = nia_lib();
+        let lib = cfa_lib();
         confined_bmap! { lib.id() => lib }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
+    use bp::dbc::Method;
+    use bp::{Outpoint, Txid};
+    use rgbstd::containers::BuilderSeal;
+    use rgbstd::interface::ContractBuilder;
+    use rgbstd::invoice::Precision;
+    use rgbstd::stl::{ContractTerms, Name, RicardianContract};
+    use rgbstd::{Amount, GenesisSeal, XChain};
+
     use super::*;
 
     #[test]
@@ -169,4 +193,64 @@ mod test {
             panic!("invalid CFA RGB25 interface implementation");
         }
     }
+
+    #[test]
+    fn edition_size_validation() {
+        let schema = cfa_schema();
+        let lib_id = cfa_lib().id();
+        assert!(schema.owned_types.contains_key(&OS_COLLECTION_ITEM));
+        assert_eq!(schema.genesis.globals.get(&GS_EDITION_SIZE), Some(&Occurrences::Once));
+        assert_eq!(schema.genesis.validator, Some(LibSite::with(FN_CFA_GENESIS_OFFSET, lib_id)));
+        assert_eq!(
+            schema.transitions.get(&TS_TRANSFER).unwrap().validator,
+            Some(LibSite::with(FN_NIA_TRANSFER_OFFSET, lib_id))
+        );
+    }
+
+    #[test]
+    fn cfa_issuance() {
+        let txid =
+            Txid::from_str("14295d5bb1a191cdb6286dc0944df938421e3dfcbf0811353ccac4100c2068c5")
+                .unwrap();
+        let contract = Rgb25::testnet::<CollectibleFungibleAsset>(
+            "ssi:anonymous",
+            "Test asset",
+            Precision::CentiMicro,
+        )
+        .unwrap()
+        .allocate(Method::TapretFirst, Outpoint::new(txid, 1), 1000u64.into())
+        .unwrap()
+        .issue_contract()
+        .expect("CFA genesis must pass validation");
+        assert!(contract.genesis.globals.contains_key(&GS_EDITION_SIZE));
+
+        let issue = |allocated: u64| {
+            let seal = XChain::Bitcoin(GenesisSeal::new_random(Method::TapretFirst, txid, 0));
+            ContractBuilder::with(
+                Identity::default(),
+                Rgb25::iface(CollectibleFungibleAsset::FEATURES),
+                CollectibleFungibleAsset::schema(),
+                CollectibleFungibleAsset::issue_impl(),
+                CollectibleFungibleAsset::types(),
+                CollectibleFungibleAsset::scripts(),
+            )
+            .add_global_state("name", Name::from_str("Collection").unwrap())
+            .unwrap()
+            .add_global_state("precision", Precision::Indivisible)
+            .unwrap()
+            .add_global_state("terms", ContractTerms {
+                text: RicardianContract::default(),
+                media: None,
+            })
+            .unwrap()
+            .add_global_state("issuedSupply", Amount::from(1000u64))
+            .unwrap()
+            .add_fungible_state("assetOwner", BuilderSeal::from(seal), allocated)
+            .unwrap()
+            .issue_contract()
+            .is_ok()
+        };
+        assert!(issue(1000));
+        assert!(!issue(999), "allocations must sum up to the edition size");
+    }
 }
@@ -0,0 +1,941 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contract Inflatable Assets (CIA) schema implementing RGB20 fungible assets
+//! interface with secondary issuance by the issuer.
+//!
+//! The genesis commits to the issuer public key ([`GS_ISSUER_KEY`]) and
+//! declares the issuer authority valency ([`VA_ISSUER_AUTHORITY`]). Secondary
+//! issuances are [`ES_ISSUE_MORE`] state extensions redeeming and re-declaring
+//! the valency, whose script checks the issued allocations against the
+//! declared additional supply ([`GS_ADDL_SUPPLY`]). Wallets must check the
+//! signature of the issuer key over each issuance, and that the cumulative
+//! issued supply stays within the maximum supply ([`GS_MAX_SUPPLY`]) with
+//! [`supply_inflation_check`], since the scripts can't read the state of the
+//! other issuances (see [consensus limitations](crate#consensus-limitations)).
+//! The RGB20 inflation interface requires an inflation allowance instead of the
+//! valency, thus it isn't implemented.
+//!
+//! The genesis may pre-commit the release schedule of the secondary issuances
+//! ([`GS_SUPPLY_SCHEDULE`]), which the genesis script checks to release exactly
+//! the rest of the maximum supply at strictly increasing heights. The schedule
+//! is not enforced by consensus: the issuance script has access neither to the
+//! mining height of the witness transaction nor to the genesis state, thus an
+//! early, late or off-schedule issuance is valid for RGB. Instead, each
+//! issuance declares the index of the next schedule entry
+//! ([`GS_SCHEDULE_NEXT_INDEX`]), and wallets must replay the issuance history
+//! with [`check_scheduled_issuance`], rejecting contracts which fail it.
+//!
+//! Each issuance may also carry a Pedersen commitment to the cumulative issued
+//! supply ([`GS_INFLATION_PROOF`]), allowing the issuer to prove the total
+//! supply without revealing the amounts of the individual issuances. The proof
+//! is not enforced by consensus: AluVM has no Pedersen commitment arithmetics
+//! beyond balancing inputs against outputs, thus the scripts ignore its value.
+//! Wallets must check the commitment chain with [`verify_inflation_proof`],
+//! rejecting contracts which fail it.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_JIF, INSTR_PUTA, INSTR_RET};
+use aluvm::isa::{Instr, MoveOp};
+use aluvm::library::{Lib, LibSite};
+use aluvm::reg::{Reg32, RegA};
+use amplify::confinement::{Confined, U16};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    ExtensionSchema, FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema,
+    Schema, TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_CNG, INSTR_LDG, INSTR_PCAS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{
+    rgbasm, Amount, Extension, Genesis, GlobalState, GlobalStateType, Identity, OpId, Operation,
+    PedersenCommitment,
+};
+use secp256k1_zkp::SECP256K1;
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::nia::{privacy_nia_types, SupplyCommitment};
+use crate::stl::{rgb_schemata_stl, BlockHeight, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    AssignmentTypeRegistry, OverflowError, TransitionTypeRegistry, ERRNO_INFLATION_MISMATCH,
+    ERRNO_INVALID_SUPPLY_SCHEDULE, ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, ES_ISSUE_MORE,
+    GS_ADDL_SUPPLY, GS_INFLATION_PROOF, GS_ISSUED_SUPPLY, GS_ISSUER_KEY, GS_MAX_SUPPLY, GS_NOMINAL,
+    GS_SCHEDULE_NEXT_INDEX, GS_SUPPLY_SCHEDULE, GS_TERMS, OS_ASSET, TS_TRANSFER,
+    VA_ISSUER_AUTHORITY,
+};
+
+/// Maximal number of entries in the supply schedule.
+pub const MAX_SCHEDULE_ENTRIES: usize = 0xFF;
+
+/// Secondary issuance planned by the supply schedule.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct ScheduleEntry {
+    /// Mining height of the secondary issuance releasing the amount.
+    pub height: BlockHeight,
+    /// Amount released by the secondary issuance.
+    pub amount: Amount,
+}
+
+/// Release schedule of the secondary issuances, ordered by the release heights.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct SupplySchedule(Confined<Vec<ScheduleEntry>, 1, MAX_SCHEDULE_ENTRIES>);
+impl StrictSerialize for SupplySchedule {}
+impl StrictDeserialize for SupplySchedule {}
+
+impl SupplySchedule {
+    fn dumb() -> Self { Self(Confined::with(ScheduleEntry::default())) }
+
+    /// Constructs supply schedule, returning `None` if there are no entries or
+    /// more than [`MAX_SCHEDULE_ENTRIES`] of them.
+    pub fn with(entries: impl IntoIterator<Item = ScheduleEntry>) -> Option<Self> {
+        Confined::try_from_iter(entries).ok().map(Self)
+    }
+
+    /// Total amount released by the schedule, or `None` if it overflows 64-bit
+    /// amount.
+    pub fn total(&self) -> Option<u64> {
+        self.0
+            .iter()
+            .try_fold(0u64, |total, entry| total.checked_add(entry.amount.value()))
+    }
+}
+
+/// Index of an entry in the supply schedule.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct ScheduleIndex(u32);
+impl StrictSerialize for ScheduleIndex {}
+impl StrictDeserialize for ScheduleIndex {}
+
+/// Errors constructing the CIA schema and its validation library.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum CiaSchemaError {
+    /// CIA validation script can't be assembled. Details: {reason}
+    ScriptAssemblyFailed { reason: String },
+
+    /// CIA validation script has opcode {found:#04x} at offset {offset} instead
+    /// of {expected:#04x}.
+    WrongOpcodeAtOffset {
+        offset: u16,
+        expected: u8,
+        found: u8,
+    },
+
+    /// type '{name}' is absent in CIA type system.
+    TypeNotFound { name: &'static str },
+}
+
+/// Assembles AluVM library with validation scripts used by the CIA schema.
+pub(crate) fn cia_lib() -> Result<Lib, CiaSchemaError> {
+    static LIB: OnceLock<Result<Lib, CiaSchemaError>> = OnceLock::new();
+    LIB.get_or_init(assemble_cia_lib).clone()
+}
+
+// `sub` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_cia_lib() -> Result<Lib, CiaSchemaError> {
+    let mut code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking pedersen commitments against reported amount of issued assets present in the
+        // global state.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read global state into s16[0]
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        // Extract 64 bits from the beginning of s16[0] into a64[0]
+        extr    s16[0],a64[0],a16[0];
+        // verify sum of pedersen commitments for assignments against a64[0] value
+        pcas    OS_ASSET;
+        test;
+        // Checking that the issued supply doesn't exceed the maximum supply, keeping the rest of
+        // the maximum supply left for secondary issuances.
+        put     a8[0],ERRNO_INFLATION_MISMATCH;
+        // Read max supply into a64[1]
+        ldg     GS_MAX_SUPPLY,a8[1],s16[1];
+        extr    s16[1],a64[1],a16[0];
+        // a64[0] := a64[1] - a64[0]; fails if the issued supply exceeds the max supply
+        sub.uc  a64[1],a64[0];
+        test;
+
+        // SUBROUTINE Supply schedule validation
+        // Checking that the supply schedule, if present, releases exactly the rest of the maximum
+        // supply in a64[0] at strictly increasing heights.
+        cng     GS_SUPPLY_SCHEDULE,a8[2];
+        ifn     a8[2];
+        jif     FN_CIA_SCHEDULE_END_OFFSET;
+        put     a8[0],ERRNO_INVALID_SUPPLY_SCHEDULE;
+        ldg     GS_SUPPLY_SCHEDULE,a8[1],s16[2];
+        // Read the number of entries into a8[3]
+        extr    s16[2],a8[3],a16[0];
+    };
+    // NB: `rgbasm!` can't copy between registers of different size, thus the
+    // instruction is constructed directly
+    code.push(Instr::Move(MoveOp::CpyA(RegA::A8, Reg32::Reg3, RegA::A16, Reg32::Reg4)));
+    code.extend(rgbasm! {
+        // Offset of the current entry, skipping the number of entries
+        put     a16[1],1;
+        // Index of the current entry
+        put     a16[2],0;
+        // Height of the previous entry
+        put     a32[0],0;
+        // Sum of the released amounts
+        put     a64[1],0;
+        eq.n    a16[2],a16[4];
+        jif     FN_CIA_SCHEDULE_SUM_OFFSET;
+        extr    s16[2],a32[1],a16[1];
+        gt.u    a32[1],a32[0];
+        test;
+        cpy     a32[1],a32[0];
+        add     a16[1],4;
+        extr    s16[2],a64[2],a16[1];
+        // a64[1] := a64[2] + a64[1]; an overflow leaves a64[1] empty, failing the check below
+        add.uc  a64[2],a64[1];
+        add     a16[1],8;
+        add     a16[2],1;
+        jmp     FN_CIA_SCHEDULE_LOOP_OFFSET;
+        eq.n    a64[0],a64[1];
+        test;
+        ret;
+
+        // SUBROUTINE Issue-more validation
+        // Checking pedersen commitments against reported amount of additional supply. The
+        // authority to issue comes from the issuer authority valency, which the schema requires
+        // the extension to redeem.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read additional supply into a64[0]
+        ldg     GS_ADDL_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        // Checking that the amount is non-zero
+        ifz     a64[0];
+        inv     st0;
+        test;
+        // verify sum of pedersen commitments for issued assets against a64[0] value
+        pcas    OS_ASSET;
+        test;
+        ret;
+    });
+    Lib::assemble::<Instr<RgbIsa>>(&code).map_err(|err| CiaSchemaError::ScriptAssemblyFailed {
+        reason: err.to_string(),
+    })
+}
+pub(crate) const FN_CIA_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_CIA_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+pub(crate) const FN_CIA_SCHEDULE_OFFSET: u16 =
+    FN_CIA_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1 + 4 + 4 + 3 + 3 + 1;
+pub(crate) const FN_CIA_SCHEDULE_LOOP_OFFSET: u16 =
+    FN_CIA_SCHEDULE_OFFSET + 4 + 2 + 3 + 4 + 4 + 3 + 3 + 4 + 4 + 4 + 4;
+pub(crate) const FN_CIA_SCHEDULE_SUM_OFFSET: u16 =
+    FN_CIA_SCHEDULE_LOOP_OFFSET + 3 + 3 + 3 + 3 + 1 + 3 + 3 + 3 + 3 + 3 + 3 + 3;
+pub(crate) const FN_CIA_SCHEDULE_END_OFFSET: u16 = FN_CIA_SCHEDULE_SUM_OFFSET + 3 + 1;
+pub(crate) const FN_CIA_ISSUE_OFFSET: u16 = FN_CIA_SCHEDULE_END_OFFSET + 1;
+
+fn cia_schema() -> Result<Schema, CiaSchemaError> {
+    let types = StandardTypes::with(rgb_schemata_stl());
+    let name = "RGB.PedersenCommitment";
+    let commitment = privacy_nia_types()
+        .resolve(name)
+        .copied()
+        .ok_or(CiaSchemaError::TypeNotFound { name })?;
+
+    let alu_lib = cia_lib()?;
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    for (offset, expected) in [
+        (FN_CIA_TRANSFER_OFFSET + 4, INSTR_PCVS),
+        (FN_CIA_GENESIS_OFFSET, INSTR_PUTA),
+        (FN_CIA_GENESIS_OFFSET + 4, INSTR_PUTA),
+        (FN_CIA_GENESIS_OFFSET + 8, INSTR_PUTA),
+        (FN_CIA_SCHEDULE_OFFSET, INSTR_CNG),
+        (FN_CIA_SCHEDULE_OFFSET + 13, INSTR_LDG),
+        (FN_CIA_SCHEDULE_LOOP_OFFSET + 3, INSTR_JIF),
+        (FN_CIA_SCHEDULE_LOOP_OFFSET + 6, INSTR_EXTR),
+        (FN_CIA_SCHEDULE_END_OFFSET, INSTR_RET),
+        (FN_CIA_ISSUE_OFFSET, INSTR_PUTA),
+        (FN_CIA_ISSUE_OFFSET + 12, INSTR_LDG),
+        (FN_CIA_ISSUE_OFFSET + 16, INSTR_EXTR),
+        (FN_CIA_ISSUE_OFFSET + 23, INSTR_PCAS),
+    ] {
+        let found = code[offset as usize];
+        if found != expected {
+            return Err(CiaSchemaError::WrongOpcodeAtOffset {
+                offset,
+                expected,
+                found,
+            });
+        }
+    }
+
+    Ok(Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("ContractInflatableAsset"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_MAX_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_ISSUER_KEY => GlobalStateSchema::once(types.get("RGBSchemata.IssuerKey")),
+            GS_ADDL_SUPPLY => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+            GS_SUPPLY_SCHEDULE => GlobalStateSchema::once(types.get("RGBSchemata.SupplySchedule")),
+            GS_SCHEDULE_NEXT_INDEX =>
+                GlobalStateSchema::many(types.get("RGBSchemata.ScheduleIndex")),
+            GS_INFLATION_PROOF => GlobalStateSchema::many(commitment),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+        },
+        valency_types: tiny_bset![VA_ISSUER_AUTHORITY],
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_MAX_SUPPLY => Occurrences::Once,
+                GS_ISSUER_KEY => Occurrences::Once,
+                GS_SUPPLY_SCHEDULE => Occurrences::NoneOrOnce,
+                GS_INFLATION_PROOF => Occurrences::NoneOrOnce,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::NoneOrMore,
+            },
+            valencies: tiny_bset![VA_ISSUER_AUTHORITY],
+            validator: Some(LibSite::with(FN_CIA_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: tiny_bmap! {
+            ES_ISSUE_MORE => ExtensionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_ADDL_SUPPLY => Occurrences::Once,
+                    GS_SCHEDULE_NEXT_INDEX => Occurrences::NoneOrOnce,
+                    GS_INFLATION_PROOF => Occurrences::NoneOrOnce,
+                },
+                redeems: tiny_bset![VA_ISSUER_AUTHORITY],
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                },
+                valencies: tiny_bset![VA_ISSUER_AUTHORITY],
+                validator: Some(LibSite::with(FN_CIA_ISSUE_OFFSET, alu_id))
+            }
+        },
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_CIA_TRANSFER_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    })
+}
+
+fn cia_rgb20() -> Result<IfaceImpl, CiaSchemaError> {
+    let schema = cia_schema()?;
+    let iface = Rgb20::iface(ContractInflatableAsset::FEATURES);
+
+    Ok(IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_MAX_SUPPLY, fname!("maxSupply")),
+            NamedField::with(GS_ISSUER_KEY, fname!("issuerKey")),
+            NamedField::with(GS_ADDL_SUPPLY, fname!("additionalSupply")),
+            NamedField::with(GS_SUPPLY_SCHEDULE, fname!("supplySchedule")),
+            NamedField::with(GS_SCHEDULE_NEXT_INDEX, fname!("scheduleNextIndex")),
+            NamedField::with(GS_INFLATION_PROOF, fname!("inflationProof")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+        },
+        valencies: tiny_bset! {
+            NamedField::with(VA_ISSUER_AUTHORITY, fname!("issuerAuthority")),
+        },
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+        },
+        extensions: tiny_bset! {
+            NamedField::with(ES_ISSUE_MORE, fname!("issue")),
+        },
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    })
+}
+
+/// Errors auditing the issuance history of a CIA contract with
+/// [`supply_inflation_check`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum InflationError {
+    /// {0}
+    #[from]
+    Overflow(OverflowError),
+
+    /// cumulative issued supply {total} exceeds the maximum supply {cap}.
+    ExceedsMaxSupply { total: u64, cap: u64 },
+
+    /// inflation proof doesn't commit to the previous supply increased by the
+    /// issued amount.
+    ProofMismatch,
+}
+
+/// Replays the issuance history of a CIA contract, starting from the
+/// `genesis_supply` and followed by the `additional_issues` of each of its
+/// [`ES_ISSUE_MORE`] extensions, returning the total issued supply.
+///
+/// Fails on the first issuance bringing the cumulative total above the
+/// `max_supply` ([`GS_MAX_SUPPLY`]), or if the total overflows 64-bit amount.
+pub fn supply_inflation_check(
+    genesis_supply: u64,
+    max_supply: u64,
+    additional_issues: impl Iterator<Item = u64>,
+) -> Result<u64, InflationError> {
+    let check = |total: u64| {
+        if total > max_supply {
+            return Err(InflationError::ExceedsMaxSupply {
+                total,
+                cap: max_supply,
+            });
+        }
+        Ok(total)
+    };
+    let mut total = check(genesis_supply)?;
+    for issue in additional_issues {
+        total = check(total.checked_add(issue).ok_or(OverflowError::U64Overflow)?)?;
+    }
+    Ok(total)
+}
+
+/// Checks the cumulative supply commitment ([`GS_INFLATION_PROOF`]) of a CIA
+/// operation.
+///
+/// The `issued` commitments are those of the [`OS_ASSET`] allocations created
+/// by the operation, and the `previous` proof is the one of the preceding
+/// issuance, or `None` for the genesis. Due to the homomorphic property of
+/// Pedersen commitments, the `next` proof must be their sum; thus the issuer
+/// can open the latest proof to the total supply with
+/// [`SupplyCommitment::verify`] without disclosing the amounts of the
+/// individual issuances.
+pub fn verify_inflation_proof(
+    previous: Option<SupplyCommitment>,
+    issued: impl IntoIterator<Item = PedersenCommitment>,
+    next: SupplyCommitment,
+) -> Result<(), InflationError> {
+    let positive = previous
+        .into_iter()
+        .map(SupplyCommitment::into_inner)
+        .chain(issued)
+        .map(PedersenCommitment::into_inner)
+        .collect::<Vec<_>>();
+    let negative = [next.into_inner().into_inner()];
+    if !secp256k1_zkp::verify_commitments_sum_to_equal(SECP256K1, &positive, &negative) {
+        return Err(InflationError::ProofMismatch);
+    }
+    Ok(())
+}
+
+/// Complete issuance record of a CIA contract reconstructed by
+/// [`compute_total_issuance`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TotalIssuanceHistory {
+    /// Supply issued by the genesis ([`GS_ISSUED_SUPPLY`]).
+    pub genesis_supply: u64,
+    /// Additional supply ([`GS_ADDL_SUPPLY`]) of each [`ES_ISSUE_MORE`]
+    /// extension, in the replay order.
+    pub additional_issues: Vec<(OpId, u64)>,
+    /// Total issued supply.
+    pub total: u64,
+}
+
+/// Errors reconstructing the issuance history of a CIA contract with
+/// [`compute_total_issuance`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ComputeError {
+    /// total issued supply overflows 64-bit amount.
+    Overflow,
+
+    /// genesis doesn't declare a valid issued supply.
+    InvalidIssuedSupply,
+
+    /// issuance {0} doesn't declare a valid additional supply.
+    InvalidAdditionalSupply(OpId),
+}
+
+pub(crate) fn global_amount(globals: &GlobalState, ty: GlobalStateType) -> Option<u64> {
+    let values = globals.get(&ty)?;
+    if values.len() != 1 {
+        return None;
+    }
+    let data = values.first()?.as_inner().clone();
+    Amount::from_strict_serialized::<U16>(data)
+        .ok()
+        .map(Amount::value)
+}
+
+/// Replays the `extensions` of a CIA contract in their chronological order,
+/// reconstructing the issuance record from the `genesis` and each of its
+/// [`ES_ISSUE_MORE`] extensions.
+///
+/// Extensions of other types or of other contracts are skipped. Fails if the
+/// total issued supply overflows 64-bit amount at any issuance.
+pub fn compute_total_issuance<'op>(
+    genesis: &Genesis,
+    extensions: impl Iterator<Item = &'op Extension>,
+) -> Result<TotalIssuanceHistory, ComputeError> {
+    let genesis_supply = global_amount(&genesis.globals, GS_ISSUED_SUPPLY)
+        .ok_or(ComputeError::InvalidIssuedSupply)?;
+    let contract_id = genesis.contract_id();
+    let mut total = genesis_supply;
+    let mut additional_issues = vec![];
+    for extension in extensions {
+        if extension.contract_id != contract_id || extension.extension_type != ES_ISSUE_MORE {
+            continue;
+        }
+        let opid = extension.id();
+        let amount = global_amount(&extension.globals, GS_ADDL_SUPPLY)
+            .ok_or(ComputeError::InvalidAdditionalSupply(opid))?;
+        total = total.checked_add(amount).ok_or(ComputeError::Overflow)?;
+        additional_issues.push((opid, amount));
+    }
+    Ok(TotalIssuanceHistory {
+        genesis_supply,
+        additional_issues,
+        total,
+    })
+}
+
+/// Errors checking a secondary issuance against the supply schedule with
+/// [`check_scheduled_issuance`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ScheduleError {
+    /// supply schedule has no entry #{0} left to be released.
+    Exhausted(u32),
+
+    /// issuance is mined at height {actual} instead of the scheduled height
+    /// {expected}.
+    HeightMismatch { expected: u32, actual: u32 },
+
+    /// issuance releases {actual} instead of the scheduled amount {expected}.
+    AmountMismatch { expected: u64, actual: u64 },
+
+    /// issuance declares next schedule index {actual} instead of {expected}.
+    IndexMismatch { expected: u32, actual: u32 },
+}
+
+/// Checks an [`ES_ISSUE_MORE`] extension against the `schedule` committed in
+/// genesis ([`GS_SUPPLY_SCHEDULE`]), returning the index of the entry to be
+/// released next.
+///
+/// The `next_index` is the index declared by the previous issuance
+/// ([`GS_SCHEDULE_NEXT_INDEX`]), or zero for the first one; the `height` is the
+/// mining height of the issuance witness transaction, while the `amount` and
+/// the `declared_index` are the [`GS_ADDL_SUPPLY`] and
+/// [`GS_SCHEDULE_NEXT_INDEX`] of the checked extension. Fails if the issuance
+/// is early, late or off-schedule.
+pub fn check_scheduled_issuance(
+    schedule: &SupplySchedule,
+    next_index: ScheduleIndex,
+    height: BlockHeight,
+    amount: u64,
+    declared_index: ScheduleIndex,
+) -> Result<ScheduleIndex, ScheduleError> {
+    let entry = schedule
+        .get(*next_index as usize)
+        .ok_or(ScheduleError::Exhausted(*next_index))?;
+    if entry.height != height {
+        return Err(ScheduleError::HeightMismatch {
+            expected: *entry.height,
+            actual: *height,
+        });
+    }
+    if entry.amount.value() != amount {
+        return Err(ScheduleError::AmountMismatch {
+            expected: entry.amount.value(),
+            actual: amount,
+        });
+    }
+    let next = ScheduleIndex(*next_index + 1);
+    if declared_index != next {
+        return Err(ScheduleError::IndexMismatch {
+            expected: *next,
+            actual: *declared_index,
+        });
+    }
+    Ok(next)
+}
+
+/// Registers names of the assignment types used by CIA schema.
+pub fn register_cia_assignment_types(r: &mut AssignmentTypeRegistry) {
+    r.register(OS_ASSET, "assetOwner");
+}
+
+/// Registers names of the transition types used by CIA schema.
+pub fn register_cia_transition_types(r: &mut TransitionTypeRegistry) {
+    r.register(TS_TRANSFER, "transfer");
+}
+
+pub struct ContractInflatableAsset;
+
+impl IssuerWrapper for ContractInflatableAsset {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { cia_schema().expect("invalid CIA schema") }
+    fn issue_impl() -> IfaceImpl { cia_rgb20().expect("invalid CIA interface implementation") }
+
+    fn types() -> TypeSystem { privacy_nia_types().as_types().clone() }
+
+    fn scripts() -> Scripts {
+        let lib = cia_lib().expect("invalid CIA validation library");
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::interface::ContractBuilder;
+    use rgbstd::invoice::Precision;
+    use rgbstd::schema::ExtensionType;
+    use rgbstd::stl::AssetSpec;
+    use rgbstd::{AssetTag, BlindingFactor, ContractId, DataState, RevealedValue, ValencyType};
+    use strict_encoding::StrictDumb;
+
+    use super::*;
+    use crate::test_helpers::{
+        add_fungible_state, add_global_state, redeem_genesis, script_errno, seal, terms,
+        validate_extension,
+    };
+    use crate::IssuerKey;
+
+    fn builder(supply: u64, max: u64) -> ContractBuilder {
+        ContractBuilder::with(
+            Identity::default(),
+            Rgb20::iface(ContractInflatableAsset::FEATURES),
+            ContractInflatableAsset::schema(),
+            ContractInflatableAsset::issue_impl(),
+            ContractInflatableAsset::types(),
+            ContractInflatableAsset::scripts(),
+        )
+        .add_global_state("spec", AssetSpec::new("TEST", "Test", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(supply))
+        .unwrap()
+        .add_global_state("maxSupply", Amount::from(max))
+        .unwrap()
+        .add_global_state("issuerKey", IssuerKey::from([2u8; 33]))
+        .unwrap()
+        .add_fungible_state("assetOwner", seal(0), supply)
+        .unwrap()
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(ContractInflatableAsset::FEATURES);
+        let schema = cia_schema().unwrap();
+        if let Err(err) = cia_rgb20().unwrap().check(&iface, &schema) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid CIA RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn inflatable_genesis() {
+        builder(1_000, 10_000)
+            .issue_contract()
+            .expect("genesis must pass CIA validation");
+        builder(10_000, 10_000)
+            .issue_contract()
+            .expect("genesis may issue the whole max supply");
+        assert!(builder(10_001, 10_000).issue_contract().is_err(), "max supply exceeded");
+    }
+
+    #[test]
+    fn issue_more() {
+        let schema = cia_schema().unwrap();
+        let extension = &schema.extensions[&ES_ISSUE_MORE];
+        assert!(extension.redeems.contains(&VA_ISSUER_AUTHORITY));
+        assert!(extension.valencies.contains(&VA_ISSUER_AUTHORITY));
+        assert!(schema.genesis.valencies.contains(&VA_ISSUER_AUTHORITY));
+
+        let contract = builder(1_000, 10_000).issue_contract().unwrap();
+        let issue = |declared: u64, issued: u64| {
+            let mut extension = redeem_genesis(&contract, ES_ISSUE_MORE, VA_ISSUER_AUTHORITY);
+            add_global_state(&mut extension, GS_ADDL_SUPPLY, Amount::from(declared));
+            add_fungible_state(&mut extension, &contract, OS_ASSET, 1, issued);
+            validate_extension(&contract, &schema, &extension)
+        };
+
+        let status = issue(4_000, 4_000);
+        assert!(status.failures.is_empty(), "issuance failed with {status}");
+        assert_eq!(script_errno(issue(4_000, 3_999)), Some(ERRNO_ISSUED_MISMATCH));
+        assert_eq!(script_errno(issue(4_000, 4_001)), Some(ERRNO_ISSUED_MISMATCH));
+        assert_eq!(script_errno(issue(0, 0)), Some(ERRNO_ISSUED_MISMATCH));
+
+        let mut extension = redeem_genesis(&contract, ES_ISSUE_MORE, ValencyType::with(0x8000));
+        add_global_state(&mut extension, GS_ADDL_SUPPLY, Amount::from(4_000u64));
+        add_fungible_state(&mut extension, &contract, OS_ASSET, 1, 4_000);
+        let status = validate_extension(&contract, &schema, &extension);
+        assert!(!status.failures.is_empty(), "issuance must redeem the issuer authority");
+    }
+
+    #[test]
+    fn supply_schedule() {
+        let entry = |height: u32, amount: u64| ScheduleEntry {
+            height: BlockHeight::from(height),
+            amount: Amount::from(amount),
+        };
+        let issue = |schedule: &[ScheduleEntry]| {
+            let schedule = SupplySchedule::with(schedule.to_vec()).unwrap();
+            builder(1_000, 10_000)
+                .add_global_state("supplySchedule", schedule)
+                .unwrap()
+                .issue_contract()
+                .is_ok()
+        };
+        assert!(issue(&[entry(100, 9_000)]));
+        assert!(issue(&[entry(100, 4_000), entry(200, 5_000)]));
+        assert!(!issue(&[entry(100, 4_000), entry(200, 4_000)]), "schedule must cover max supply");
+        assert!(!issue(&[entry(200, 4_000), entry(100, 5_000)]), "heights must increase");
+        assert!(!issue(&[entry(100, 4_000), entry(100, 5_000)]), "heights must increase");
+        assert!(!issue(&[entry(0, 9_000)]), "heights must increase");
+        assert!(!issue(&[entry(100, u64::MAX), entry(200, 9_001)]), "amounts must not overflow");
+
+        let schedule = SupplySchedule::with([entry(100, 4_000), entry(200, 5_000)]).unwrap();
+        assert_eq!(schedule.total(), Some(9_000));
+        let check = |next: u32, height: u32, amount: u64, declared: u32| {
+            check_scheduled_issuance(
+                &schedule,
+                ScheduleIndex::from(next),
+                BlockHeight::from(height),
+                amount,
+                ScheduleIndex::from(declared),
+            )
+        };
+        assert_eq!(check(0, 100, 4_000, 1), Ok(ScheduleIndex::from(1)));
+        assert_eq!(check(1, 200, 5_000, 2), Ok(ScheduleIndex::from(2)));
+        assert_eq!(check(2, 300, 1, 3), Err(ScheduleError::Exhausted(2)));
+        assert_eq!(
+            check(1, 199, 5_000, 2),
+            Err(ScheduleError::HeightMismatch {
+                expected: 200,
+                actual: 199
+            })
+        );
+        assert_eq!(
+            check(0, 100, 5_000, 1),
+            Err(ScheduleError::AmountMismatch {
+                expected: 4_000,
+                actual: 5_000
+            })
+        );
+        assert_eq!(
+            check(0, 100, 4_000, 0),
+            Err(ScheduleError::IndexMismatch {
+                expected: 1,
+                actual: 0
+            })
+        );
+
+        // The schedule is checked by wallets only, thus consensus accepts an
+        // off-schedule issuance.
+        let contract = builder(1_000, 10_000)
+            .add_global_state("supplySchedule", schedule)
+            .unwrap()
+            .issue_contract()
+            .unwrap();
+        let mut extension = redeem_genesis(&contract, ES_ISSUE_MORE, VA_ISSUER_AUTHORITY);
+        add_global_state(&mut extension, GS_ADDL_SUPPLY, Amount::from(1u64));
+        add_fungible_state(&mut extension, &contract, OS_ASSET, 1, 1);
+        let status = validate_extension(&contract, &cia_schema().unwrap(), &extension);
+        assert!(status.failures.is_empty(), "issuance failed with {status}");
+    }
+
+    #[test]
+    fn inflation_proof() {
+        let tag = AssetTag::new_random("inflation", OS_ASSET);
+        let genesis = RevealedValue::new_random_blinding(1_000u64, tag);
+        let issue = RevealedValue::new_random_blinding(500u64, tag);
+        let commit = |value: &RevealedValue| SupplyCommitment::commit(value).into_inner();
+        let total = |supply: u64, values: &[&RevealedValue]| {
+            let blinding =
+                BlindingFactor::zero_balanced(values.iter().map(|value| value.blinding), [])
+                    .unwrap();
+            SupplyCommitment::commit(&RevealedValue::with_blinding(supply, blinding, tag))
+        };
+
+        let genesis_proof = total(1_000, &[&genesis]);
+        let issue_proof = total(1_500, &[&genesis, &issue]);
+        assert_eq!(verify_inflation_proof(None, [commit(&genesis)], genesis_proof), Ok(()));
+        assert_eq!(
+            verify_inflation_proof(Some(genesis_proof), [commit(&issue)], issue_proof),
+            Ok(())
+        );
+        assert_eq!(
+            verify_inflation_proof(
+                Some(genesis_proof),
+                [commit(&issue)],
+                total(1_501, &[&genesis, &issue])
+            ),
+            Err(InflationError::ProofMismatch)
+        );
+        assert_eq!(
+            verify_inflation_proof(Some(genesis_proof), [], issue_proof),
+            Err(InflationError::ProofMismatch)
+        );
+
+        let contract = builder(1_000, 10_000)
+            .add_global_state("inflationProof", genesis_proof)
+            .unwrap()
+            .issue_contract()
+            .expect("genesis must accept inflation proof");
+
+        // The proof is checked by wallets only, thus consensus accepts an
+        // issuance with a mismatching one.
+        let mut extension = redeem_genesis(&contract, ES_ISSUE_MORE, VA_ISSUER_AUTHORITY);
+        add_global_state(&mut extension, GS_ADDL_SUPPLY, Amount::from(500u64));
+        add_global_state(&mut extension, GS_INFLATION_PROOF, genesis_proof);
+        add_fungible_state(&mut extension, &contract, OS_ASSET, 1, 500);
+        let status = validate_extension(&contract, &cia_schema().unwrap(), &extension);
+        assert!(status.failures.is_empty(), "issuance failed with {status}");
+    }
+
+    #[test]
+    fn total_issuance() {
+        let contract = builder(1_000, 10_000).issue_contract().unwrap();
+        let genesis = &contract.genesis;
+        let extension = |ty, amount: u64| {
+            let mut extension = Extension::strict_dumb();
+            extension.contract_id = genesis.contract_id();
+            extension.extension_type = ty;
+            let data = Amount::from(amount).to_strict_serialized::<U16>().unwrap();
+            extension
+                .globals
+                .add_state(GS_ADDL_SUPPLY, DataState::from(data))
+                .unwrap();
+            extension
+        };
+        let first = extension(ES_ISSUE_MORE, 4_000);
+        let second = extension(ES_ISSUE_MORE, 5_000);
+        let other = extension(ExtensionType::with(0x8000), 7_000);
+        let mut foreign = extension(ES_ISSUE_MORE, 1);
+        foreign.contract_id = ContractId::strict_dumb();
+
+        let history =
+            compute_total_issuance(genesis, [&first, &other, &foreign, &second].into_iter())
+                .unwrap();
+        assert_eq!(history, TotalIssuanceHistory {
+            genesis_supply: 1_000,
+            additional_issues: vec![(first.id(), 4_000), (second.id(), 5_000)],
+            total: 10_000,
+        });
+        assert_eq!(
+            compute_total_issuance(genesis, [].into_iter())
+                .unwrap()
+                .total,
+            1_000
+        );
+
+        let overflow = extension(ES_ISSUE_MORE, u64::MAX);
+        assert_eq!(
+            compute_total_issuance(genesis, [&first, &overflow].into_iter()),
+            Err(ComputeError::Overflow)
+        );
+        let mut invalid = extension(ES_ISSUE_MORE, 1);
+        invalid.globals = none!();
+        assert_eq!(
+            compute_total_issuance(genesis, [&invalid].into_iter()),
+            Err(ComputeError::InvalidAdditionalSupply(invalid.id()))
+        );
+    }
+
+    #[test]
+    fn inflation_check() {
+        assert_eq!(supply_inflation_check(1_000, 10_000, [].into_iter()), Ok(1_000));
+        assert_eq!(supply_inflation_check(1_000, 10_000, [4_000, 5_000].into_iter()), Ok(10_000));
+        assert_eq!(
+            supply_inflation_check(1_000, 10_000, [4_000, 5_000, 1].into_iter()),
+            Err(InflationError::ExceedsMaxSupply {
+                total: 10_001,
+                cap: 10_000
+            })
+        );
+        assert_eq!(
+            supply_inflation_check(20_000, 10_000, [].into_iter()),
+            Err(InflationError::ExceedsMaxSupply {
+                total: 20_000,
+                cap: 10_000
+            })
+        );
+        assert_eq!(
+            supply_inflation_check(1, u64::MAX, [u64::MAX].into_iter()),
+            Err(InflationError::Overflow(OverflowError::U64Overflow))
+        );
+    }
+}
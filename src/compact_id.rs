@@ -0,0 +1,114 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compact user-facing encoding of schema ids.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::ByteArray;
+use bech32::{FromBase32, ToBase32, Variant};
+use rgbstd::SchemaId;
+
+/// Human-readable prefix of the [`CompactSchemaId`] encoding.
+pub const COMPACT_SCHEMA_ID_HRP: &str = "rgbschem";
+
+/// Schema id in a compact user-facing form, encoded with Bech32m using
+/// [`COMPACT_SCHEMA_ID_HRP`] prefix. Converts from and into [`SchemaId`] with
+/// [`From`].
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, From)]
+#[wrapper(Deref)]
+pub struct CompactSchemaId(SchemaId);
+
+/// Errors parsing [`CompactSchemaId`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum CompactSchemaIdError {
+    /// invalid Bech32 string. Details: {0}
+    #[from]
+    Bech32(bech32::Error),
+
+    /// schema id must be encoded with Bech32m and not Bech32.
+    WrongVariant,
+
+    /// schema id has prefix '{0}' instead of 'rgbschem'.
+    WrongPrefix(String),
+
+    /// schema id must be 32 bytes long, while {0} bytes are encoded.
+    WrongLength(usize),
+}
+
+impl Display for CompactSchemaId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let data = self.0.to_byte_array().to_base32();
+        let s = bech32::encode(COMPACT_SCHEMA_ID_HRP, data, Variant::Bech32m)
+            .expect("static prefix is valid");
+        f.write_str(&s)
+    }
+}
+
+impl FromStr for CompactSchemaId {
+    type Err = CompactSchemaIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hrp, data, variant) = bech32::decode(s)?;
+        if variant != Variant::Bech32m {
+            return Err(CompactSchemaIdError::WrongVariant);
+        }
+        if hrp != COMPACT_SCHEMA_ID_HRP {
+            return Err(CompactSchemaIdError::WrongPrefix(hrp));
+        }
+        let data = Vec::<u8>::from_base32(&data)?;
+        let id = <[u8; 32]>::try_from(data.as_slice())
+            .map_err(|_| CompactSchemaIdError::WrongLength(data.len()))?;
+        Ok(Self(SchemaId::from_byte_array(id)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ifaces::IssuerWrapper;
+
+    use super::*;
+    use crate::NonInflatableAsset;
+
+    #[test]
+    fn compact_schema_id() {
+        let id = NonInflatableAsset::schema().schema_id();
+        let compact = CompactSchemaId::from(id);
+        let s = compact.to_string();
+        assert!(s.starts_with("rgbschem1"));
+        assert_eq!(SchemaId::from(CompactSchemaId::from_str(&s).unwrap()), id);
+        assert_eq!(s.to_uppercase().parse::<CompactSchemaId>(), Ok(compact));
+
+        let bech32 =
+            bech32::encode(COMPACT_SCHEMA_ID_HRP, [0u8; 32].to_base32(), Variant::Bech32).unwrap();
+        assert_eq!(CompactSchemaId::from_str(&bech32), Err(CompactSchemaIdError::WrongVariant));
+        let other = bech32::encode("rgb", [0u8; 32].to_base32(), Variant::Bech32m).unwrap();
+        assert_eq!(
+            CompactSchemaId::from_str(&other),
+            Err(CompactSchemaIdError::WrongPrefix(s!("rgb")))
+        );
+        let short =
+            bech32::encode(COMPACT_SCHEMA_ID_HRP, [0u8; 31].to_base32(), Variant::Bech32m).unwrap();
+        assert_eq!(CompactSchemaId::from_str(&short), Err(CompactSchemaIdError::WrongLength(31)));
+    }
+}
@@ -0,0 +1,528 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparison of schema versions, classifying the changes as breaking or
+//! non-breaking for the contracts issued under the previous version.
+//!
+//! A change is non-breaking if every operation valid under the old schema
+//! remains valid under the new one, like adding a new state transition or an
+//! optional global state; otherwise it is breaking. Since the schema id commits
+//! to the whole schema, any change produces a new schema id; the comparison
+//! only tells whether the existing contracts remain valid under the new rules.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
+
+use aluvm::library::LibSite;
+use rgbstd::schema::{Occurrences, OpSchema, Schema};
+use rgbstd::{
+    AssignmentType, ExtensionType, GlobalStateType, MetaType, TransitionType, ValencyType,
+};
+
+/// Impact of a schema change on the contracts issued under the previous schema
+/// version.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display)]
+pub enum ChangeKind {
+    /// Operations valid under the old schema may become invalid.
+    #[display("breaking")]
+    Breaking,
+    /// Operations valid under the old schema remain valid.
+    #[display("non-breaking")]
+    NonBreaking,
+}
+
+/// Operation of a schema.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display)]
+pub enum OpRef {
+    #[display("genesis")]
+    Genesis,
+    #[display("transition #{0}")]
+    Transition(TransitionType),
+    #[display("extension #{0}")]
+    Extension(ExtensionType),
+}
+
+/// State of a schema operation.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display)]
+pub enum OpState {
+    #[display("metadata #{0}")]
+    Meta(MetaType),
+    #[display("global state #{0}")]
+    Global(GlobalStateType),
+    #[display("input #{0}")]
+    Input(AssignmentType),
+    #[display("redeemed valency #{0}")]
+    Redeemed(ValencyType),
+    #[display("assignment #{0}")]
+    Assignment(AssignmentType),
+    #[display("valency #{0}")]
+    Valency(ValencyType),
+}
+
+/// Difference between two schema versions.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(doc_comments)]
+pub enum Change {
+    /// metadata type #{0} is added.
+    MetaTypeAdded(MetaType),
+    /// metadata type #{0} is removed.
+    MetaTypeRemoved(MetaType),
+    /// metadata type #{0} changes its semantic type.
+    MetaTypeChanged(MetaType),
+    /// global state type #{0} is added.
+    GlobalStateAdded(GlobalStateType),
+    /// global state type #{0} is removed.
+    GlobalStateRemoved(GlobalStateType),
+    /// global state type #{0} changes its semantic type or the maximal number
+    /// of items.
+    GlobalStateChanged(GlobalStateType),
+    /// owned state type #{0} is added.
+    OwnedStateAdded(AssignmentType),
+    /// owned state type #{0} is removed.
+    OwnedStateRemoved(AssignmentType),
+    /// owned state type #{0} changes its state schema.
+    OwnedStateChanged(AssignmentType),
+    /// valency type #{0} is added.
+    ValencyTypeAdded(ValencyType),
+    /// valency type #{0} is removed.
+    ValencyTypeRemoved(ValencyType),
+    /// {0} is added.
+    OperationAdded(OpRef),
+    /// {0} is removed.
+    OperationRemoved(OpRef),
+    /// validator of {0} is changed.
+    ValidatorChanged(OpRef),
+    /// {state} is added to {op}.
+    OpStateAdded { op: OpRef, state: OpState },
+    /// {state} is removed from {op}.
+    OpStateRemoved { op: OpRef, state: OpState },
+    /// occurrences of {state} in {op} are changed.
+    OccurrencesChanged { op: OpRef, state: OpState },
+}
+
+/// Schema change tagged with its impact.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    pub change: Change,
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} change: {}", self.kind, self.change)
+    }
+}
+
+/// Changes between two schema versions produced by
+/// [`SchemaComparator::compare`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct SchemaChangeset {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaChangeset {
+    /// Checks whether the schema versions are identical.
+    pub fn is_empty(&self) -> bool { self.changes.is_empty() }
+
+    /// Checks whether some of the changes may invalidate contracts issued under
+    /// the old schema.
+    pub fn is_breaking(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.kind == ChangeKind::Breaking)
+    }
+
+    /// Iterates over the breaking changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|change| change.kind == ChangeKind::Breaking)
+            .map(|change| &change.change)
+    }
+
+    fn push(&mut self, kind: ChangeKind, change: Change) {
+        self.changes.push(SchemaChange { kind, change });
+    }
+
+    fn breaking_if(&mut self, breaking: bool, change: Change) {
+        let kind = if breaking {
+            ChangeKind::Breaking
+        } else {
+            ChangeKind::NonBreaking
+        };
+        self.push(kind, change);
+    }
+
+    fn diff_keys<K: Ord + Copy, V>(
+        &mut self,
+        old: &BTreeMap<K, V>,
+        new: &BTreeMap<K, V>,
+        added: impl Fn(K) -> Change,
+        removed: impl Fn(K) -> Change,
+        changed: impl Fn(K, &V, &V) -> Option<(bool, Change)>,
+    ) {
+        for (key, old_value) in old {
+            match new.get(key) {
+                None => self.push(ChangeKind::Breaking, removed(*key)),
+                Some(new_value) => {
+                    if let Some((breaking, change)) = changed(*key, old_value, new_value) {
+                        self.breaking_if(breaking, change);
+                    }
+                }
+            }
+        }
+        for key in new.keys().filter(|key| !old.contains_key(key)) {
+            self.push(ChangeKind::NonBreaking, added(*key));
+        }
+    }
+
+    fn diff_set<K: Ord + Copy>(
+        &mut self,
+        op: OpRef,
+        old: &BTreeSet<K>,
+        new: &BTreeSet<K>,
+        state: impl Fn(K) -> OpState,
+        breaking_add: bool,
+    ) {
+        for key in old.difference(new) {
+            self.push(ChangeKind::Breaking, Change::OpStateRemoved {
+                op,
+                state: state(*key),
+            });
+        }
+        for key in new.difference(old) {
+            self.breaking_if(breaking_add, Change::OpStateAdded {
+                op,
+                state: state(*key),
+            });
+        }
+    }
+
+    fn diff_occurrences<K: Ord + Copy>(
+        &mut self,
+        op: OpRef,
+        old: &BTreeMap<K, Occurrences>,
+        new: &BTreeMap<K, Occurrences>,
+        state: impl Fn(K) -> OpState,
+    ) {
+        for (key, old_occ) in old {
+            let state = state(*key);
+            match new.get(key) {
+                None => self.push(ChangeKind::Breaking, Change::OpStateRemoved { op, state }),
+                Some(new_occ) if new_occ != old_occ => {
+                    // Relaxing the number of occurrences keeps the old operations valid
+                    let breaking = new_occ.min_value() > old_occ.min_value() ||
+                        new_occ.max_value() < old_occ.max_value();
+                    self.breaking_if(breaking, Change::OccurrencesChanged { op, state });
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, new_occ) in new.iter().filter(|(key, _)| !old.contains_key(key)) {
+            // The old operations can't contain state required by the new schema
+            let change = Change::OpStateAdded {
+                op,
+                state: state(*key),
+            };
+            self.breaking_if(new_occ.min_value() > 0, change);
+        }
+    }
+
+    fn diff_operation(
+        &mut self,
+        op: OpRef,
+        old: (&impl OpSchema, Option<LibSite>),
+        new: (&impl OpSchema, Option<LibSite>),
+    ) {
+        let ((old, old_validator), (new, new_validator)) = (old, new);
+        // Metadata declared by the schema is required, while undeclared metadata is
+        // rejected
+        self.diff_set(
+            op,
+            old.metadata().as_inner(),
+            new.metadata().as_inner(),
+            OpState::Meta,
+            true,
+        );
+        self.diff_occurrences(
+            op,
+            old.globals().as_inner(),
+            new.globals().as_inner(),
+            OpState::Global,
+        );
+        if let (Some(old_inputs), Some(new_inputs)) = (old.inputs(), new.inputs()) {
+            self.diff_occurrences(op, old_inputs.as_inner(), new_inputs.as_inner(), OpState::Input);
+        }
+        if let (Some(old_redeems), Some(new_redeems)) = (old.redeems(), new.redeems()) {
+            self.diff_set(
+                op,
+                old_redeems.as_inner(),
+                new_redeems.as_inner(),
+                OpState::Redeemed,
+                false,
+            );
+        }
+        self.diff_occurrences(
+            op,
+            old.assignments().as_inner(),
+            new.assignments().as_inner(),
+            OpState::Assignment,
+        );
+        self.diff_set(
+            op,
+            old.valencies().as_inner(),
+            new.valencies().as_inner(),
+            OpState::Valency,
+            false,
+        );
+        if old_validator != new_validator {
+            self.push(ChangeKind::Breaking, Change::ValidatorChanged(op));
+        }
+    }
+}
+
+/// Comparator of schema versions.
+pub struct SchemaComparator;
+
+impl SchemaComparator {
+    /// Lists changes from the `old` to the `new` schema version, classifying
+    /// them as breaking or non-breaking for the contracts issued under the
+    /// `old` schema.
+    pub fn compare(old: &Schema, new: &Schema) -> SchemaChangeset {
+        let mut changeset = SchemaChangeset::default();
+
+        changeset.diff_keys(
+            old.meta_types.as_inner(),
+            new.meta_types.as_inner(),
+            Change::MetaTypeAdded,
+            Change::MetaTypeRemoved,
+            |ty, old, new| (old != new).then_some((true, Change::MetaTypeChanged(ty))),
+        );
+        changeset.diff_keys(
+            old.global_types.as_inner(),
+            new.global_types.as_inner(),
+            Change::GlobalStateAdded,
+            Change::GlobalStateRemoved,
+            |ty, old, new| {
+                (old != new).then_some((
+                    old.sem_id != new.sem_id || new.max_items < old.max_items,
+                    Change::GlobalStateChanged(ty),
+                ))
+            },
+        );
+        changeset.diff_keys(
+            old.owned_types.as_inner(),
+            new.owned_types.as_inner(),
+            Change::OwnedStateAdded,
+            Change::OwnedStateRemoved,
+            |ty, old, new| (old != new).then_some((true, Change::OwnedStateChanged(ty))),
+        );
+        let old_valencies = old.valency_types.as_inner();
+        let new_valencies = new.valency_types.as_inner();
+        for ty in old_valencies.difference(new_valencies) {
+            changeset.push(ChangeKind::Breaking, Change::ValencyTypeRemoved(*ty));
+        }
+        for ty in new_valencies.difference(old_valencies) {
+            changeset.push(ChangeKind::NonBreaking, Change::ValencyTypeAdded(*ty));
+        }
+
+        changeset.diff_operation(
+            OpRef::Genesis,
+            (&old.genesis, old.genesis.validator),
+            (&new.genesis, new.genesis.validator),
+        );
+        for (ty, old_ts) in &old.transitions {
+            let op = OpRef::Transition(*ty);
+            match new.transitions.get(ty) {
+                None => changeset.push(ChangeKind::Breaking, Change::OperationRemoved(op)),
+                Some(new_ts) => changeset.diff_operation(
+                    op,
+                    (old_ts, old_ts.validator),
+                    (new_ts, new_ts.validator),
+                ),
+            }
+        }
+        for ty in new
+            .transitions
+            .keys()
+            .filter(|ty| !old.transitions.contains_key(*ty))
+        {
+            let op = OpRef::Transition(*ty);
+            changeset.push(ChangeKind::NonBreaking, Change::OperationAdded(op));
+        }
+        for (ty, old_ext) in &old.extensions {
+            let op = OpRef::Extension(*ty);
+            match new.extensions.get(ty) {
+                None => changeset.push(ChangeKind::Breaking, Change::OperationRemoved(op)),
+                Some(new_ext) => changeset.diff_operation(
+                    op,
+                    (old_ext, old_ext.validator),
+                    (new_ext, new_ext.validator),
+                ),
+            }
+        }
+        for ty in new
+            .extensions
+            .keys()
+            .filter(|ty| !old.extensions.contains_key(*ty))
+        {
+            let op = OpRef::Extension(*ty);
+            changeset.push(ChangeKind::NonBreaking, Change::OperationAdded(op));
+        }
+
+        changeset
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::schema::GlobalStateSchema;
+    use rgbstd::OwnedStateSchema;
+
+    use super::*;
+    use crate::{
+        nia_schema, nia_schema_with_features, NiaFeatures, GS_ISSUED_SUPPLY, GS_MAX_SUPPLY,
+        OS_ASSET, OS_FREEZE_RIGHT, TS_FREEZE, TS_TRANSFER,
+    };
+
+    #[test]
+    fn identical() {
+        let schema = nia_schema().unwrap();
+        let changeset = SchemaComparator::compare(&schema, &schema);
+        assert!(changeset.is_empty());
+        assert!(!changeset.is_breaking());
+    }
+
+    #[test]
+    fn non_breaking() {
+        let old = nia_schema().unwrap();
+        let mut new = old.clone();
+        let ty = GlobalStateType::with(9000);
+        let sem_id = new.global_types[&GS_ISSUED_SUPPLY].sem_id;
+        new.global_types
+            .insert(ty, GlobalStateSchema::once(sem_id))
+            .unwrap();
+        new.genesis
+            .globals
+            .insert(ty, Occurrences::NoneOrOnce)
+            .unwrap();
+        let transfer = new.transitions[&TS_TRANSFER].clone();
+        new.transitions
+            .insert(TransitionType::with(9000), transfer)
+            .unwrap();
+
+        let changeset = SchemaComparator::compare(&old, &new);
+        assert!(!changeset.is_breaking());
+        assert_eq!(changeset.changes, vec![
+            SchemaChange {
+                kind: ChangeKind::NonBreaking,
+                change: Change::GlobalStateAdded(ty),
+            },
+            SchemaChange {
+                kind: ChangeKind::NonBreaking,
+                change: Change::OpStateAdded {
+                    op: OpRef::Genesis,
+                    state: OpState::Global(ty),
+                },
+            },
+            SchemaChange {
+                kind: ChangeKind::NonBreaking,
+                change: Change::OperationAdded(OpRef::Transition(TransitionType::with(9000))),
+            },
+        ]);
+    }
+
+    #[test]
+    fn breaking() {
+        let old = nia_schema_with_features(NiaFeatures::METADATA).unwrap();
+
+        let mut new = old.clone();
+        new.global_types.remove(&GS_MAX_SUPPLY).unwrap();
+        new.genesis.globals.remove(&GS_MAX_SUPPLY).unwrap();
+        let changeset = SchemaComparator::compare(&old, &new);
+        assert!(changeset.breaking().eq([
+            &Change::GlobalStateRemoved(GS_MAX_SUPPLY),
+            &Change::OpStateRemoved {
+                op: OpRef::Genesis,
+                state: OpState::Global(GS_MAX_SUPPLY),
+            },
+        ]));
+
+        let mut new = old.clone();
+        new.owned_types
+            .insert(OS_ASSET, OwnedStateSchema::Declarative)
+            .unwrap();
+        let changeset = SchemaComparator::compare(&old, &new);
+        assert!(changeset
+            .breaking()
+            .eq([&Change::OwnedStateChanged(OS_ASSET)]));
+
+        let mut new = old.clone();
+        let validator = new.genesis.validator.unwrap();
+        new.genesis.validator = Some(LibSite::with(validator.pos + 1, validator.lib));
+        let changeset = SchemaComparator::compare(&old, &new);
+        assert!(changeset
+            .breaking()
+            .eq([&Change::ValidatorChanged(OpRef::Genesis)]));
+
+        let mut new = old.clone();
+        new.transitions.remove(&TS_TRANSFER).unwrap();
+        let changeset = SchemaComparator::compare(&old, &new);
+        assert!(changeset.is_breaking());
+        assert!(changeset
+            .breaking()
+            .eq([&Change::OperationRemoved(OpRef::Transition(TS_TRANSFER))]));
+
+        let mut new = old.clone();
+        new.genesis
+            .globals
+            .insert(GS_MAX_SUPPLY, Occurrences::Once)
+            .unwrap();
+        let changeset = SchemaComparator::compare(&old, &new);
+        assert!(changeset.breaking().eq([&Change::OccurrencesChanged {
+            op: OpRef::Genesis,
+            state: OpState::Global(GS_MAX_SUPPLY),
+        }]));
+    }
+
+    #[test]
+    fn feature_upgrade() {
+        let old = nia_schema().unwrap();
+        let new = nia_schema_with_features(NiaFeatures::FREEZE).unwrap();
+        let changeset = SchemaComparator::compare(&old, &new);
+        assert!(changeset.changes.contains(&SchemaChange {
+            kind: ChangeKind::NonBreaking,
+            change: Change::OperationAdded(OpRef::Transition(TS_FREEZE)),
+        }));
+        assert!(changeset.changes.contains(&SchemaChange {
+            kind: ChangeKind::NonBreaking,
+            change: Change::OwnedStateAdded(OS_FREEZE_RIGHT),
+        }));
+        assert_eq!(
+            SchemaChange {
+                kind: ChangeKind::Breaking,
+                change: Change::OperationRemoved(OpRef::Transition(TS_FREEZE)),
+            }
+            .to_string(),
+            format!("breaking change: transition #{TS_FREEZE} is removed.")
+        );
+    }
+}
@@ -0,0 +1,399 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configuration files for parameter-driven asset issuance.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+use amplify::hex::FromHex;
+use bp::seals::SecretSeal;
+use rgbstd::containers::BuilderSeal;
+use rgbstd::interface::ContractBuilder;
+use rgbstd::stl::{AssetSpec, ContractTerms, Name, RicardianContract, Ticker};
+use rgbstd::{Amount, Identity, XChain};
+use serde::Deserialize;
+
+use crate::{
+    check_allocation_sum, validate_precision, validate_url, AssetDescription, BlindedSeal,
+    NiaGenesisError, NiaIssuanceParams, OverflowError, WebsiteUrl,
+};
+
+/// Maximal length of an asset ticker.
+pub const MAX_TICKER_LEN: usize = 8;
+
+/// Errors loading or validating [`NiaConfig`].
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum NiaConfigError {
+    /// unable to read configuration file. Details: {0}
+    #[from]
+    Io(io::Error),
+
+    /// invalid configuration file. Details: {0}
+    #[from]
+    Toml(toml::de::Error),
+
+    /// ticker '{0}' must consist of 1 to 8 uppercase ASCII letters.
+    InvalidTicker(String),
+
+    /// asset name '{0}' must consist of 1 to 40 printable ASCII characters.
+    InvalidName(String),
+
+    /// asset description must consist of 1 to 1024 ASCII characters.
+    InvalidDescription,
+
+    /// website '{0}' must be HTTP or HTTPS URL of up to 256 characters.
+    InvalidWebsite(String),
+
+    /// precision {0} exceeds the maximal number of decimal places.
+    InvalidPrecision(u8),
+
+    /// total supply must be non-zero.
+    ZeroSupply,
+
+    /// max supply {max} is less than the total supply {total}.
+    MaxSupplyTooLow { max: u64, total: u64 },
+}
+
+/// NIA issuance parameters read from a TOML configuration file.
+///
+/// ```toml
+/// name = "Test asset"
+/// ticker = "TEST"
+/// precision = 8
+/// total_supply = 21000000
+/// max_supply = 21000000
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+pub struct NiaConfig {
+    pub name: String,
+    pub ticker: String,
+    pub precision: u8,
+    pub total_supply: u64,
+    pub description: Option<String>,
+    pub website: Option<String>,
+    pub max_supply: Option<u64>,
+}
+
+impl FromStr for NiaConfig {
+    type Err = NiaConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let config: NiaConfig = toml::from_str(s)?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+impl NiaConfig {
+    /// Loads and validates configuration from a TOML file.
+    pub fn from_file(path: &Path) -> Result<Self, NiaConfigError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Checks that the configuration describes a valid NIA contract.
+    pub fn validate(&self) -> Result<(), NiaConfigError> {
+        if self.ticker.is_empty() ||
+            self.ticker.len() > MAX_TICKER_LEN ||
+            !self.ticker.bytes().all(|c| c.is_ascii_uppercase())
+        {
+            return Err(NiaConfigError::InvalidTicker(self.ticker.clone()));
+        }
+        if Name::from_str(&self.name).is_err() {
+            return Err(NiaConfigError::InvalidName(self.name.clone()));
+        }
+        if matches!(&self.description, Some(description) if AssetDescription::from_str(description).is_err())
+        {
+            return Err(NiaConfigError::InvalidDescription);
+        }
+        if matches!(&self.website, Some(website) if !validate_url(website)) {
+            return Err(NiaConfigError::InvalidWebsite(self.website.clone().unwrap_or_default()));
+        }
+        if !validate_precision(self.precision) {
+            return Err(NiaConfigError::InvalidPrecision(self.precision));
+        }
+        if self.total_supply == 0 {
+            return Err(NiaConfigError::ZeroSupply);
+        }
+        match self.max_supply {
+            Some(max) if max < self.total_supply => Err(NiaConfigError::MaxSupplyTooLow {
+                max,
+                total: self.total_supply,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Errors loading NIA genesis configuration with [`genesis_params_from_toml`].
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum TomlParseError {
+    /// {0}
+    #[from]
+    #[from(io::Error)]
+    #[from(toml::de::Error)]
+    Config(NiaConfigError),
+
+    /// genesis has no asset allocations.
+    NoAllocations,
+
+    /// allocation seal '{0}' is not a hex-encoded concealed seal.
+    InvalidSeal(String),
+
+    /// seal {0} receives more than one allocation.
+    DuplicateSeal(String),
+
+    /// {0}
+    #[from]
+    Overflow(OverflowError),
+
+    /// asset allocations sum up to {allocated} while the total supply is
+    /// {issued}.
+    SupplyMismatch { issued: u64, allocated: u64 },
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+struct GenesisConfig {
+    asset: NiaConfig,
+    #[serde(default)]
+    allocation: Vec<AllocationConfig>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+struct AllocationConfig {
+    seal: String,
+    amount: u64,
+}
+
+/// NIA genesis parameters together with the asset allocations, read from a TOML
+/// configuration file by [`genesis_params_from_toml`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct NiaGenesisParams {
+    pub issuance: NiaIssuanceParams,
+    /// Allocations of the total supply to the concealed seals.
+    pub allocations: Vec<(BlindedSeal, u64)>,
+}
+
+impl FromStr for NiaGenesisParams {
+    type Err = TomlParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let config: GenesisConfig = toml::from_str(s).map_err(NiaConfigError::from)?;
+        config.asset.validate()?;
+        if config.allocation.is_empty() {
+            return Err(TomlParseError::NoAllocations);
+        }
+        let mut seals = BTreeSet::new();
+        let mut allocations = Vec::with_capacity(config.allocation.len());
+        for allocation in config.allocation {
+            let seal = SecretSeal::from_hex(&allocation.seal)
+                .map_err(|_| TomlParseError::InvalidSeal(allocation.seal.clone()))?;
+            if !seals.insert(seal) {
+                return Err(TomlParseError::DuplicateSeal(allocation.seal));
+            }
+            allocations.push((BuilderSeal::from(XChain::Bitcoin(seal)), allocation.amount));
+        }
+        let allocated = check_allocation_sum(&allocations)?;
+        let issued = config.asset.total_supply;
+        if allocated != issued {
+            return Err(TomlParseError::SupplyMismatch { issued, allocated });
+        }
+        Ok(NiaGenesisParams {
+            issuance: NiaIssuanceParams::from_config(config.asset),
+            allocations,
+        })
+    }
+}
+
+impl NiaGenesisParams {
+    /// Constructs a contract builder with the genesis global state and the
+    /// asset allocations.
+    // Error type wraps the one returned by the `ContractBuilder` methods
+    #[allow(clippy::result_large_err)]
+    pub fn genesis_builder(&self, issuer: Identity) -> Result<ContractBuilder, NiaGenesisError> {
+        self.issuance.genesis_builder(issuer, &self.allocations)
+    }
+}
+
+/// Loads and validates NIA genesis parameters from a TOML file with `[asset]`
+/// section, having the same fields as [`NiaConfig`], and `[[allocation]]` array
+/// of hex-encoded concealed seals with the allocated amounts.
+///
+/// ```toml
+/// [asset]
+/// name = "Test asset"
+/// ticker = "TEST"
+/// precision = 8
+/// total_supply = 1000
+///
+/// [[allocation]]
+/// seal = "0101010101010101010101010101010101010101010101010101010101010101"
+/// amount = 1000
+/// ```
+pub fn genesis_params_from_toml(path: &Path) -> Result<NiaGenesisParams, TomlParseError> {
+    fs::read_to_string(path)?.parse()
+}
+
+impl NiaIssuanceParams {
+    /// Converts configuration into genesis parameters.
+    ///
+    /// # Panics
+    ///
+    /// If the configuration is invalid, i.e. [`NiaConfig::validate`] fails.
+    pub fn from_config(cfg: NiaConfig) -> NiaIssuanceParams {
+        cfg.validate().expect("invalid NIA configuration");
+        let spec = AssetSpec {
+            ticker: Ticker::from_str(&cfg.ticker).expect("validated ticker"),
+            name: Name::from_str(&cfg.name).expect("validated name"),
+            details: None,
+            precision: cfg.precision.try_into().expect("validated precision"),
+        };
+        NiaIssuanceParams {
+            spec,
+            terms: ContractTerms {
+                text: RicardianContract::default(),
+                media: None,
+            },
+            issued_supply: Amount::from(cfg.total_supply),
+            max_supply: cfg.max_supply.map(Amount::from),
+            website: cfg
+                .website
+                .as_deref()
+                .map(|website| WebsiteUrl::from_str(website).expect("validated website")),
+            description: cfg.description.as_deref().map(|description| {
+                AssetDescription::from_str(description).expect("validated description")
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bp::dbc::Method;
+    use bp::Txid;
+    use rgbstd::containers::BuilderSeal;
+    use rgbstd::{GenesisSeal, Identity, XChain};
+
+    use super::*;
+    use crate::GS_DESCRIPTION;
+
+    const CONFIG: &str = r#"
+        name = "Test asset"
+        ticker = "TEST"
+        precision = 8
+        total_supply = 1000
+        max_supply = 2000
+    "#;
+
+    #[test]
+    fn config_issuance() {
+        let cfg = NiaConfig::from_str(CONFIG).unwrap();
+        assert_eq!(cfg.description, None);
+        let cfg =
+            NiaConfig::from_str(&format!("{CONFIG}\ndescription = \"Test description\"")).unwrap();
+        let params = NiaIssuanceParams::from_config(cfg);
+        assert_eq!(
+            params.description,
+            Some(AssetDescription::from_str("Test description").unwrap())
+        );
+        let txid =
+            Txid::from_str("14295d5bb1a191cdb6286dc0944df938421e3dfcbf0811353ccac4100c2068c5")
+                .unwrap();
+        let seal = XChain::Bitcoin(GenesisSeal::new_random(Method::TapretFirst, txid, 0));
+        let contract = params
+            .contract_builder(Identity::default())
+            .unwrap()
+            .add_fungible_state("assetOwner", BuilderSeal::from(seal), 1000u64)
+            .unwrap()
+            .issue_contract()
+            .expect("genesis must pass NIA validation");
+        assert!(contract.genesis.globals.contains_key(&GS_DESCRIPTION));
+    }
+
+    const GENESIS: &str = r#"
+        [asset]
+        name = "Test asset"
+        ticker = "TEST"
+        precision = 8
+        total_supply = 1000
+
+        [[allocation]]
+        seal = "0101010101010101010101010101010101010101010101010101010101010101"
+        amount = 600
+
+        [[allocation]]
+        seal = "0202020202020202020202020202020202020202020202020202020202020202"
+        amount = 400
+    "#;
+
+    #[test]
+    fn genesis_params() {
+        let params = NiaGenesisParams::from_str(GENESIS).unwrap();
+        assert_eq!(params.issuance.issued_supply, Amount::from(1000u64));
+        assert_eq!(params.allocations.len(), 2);
+        assert_eq!(
+            params.allocations[0],
+            (BuilderSeal::from(XChain::Bitcoin(SecretSeal::from([1u8; 32]))), 600)
+        );
+        params
+            .genesis_builder(Identity::default())
+            .unwrap()
+            .issue_contract()
+            .expect("genesis must pass NIA validation");
+
+        let invalid = [
+            GENESIS.replace("TEST", "TOOLONGTICKER"),
+            GENESIS.replace("precision = 8", "precision = 19"),
+            GENESIS.replace("amount = 400", "amount = 401"),
+            GENESIS.replace("02020202", "01010101"),
+            GENESIS.replace("02020202", "zz"),
+            GENESIS.replace("[[allocation]]", "[[allocations]]"),
+        ];
+        for config in invalid {
+            assert!(NiaGenesisParams::from_str(&config).is_err(), "{config}");
+        }
+        assert!(matches!(
+            NiaGenesisParams::from_str(&GENESIS.replace("02020202", "01010101")),
+            Err(TomlParseError::DuplicateSeal(_))
+        ));
+    }
+
+    #[test]
+    fn config_validation() {
+        let invalid = [
+            CONFIG.replace("TEST", "test"),
+            CONFIG.replace("TEST", "TOOLONGTICKER"),
+            CONFIG.replace("precision = 8", "precision = 19"),
+            CONFIG.replace("total_supply = 1000", "total_supply = 0"),
+            CONFIG.replace("max_supply = 2000", "max_supply = 999"),
+            CONFIG.replace("ticker", "symbol"),
+            format!("{CONFIG}\nwebsite = \"example.com\""),
+            format!("{CONFIG}\ndescription = \"\""),
+        ];
+        for config in invalid {
+            assert!(NiaConfig::from_str(&config).is_err(), "{config}");
+        }
+    }
+}
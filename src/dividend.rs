@@ -0,0 +1,555 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dividend token schema implementing RGB20 fungible assets interface with
+//! dividend distributions proportional to the holdings.
+//!
+//! The fungible part of the contract are the shares ([`OS_ASSET`]) entitling
+//! their holders to dividends. The issuer, holding the dividend authority right
+//! ([`OS_DIVIDEND_AUTHORITY`]), distributes a dividend pool with
+//! [`TS_DISTRIBUTE_DIVIDENDS`] transition, which records the pool amount
+//! ([`GS_DIVIDEND_POOL`]) and assigns dividend credits ([`DividendCredit`]) to
+//! the holders. A holder claims its dividend with [`TS_CLAIM_DIVIDEND`],
+//! burning the credit and producing a claim receipt ([`ClaimReceipt`]) for no
+//! more than the credited amount, which the issuer pays out of the contract.
+//!
+//! The script sees only the shares spent by the distribution, thus it only
+//! checks that the credits don't exceed the distributed pool; each holder is
+//! expected to check that its credit matches [`DividendCredit::proportional`]
+//! amount for the shares it held at the distribution.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_JIF, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use aluvm::reg::Reg32;
+use amplify::Wrapper;
+use bp::Outpoint;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_CNS, INSTR_LDG, INSTR_LDP, INSTR_LDS, INSTR_PCVS};
+use rgbstd::vm::{ContractOp, RgbIsa};
+use rgbstd::{rgbasm, Amount, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PRECISION,
+    GS_TERMS, OS_ASSET, TS_TRANSFER,
+};
+
+/// Amount of dividends distributed by a [`TS_DISTRIBUTE_DIVIDENDS`] operation.
+pub const GS_DIVIDEND_POOL: GlobalStateType = GlobalStateType::with(4900);
+
+/// Right of the issuer to distribute dividends.
+pub const OS_DIVIDEND_AUTHORITY: AssignmentType = AssignmentType::with(6800);
+/// Dividend claimable by a share holder ([`DividendCredit`]).
+pub const OS_DIVIDEND_CREDIT: AssignmentType = AssignmentType::with(6801);
+/// Receipt for a claimed dividend ([`ClaimReceipt`]).
+pub const OS_CLAIM_RECEIPT: AssignmentType = AssignmentType::with(6802);
+
+pub const TS_DISTRIBUTE_DIVIDENDS: TransitionType = TransitionType::with(12900);
+pub const TS_CLAIM_DIVIDEND: TransitionType = TransitionType::with(12901);
+
+/// Dividend credits exceed the distributed pool, or the claim doesn't match the
+/// credit. Not a part of RGB20 interface errors.
+pub const ERRNO_INVALID_DIVIDEND: u8 = 61;
+
+pub(crate) const FN_DIVIDEND_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_DIVIDEND_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+pub const FN_DISTRIBUTE_DIVIDENDS_OFFSET: u16 =
+    FN_DIVIDEND_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1 + 1;
+const FN_DISTRIBUTE_LOOP_OFFSET: u16 =
+    FN_DISTRIBUTE_DIVIDENDS_OFFSET + 4 + 4 + 4 + 4 + 3 + 4 + 4 + 4 + 4 + 4;
+pub const FN_CLAIM_DIVIDEND_OFFSET: u16 =
+    FN_DISTRIBUTE_LOOP_OFFSET + 4 + 3 + 3 + 1 + 3 + 3 + 3 + 3 + 1 + 1 + 1;
+
+/// Dividend credited to a share holder, kept as [`OS_DIVIDEND_CREDIT`] owned
+/// state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct DividendCredit {
+    /// Seal of the share holder the dividend is credited to.
+    pub holder_seal: Outpoint,
+    /// Amount of the dividend the holder may claim.
+    pub claimable_amount: u64,
+}
+impl StrictSerialize for DividendCredit {}
+impl StrictDeserialize for DividendCredit {}
+
+impl DividendCredit {
+    /// Constructs credit of the `pool` part proportional to the holder `shares`
+    /// of the `total_shares`, rounded down.
+    ///
+    /// Returns `None` if the total shares are zero.
+    pub fn proportional(
+        holder_seal: Outpoint,
+        shares: Amount,
+        total_shares: Amount,
+        pool: u64,
+    ) -> Option<Self> {
+        let claimable_amount =
+            (pool as u128 * shares.value() as u128).checked_div(total_shares.value() as u128)?;
+        Some(DividendCredit {
+            holder_seal,
+            claimable_amount: u64::try_from(claimable_amount).ok()?,
+        })
+    }
+}
+
+/// Receipt for a claimed dividend, kept as [`OS_CLAIM_RECEIPT`] owned state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct ClaimReceipt {
+    /// Seal of the share holder, equal to the one of the burned
+    /// [`DividendCredit`].
+    pub holder_seal: Outpoint,
+    /// Claimed amount, not exceeding the credited one.
+    pub claimed_amount: u64,
+}
+impl StrictSerialize for ClaimReceipt {}
+impl StrictDeserialize for ClaimReceipt {}
+
+/// Assembles AluVM library with validation scripts used by the dividend token
+/// schema.
+// `add` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn dividend_lib() -> Lib {
+    let mut code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the issued supply matches the sum of allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Distribution validation
+        // Checking that the sum of all dividend credits doesn't exceed the distributed pool.
+        put     a8[0],ERRNO_INVALID_DIVIDEND;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read the pool into a64[0]
+        ldg     GS_DIVIDEND_POOL,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        // Offset of the claimable amount within the credit
+        put     a16[1],36;
+        put     a16[2],1;
+        // Credit index
+        put     a16[3],0;
+        // Sum of the credits
+        put     a64[1],0;
+    };
+    // `rgbasm!` has no mnemonic for counting owned state assignments; put their
+    // number to a16[4]
+    code.push(Instr::ExtensionCodes(RgbIsa::Contract(ContractOp::CnS(
+        OS_DIVIDEND_CREDIT,
+        Reg32::Reg4,
+    ))));
+    code.extend(rgbasm! {
+        lds     OS_DIVIDEND_CREDIT,a16[3],s16[1];
+        extr    s16[1],a64[2],a16[1];
+        // Fails on the sum overflow
+        add.uc  a64[2],a64[1];
+        test;
+        // Move to the next credit, if any
+        add.uc  a16[2],a16[3];
+        lt.u    a16[3],a16[4];
+        jif     FN_DISTRIBUTE_LOOP_OFFSET;
+        gt.u    a64[1],a64[0];
+        inv     st0;
+        test;
+        ret;
+
+        // SUBROUTINE Claim validation
+        // Checking that the receipt is issued to the credited holder for no more than the
+        // credited amount.
+        put     a8[0],ERRNO_INVALID_DIVIDEND;
+        put     a16[0],0;
+        // Offsets of the output number and the amount within the credit and the receipt
+        put     a16[1],32;
+        put     a16[2],36;
+        ldp     OS_DIVIDEND_CREDIT,a16[0],s16[0];
+        lds     OS_CLAIM_RECEIPT,a16[0],s16[1];
+        // Compare holder seals
+        extr    s16[0],r256[0],a16[0];
+        extr    s16[1],r256[1],a16[0];
+        eq.n    r256[0],r256[1];
+        test;
+        extr    s16[0],a32[0],a16[1];
+        extr    s16[1],a32[1],a16[1];
+        eq.n    a32[0],a32[1];
+        test;
+        // Check that the claimed amount doesn't exceed the credit
+        extr    s16[0],a64[0],a16[2];
+        extr    s16[1],a64[1],a16[2];
+        gt.u    a64[1],a64[0];
+        inv     st0;
+        test;
+        ret;
+    });
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong dividend token script")
+}
+
+fn dividend_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = dividend_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_DIVIDEND_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_DIVIDEND_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_DIVIDEND_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_DIVIDEND_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_DISTRIBUTE_DIVIDENDS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_DISTRIBUTE_DIVIDENDS_OFFSET + 12), GS_DIVIDEND_POOL.to_inner());
+    assert_eq!(code[FN_DISTRIBUTE_LOOP_OFFSET as usize - 4], INSTR_CNS);
+    assert_eq!(state_arg(FN_DISTRIBUTE_LOOP_OFFSET - 4), OS_DIVIDEND_CREDIT.to_inner());
+    assert_eq!(code[FN_DISTRIBUTE_LOOP_OFFSET as usize], INSTR_LDS);
+    assert_eq!(code[FN_DISTRIBUTE_LOOP_OFFSET as usize + 17], INSTR_JIF);
+    assert_eq!(code[FN_CLAIM_DIVIDEND_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_CLAIM_DIVIDEND_OFFSET as usize + 16], INSTR_LDP);
+    assert_eq!(state_arg(FN_CLAIM_DIVIDEND_OFFSET + 16), OS_DIVIDEND_CREDIT.to_inner());
+    assert_eq!(code[FN_CLAIM_DIVIDEND_OFFSET as usize + 20], INSTR_LDS);
+    assert_eq!(state_arg(FN_CLAIM_DIVIDEND_OFFSET + 20), OS_CLAIM_RECEIPT.to_inner());
+    assert_eq!(code[FN_CLAIM_DIVIDEND_OFFSET as usize + 44], INSTR_EXTR);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("DividendTokenSchema"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_DIVIDEND_POOL => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_DIVIDEND_AUTHORITY => OwnedStateSchema::Declarative,
+            OS_DIVIDEND_CREDIT =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.DividendCredit")),
+            OS_CLAIM_RECEIPT => OwnedStateSchema::Structured(types.get("RGBSchemata.ClaimReceipt")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_DIVIDEND_AUTHORITY => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_DIVIDEND_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_DIVIDEND_TRANSFER_OFFSET, alu_id))
+            },
+            TS_DISTRIBUTE_DIVIDENDS => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_DIVIDEND_POOL => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_DIVIDEND_AUTHORITY => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_DIVIDEND_AUTHORITY => Occurrences::Once,
+                    OS_DIVIDEND_CREDIT => Occurrences::OnceOrMore,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_DISTRIBUTE_DIVIDENDS_OFFSET, alu_id))
+            },
+            TS_CLAIM_DIVIDEND => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_DIVIDEND_CREDIT => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_CLAIM_RECEIPT => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_CLAIM_DIVIDEND_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn dividend_rgb20() -> IfaceImpl {
+    let schema = dividend_schema();
+    let iface = Rgb20::iface(DividendToken::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_DIVIDEND_POOL, fname!("dividendPool")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+            NamedField::with(OS_DIVIDEND_AUTHORITY, fname!("dividendAuthority")),
+            NamedField::with(OS_DIVIDEND_CREDIT, fname!("dividendCredit")),
+            NamedField::with(OS_CLAIM_RECEIPT, fname!("claimReceipt")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_DISTRIBUTE_DIVIDENDS, fname!("distributeDividends")),
+            NamedField::with(TS_CLAIM_DIVIDEND, fname!("claimDividend")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Shares entitling their holders to the dividends distributed by the issuer.
+pub struct DividendToken;
+
+impl IssuerWrapper for DividendToken {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { dividend_schema() }
+    fn issue_impl() -> IfaceImpl { dividend_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = dividend_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer_with_assignments, script_errno, seal, spec,
+        spend_genesis, txid, validate_transition,
+    };
+
+    fn credit(vout: u32, claimable_amount: u64) -> DividendCredit {
+        DividendCredit {
+            holder_seal: Outpoint::new(txid(), vout),
+            claimable_amount,
+        }
+    }
+
+    /// Issues 1000 dividend shares with the `issued` supply, optionally
+    /// crediting the `credit`.
+    fn contract(issued: u64, credit: Option<DividendCredit>) -> Option<Contract> {
+        let builder = issuer_with_assignments::<DividendToken>(&[OS_DIVIDEND_CREDIT]);
+        let mut builder = fungible_genesis(builder, spec("DIV", "Dividend shares"), issued)
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_rights("dividendAuthority", seal(1))
+            .unwrap();
+        if let Some(credit) = credit {
+            builder = builder.add_data("dividendCredit", seal(2), credit).unwrap();
+        }
+        let contract = builder.issue_contract().ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(DividendToken::FEATURES);
+        if let Err(err) = dividend_rgb20().check(&iface, &dividend_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid dividend token RGB20 interface implementation");
+        }
+        let schema = dividend_schema();
+        let claim = &schema.transitions[&TS_CLAIM_DIVIDEND];
+        assert_eq!(claim.inputs.get(&OS_DIVIDEND_CREDIT), Some(&Occurrences::Once));
+        assert_eq!(claim.assignments.get(&OS_CLAIM_RECEIPT), Some(&Occurrences::Once));
+    }
+
+    #[test]
+    fn state_layout() {
+        // Scripts read the output number at offset 32 and the amounts at offset 36
+        let holder_seal = Outpoint::new(txid(), 7);
+        let credit = DividendCredit {
+            holder_seal,
+            claimable_amount: 250,
+        };
+        let data = credit.to_strict_serialized::<64>().unwrap();
+        assert_eq!(data.len(), 44);
+        assert_eq!(&data[32..36], &7u32.to_le_bytes());
+        assert_eq!(&data[36..], &250u64.to_le_bytes());
+        let receipt = ClaimReceipt {
+            holder_seal,
+            claimed_amount: 250,
+        };
+        assert_eq!(receipt.to_strict_serialized::<64>().unwrap(), data);
+    }
+
+    #[test]
+    fn proportional_credits() {
+        let seal = Outpoint::new(txid(), 0);
+        let total = Amount::from(3000u64);
+        let credit = |shares: u64| {
+            DividendCredit::proportional(seal, Amount::from(shares), total, 1000)
+                .unwrap()
+                .claimable_amount
+        };
+        assert_eq!(credit(1500), 500);
+        assert_eq!(credit(1000), 333);
+        assert_eq!(credit(3000), 1000);
+        assert_eq!(credit(0), 0);
+        assert!(credit(1000) + credit(1000) + credit(1000) <= 1000);
+        let max = Amount::from(u64::MAX);
+        assert_eq!(DividendCredit::proportional(seal, max, Amount::from(1u64), u64::MAX), None);
+        assert_eq!(DividendCredit::proportional(seal, total, Amount::ZERO, 1000), None);
+    }
+
+    #[test]
+    fn dividend_genesis() {
+        assert!(contract(1000, None).is_some());
+        assert!(contract(999, None).is_none());
+    }
+
+    #[test]
+    fn transfer() {
+        let contract = contract(1000, None).unwrap();
+        let transfer = |amount: u64| {
+            let transition = spend_genesis::<DividendToken>(&contract, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &DividendToken::schema(), &transition)
+        };
+
+        let status = transfer(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn distribute_dividends() {
+        let contract = contract(1000, None).unwrap();
+        let distribute = |pool: u64, credits: &[u64]| {
+            let mut builder = spend_genesis::<DividendToken>(&contract, "distributeDividends")
+                .add_global_state("dividendPool", Amount::from(pool))
+                .unwrap()
+                .add_rights("dividendAuthority", graph_seal(0))
+                .unwrap();
+            for (vout, amount) in (1u32..).zip(credits) {
+                builder = builder
+                    .add_data("dividendCredit", graph_seal(vout), credit(vout, *amount))
+                    .unwrap();
+            }
+            let transition = builder.complete_transition().unwrap();
+            validate_transition(&contract, &DividendToken::schema(), &transition)
+        };
+
+        let status = distribute(1000, &[500, 333]);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = distribute(1000, &[500, 500]);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(distribute(1000, &[500, 501])), Some(ERRNO_INVALID_DIVIDEND));
+        let status = distribute(1000, &[u64::MAX, 1]);
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_DIVIDEND));
+    }
+
+    #[test]
+    fn claim_dividend() {
+        let contract = contract(1000, Some(credit(7, 250))).unwrap();
+        let claim = |holder_seal: Outpoint, claimed_amount: u64| {
+            let receipt = ClaimReceipt {
+                holder_seal,
+                claimed_amount,
+            };
+            let transition = spend_genesis::<DividendToken>(&contract, "claimDividend")
+                .add_data("claimReceipt", graph_seal(0), receipt)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &DividendToken::schema(), &transition)
+        };
+
+        let holder = Outpoint::new(txid(), 7);
+        let status = claim(holder, 250);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = claim(holder, 100);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(claim(holder, 251)), Some(ERRNO_INVALID_DIVIDEND));
+        let other = Outpoint::new(txid(), 8);
+        assert_eq!(script_errno(claim(other, 250)), Some(ERRNO_INVALID_DIVIDEND));
+    }
+}
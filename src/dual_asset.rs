@@ -0,0 +1,335 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dual asset schema implementing RGB20 fungible assets interface for contracts
+//! issuing two independent fungible tokens from a single genesis.
+//!
+//! The primary token ([`OS_PRIMARY`]) is exposed through the standard RGB20
+//! names, so wallets not aware of the schema see it as a regular fixed-supply
+//! asset. The secondary token ([`OS_SECONDARY`]) has its own specification,
+//! issued supply and transfer transition; each token is validated by its own
+//! Pedersen commitment subroutine and the two never mix.
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_PCAS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_types::TypeSystem;
+
+use crate::stl::rgb_schemata_stl;
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_TERMS,
+    OS_ASSET, TS_TRANSFER,
+};
+
+/// Specification of the primary token (RGB20 `spec`).
+pub const GS_PRIMARY_NOMINAL: GlobalStateType = GS_NOMINAL;
+/// Issued supply of the primary token (RGB20 `issuedSupply`).
+pub const GS_PRIMARY_ISSUED_SUPPLY: GlobalStateType = GS_ISSUED_SUPPLY;
+/// Specification of the secondary token.
+pub const GS_SECONDARY_NOMINAL: GlobalStateType = GlobalStateType::with(3500);
+/// Issued supply of the secondary token.
+pub const GS_SECONDARY_ISSUED_SUPPLY: GlobalStateType = GlobalStateType::with(3501);
+
+/// Fungible allocation of the primary token (RGB20 `assetOwner`).
+pub const OS_PRIMARY: AssignmentType = OS_ASSET;
+/// Fungible allocation of the secondary token.
+pub const OS_SECONDARY: AssignmentType = AssignmentType::with(5400);
+
+/// Transfer of the primary token (RGB20 `transfer`).
+pub const TS_TRANSFER_PRIMARY: TransitionType = TS_TRANSFER;
+/// Transfer of the secondary token.
+pub const TS_TRANSFER_SECONDARY: TransitionType = TransitionType::with(11400);
+
+pub const FN_PRIMARY_TRANSFER_OFFSET: u16 = 0;
+pub const FN_SECONDARY_TRANSFER_OFFSET: u16 = FN_PRIMARY_TRANSFER_OFFSET + 4 + 3 + 2;
+pub(crate) const FN_DUAL_GENESIS_OFFSET: u16 = FN_SECONDARY_TRANSFER_OFFSET + 4 + 3 + 2;
+
+/// Assembles AluVM library with validation scripts used by the dual asset
+/// schema.
+pub fn dual_asset_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Primary transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_PRIMARY;
+        test;
+        ret;
+
+        // SUBROUTINE Secondary transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_SECONDARY;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the issued supply of each token matches the sum of its allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_PRIMARY_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_PRIMARY;
+        test;
+        ldg     GS_SECONDARY_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_SECONDARY;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong dual asset script")
+}
+
+fn dual_asset_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = dual_asset_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_PRIMARY_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_PRIMARY_TRANSFER_OFFSET + 4), OS_PRIMARY.to_inner());
+    assert_eq!(code[FN_SECONDARY_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_SECONDARY_TRANSFER_OFFSET + 4), OS_SECONDARY.to_inner());
+    assert_eq!(code[FN_DUAL_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_DUAL_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_DUAL_GENESIS_OFFSET + 12), GS_PRIMARY_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_DUAL_GENESIS_OFFSET as usize + 19], INSTR_PCAS);
+    assert_eq!(state_arg(FN_DUAL_GENESIS_OFFSET + 19), OS_PRIMARY.to_inner());
+    assert_eq!(code[FN_DUAL_GENESIS_OFFSET as usize + 23], INSTR_LDG);
+    assert_eq!(state_arg(FN_DUAL_GENESIS_OFFSET + 23), GS_SECONDARY_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_DUAL_GENESIS_OFFSET as usize + 30], INSTR_PCAS);
+    assert_eq!(state_arg(FN_DUAL_GENESIS_OFFSET + 30), OS_SECONDARY.to_inner());
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("DualAssetSchema"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_PRIMARY_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRIMARY_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_SECONDARY_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_SECONDARY_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_PRIMARY => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_SECONDARY => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_PRIMARY_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRIMARY_ISSUED_SUPPLY => Occurrences::Once,
+                GS_SECONDARY_NOMINAL => Occurrences::Once,
+                GS_SECONDARY_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_PRIMARY => Occurrences::OnceOrMore,
+                OS_SECONDARY => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_DUAL_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER_PRIMARY => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_PRIMARY => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_PRIMARY => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_PRIMARY_TRANSFER_OFFSET, alu_id))
+            },
+            TS_TRANSFER_SECONDARY => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_SECONDARY => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_SECONDARY => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_SECONDARY_TRANSFER_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn dual_asset_rgb20() -> IfaceImpl {
+    let schema = dual_asset_schema();
+    let iface = Rgb20::iface(DualAsset::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_PRIMARY_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRIMARY_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_SECONDARY_NOMINAL, fname!("secondarySpec")),
+            NamedField::with(GS_SECONDARY_ISSUED_SUPPLY, fname!("secondaryIssuedSupply")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_PRIMARY, fname!("assetOwner")),
+            NamedField::with(OS_SECONDARY, fname!("secondaryAssetOwner")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER_PRIMARY, fname!("transfer")),
+            NamedField::with(TS_TRANSFER_SECONDARY, fname!("transferSecondary")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+pub struct DualAsset;
+
+impl IssuerWrapper for DualAsset {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { dual_asset_schema() }
+    fn issue_impl() -> IfaceImpl { dual_asset_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = dual_asset_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::invoice::Precision;
+    use rgbstd::stl::AssetSpec;
+    use rgbstd::Amount;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(DualAsset::FEATURES);
+        if let Err(err) = dual_asset_rgb20().check(&iface, &dual_asset_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid dual asset RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn independent_transfers() {
+        let schema = dual_asset_schema();
+        let primary = schema.transitions.get(&TS_TRANSFER_PRIMARY).unwrap();
+        assert!(primary.inputs.keys().eq([&OS_PRIMARY]));
+        assert!(primary.assignments.keys().eq([&OS_PRIMARY]));
+        let secondary = schema.transitions.get(&TS_TRANSFER_SECONDARY).unwrap();
+        assert!(secondary.inputs.keys().eq([&OS_SECONDARY]));
+        assert!(secondary.assignments.keys().eq([&OS_SECONDARY]));
+    }
+
+    /// Issues 1000 primary and 50 000 secondary tokens.
+    fn contract(primary: u64, secondary: u64) -> Option<Contract> {
+        let contract = fungible_genesis(issuer::<DualAsset>(), spec("GOV", "Governance"), primary)
+            .add_global_state("secondarySpec", AssetSpec::new("FEE", "Fee", Precision::Centi))
+            .unwrap()
+            .add_global_state("secondaryIssuedSupply", Amount::from(secondary))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_fungible_state("secondaryAssetOwner", seal(1), 50_000u64)
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn dual_genesis() {
+        assert!(contract(1000, 50_000).is_some());
+        assert!(contract(50_000, 1000).is_none());
+        assert!(contract(1000, 1000).is_none());
+    }
+
+    #[test]
+    fn transfer() {
+        let contract = contract(1000, 50_000).unwrap();
+        let transfer = |name: &'static str, owner: &'static str, amounts: [u64; 2]| {
+            let transition = spend_genesis::<DualAsset>(&contract, name)
+                .add_fungible_state(owner, graph_seal(0), amounts[0])
+                .unwrap()
+                .add_fungible_state(owner, graph_seal(1), amounts[1])
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &DualAsset::schema(), &transition)
+        };
+
+        let status = transfer("transfer", "assetOwner", [400, 600]);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = transfer("transfer", "assetOwner", [400, 700]);
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+        let status = transfer("transferSecondary", "secondaryAssetOwner", [20_000, 30_000]);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = transfer("transferSecondary", "secondaryAssetOwner", [20_000, 40_000]);
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+}
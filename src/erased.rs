@@ -0,0 +1,62 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Object-safe wrapper over schema issuers.
+
+use ifaces::IssuerWrapper;
+use rgbstd::interface::IfaceImpl;
+use rgbstd::validation::Scripts;
+use rgbstd::{Schema, SchemaId};
+use strict_types::TypeSystem;
+
+/// Object-safe counterpart of [`IssuerWrapper`], allowing to keep different
+/// schemata behind `dyn` references. Implemented for all [`IssuerWrapper`]
+/// types.
+pub trait ErasedIssuerWrapper {
+    fn erased_schema(&self) -> Schema;
+    fn erased_schema_id(&self) -> SchemaId { self.erased_schema().schema_id() }
+    fn erased_issue_impl(&self) -> IfaceImpl;
+    fn erased_scripts(&self) -> Scripts;
+    fn erased_types(&self) -> TypeSystem;
+}
+
+impl<W: IssuerWrapper> ErasedIssuerWrapper for W {
+    fn erased_schema(&self) -> Schema { W::schema() }
+    fn erased_issue_impl(&self) -> IfaceImpl { W::issue_impl() }
+    fn erased_scripts(&self) -> Scripts { W::scripts() }
+    fn erased_types(&self) -> TypeSystem { W::types() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{NonInflatableAsset, UniqueDigitalAsset, NIA_SCHEMA_ID};
+
+    #[test]
+    fn erased_wrappers() {
+        let wrappers: Vec<Box<dyn ErasedIssuerWrapper>> =
+            vec![Box::new(NonInflatableAsset), Box::new(UniqueDigitalAsset)];
+        assert_eq!(wrappers[0].erased_schema_id(), *NIA_SCHEMA_ID);
+        assert_eq!(wrappers[1].erased_schema_id(), UniqueDigitalAsset::schema().schema_id());
+        assert_eq!(wrappers[0].erased_scripts(), NonInflatableAsset::scripts());
+        assert_eq!(wrappers[0].erased_types().id(), NonInflatableAsset::types().id());
+    }
+}
@@ -0,0 +1,540 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Game item schema implementing RGB21 NFT interface for in-game items with
+//! durability and level mechanics.
+//!
+//! Each item is a unique allocation of item data ([`GameItemData`]). Holders
+//! can transfer items ([`TS_TRANSFER_ITEM`]) and use them ([`TS_USE_ITEM`]),
+//! which wears an item down by a single durability point. The game issuer,
+//! holding the game authority right ([`OS_GAME_AUTHORITY`]), repairs items back
+//! to their maximum durability ([`TS_REPAIR_ITEM`]) and upgrades them to the
+//! next level ([`TS_UPGRADE_ITEM`]).
+//!
+//! Each upgrade repeats the maximum item level ([`GS_MAX_LEVEL`]) in its global
+//! state (see [consensus limitations](crate#consensus-limitations)); since this
+//! value is provided by the operation itself, upgrades require the game
+//! authority right as well.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA, INSTR_SUB};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb21, IssuerWrapper, Rgb21, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDP, INSTR_LDS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{ERRNO_NON_EQUAL_IN_OUT, GS_ATTACH, GS_NOMINAL, GS_TERMS, GS_TOKENS, TS_TRANSFER};
+
+/// Identifier of the game the items belong to.
+pub const GS_GAME_ID: GlobalStateType = GlobalStateType::with(3400);
+/// Maximum level items can be upgraded to ([`ItemLevel`]).
+pub const GS_MAX_LEVEL: GlobalStateType = GlobalStateType::with(3401);
+
+/// Allocation of a single game item ([`GameItemData`]).
+pub const OS_ITEM: AssignmentType = AssignmentType::with(5300);
+/// Right of the game issuer to repair and upgrade items.
+pub const OS_GAME_AUTHORITY: AssignmentType = AssignmentType::with(5301);
+
+pub const TS_TRANSFER_ITEM: TransitionType = TS_TRANSFER;
+pub const TS_USE_ITEM: TransitionType = TransitionType::with(11300);
+pub const TS_REPAIR_ITEM: TransitionType = TransitionType::with(11301);
+pub const TS_UPGRADE_ITEM: TransitionType = TransitionType::with(11302);
+
+/// Error raised when a game item operation changes item data in a way not
+/// allowed by the operation type.
+pub const ERRNO_INVALID_ITEM_STATE: u8 = 45;
+
+pub(crate) const FN_TRANSFER_ITEM_OFFSET: u16 = 0;
+pub const FN_USE_ITEM_OFFSET: u16 = 4 + 4 + 4 + 4 + 2 + 1 + 1;
+pub const FN_REPAIR_ITEM_OFFSET: u16 = FN_USE_ITEM_OFFSET + 16 + 14 + 29 + 3;
+pub const FN_UPGRADE_ITEM_OFFSET: u16 = FN_REPAIR_ITEM_OFFSET + 16 + 14 + 18 + 3;
+pub const FN_ITEM_SHARED_OFFSET: u16 = FN_UPGRADE_ITEM_OFFSET + 16 + 14 + 36 + 3;
+
+/// Level of a game item.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct ItemLevel(u8);
+impl StrictSerialize for ItemLevel {}
+impl StrictDeserialize for ItemLevel {}
+
+/// Data of a single game item, kept as [`OS_ITEM`] owned state.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct GameItemData {
+    /// Game-specific type of the item.
+    pub item_type: u16,
+    /// Number of times the item can be used before it has to be repaired.
+    pub durability: u16,
+    /// Durability of a new or repaired item.
+    pub max_durability: u16,
+    /// Current level of the item.
+    pub level: u8,
+}
+impl StrictSerialize for GameItemData {}
+impl StrictDeserialize for GameItemData {}
+
+/// Assembles AluVM library with validation scripts used by the game item
+/// schema.
+// `add` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn game_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Checking that the item data are kept unchanged.
+        put     a16[0],0;
+        ldp     OS_ITEM,a16[0],s16[0];
+        lds     OS_ITEM,a16[0],s16[1];
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        eq      s16[0],s16[1];
+        test;
+        ret;
+
+        // SUBROUTINE Item use validation
+        put     a16[0],0;
+        ldp     OS_ITEM,a16[0],s16[0];
+        lds     OS_ITEM,a16[0],s16[1];
+        put     a8[0],ERRNO_INVALID_ITEM_STATE;
+        // Checking that the level is kept unchanged.
+        put     a16[3],6;
+        extr    s16[0],a8[1],a16[3];
+        extr    s16[1],a8[2],a16[3];
+        eq.n    a8[1],a8[2];
+        test;
+        // Reading old and new durability.
+        put     a16[3],2;
+        extr    s16[0],a16[6],a16[3];
+        extr    s16[1],a16[7],a16[3];
+        // Checking that the item is not worn out.
+        put     a16[8],0;
+        gt.u    a16[6],a16[8];
+        test;
+        // Checking that new durability == old durability - 1.
+        put     a16[9],1;
+        sub.uc  a16[6],a16[9];
+        eq.n    a16[9],a16[7];
+        test;
+        jmp     FN_ITEM_SHARED_OFFSET;
+
+        // SUBROUTINE Item repair validation
+        put     a16[0],0;
+        ldp     OS_ITEM,a16[0],s16[0];
+        lds     OS_ITEM,a16[0],s16[1];
+        put     a8[0],ERRNO_INVALID_ITEM_STATE;
+        // Checking that the level is kept unchanged.
+        put     a16[3],6;
+        extr    s16[0],a8[1],a16[3];
+        extr    s16[1],a8[2],a16[3];
+        eq.n    a8[1],a8[2];
+        test;
+        // Checking that new durability == max durability.
+        put     a16[3],2;
+        extr    s16[1],a16[7],a16[3];
+        put     a16[3],4;
+        extr    s16[1],a16[4],a16[3];
+        eq.n    a16[7],a16[4];
+        test;
+        jmp     FN_ITEM_SHARED_OFFSET;
+
+        // SUBROUTINE Item upgrade validation
+        put     a16[0],0;
+        ldp     OS_ITEM,a16[0],s16[0];
+        lds     OS_ITEM,a16[0],s16[1];
+        put     a8[0],ERRNO_INVALID_ITEM_STATE;
+        // Checking that the durability is kept unchanged.
+        put     a16[3],2;
+        extr    s16[0],a16[6],a16[3];
+        extr    s16[1],a16[7],a16[3];
+        eq.n    a16[6],a16[7];
+        test;
+        // Reading old and new levels.
+        put     a16[3],6;
+        extr    s16[0],a8[1],a16[3];
+        extr    s16[1],a8[2],a16[3];
+        // Reading the maximum level.
+        put     a8[4],0;
+        ldg     GS_MAX_LEVEL,a8[4],s16[2];
+        extr    s16[2],a8[3],a16[0];
+        // Checking that old level < max level.
+        lt.u    a8[1],a8[3];
+        test;
+        // Checking that new level == old level + 1.
+        put     a8[5],1;
+        add.uc  a8[1],a8[5];
+        eq.n    a8[5],a8[2];
+        test;
+        jmp     FN_ITEM_SHARED_OFFSET;
+
+        // SUBROUTINE Shared code
+        // Checking that the item type is kept unchanged.
+        extr    s16[0],a16[1],a16[0];
+        extr    s16[1],a16[2],a16[0];
+        eq.n    a16[1],a16[2];
+        test;
+        // Checking that the max durability is kept unchanged.
+        put     a16[3],4;
+        extr    s16[0],a16[4],a16[3];
+        extr    s16[1],a16[5],a16[3];
+        eq.n    a16[4],a16[5];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong game item script")
+}
+
+fn game_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+    let rgb21_types = StandardTypes::with(Rgb21::stl());
+
+    let alu_lib = game_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_TRANSFER_ITEM_OFFSET as usize + 4], INSTR_LDP);
+    assert_eq!(code[FN_USE_ITEM_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_USE_ITEM_OFFSET as usize + 52], INSTR_SUB);
+    assert_eq!(code[FN_REPAIR_ITEM_OFFSET as usize + 8], INSTR_LDS);
+    assert_eq!(code[FN_UPGRADE_ITEM_OFFSET as usize + 44], INSTR_LDG);
+    assert_eq!(state_arg(FN_UPGRADE_ITEM_OFFSET + 44), GS_MAX_LEVEL.to_inner());
+    assert_eq!(code[FN_ITEM_SHARED_OFFSET as usize], INSTR_EXTR);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("GameItemSchema"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_TOKENS => GlobalStateSchema::once(rgb21_types.get("RGB21.TokenData")),
+            GS_ATTACH => GlobalStateSchema::once(rgb21_types.get("RGB21.AttachmentType")),
+            GS_GAME_ID => GlobalStateSchema::once(types.get("RGBContract.Details")),
+            GS_MAX_LEVEL => GlobalStateSchema::many(types.get("RGBSchemata.ItemLevel")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ITEM => OwnedStateSchema::Structured(types.get("RGBSchemata.GameItemData")),
+            OS_GAME_AUTHORITY => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::NoneOrOnce,
+                GS_ATTACH => Occurrences::NoneOrOnce,
+                GS_GAME_ID => Occurrences::Once,
+                GS_MAX_LEVEL => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ITEM => Occurrences::OnceOrMore,
+                OS_GAME_AUTHORITY => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: None,
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER_ITEM => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ITEM => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_ITEM => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_TRANSFER_ITEM_OFFSET, alu_id))
+            },
+            TS_USE_ITEM => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ITEM => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_ITEM => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_USE_ITEM_OFFSET, alu_id))
+            },
+            TS_REPAIR_ITEM => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ITEM => Occurrences::Once,
+                    OS_GAME_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_ITEM => Occurrences::Once,
+                    OS_GAME_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_REPAIR_ITEM_OFFSET, alu_id))
+            },
+            TS_UPGRADE_ITEM => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_MAX_LEVEL => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_ITEM => Occurrences::Once,
+                    OS_GAME_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_ITEM => Occurrences::Once,
+                    OS_GAME_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_UPGRADE_ITEM_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn game_rgb21() -> IfaceImpl {
+    let schema = game_schema();
+    let iface = Rgb21::iface(GameItem::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_TOKENS, fname!("tokens")),
+            NamedField::with(GS_ATTACH, fname!("attachmentTypes")),
+            NamedField::with(GS_GAME_ID, fname!("gameId")),
+            NamedField::with(GS_MAX_LEVEL, fname!("maxLevel")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ITEM, fname!("assetOwner")),
+            NamedField::with(OS_GAME_AUTHORITY, fname!("gameAuthority")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER_ITEM, fname!("transfer")),
+            NamedField::with(TS_USE_ITEM, fname!("useItem")),
+            NamedField::with(TS_REPAIR_ITEM, fname!("repairItem")),
+            NamedField::with(TS_UPGRADE_ITEM, fname!("upgradeItem")),
+        },
+        extensions: none!(),
+        errors: tiny_bset! {
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualValues")),
+        },
+    }
+}
+
+/// In-game items with durability and level mechanics.
+pub struct GameItem;
+
+impl IssuerWrapper for GameItem {
+    const FEATURES: rgb21::Features = rgb21::Features::NONE;
+    type IssuingIface = Rgb21;
+
+    fn schema() -> Schema { game_schema() }
+    fn issue_impl() -> IfaceImpl { game_rgb21() }
+
+    fn types() -> TypeSystem {
+        let mut types = StandardTypes::with(rgb_schemata_stl()).type_system();
+        types
+            .extend(StandardTypes::with(Rgb21::stl()).type_system())
+            .expect("game item type system is too large");
+        types
+    }
+
+    fn scripts() -> Scripts {
+        let lib = game_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::stl::Details;
+
+    use super::*;
+    use crate::test_helpers::{
+        asset_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb21::iface(GameItem::FEATURES);
+        if let Err(err) = game_rgb21().check(&iface, &game_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid game item RGB21 interface implementation");
+        }
+    }
+
+    #[test]
+    fn item_layout() {
+        // Item scripts read the item type at offset 0, durability at offset 2, max
+        // durability at offset 4 and the level at offset 6
+        let item = GameItemData {
+            item_type: 0x0102,
+            durability: 0x0304,
+            max_durability: 0x0506,
+            level: 7,
+        };
+        let data = item.to_strict_serialized::<8>().unwrap();
+        assert_eq!(data.as_slice(), &[0x02, 0x01, 0x04, 0x03, 0x06, 0x05, 7]);
+    }
+
+    fn sword(durability: u16, level: u8) -> GameItemData {
+        GameItemData {
+            item_type: 1,
+            durability,
+            max_durability: 100,
+            level,
+        }
+    }
+
+    /// Issues the `item` together with the game authority.
+    fn contract(item: GameItemData) -> Contract {
+        asset_genesis(issuer::<GameItem>(), spec("SWORD", "Sword"))
+            .add_global_state("gameId", Details::from("Example Game"))
+            .unwrap()
+            .add_global_state("maxLevel", ItemLevel::from(10))
+            .unwrap()
+            .add_data("assetOwner", seal(0), item)
+            .unwrap()
+            .add_rights("gameAuthority", seal(1))
+            .unwrap()
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    #[test]
+    fn game_genesis() { contract(sword(100, 1)); }
+
+    #[test]
+    fn transfer() {
+        let contract = contract(sword(100, 1));
+        let transfer = |item: GameItemData| {
+            let transition = spend_genesis::<GameItem>(&contract, "transfer")
+                .add_data("assetOwner", graph_seal(0), item)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &GameItem::schema(), &transition)
+        };
+
+        let status = transfer(sword(100, 1));
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(sword(100, 2))), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn use_item() {
+        let use_item = |owned: GameItemData, used: GameItemData| {
+            let contract = contract(owned);
+            let transition = spend_genesis::<GameItem>(&contract, "useItem")
+                .add_data("assetOwner", graph_seal(0), used)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &GameItem::schema(), &transition)
+        };
+
+        let status = use_item(sword(100, 1), sword(99, 1));
+        assert!(status.failures.is_empty(), "{status}");
+        let status = use_item(sword(100, 1), sword(100, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_ITEM_STATE));
+        let status = use_item(sword(100, 1), sword(99, 2));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_ITEM_STATE));
+        let status = use_item(sword(0, 1), sword(0, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_ITEM_STATE));
+    }
+
+    #[test]
+    fn repair_item() {
+        let contract = contract(sword(10, 1));
+        let repair = |item: GameItemData| {
+            let transition = spend_genesis::<GameItem>(&contract, "repairItem")
+                .add_data("assetOwner", graph_seal(0), item)
+                .unwrap()
+                .add_rights("gameAuthority", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &GameItem::schema(), &transition)
+        };
+
+        let status = repair(sword(100, 1));
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(repair(sword(50, 1))), Some(ERRNO_INVALID_ITEM_STATE));
+        let reforged = GameItemData {
+            max_durability: 200,
+            ..sword(200, 1)
+        };
+        assert_eq!(script_errno(repair(reforged)), Some(ERRNO_INVALID_ITEM_STATE));
+    }
+
+    #[test]
+    fn upgrade_item() {
+        let upgrade = |level: u8, upgraded: GameItemData| {
+            let contract = contract(sword(100, level));
+            let transition = spend_genesis::<GameItem>(&contract, "upgradeItem")
+                .add_global_state("maxLevel", ItemLevel::from(10))
+                .unwrap()
+                .add_data("assetOwner", graph_seal(0), upgraded)
+                .unwrap()
+                .add_rights("gameAuthority", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &GameItem::schema(), &transition)
+        };
+
+        let status = upgrade(1, sword(100, 2));
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(upgrade(1, sword(100, 3))), Some(ERRNO_INVALID_ITEM_STATE));
+        assert_eq!(script_errno(upgrade(1, sword(90, 2))), Some(ERRNO_INVALID_ITEM_STATE));
+        assert_eq!(script_errno(upgrade(10, sword(100, 11))), Some(ERRNO_INVALID_ITEM_STATE));
+    }
+}
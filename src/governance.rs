@@ -0,0 +1,620 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Governance token schema implementing RGB20 fungible assets interface with
+//! vote delegation and proposal lifecycle.
+//!
+//! Token holders delegate their balance by converting it into vote weight
+//! assigned to a delegate and a delegation record, which is required to convert
+//! the weight back into tokens. Vote weight is used to create proposals and to
+//! cast ballots; proposals are tracked as structured owned state which can only
+//! move from the open to the closed status.
+//!
+//! The scripts guarantee that each ballot is backed by exactly the vote weight
+//! spent in the voting operation and that a single ballot is produced per
+//! operation. Since they see only the operation being validated (see [consensus
+//! limitations](crate#consensus-limitations)), checking that the
+//! same (re-assigned) weight is not used twice for the same proposal is left to
+//! the vote tallying software.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use bp::Outpoint;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDP, INSTR_LDS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, Amount, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PRECISION,
+    GS_TERMS, OS_ASSET, TS_TRANSFER,
+};
+
+/// Number of the last created proposal.
+pub const GS_PROPOSAL_COUNT: GlobalStateType = GlobalStateType::with(2400);
+
+/// Fungible vote weight, issued in the amount of the delegated token balance.
+pub const OS_VOTE_WEIGHT: AssignmentType = AssignmentType::with(4400);
+/// Record of a delegation ([`VoteDelegation`]), required to undelegate the vote
+/// weight.
+pub const OS_DELEGATED_VOTES: AssignmentType = AssignmentType::with(4401);
+/// Proposal and its lifecycle status ([`Proposal`]).
+pub const OS_PROPOSAL: AssignmentType = AssignmentType::with(4402);
+/// Ballot cast for a proposal ([`Ballot`]).
+pub const OS_BALLOT: AssignmentType = AssignmentType::with(4403);
+
+pub const TS_DELEGATE: TransitionType = TransitionType::with(10400);
+pub const TS_UNDELEGATE: TransitionType = TransitionType::with(10401);
+pub const TS_VOTE: TransitionType = TransitionType::with(10402);
+pub const TS_CREATE_PROPOSAL: TransitionType = TransitionType::with(10403);
+pub const TS_CLOSE_PROPOSAL: TransitionType = TransitionType::with(10404);
+
+/// Proposal number or status transition is invalid. Not a part of RGB20
+/// interface errors.
+pub const ERRNO_INVALID_PROPOSAL: u8 = 40;
+
+pub(crate) const FN_GOVERNANCE_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_GOVERNANCE_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+pub const FN_DELEGATE_OFFSET: u16 = FN_GOVERNANCE_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 2;
+pub const FN_UNDELEGATE_OFFSET: u16 = FN_DELEGATE_OFFSET + 4 + 4 + 4 + 3 + 3 + 3 + 2;
+pub const FN_VOTE_OFFSET: u16 = FN_UNDELEGATE_OFFSET + 4 + 4 + 4 + 3 + 3 + 3 + 2;
+pub const FN_CREATE_PROPOSAL_OFFSET: u16 = FN_VOTE_OFFSET + 4 + 3 + 4 + 4 + 3 + 3 + 2;
+pub const FN_CLOSE_PROPOSAL_OFFSET: u16 =
+    FN_CREATE_PROPOSAL_OFFSET + 4 + 3 + 1 + 4 + 4 + 4 + 4 + 3 + 4 + 3 + 3 + 1 + 4 + 3 + 2 + 2;
+
+/// Lifecycle status of a governance proposal.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, tags = repr, into_u8, try_from_u8)]
+#[repr(u8)]
+pub enum ProposalStatus {
+    #[strict_type(dumb)]
+    Open = 0,
+    Closed = 1,
+}
+
+/// Governance proposal, kept as [`OS_PROPOSAL`] owned state.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct Proposal {
+    /// Proposal number, matching [`GS_PROPOSAL_COUNT`] of the operation
+    /// creating the proposal.
+    pub id: u64,
+    pub status: ProposalStatus,
+}
+impl StrictSerialize for Proposal {}
+impl StrictDeserialize for Proposal {}
+
+/// Delegation record, kept as [`OS_DELEGATED_VOTES`] owned state.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct VoteDelegation {
+    /// Amount of tokens converted into the vote weight.
+    pub amount: Amount,
+    /// Seal of the token holder who delegated the votes.
+    pub delegator: Outpoint,
+    /// Seal of the delegate receiving the vote weight.
+    pub delegate: Outpoint,
+}
+impl StrictSerialize for VoteDelegation {}
+impl StrictDeserialize for VoteDelegation {}
+
+/// Choice made by a ballot.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, tags = repr, into_u8, try_from_u8)]
+#[repr(u8)]
+pub enum VoteChoice {
+    #[strict_type(dumb)]
+    For = 0,
+    Against = 1,
+    Abstain = 2,
+}
+
+/// Ballot cast for a proposal, kept as [`OS_BALLOT`] owned state.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct Ballot {
+    /// Total vote weight spent by the voting operation.
+    pub weight: Amount,
+    pub proposal: u64,
+    pub choice: VoteChoice,
+}
+impl StrictSerialize for Ballot {}
+impl StrictDeserialize for Ballot {}
+
+/// Assembles AluVM library with validation scripts used by the governance token
+/// schema.
+pub fn governance_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the issued supply matches the sum of allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Delegation validation
+        // Checking that the spent tokens, the delegated amount and the issued vote weight match.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        // Read delegated amount into a64[0]
+        lds     OS_DELEGATED_VOTES,a16[0],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_ASSET;
+        pcas    OS_VOTE_WEIGHT;
+        test;
+        ret;
+
+        // SUBROUTINE Undelegation validation
+        // Checking that the delegated amount of vote weight is returned in exchange for tokens.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        // Read delegated amount into a64[0]
+        ldp     OS_DELEGATED_VOTES,a16[0],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_VOTE_WEIGHT;
+        pcas    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Vote validation
+        // Checking that the vote weight is preserved and the ballot is backed by all of it.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_VOTE_WEIGHT;
+        put     a16[0],0;
+        // Read ballot weight into a64[0]
+        lds     OS_BALLOT,a16[0],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_VOTE_WEIGHT;
+        test;
+        ret;
+
+        // SUBROUTINE Proposal creation validation
+        // Checking that the proposer keeps the vote weight, and the new proposal is open and
+        // numbered with the proposal count.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_VOTE_WEIGHT;
+        test;
+        put     a8[0],ERRNO_INVALID_PROPOSAL;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read proposal count into a64[0]
+        ldg     GS_PROPOSAL_COUNT,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        // Read proposal number into a64[1]
+        lds     OS_PROPOSAL,a16[0],s16[1];
+        extr    s16[1],a64[1],a16[0];
+        eq.n    a64[0],a64[1];
+        test;
+        // Read proposal status into a8[2]
+        put     a16[1],8;
+        extr    s16[1],a8[2],a16[1];
+        ifz     a8[2];
+        test;
+        ret;
+
+        // SUBROUTINE Proposal closing validation
+        // Checking that an open proposal is closed under the same number.
+        put     a8[0],ERRNO_INVALID_PROPOSAL;
+        put     a16[0],0;
+        put     a16[1],8;
+        ldp     OS_PROPOSAL,a16[0],s16[0];
+        lds     OS_PROPOSAL,a16[0],s16[1];
+        extr    s16[0],a64[0],a16[0];
+        extr    s16[1],a64[1],a16[0];
+        eq.n    a64[0],a64[1];
+        test;
+        extr    s16[0],a8[2],a16[1];
+        ifz     a8[2];
+        test;
+        extr    s16[1],a8[2],a16[1];
+        put     a8[3],1;
+        eq.n    a8[2],a8[3];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong governance token script")
+}
+
+fn governance_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = governance_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_GOVERNANCE_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_GOVERNANCE_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_GOVERNANCE_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_GOVERNANCE_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_DELEGATE_OFFSET as usize + 8], INSTR_LDS);
+    assert_eq!(state_arg(FN_DELEGATE_OFFSET + 8), OS_DELEGATED_VOTES.to_inner());
+    assert_eq!(code[FN_UNDELEGATE_OFFSET as usize + 8], INSTR_LDP);
+    assert_eq!(state_arg(FN_UNDELEGATE_OFFSET + 8), OS_DELEGATED_VOTES.to_inner());
+    assert_eq!(code[FN_VOTE_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_VOTE_OFFSET + 4), OS_VOTE_WEIGHT.to_inner());
+    assert_eq!(code[FN_VOTE_OFFSET as usize + 18], INSTR_PCPS);
+    assert_eq!(code[FN_CREATE_PROPOSAL_OFFSET as usize + 20], INSTR_LDG);
+    assert_eq!(state_arg(FN_CREATE_PROPOSAL_OFFSET + 20), GS_PROPOSAL_COUNT.to_inner());
+    assert_eq!(code[FN_CREATE_PROPOSAL_OFFSET as usize + 27], INSTR_LDS);
+    assert_eq!(state_arg(FN_CREATE_PROPOSAL_OFFSET + 27), OS_PROPOSAL.to_inner());
+    assert_eq!(code[FN_CLOSE_PROPOSAL_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_CLOSE_PROPOSAL_OFFSET as usize + 12], INSTR_LDP);
+    assert_eq!(state_arg(FN_CLOSE_PROPOSAL_OFFSET + 12), OS_PROPOSAL.to_inner());
+    assert_eq!(code[FN_CLOSE_PROPOSAL_OFFSET as usize + 20], INSTR_EXTR);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("GovernanceToken"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_PROPOSAL_COUNT => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_VOTE_WEIGHT => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_DELEGATED_VOTES =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.VoteDelegation")),
+            OS_PROPOSAL => OwnedStateSchema::Structured(types.get("RGBSchemata.Proposal")),
+            OS_BALLOT => OwnedStateSchema::Structured(types.get("RGBSchemata.Ballot")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_GOVERNANCE_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_GOVERNANCE_TRANSFER_OFFSET, alu_id))
+            },
+            TS_DELEGATE => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_VOTE_WEIGHT => Occurrences::OnceOrMore,
+                    OS_DELEGATED_VOTES => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_DELEGATE_OFFSET, alu_id))
+            },
+            TS_UNDELEGATE => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_VOTE_WEIGHT => Occurrences::OnceOrMore,
+                    OS_DELEGATED_VOTES => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_UNDELEGATE_OFFSET, alu_id))
+            },
+            TS_VOTE => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_VOTE_WEIGHT => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_VOTE_WEIGHT => Occurrences::OnceOrMore,
+                    OS_BALLOT => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_VOTE_OFFSET, alu_id))
+            },
+            TS_CREATE_PROPOSAL => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_PROPOSAL_COUNT => Occurrences::Once
+                },
+                inputs: tiny_bmap! {
+                    OS_VOTE_WEIGHT => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_VOTE_WEIGHT => Occurrences::OnceOrMore,
+                    OS_PROPOSAL => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_CREATE_PROPOSAL_OFFSET, alu_id))
+            },
+            TS_CLOSE_PROPOSAL => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_PROPOSAL => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_PROPOSAL => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_CLOSE_PROPOSAL_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn governance_rgb20() -> IfaceImpl {
+    let schema = governance_schema();
+    let iface = Rgb20::iface(GovernanceToken::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_PROPOSAL_COUNT, fname!("proposalCount")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+            NamedField::with(OS_VOTE_WEIGHT, fname!("voteWeight")),
+            NamedField::with(OS_DELEGATED_VOTES, fname!("delegatedVotes")),
+            NamedField::with(OS_PROPOSAL, fname!("proposal")),
+            NamedField::with(OS_BALLOT, fname!("ballot")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_DELEGATE, fname!("delegate")),
+            NamedField::with(TS_UNDELEGATE, fname!("undelegate")),
+            NamedField::with(TS_VOTE, fname!("vote")),
+            NamedField::with(TS_CREATE_PROPOSAL, fname!("createProposal")),
+            NamedField::with(TS_CLOSE_PROPOSAL, fname!("closeProposal")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Governance token issuer.
+///
+/// Since vote weight is a fungible state, its asset tag must be added to the
+/// genesis at issuance (`voteWeight` field of the contract builder).
+pub struct GovernanceToken;
+
+impl IssuerWrapper for GovernanceToken {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { governance_schema() }
+    fn issue_impl() -> IfaceImpl { governance_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = governance_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::AssetTag;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, issuer_with_assignments, script_errno, seal, spec,
+        spend_genesis, txid, validate_transition,
+    };
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(GovernanceToken::FEATURES);
+        if let Err(err) = governance_rgb20().check(&iface, &governance_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid governance token RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn proposal_layout() {
+        // Scripts read proposal number from the first 8 bytes and its status from the
+        // ninth byte
+        let proposal = Proposal {
+            id: 0x0102,
+            status: ProposalStatus::Closed,
+        };
+        let data = proposal.to_strict_serialized::<16>().unwrap();
+        assert_eq!(data.as_slice(), &[0x02, 0x01, 0, 0, 0, 0, 0, 0, 1]);
+        let ballot = Ballot {
+            weight: Amount::from(500u64),
+            proposal: 1,
+            choice: VoteChoice::Against,
+        };
+        let data = ballot.to_strict_serialized::<32>().unwrap();
+        assert_eq!(&data[..8], &500u64.to_le_bytes());
+    }
+
+    /// Issues governance token with 1000 tokens, optionally granting `votes` of
+    /// vote weight.
+    fn contract(issued: u64, votes: Option<u64>) -> Option<Contract> {
+        let builder = match votes {
+            Some(_) => issuer_with_assignments::<GovernanceToken>(&[OS_VOTE_WEIGHT]),
+            None => issuer::<GovernanceToken>(),
+        };
+        let mut builder = fungible_genesis(builder, spec("GOV", "Governance token"), issued)
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_asset_tag("voteWeight", AssetTag::new_random("governance", OS_VOTE_WEIGHT))
+            .unwrap();
+        if let Some(votes) = votes {
+            builder = builder
+                .add_fungible_state("voteWeight", seal(1), votes)
+                .unwrap();
+        }
+        let contract = builder.issue_contract().ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn governance_genesis() {
+        assert!(contract(1000, None).is_some());
+        assert!(contract(999, None).is_none());
+    }
+
+    #[test]
+    fn delegate() {
+        let contract = contract(1000, None).unwrap();
+        let delegate = |weight: u64, amount: u64| {
+            let delegation = VoteDelegation {
+                amount: Amount::from(amount),
+                delegator: Outpoint::new(txid(), 0),
+                delegate: Outpoint::new(txid(), 1),
+            };
+            let transition = spend_genesis::<GovernanceToken>(&contract, "delegate")
+                .add_fungible_state("voteWeight", graph_seal(0), weight)
+                .unwrap()
+                .add_data("delegatedVotes", graph_seal(1), delegation)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &GovernanceToken::schema(), &transition)
+        };
+
+        let status = delegate(1000, 1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(delegate(900, 1000)), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(delegate(900, 900)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn vote() {
+        let contract = contract(1000, Some(500)).unwrap();
+        let vote = |weight: u64| {
+            let ballot = Ballot {
+                weight: Amount::from(weight),
+                proposal: 1,
+                choice: VoteChoice::For,
+            };
+            let transition = spend_genesis::<GovernanceToken>(&contract, "vote")
+                .add_fungible_state("voteWeight", graph_seal(0), 500u64)
+                .unwrap()
+                .add_data("ballot", graph_seal(1), ballot)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &GovernanceToken::schema(), &transition)
+        };
+
+        let status = vote(500);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(vote(400)), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(vote(600)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn create_proposal() {
+        let contract = contract(1000, Some(500)).unwrap();
+        let create = |weight: u64, count: u64, id: u64| {
+            let proposal = Proposal {
+                id,
+                status: ProposalStatus::Open,
+            };
+            let transition = spend_genesis::<GovernanceToken>(&contract, "createProposal")
+                .add_global_state("proposalCount", Amount::from(count))
+                .unwrap()
+                .add_fungible_state("voteWeight", graph_seal(0), weight)
+                .unwrap()
+                .add_data("proposal", graph_seal(1), proposal)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &GovernanceToken::schema(), &transition)
+        };
+
+        let status = create(500, 1, 1);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(create(500, 1, 2)), Some(ERRNO_INVALID_PROPOSAL));
+        assert_eq!(script_errno(create(1000, 1, 1)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+}
@@ -0,0 +1,352 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Identity schema implementing RGB22 decentralized identity interface.
+//!
+//! The registry, identified by its own decentralized identifier
+//! ([`GS_REGISTRY_ID`]) and public key ([`GS_REGISTRY_KEY`]), holds the
+//! registry authority ([`OS_REGISTRY_AUTHORITY`]), which it spends to issue
+//! identity claims ([`OS_IDENTITY_CLAIM`]) with [`TS_ISSUE_IDENTITY`] and to
+//! revoke them with [`TS_REVOKE_IDENTITY`]. Identity claims are
+//! non-transferable: the schema has no transfer, and the holder of a claim may
+//! only replace the hash of its metadata with [`TS_UPDATE_METADATA`], keeping
+//! the identifier and the public key of the claim unchanged.
+//!
+//! Since revocation spends the claim, it requires the claim holder to co-sign
+//! the revoking witness transaction; wallets should treat claims as revoked
+//! once the registry announces the revocation.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{IssuerWrapper, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDP, INSTR_LDS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, OwnedStateSchema, TransitionType};
+use strict_types::TypeSystem;
+
+use crate::rgb22::Rgb22;
+use crate::stl::rgb_schemata_stl;
+
+/// Decentralized identifier of the identity registry ([`crate::Did`]).
+pub const GS_REGISTRY_ID: GlobalStateType = GlobalStateType::with(3900);
+/// Public key of the identity registry ([`crate::IssuerKey`]).
+pub const GS_REGISTRY_KEY: GlobalStateType = GlobalStateType::with(3901);
+
+/// Non-transferable identity claim ([`crate::IdentityClaim`]).
+pub const OS_IDENTITY_CLAIM: AssignmentType = AssignmentType::with(5800);
+/// Right of the registry to issue and revoke identity claims.
+pub const OS_REGISTRY_AUTHORITY: AssignmentType = AssignmentType::with(5801);
+
+pub const TS_ISSUE_IDENTITY: TransitionType = TransitionType::with(11900);
+pub const TS_REVOKE_IDENTITY: TransitionType = TransitionType::with(11901);
+pub const TS_UPDATE_METADATA: TransitionType = TransitionType::with(11902);
+
+/// Error raised when a metadata update changes anything but the metadata hash
+/// of the claim.
+pub const ERRNO_IMMUTABLE_CLAIM: u8 = 51;
+
+pub const FN_UPDATE_METADATA_OFFSET: u16 = 0;
+pub(crate) const FN_UPDATE_METADATA_LOOP_OFFSET: u16 =
+    FN_UPDATE_METADATA_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 3 + 1 + 4 + 3;
+pub(crate) const FN_UPDATE_METADATA_END_OFFSET: u16 =
+    FN_UPDATE_METADATA_LOOP_OFFSET + 3 + 3 + 3 + 3 + 3 + 1 + 3 + 3;
+
+/// Assembles AluVM library with validation scripts used by the identity schema.
+// `sub` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn identity_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Metadata update validation
+        put     a8[0],ERRNO_IMMUTABLE_CLAIM;
+        put     a16[0],0;
+        // Read the previous claim into s16[0] and the updated claim into s16[1]
+        ldp     OS_IDENTITY_CLAIM,a16[0],s16[0];
+        lds     OS_IDENTITY_CLAIM,a16[0],s16[1];
+        len     s16[0],a16[1];
+        len     s16[1],a16[2];
+        eq.n    a16[1],a16[2];
+        test;
+        // The metadata hash takes the last 32 bytes; everything before it must be unchanged,
+        // which is checked byte by byte since AluVM can't split strings.
+        put     a16[2],32;
+        sub.uc  a16[1],a16[2];
+        eq.n    a16[0],a16[2];
+        jif     FN_UPDATE_METADATA_END_OFFSET;
+        extr    s16[0],a8[1],a16[0];
+        extr    s16[1],a8[2],a16[0];
+        eq.n    a8[1],a8[2];
+        test;
+        add     a16[0],1;
+        jmp     FN_UPDATE_METADATA_LOOP_OFFSET;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong identity script")
+}
+
+fn identity_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = identity_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_UPDATE_METADATA_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_UPDATE_METADATA_OFFSET as usize + 8], INSTR_LDP);
+    assert_eq!(state_arg(FN_UPDATE_METADATA_OFFSET + 8), OS_IDENTITY_CLAIM.to_inner());
+    assert_eq!(code[FN_UPDATE_METADATA_OFFSET as usize + 12], INSTR_LDS);
+    assert_eq!(state_arg(FN_UPDATE_METADATA_OFFSET + 12), OS_IDENTITY_CLAIM.to_inner());
+    assert_eq!(code[FN_UPDATE_METADATA_LOOP_OFFSET as usize + 6], INSTR_EXTR);
+    assert_eq!(code.len(), FN_UPDATE_METADATA_END_OFFSET as usize + 1);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("DecentralizedIdentity"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_REGISTRY_ID => GlobalStateSchema::once(types.get("RGBSchemata.Did")),
+            GS_REGISTRY_KEY => GlobalStateSchema::once(types.get("RGBSchemata.IssuerKey")),
+        },
+        owned_types: tiny_bmap! {
+            OS_IDENTITY_CLAIM =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.IdentityClaim")),
+            OS_REGISTRY_AUTHORITY => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_REGISTRY_ID => Occurrences::Once,
+                GS_REGISTRY_KEY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_IDENTITY_CLAIM => Occurrences::NoneOrMore,
+                OS_REGISTRY_AUTHORITY => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: None,
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_ISSUE_IDENTITY => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_REGISTRY_AUTHORITY => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_IDENTITY_CLAIM => Occurrences::OnceOrMore,
+                    OS_REGISTRY_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: None,
+            },
+            TS_REVOKE_IDENTITY => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_IDENTITY_CLAIM => Occurrences::OnceOrMore,
+                    OS_REGISTRY_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_REGISTRY_AUTHORITY => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: None,
+            },
+            TS_UPDATE_METADATA => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_IDENTITY_CLAIM => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_IDENTITY_CLAIM => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_UPDATE_METADATA_OFFSET, alu_id)),
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn identity_rgb22() -> IfaceImpl {
+    let schema = identity_schema();
+    let iface = Rgb22::iface(IdentityIssuer::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_REGISTRY_ID, fname!("registryId")),
+            NamedField::with(GS_REGISTRY_KEY, fname!("registryKey")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_IDENTITY_CLAIM, fname!("identityClaim")),
+            NamedField::with(OS_REGISTRY_AUTHORITY, fname!("registryAuthority")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_ISSUE_IDENTITY, fname!("issueIdentity")),
+            NamedField::with(TS_REVOKE_IDENTITY, fname!("revokeIdentity")),
+            NamedField::with(TS_UPDATE_METADATA, fname!("updateMetadata")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![NamedVariant::with(ERRNO_IMMUTABLE_CLAIM, vname!("immutableClaim")),],
+    }
+}
+
+/// Registry of non-transferable decentralized identity claims.
+pub struct IdentityIssuer;
+
+impl IssuerWrapper for IdentityIssuer {
+    const FEATURES: () = ();
+    type IssuingIface = Rgb22;
+
+    fn schema() -> Schema { identity_schema() }
+    fn issue_impl() -> IfaceImpl { identity_rgb22() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = identity_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use rgbstd::containers::Contract;
+    use strict_encoding::StrictSerialize;
+
+    use super::*;
+    use crate::test_helpers::{
+        graph_seal, issuer, script_errno, seal, spend_genesis, validate_transition,
+    };
+    use crate::{Did, IdentityClaim, IssuerKey};
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb22::iface(IdentityIssuer::FEATURES);
+        if let Err(err) = identity_rgb22().check(&iface, &identity_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid identity RGB22 interface implementation");
+        }
+    }
+
+    #[test]
+    fn non_transferable() {
+        let schema = identity_schema();
+        assert_eq!(schema.transitions.len(), 3);
+        for transition in schema.transitions.values() {
+            assert!(
+                !transition.inputs.contains_key(&OS_IDENTITY_CLAIM) ||
+                    !transition.assignments.contains_key(&OS_IDENTITY_CLAIM) ||
+                    transition.validator ==
+                        Some(LibSite::with(FN_UPDATE_METADATA_OFFSET, identity_lib().id()))
+            );
+        }
+        let revoke = &schema.transitions[&TS_REVOKE_IDENTITY];
+        assert!(revoke.inputs.contains_key(&OS_REGISTRY_AUTHORITY));
+    }
+
+    #[test]
+    fn claim_layout() {
+        // Metadata update script treats the last 32 bytes of the claim as the metadata
+        // hash
+        let data = claim("did:rgb:alice", 7)
+            .to_strict_serialized::<512>()
+            .unwrap();
+        assert_eq!(data.len(), 1 + 13 + 33 + 32);
+        assert_eq!(&data[data.len() - 32..], &[7u8; 32]);
+    }
+
+    fn claim(did: &str, metadata_hash: u8) -> IdentityClaim {
+        IdentityClaim {
+            did: Did::from_str(did).unwrap(),
+            public_key: [2u8; 33],
+            metadata_hash: [metadata_hash; 32],
+        }
+    }
+
+    /// Issues the identity claim of alice together with the registry authority.
+    fn contract() -> Contract {
+        issuer::<IdentityIssuer>()
+            .add_global_state("registryId", Did::from_str("did:rgb:registry").unwrap())
+            .unwrap()
+            .add_global_state("registryKey", IssuerKey::from([2u8; 33]))
+            .unwrap()
+            .add_data("identityClaim", seal(0), claim("did:rgb:alice", 0))
+            .unwrap()
+            .add_rights("registryAuthority", seal(1))
+            .unwrap()
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    #[test]
+    fn registry_genesis() { contract(); }
+
+    #[test]
+    fn update_metadata() {
+        let contract = contract();
+        let update = |claim: IdentityClaim| {
+            let transition = spend_genesis::<IdentityIssuer>(&contract, "updateMetadata")
+                .add_data("identityClaim", graph_seal(0), claim)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &IdentityIssuer::schema(), &transition)
+        };
+
+        let status = update(claim("did:rgb:alice", 7));
+        assert!(status.failures.is_empty(), "{status}");
+        let status = update(claim("did:rgb:alice", 0));
+        assert!(status.failures.is_empty(), "{status}");
+        let status = update(claim("did:rgb:carol", 7));
+        assert_eq!(script_errno(status), Some(ERRNO_IMMUTABLE_CLAIM));
+        let status = update(claim("did:rgb:bob", 7));
+        assert_eq!(script_errno(status), Some(ERRNO_IMMUTABLE_CLAIM));
+        let rekeyed = IdentityClaim {
+            public_key: [3u8; 33],
+            ..claim("did:rgb:alice", 7)
+        };
+        assert_eq!(script_errno(update(rekeyed)), Some(ERRNO_IMMUTABLE_CLAIM));
+    }
+}
@@ -19,24 +19,371 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! RGB schemata: contract schemata together with their interface
+//! implementations and AluVM validation libraries.
+//!
+//! # Consensus limitations
+//!
+//! Validation scripts run by RGB consensus see only the operation being
+//! validated and the state it spends. Thus, the schemata are designed around
+//! the rules the scripts can't check:
+//!
+//! - AluVM has no access to the mining height of the witness transaction, so
+//!   block heights and durations committed to by a contract are not checked
+//!   against the time of an operation;
+//! - AluVM can't verify signatures, so authorization by a key is represented by
+//!   spending an owned right assigned to a seal controlled by that key, while
+//!   signed data carried by an operation is verified by wallets;
+//! - AluVM has no Pedersen commitment arithmetics beyond balancing the amounts
+//!   of inputs against outputs, so commitments carried in the global state are
+//!   verified by wallets;
+//! - scripts can't read the global state of other operations nor the seals of
+//!   the assignments, so operations depending on the contract state repeat the
+//!   values they need, and wallets check them against the contract.
+//!
+//! The documentation of each schema lists which of its rules are left to
+//! wallets.
+//!
+//! Contract designs whose core rule falls under these limitations are not
+//! provided, since a schema can't promise what consensus doesn't check:
+//!
+//! - time-locked staking, where unstaking is valid only once the lock period
+//!   has passed since the stake height;
+//! - escrow with a refund timeout, where only the counterparty may claim before
+//!   the timeout and only the owner may take a refund after it;
+//! - vouchers which can't be redeemed after their expiry height;
+//! - subscriptions renewed by extending their expiry height by a period;
+//! - airdrops claimed with a Merkle proof against a root committed in the
+//!   genesis;
+//! - NFTs revoked by the issuer alone or not transferable past an expiry
+//!   height.
+//!
+//! # Platform support
+//!
+//! The crate requires `std`: RGB standard library, RGB consensus and strict
+//! types it is built upon have no `no_std` support. Library code doesn't read
+//! the system clock, so interface timestamps are fixed and the produced kits
+//! are reproducible.
+
 #[macro_use]
 extern crate amplify;
 #[macro_use]
 extern crate strict_types;
 
+use rgbstd::{
+    AssignmentType, ExtensionType, GlobalStateType, MetaType, TransitionType, ValencyType,
+};
+
+mod audit;
+mod basket;
+mod bond;
+mod bridge;
+mod builder;
+mod bundle;
+mod carbon;
+mod certificate;
 mod cfa;
+mod cia;
+mod comparator;
+mod compact_id;
+#[cfg(feature = "toml")]
+mod config;
+mod dividend;
+mod dual_asset;
+mod erased;
+mod gaming;
+mod governance;
+mod identity;
+mod licensing;
+mod locking;
+mod loyalty;
+mod multisig;
+mod music_rights;
 mod nia;
+mod nominal;
+mod oracle;
+mod partially_blinded;
+mod prediction_market;
+mod property_rights;
+mod registry;
+mod reissuance;
+mod rekey;
+mod reputation;
+mod rgb22;
+#[cfg(feature = "serde")]
+mod serde;
+mod snapshot;
+mod stl;
+mod synthetic;
+#[cfg(test)]
+mod test_helpers;
+mod tranche;
+mod type_registry;
 mod uda;
+mod validate;
+mod vesting;
+mod whitelist;
 
-pub use cfa::CollectibleFungibleAsset;
-pub use nia::NonInflatableAsset;
-use rgbstd::{AssignmentType, GlobalStateType, TransitionType};
-pub use uda::UniqueDigitalAsset;
+pub use audit::{
+    audit_schema, script_coverage_analysis, CoverageReport, GlobalStateField, OpcodeMismatch,
+    OwnedStateField, SchemaAuditReport, TransitionAuditEntry,
+};
+pub use basket::{
+    basket_lib, BasketAllocation, BasketComponent, BasketTokenIssuer, ERRNO_INVALID_BASKET,
+    FN_MINT_BASKET_OFFSET, FN_REDEEM_BASKET_OFFSET, GS_BASKET_COMPOSITION, GS_MINTED_SHARES,
+    GS_REDEEMED_SHARES, GS_UNDERLYING_AMOUNT, OS_BASKET_CUSTODIAN, OS_BASKET_SHARE, TS_MINT_BASKET,
+    TS_REDEEM_BASKET,
+};
+pub use bond::{
+    bond_lib, BondIssuer, BondNote, ERRNO_INVALID_BOND, FN_COUPON_PAYMENT_OFFSET,
+    FN_MATURITY_REDEMPTION_OFFSET, GS_COUPON_INTERVAL, GS_COUPON_RATE, GS_FACE_VALUE,
+    GS_MATURITY_HEIGHT, OS_BOND, TS_COUPON_PAYMENT, TS_MATURITY_REDEMPTION,
+};
+pub use bridge::{
+    bridge_lib, InvalidSourceChain, LockTxProof, RedemptionRequest, SourceChain, TokenBridgeSchema,
+    ERRNO_INVALID_BRIDGE_AMOUNT, FN_UNWRAP_OFFSET, FN_WRAP_OFFSET, GS_BRIDGE_CUSTODIAN_KEY,
+    GS_LOCK_TX_PROOF, GS_SOURCE_CHAIN, OS_BRIDGE_CUSTODIAN,
+    OS_REDEMPTION_RECEIPT as OS_BRIDGE_REDEMPTION_RECEIPT, OS_WRAPPED_ASSET, TS_UNWRAP, TS_WRAP,
+};
+pub use builder::{
+    BlindedSeal, CiaGenesisBuilder, GenesisBuilder, GenesisBuilderError, GenesisData,
+    NiaGenesisBuilder, UdaGenesisBuilder,
+};
+pub use bundle::{BundleLoadError, SchemaPublicationBundle};
+pub use carbon::{
+    carbon_lib, CarbonCredit, RetirementReceipt, VintageYear, FN_RETIRE_OFFSET, GS_PROJECT_ID,
+    GS_REGISTRY_NAME, GS_RETIRED_SUPPLY, GS_VINTAGE_YEAR, OS_RETIREMENT_RECEIPT, TS_RETIRE,
+};
+pub use certificate::{
+    certificate_lib, Certificate, CertificateSchema, ERRNO_INVALID_CERTIFICATE,
+    FN_RENEW_CERT_OFFSET, FN_REVOKE_CERT_OFFSET, GS_ISSUER_DID, OS_CERTIFICATE,
+    OS_CERTIFIER_AUTHORITY, OS_REVOKED_CERT, TS_ISSUE_CERT, TS_RENEW_CERT, TS_REVOKE_CERT,
+};
+pub use cfa::{
+    cfa_lib, cfa_schema, CollectibleFungibleAsset, GS_COLLECTION_DATA, GS_EDITION_SIZE,
+    OS_COLLECTION_ITEM,
+};
+pub use cia::{
+    check_scheduled_issuance, compute_total_issuance, register_cia_assignment_types,
+    register_cia_transition_types, supply_inflation_check, verify_inflation_proof, CiaSchemaError,
+    ComputeError, ContractInflatableAsset, InflationError, ScheduleEntry, ScheduleError,
+    ScheduleIndex, SupplySchedule, TotalIssuanceHistory,
+};
+pub use compact_id::{CompactSchemaId, CompactSchemaIdError, COMPACT_SCHEMA_ID_HRP};
+pub use comparator::{
+    Change, ChangeKind, OpRef, OpState, SchemaChange, SchemaChangeset, SchemaComparator,
+};
+#[cfg(feature = "toml")]
+pub use config::{
+    genesis_params_from_toml, NiaConfig, NiaConfigError, NiaGenesisParams, TomlParseError,
+    MAX_TICKER_LEN,
+};
+pub use dividend::{
+    dividend_lib, ClaimReceipt, DividendCredit, DividendToken, ERRNO_INVALID_DIVIDEND,
+    FN_CLAIM_DIVIDEND_OFFSET, FN_DISTRIBUTE_DIVIDENDS_OFFSET, GS_DIVIDEND_POOL, OS_CLAIM_RECEIPT,
+    OS_DIVIDEND_AUTHORITY, OS_DIVIDEND_CREDIT, TS_CLAIM_DIVIDEND, TS_DISTRIBUTE_DIVIDENDS,
+};
+pub use dual_asset::{
+    dual_asset_lib, DualAsset, FN_PRIMARY_TRANSFER_OFFSET, FN_SECONDARY_TRANSFER_OFFSET,
+    GS_PRIMARY_ISSUED_SUPPLY, GS_PRIMARY_NOMINAL, GS_SECONDARY_ISSUED_SUPPLY, GS_SECONDARY_NOMINAL,
+    OS_PRIMARY, OS_SECONDARY, TS_TRANSFER_PRIMARY, TS_TRANSFER_SECONDARY,
+};
+pub use erased::ErasedIssuerWrapper;
+pub use gaming::{
+    game_lib, GameItem, GameItemData, ItemLevel, ERRNO_INVALID_ITEM_STATE, FN_ITEM_SHARED_OFFSET,
+    FN_REPAIR_ITEM_OFFSET, FN_UPGRADE_ITEM_OFFSET, FN_USE_ITEM_OFFSET, GS_GAME_ID, GS_MAX_LEVEL,
+    OS_GAME_AUTHORITY, OS_ITEM, TS_REPAIR_ITEM, TS_TRANSFER_ITEM, TS_UPGRADE_ITEM, TS_USE_ITEM,
+};
+pub use governance::{
+    governance_lib, Ballot, GovernanceToken, Proposal, ProposalStatus, VoteChoice, VoteDelegation,
+    ERRNO_INVALID_PROPOSAL, FN_CLOSE_PROPOSAL_OFFSET, FN_CREATE_PROPOSAL_OFFSET,
+    FN_DELEGATE_OFFSET, FN_UNDELEGATE_OFFSET, FN_VOTE_OFFSET, GS_PROPOSAL_COUNT, OS_BALLOT,
+    OS_DELEGATED_VOTES, OS_PROPOSAL, OS_VOTE_WEIGHT, TS_CLOSE_PROPOSAL, TS_CREATE_PROPOSAL,
+    TS_DELEGATE, TS_UNDELEGATE, TS_VOTE,
+};
+pub use identity::{
+    identity_lib, IdentityIssuer, ERRNO_IMMUTABLE_CLAIM, FN_UPDATE_METADATA_OFFSET, GS_REGISTRY_ID,
+    GS_REGISTRY_KEY, OS_IDENTITY_CLAIM, OS_REGISTRY_AUTHORITY, TS_ISSUE_IDENTITY,
+    TS_REVOKE_IDENTITY, TS_UPDATE_METADATA,
+};
+pub use licensing::{
+    licensing_lib, License, LicensingSchema, FN_TRANSFER_LICENSE_OFFSET, GS_LICENSE_TERMS_HASH,
+    GS_LICENSOR_KEY, OS_LICENSE, OS_LICENSOR_AUTHORITY, TS_GRANT_LICENSE, TS_REVOKE_LICENSE,
+    TS_RIGHTS_TRANSFER, TS_TRANSFER_LICENSE,
+};
+pub use locking::{locking_lib, TokenLock, TokenLocking, FN_LOCK_OFFSET, FN_UNLOCK_OFFSET};
+pub use loyalty::{
+    loyalty_lib, LoyaltyPoints, RedemptionReceipt, FN_AWARD_POINTS_OFFSET, FN_REDEEM_POINTS_OFFSET,
+    GS_AWARDED_POINTS, GS_PROGRAM_NAME, GS_REDEMPTION_RATE, OS_ISSUER_AUTHORITY, OS_LOYALTY_POINTS,
+    OS_REDEMPTION_RECEIPT, TS_AWARD_POINTS, TS_REDEEM_POINTS,
+};
+pub use multisig::{
+    multisig_lib, MultiSigSchema, RequiredSigs, SignerRight, ERRNO_INSUFFICIENT_SIGNATURES,
+    ERRNO_INVALID_SIGNERS, FN_MULTISIG_FREEZE_OFFSET, FN_MULTISIG_GENESIS_OFFSET,
+    FN_MULTISIG_OFFSET, FN_MULTISIG_UNFREEZE_OFFSET, GS_REQUIRED_SIGS, GS_SIGNER_KEYS, MAX_SIGNERS,
+    OS_SIGNER_RIGHT,
+};
+pub use music_rights::{
+    music_rights_lib, InvalidIsrc, Isrc, MusicRights, RightsType, RoyaltyClaim,
+    ERRNO_INVALID_ROYALTY, FN_COLLECT_ROYALTY_OFFSET, GS_COMPOSER_IDENTITY, GS_ISRC,
+    GS_RIGHTS_TYPE, GS_TOTAL_ROYALTY_SHARES, OS_RIGHTS_UNIT, OS_ROYALTY_CLAIM, TS_COLLECT_ROYALTY,
+};
+pub use nia::{
+    check_allocation_sum, nia_base_lib, nia_bundle, nia_iface_impl, nia_iface_impl_with_features,
+    nia_lib, nia_schema, nia_schema_with_features, nia_schema_with_metadata_extensions,
+    privacy_nia_iface_impl, privacy_nia_schema, register_nia_assignment_types,
+    register_nia_transition_types, validate_precision, verify_burn_proof,
+    BurnableNonInflatableAsset, ExtensionError, NiaBundledArtifacts, NiaFeatures, NiaGenesisError,
+    NiaIssuanceParams, NiaSchemaError, NonInflatableAsset, OverflowError,
+    PrivacyPreservingNonInflatableAsset, SupplyCommitment, FN_ZERO_AMOUNT_GUARD_OFFSET,
+    MAX_PRECISION, NIA_SCHEMA_ID,
+};
+pub use nominal::{
+    compute_net_supply, nominal_lib, NetSupplyError, NominalAsset, FN_NOMINAL_BURN_OFFSET,
+    FN_NOMINAL_ISSUE_OFFSET, GS_NET_SUPPLY,
+};
+pub use oracle::{
+    oracle_transfer_lib, OracleAttestation, OracleTransfer, FN_ORACLE_TRANSFER_OFFSET,
+    MS_ORACLE_ATTESTATION, TS_ORACLE_TRANSFER,
+};
+pub use partially_blinded::{
+    partially_blinded_iface_impl, partially_blinded_schema, reveal_lib, PartiallyBlindedSchema,
+    RevealOpening, RevealedAsset, ERRNO_INVALID_REVEAL, FN_REVEAL_OFFSET, MS_REVEAL_OPENING,
+    OS_BLINDED_ASSET, OS_REVEALED_ASSET, TS_PARTIAL_REVEAL, TS_REVEAL,
+};
+pub use prediction_market::{
+    prediction_market_lib, MarketOutcome, PredictionMarketSchema, QuestionHash, ERRNO_INVALID_PAIR,
+    ERRNO_INVALID_REDEMPTION, FN_MINT_PAIR_OFFSET, FN_REDEEM_OFFSET, GS_MINTED_PAIRS, GS_OUTCOME,
+    GS_QUESTION_HASH, GS_REDEEMED_AMOUNT, GS_RESOLUTION_HEIGHT, OS_MINT_RIGHT, OS_NO_TOKEN,
+    OS_ORACLE_RIGHT, OS_YES_TOKEN, TS_MINT_PAIR, TS_REDEEM_WINNING, TS_RESOLVE, TS_TRANSFER_NO,
+    TS_TRANSFER_YES,
+};
+pub use property_rights::{
+    property_rights_lib, property_rights_rgb20, property_rights_schema, KycAttestation,
+    KycRequired, PropertyRightsToken, PropertyValue, ERRNO_KYC_MISMATCH, FN_KYC_TRANSFER_OFFSET,
+    GS_JURISDICTION, GS_KYC_REQUIRED, GS_PROPERTY_ADDRESS, GS_PROPERTY_TITLE_ID, GS_TOTAL_VALUE,
+    OS_KYC_ATTESTATION, OS_KYC_AUTHORITY, OS_PROPERTY_SHARE, TS_ATTEST_KYC,
+};
+pub use registry::SchemaRegistry;
+pub use reissuance::{
+    reissuance_lib, ReissuanceExtension, ERRNO_INVALID_REISSUANCE, ES_REISSUE, FN_REISSUE_OFFSET,
+    GS_EXTENSION_AUTHORITY, OS_REISSUED_ASSET,
+};
+pub use rekey::{
+    rekey_lib, IssuerRekey, RekeyProof, ERRNO_INVALID_REKEY, FN_REKEY_OFFSET,
+    GS_CURRENT_ISSUER_KEY, GS_REKEY_PROOF, OS_REKEY_RIGHT, TS_REKEY,
+};
+pub use reputation::{
+    reputation, reputation_lib, Reputation, ReputationIface, ReputationScore, ReputationToken,
+    ERRNO_INVALID_SCORE_UPDATE, ERRNO_SCORE_CAP_EXCEEDED, FN_DECREMENT_SCORE_OFFSET,
+    FN_INCREMENT_SCORE_OFFSET, FN_ISSUE_REPUTATION_OFFSET, FN_REPUTATION_GENESIS_OFFSET,
+    GS_SCORE_CAP, GS_SCORE_DELTA, LIB_NAME_REPUTATION, OS_REPUTATION, OS_REPUTATION_AUTHORITY,
+    REPUTATION_IFACE_ID, TS_DECREMENT_SCORE, TS_INCREMENT_SCORE, TS_ISSUE_REPUTATION,
+};
+pub use rgb22::{identity, Did, IdentityClaim, InvalidDid, Rgb22, LIB_NAME_RGB22, RGB22_IFACE_ID};
+pub use schemata_derive::IssuerWrapper;
+pub use snapshot::{
+    schema_types, verify_against_current_stl, DriftError, TypeSystemSnapshot, NIA_TYPES_SNAPSHOT,
+};
+pub use stl::{
+    rgb_schemata_stl, validate_url, AssetDescription, AssetTicker, BasisPoints, BlockCount,
+    BlockHeight, BurnLogEntry, ComplianceFlags, IconData, IconMime, InvalidAssetDescription,
+    InvalidIconMime, InvalidLegalTermsUrl, InvalidWebsiteUrl, IssuerKey, LegalTermsHash,
+    LegalTermsUrl, ProofOfBurnEntry, ReserveAttestation, WebsiteUrl, LIB_NAME_RGB_SCHEMATA,
+    MAX_ICON_DATA_LEN,
+};
+pub use synthetic::{
+    synthetic_lib, InvalidUnderlyingAssetName, OraclePrice, PriceAttestation, SyntheticAssetSchema,
+    UnderlyingAssetName, ERRNO_INVALID_REBALANCE, FN_REBALANCE_OFFSET, GS_LAST_KNOWN_PRICE,
+    GS_REBALANCE_TOLERANCE, GS_UNDERLYING_ASSET_NAME, MS_PRICE_ATTESTATION, OS_REBALANCE_RIGHT,
+    OS_SYNTHETIC, TS_REBALANCE,
+};
+pub use tranche::{
+    tranche_lib, TrancheAsset, TrancheIndex, TrancheRight, TrancheSizes, ERRNO_INVALID_TRANCHE,
+    ERRNO_TRANCHE_OVERSIZED, FN_OPEN_TRANCHE_OFFSET, GS_CURRENT_TRANCHE, GS_TRANCHE_COUNT,
+    GS_TRANCHE_ISSUED, GS_TRANCHE_SIZES, MAX_TRANCHES, OS_TRANCHE_RIGHT, OS_TRANCHE_TOKEN,
+    TS_OPEN_TRANCHE,
+};
+pub use type_registry::{AssignmentTypeRegistry, TransitionTypeRegistry};
+pub use uda::{register_uda_assignment_types, register_uda_transition_types, UniqueDigitalAsset};
+pub use validate::{validate_scripts, ScriptValidationError};
+pub use vesting::{
+    vesting_lib, VestingAsset, VestingSchemaError, FN_CLIFF_OFFSET, FN_LINEAR_OFFSET,
+    GS_CLIFF_DURATION, GS_LOCKED_SUPPLY, GS_TOTAL_VESTING_DURATION, GS_VESTING_START,
+    MS_LOCKED_AMOUNT, MS_RELEASED_AMOUNT, OS_RELEASE_RIGHT, OS_VESTING_ALLOCATION,
+    TS_CLIFF_RELEASE, TS_LINEAR_RELEASE,
+};
+pub use whitelist::{
+    TransferWhitelist, WhitelistNode, WhitelistProof, WhitelistRoot, WhitelistTree,
+    WhitelistVersion, GS_WHITELIST_COMMITMENT, GS_WHITELIST_VERSION, MAX_WHITELIST_DEPTH,
+    MS_WHITELIST_PROOF, OS_WHITELIST_RIGHT, TS_UPDATE_WHITELIST,
+};
+
+#[cfg(feature = "serde")]
+pub use self::serde::{OpSchemaJson, SchemaJson};
 
 // RGB20
 pub const GS_NOMINAL: GlobalStateType = GlobalStateType::with(2000);
 pub const GS_TERMS: GlobalStateType = GlobalStateType::with(2001);
+pub const GS_PRECISION: GlobalStateType = GlobalStateType::with(2002);
+/// Ticker of the asset, denormalized from [`GS_NOMINAL`] to let wallets look it
+/// up without decoding the whole asset specification ([`AssetTicker`]).
+/// Optional in genesis, since generic RGB20 issuers don't know about it.
+pub const GS_TICKER: GlobalStateType = GlobalStateType::with(2003);
+/// Bitcoin block height at which the contract genesis was first anchored
+/// ([`BlockHeight`]). The value is informational and is not validated by the
+/// schema.
+pub const GS_CREATION_HEIGHT: GlobalStateType = GlobalStateType::with(2004);
 pub const GS_ISSUED_SUPPLY: GlobalStateType = GlobalStateType::with(2010);
+pub const GS_MAX_SUPPLY: GlobalStateType = GlobalStateType::with(2011);
+pub const GS_ADDL_SUPPLY: GlobalStateType = GlobalStateType::with(2012);
+/// Release schedule of the inflation allowance pre-committed in genesis
+/// ([`SupplySchedule`]).
+pub const GS_SUPPLY_SCHEDULE: GlobalStateType = GlobalStateType::with(2013);
+/// Index of the supply schedule entry to be released next by the secondary
+/// issuance ([`ScheduleIndex`]).
+pub const GS_SCHEDULE_NEXT_INDEX: GlobalStateType = GlobalStateType::with(2014);
+/// Pedersen commitment to the cumulative issued supply of an inflatable asset
+/// ([`SupplyCommitment`]).
+pub const GS_INFLATION_PROOF: GlobalStateType = GlobalStateType::with(2015);
+pub const GS_BURNED_SUPPLY: GlobalStateType = GlobalStateType::with(2020);
+pub const GS_BURN_CONSIGNMENT_URL: GlobalStateType = GlobalStateType::with(2021);
+/// Append-only log of the burns ([`BurnLogEntry`]), one entry per burn
+/// operation.
+pub const GS_BURN_LOG: GlobalStateType = GlobalStateType::with(2022);
+/// Published proofs that the burned tokens were destroyed on the Bitcoin side
+/// ([`ProofOfBurnEntry`]), one entry per burn operation.
+pub const GS_PROOF_OF_BURN: GlobalStateType = GlobalStateType::with(2023);
+pub const GS_RENAME_HISTORY: GlobalStateType = GlobalStateType::with(2040);
+pub const GS_ISSUER_KEY: GlobalStateType = GlobalStateType::with(2050);
+/// Public key of the oracle attesting external data to the contract
+/// ([`IssuerKey`]).
+pub const GS_ORACLE_KEY: GlobalStateType = GlobalStateType::with(2051);
+/// Informational attestations of the reserves backing a custodial asset
+/// ([`ReserveAttestation`]).
+pub const GS_PROOF_OF_RESERVES: GlobalStateType = GlobalStateType::with(2060);
+/// URL of the legal agreement between the issuer and the asset holders, like
+/// terms of service, prospectus or offering memorandum ([`LegalTermsUrl`]).
+pub const GS_LEGAL_TERMS_URL: GlobalStateType = GlobalStateType::with(2070);
+/// SHA256 hash of the legal agreement document ([`LegalTermsHash`]). Required
+/// by [`GS_LEGAL_TERMS_URL`], yet may be committed alone to identify an
+/// agreement distributed off-line.
+pub const GS_LEGAL_TERMS_HASH: GlobalStateType = GlobalStateType::with(2071);
+/// Compliance requirements enforced by wallets off-chain ([`ComplianceFlags`]).
+pub const GS_COMPLIANCE_FLAGS: GlobalStateType = GlobalStateType::with(2090);
+/// MIME type of the asset icon, like `image/svg+xml` ([`IconMime`]).
+pub const GS_ICON_MIME: GlobalStateType = GlobalStateType::with(2091);
+/// Raw data of the asset icon ([`IconData`]).
+pub const GS_ICON_DATA: GlobalStateType = GlobalStateType::with(2092);
+/// Plain-language description of the asset ([`AssetDescription`]),
+/// complementing the asset specification and the contract terms.
+pub const GS_DESCRIPTION: GlobalStateType = GlobalStateType::with(2093);
+/// URL of the issuer website ([`WebsiteUrl`]).
+pub const GS_WEBSITE: GlobalStateType = GlobalStateType::with(2094);
+
+pub const MS_ALLOWED_INFLATION: MetaType = MetaType::with(2010);
+pub const MS_BURN_PROOF: MetaType = MetaType::with(2020);
+pub const MS_FROZEN_AMOUNT: MetaType = MetaType::with(2030);
 
 // RGB21
 pub const GS_TOKENS: GlobalStateType = GlobalStateType::with(2102);
@@ -44,12 +391,83 @@ pub const GS_ENGRAVINGS: GlobalStateType = GlobalStateType::with(2103);
 pub const GS_ATTACH: GlobalStateType = GlobalStateType::with(2104);
 
 pub const OS_ASSET: AssignmentType = AssignmentType::with(4000);
+pub const OS_INFLATION: AssignmentType = AssignmentType::with(4010);
+pub const OS_BURN_RIGHT: AssignmentType = AssignmentType::with(4020);
+/// Frozen assets, which can't be transferred until unfrozen or confiscated by
+/// the holder of the freeze or confiscation right. Always defined as 64-bit
+/// fungible state, so wallets can handle frozen allocations of all schemata
+/// uniformly.
+pub const OS_FROZEN: AssignmentType = AssignmentType::with(4030);
+pub const OS_FREEZE_RIGHT: AssignmentType = AssignmentType::with(4031);
+pub const OS_CONFISCATE_RIGHT: AssignmentType = AssignmentType::with(4032);
+/// Right of the issuer to rename the asset with [`TS_RENAME`].
+pub const OS_UPDATE_RIGHT: AssignmentType = AssignmentType::with(4040);
+/// Locked assets ([`TokenLock`]), which are converted back into [`OS_ASSET`]
+/// once the unlock height is reached. Shared by the schemata composed with
+/// [`TokenLocking`].
+pub const OS_LOCK: AssignmentType = AssignmentType::with(4050);
+
+/// Valency of the contract issuer: its holder is authorized to perform
+/// privileged operations.
+///
+/// All schemata declaring an issuer authority valency must use this id.
+/// Valencies are redeemed only by state extensions and can't gate state
+/// transitions, thus privileged transitions are additionally required to spend
+/// an owned right, like [`OS_ISSUER_AUTHORITY`].
+pub const VA_ISSUER_AUTHORITY: ValencyType = ValencyType::with(6000);
 
 pub const TS_TRANSFER: TransitionType = TransitionType::with(10000);
+/// Distribution of assets to multiple independent recipients, validated exactly
+/// as [`TS_TRANSFER`] and distinguished from it only for presentation by
+/// wallets.
+pub const TS_BATCH_TRANSFER: TransitionType = TransitionType::with(10001);
+pub const TS_ISSUE_MORE: TransitionType = TransitionType::with(10010);
+pub const TS_BURN: TransitionType = TransitionType::with(10020);
+pub const TS_FREEZE: TransitionType = TransitionType::with(10030);
+pub const TS_UNFREEZE: TransitionType = TransitionType::with(10031);
+pub const TS_CONFISCATE: TransitionType = TransitionType::with(10032);
+/// Renaming of the asset by the issuer, spending [`OS_UPDATE_RIGHT`].
+pub const TS_RENAME: TransitionType = TransitionType::with(10040);
+pub const TS_LOCK: TransitionType = TransitionType::with(10060);
+pub const TS_UNLOCK: TransitionType = TransitionType::with(10061);
+/// Splitting of an asset allocation into several allocations, normally of the
+/// same owner.
+pub const TS_SPLIT: TransitionType = TransitionType::with(10070);
+/// Merging of several asset allocations into a single one, normally of the same
+/// owner.
+pub const TS_MERGE: TransitionType = TransitionType::with(10071);
+
+/// Secondary issuance of assets by the issuer, redeeming and re-declaring
+/// [`VA_ISSUER_AUTHORITY`].
+pub const ES_ISSUE_MORE: ExtensionType = ExtensionType::with(10010);
 
 pub const ERRNO_NON_EQUAL_IN_OUT: u8 = 0;
 pub const ERRNO_ISSUED_MISMATCH: u8 = 1;
+pub const ERRNO_INFLATION_MISMATCH: u8 = 2;
+pub const ERRNO_INFLATION_EXCEEDS_ALLOWANCE: u8 = 3;
+/// Legal terms URL is committed to the contract without the hash of the legal
+/// document.
+pub const ERRNO_LEGAL_TERMS_UNHASHED: u8 = 4;
+/// Transfer moves a zero amount of the asset.
+pub const ERRNO_ZERO_AMOUNT: u8 = 5;
+/// Icon data is committed to the contract without its MIME type.
+pub const ERRNO_ICON_WITHOUT_MIME: u8 = 6;
+/// Supply schedule doesn't release exactly the inflation allowance at strictly
+/// increasing heights.
+pub const ERRNO_INVALID_SUPPLY_SCHEDULE: u8 = 7;
+/// Burn log entry doesn't record the burned amount. Not a part of RGB20
+/// interface errors.
+pub const ERRNO_BURN_LOG_MISMATCH: u8 = 8;
+/// Ticker doesn't match the one of the asset specification. Not a part of RGB20
+/// interface errors.
+pub const ERRNO_TICKER_MISMATCH: u8 = 9;
 pub const ERRNO_NON_FRACTIONAL: u8 = 10;
+/// Proof of burn entry doesn't record the burned amount. Not a part of RGB20
+/// interface errors.
+pub const ERRNO_PROOF_OF_BURN_MISMATCH: u8 = 11;
+/// New asset specification of the rename has an empty ticker or isn't logged
+/// into the rename history. Not a part of RGB20 interface errors.
+pub const ERRNO_INVALID_RENAME: u8 = 12;
 
 pub mod dumb {
     use rgbstd::resolvers::ResolveHeight;
@@ -71,3 +489,38 @@ pub mod dumb {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use rgbstd::schema::{FungibleType, OwnedStateSchema};
+
+    use super::*;
+
+    #[test]
+    fn shared_frozen_state() {
+        let mut schemata = vec![nia_schema_with_features(NiaFeatures::ALL).unwrap()];
+        let registry = SchemaRegistry::with_builtin_schemata();
+        schemata.extend(
+            registry
+                .schema_ids()
+                .map(|id| registry.lookup(id).unwrap().erased_schema()),
+        );
+        for schema in schemata {
+            if let Some(state) = schema.owned_types.get(&OS_FROZEN) {
+                assert_eq!(state, &OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit));
+            }
+        }
+    }
+
+    #[test]
+    fn shared_valencies() {
+        let registry = SchemaRegistry::with_builtin_schemata();
+        for id in registry.schema_ids() {
+            let schema = registry.lookup(id).unwrap().erased_schema();
+            assert!(schema
+                .valency_types
+                .iter()
+                .all(|ty| *ty == VA_ISSUER_AUTHORITY));
+        }
+    }
+}
@@ -0,0 +1,423 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Licensing schema implementing RGB21 NFT interface for the tokenization of
+//! intellectual property licenses.
+//!
+//! Each license is a unique allocation of license data ([`License`]) naming the
+//! licensee, the licensed rights and the expiry height. The licensor,
+//! identified by its public key ([`GS_LICENSOR_KEY`]) and committing to the
+//! license terms ([`GS_LICENSE_TERMS_HASH`]), holds the licensor authority
+//! ([`OS_LICENSOR_AUTHORITY`]), which it spends to grant new licenses
+//! with [`TS_GRANT_LICENSE`] and to revoke them with [`TS_REVOKE_LICENSE`],
+//! destroying the license allocation.
+//!
+//! Licensees transfer their licenses with [`TS_TRANSFER_LICENSE`], which may
+//! change only the licensee identity, keeping the licensed rights and the
+//! expiry height unchanged. The licensor signature is represented by spending
+//! the licensor authority, and since revocation spends the license, it requires
+//! the licensee to co-sign the witness transaction. License expiry is not
+//! checked by the scripts (see [consensus
+//! limitations](crate#consensus-limitations)) and is left to the verifier.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb21, IssuerWrapper, Rgb21, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema, TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDP, INSTR_LDS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, BlockHeight, LIB_NAME_RGB_SCHEMATA};
+use crate::{ERRNO_NON_EQUAL_IN_OUT, GS_ATTACH, GS_NOMINAL, GS_TERMS, GS_TOKENS};
+
+/// Public key of the licensor ([`crate::IssuerKey`]).
+pub const GS_LICENSOR_KEY: GlobalStateType = GlobalStateType::with(5200);
+/// SHA256 hash of the license terms ([`crate::LegalTermsHash`]).
+pub const GS_LICENSE_TERMS_HASH: GlobalStateType = GlobalStateType::with(5201);
+
+/// Allocation of a single license ([`License`]).
+pub const OS_LICENSE: AssignmentType = AssignmentType::with(7000);
+/// Right of the licensor to grant and revoke licenses.
+pub const OS_LICENSOR_AUTHORITY: AssignmentType = AssignmentType::with(7001);
+
+pub const TS_GRANT_LICENSE: TransitionType = TransitionType::with(13100);
+pub const TS_TRANSFER_LICENSE: TransitionType = TransitionType::with(13101);
+pub const TS_REVOKE_LICENSE: TransitionType = TransitionType::with(13102);
+/// Transfer of the license rights to a new licensee ([`TS_TRANSFER_LICENSE`]).
+pub const TS_RIGHTS_TRANSFER: TransitionType = TS_TRANSFER_LICENSE;
+
+pub const FN_TRANSFER_LICENSE_OFFSET: u16 = 0;
+pub(crate) const FN_LICENSE_EXPIRY_OFFSET: u16 =
+    FN_TRANSFER_LICENSE_OFFSET + 4 + 4 + 4 + 4 + 4 + 3 + 3 + 3 + 1;
+
+/// Data of a single license, kept as [`OS_LICENSE`] owned state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct License {
+    /// Identity of the licensee, like its decentralized identifier, zero-padded
+    /// to 64 bytes.
+    pub licensee_identity: [u8; 64],
+    /// Bitmap of the rights granted by the license, defined by the license
+    /// terms.
+    pub rights_bitmap: u32,
+    /// Height of the last block at which the license is valid.
+    pub expiry_height: BlockHeight,
+}
+impl StrictSerialize for License {}
+impl StrictDeserialize for License {}
+
+impl License {
+    fn dumb() -> Self {
+        Self {
+            licensee_identity: [0u8; 64],
+            rights_bitmap: 0,
+            expiry_height: BlockHeight::default(),
+        }
+    }
+}
+
+/// Assembles AluVM library with validation scripts used by the licensing
+/// schema.
+pub fn licensing_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE License transfer validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        ldp     OS_LICENSE,a16[0],s16[0];
+        lds     OS_LICENSE,a16[0],s16[1];
+        // Checking that the rights bitmap is kept unchanged.
+        put     a16[1],64;
+        extr    s16[0],a32[0],a16[1];
+        extr    s16[1],a32[1],a16[1];
+        eq.n    a32[0],a32[1];
+        test;
+        // Checking that the expiry height is kept unchanged.
+        put     a16[1],68;
+        extr    s16[0],a32[0],a16[1];
+        extr    s16[1],a32[1],a16[1];
+        eq.n    a32[0],a32[1];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong licensing script")
+}
+
+fn licensing_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+    let rgb21_types = StandardTypes::with(Rgb21::stl());
+
+    let alu_lib = licensing_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_TRANSFER_LICENSE_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_TRANSFER_LICENSE_OFFSET as usize + 8], INSTR_LDP);
+    assert_eq!(state_arg(FN_TRANSFER_LICENSE_OFFSET + 8), OS_LICENSE.to_inner());
+    assert_eq!(code[FN_TRANSFER_LICENSE_OFFSET as usize + 12], INSTR_LDS);
+    assert_eq!(state_arg(FN_TRANSFER_LICENSE_OFFSET + 12), OS_LICENSE.to_inner());
+    assert_eq!(code[FN_LICENSE_EXPIRY_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_LICENSE_EXPIRY_OFFSET as usize + 4], INSTR_EXTR);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("Licensing"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_TOKENS => GlobalStateSchema::once(rgb21_types.get("RGB21.TokenData")),
+            GS_ATTACH => GlobalStateSchema::once(rgb21_types.get("RGB21.AttachmentType")),
+            GS_LICENSOR_KEY => GlobalStateSchema::once(types.get("RGBSchemata.IssuerKey")),
+            GS_LICENSE_TERMS_HASH =>
+                GlobalStateSchema::once(types.get("RGBSchemata.LegalTermsHash")),
+        },
+        owned_types: tiny_bmap! {
+            OS_LICENSE => OwnedStateSchema::Structured(types.get("RGBSchemata.License")),
+            OS_LICENSOR_AUTHORITY => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_TOKENS => Occurrences::NoneOrOnce,
+                GS_ATTACH => Occurrences::NoneOrOnce,
+                GS_LICENSOR_KEY => Occurrences::Once,
+                GS_LICENSE_TERMS_HASH => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_LICENSE => Occurrences::NoneOrMore,
+                OS_LICENSOR_AUTHORITY => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: None,
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_GRANT_LICENSE => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_LICENSOR_AUTHORITY => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_LICENSE => Occurrences::OnceOrMore,
+                    OS_LICENSOR_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: None
+            },
+            TS_TRANSFER_LICENSE => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_LICENSE => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_LICENSE => Occurrences::Once
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_TRANSFER_LICENSE_OFFSET, alu_id))
+            },
+            TS_REVOKE_LICENSE => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_LICENSE => Occurrences::Once,
+                    OS_LICENSOR_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_LICENSOR_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: None
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn licensing_rgb21() -> IfaceImpl {
+    let schema = licensing_schema();
+    let iface = Rgb21::iface(LicensingSchema::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_TOKENS, fname!("tokens")),
+            NamedField::with(GS_ATTACH, fname!("attachmentTypes")),
+            NamedField::with(GS_LICENSOR_KEY, fname!("licensorKey")),
+            NamedField::with(GS_LICENSE_TERMS_HASH, fname!("licenseTermsHash")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_LICENSE, fname!("assetOwner")),
+            NamedField::with(OS_LICENSOR_AUTHORITY, fname!("licensorAuthority")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER_LICENSE, fname!("transfer")),
+            NamedField::with(TS_GRANT_LICENSE, fname!("grantLicense")),
+            NamedField::with(TS_REVOKE_LICENSE, fname!("revokeLicense")),
+        },
+        extensions: none!(),
+        errors: tiny_bset! {
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualValues")),
+        },
+    }
+}
+
+/// Intellectual property licenses granted and revoked by the licensor.
+pub struct LicensingSchema;
+
+impl IssuerWrapper for LicensingSchema {
+    const FEATURES: rgb21::Features = rgb21::Features::NONE;
+    type IssuingIface = Rgb21;
+
+    fn schema() -> Schema { licensing_schema() }
+    fn issue_impl() -> IfaceImpl { licensing_rgb21() }
+
+    fn types() -> TypeSystem {
+        let mut types = StandardTypes::with(rgb_schemata_stl()).type_system();
+        types
+            .extend(StandardTypes::with(Rgb21::stl()).type_system())
+            .expect("licensing type system is too large");
+        types
+    }
+
+    fn scripts() -> Scripts {
+        let lib = licensing_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+
+    use super::*;
+    use crate::test_helpers::{
+        asset_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+    use crate::{IssuerKey, LegalTermsHash};
+
+    fn license_to(licensee: &[u8], rights_bitmap: u32, expiry_height: u32) -> License {
+        let mut licensee_identity = [0u8; 64];
+        licensee_identity[..licensee.len()].copy_from_slice(licensee);
+        License {
+            licensee_identity,
+            rights_bitmap,
+            expiry_height: BlockHeight::from(expiry_height),
+        }
+    }
+
+    fn license() -> License { license_to(b"did:rgb:alice", 0b101, 900_000) }
+
+    fn contract() -> Contract {
+        asset_genesis(issuer::<LicensingSchema>(), spec("LIC", "Patent license"))
+            .add_global_state("licensorKey", IssuerKey::from([2u8; 33]))
+            .unwrap()
+            .add_global_state("licenseTermsHash", LegalTermsHash::from([7u8; 32]))
+            .unwrap()
+            .add_data("assetOwner", seal(0), license())
+            .unwrap()
+            .add_rights("licensorAuthority", seal(1))
+            .unwrap()
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb21::iface(LicensingSchema::FEATURES);
+        if let Err(err) = licensing_rgb21().check(&iface, &licensing_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid licensing RGB21 interface implementation");
+        }
+    }
+
+    #[test]
+    fn licensor_gated_operations() {
+        let schema = licensing_schema();
+        for ty in [TS_GRANT_LICENSE, TS_REVOKE_LICENSE] {
+            let transition = &schema.transitions[&ty];
+            assert_eq!(transition.inputs.get(&OS_LICENSOR_AUTHORITY), Some(&Occurrences::Once));
+        }
+        let transfer = &schema.transitions[&TS_RIGHTS_TRANSFER];
+        assert!(!transfer.inputs.contains_key(&OS_LICENSOR_AUTHORITY));
+        assert!(!schema.transitions[&TS_REVOKE_LICENSE]
+            .assignments
+            .contains_key(&OS_LICENSE));
+    }
+
+    #[test]
+    fn license_layout() {
+        // Transfer script reads the rights bitmap at offset 64 and the expiry height at
+        // offset 68
+        let data = license().to_strict_serialized::<72>().unwrap();
+        assert_eq!(data.len(), 72);
+        assert_eq!(&data[64..68], &0b101u32.to_le_bytes());
+        assert_eq!(&data[68..], &900_000u32.to_le_bytes());
+    }
+
+    #[test]
+    fn licensing_genesis() { contract(); }
+
+    #[test]
+    fn transfer_license() {
+        let contract = contract();
+        let transfer = |license: License| {
+            let transition = spend_genesis::<LicensingSchema>(&contract, "transfer")
+                .add_data("assetOwner", graph_seal(0), license)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &licensing_schema(), &transition)
+        };
+
+        let status = transfer(license_to(b"did:rgb:bob", 0b101, 900_000));
+        assert!(status.failures.is_empty(), "{status}");
+        let status = transfer(license_to(b"did:rgb:bob", 0b111, 900_000));
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+        let status = transfer(license_to(b"did:rgb:bob", 0b101, 1_000_000));
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn grant_license() {
+        let contract = contract();
+        let transition = spend_genesis::<LicensingSchema>(&contract, "grantLicense")
+            .add_rights("licensorAuthority", graph_seal(0))
+            .unwrap()
+            .add_data("assetOwner", graph_seal(1), license_to(b"did:rgb:bob", 0b1, 950_000))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let status = validate_transition(&contract, &licensing_schema(), &transition);
+        assert!(status.failures.is_empty(), "{status}");
+    }
+
+    #[test]
+    fn revoke_license() {
+        let contract = contract();
+        let revoke = |license: Option<License>| {
+            let mut builder = spend_genesis::<LicensingSchema>(&contract, "revokeLicense")
+                .add_rights("licensorAuthority", graph_seal(0))
+                .unwrap();
+            if let Some(license) = license {
+                builder = builder
+                    .add_data("assetOwner", graph_seal(1), license)
+                    .unwrap();
+            }
+            let transition = builder.complete_transition().unwrap();
+            validate_transition(&contract, &licensing_schema(), &transition)
+        };
+
+        let status = revoke(None);
+        assert!(status.failures.is_empty(), "{status}");
+        assert!(!revoke(Some(license())).failures.is_empty());
+    }
+}
@@ -0,0 +1,349 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! General purpose token locking, composable into the schemata with
+//! [`OS_ASSET`] fungible state.
+//!
+//! [`TS_LOCK`] transition converts assets into a single lock ([`OS_LOCK`])
+//! keeping the locked amount together with the height at which it unlocks
+//! ([`TokenLock`]), and [`TS_UNLOCK`] converts the lock back into assets. The
+//! scripts only guarantee that the locked and unlocked amounts match
+//! the lock; wallets must check that the unlock is mined at or after the unlock
+//! height with [`TokenLock::is_unlocked`] (see [consensus
+//! limitations](crate#consensus-limitations)).
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA, INSTR_RET};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use rgbstd::interface::{IfaceImpl, NamedField};
+use rgbstd::schema::{Occurrences, Schema, TransitionSchema};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDP, INSTR_LDS, INSTR_PCAS, INSTR_PCPS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, Amount, OwnedStateSchema};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+use crate::nia::nia_type;
+use crate::stl::{BlockHeight, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    nia_base_lib, nia_lib, NiaSchemaError, ERRNO_NON_EQUAL_IN_OUT, OS_ASSET, OS_LOCK, TS_LOCK,
+    TS_UNLOCK,
+};
+
+pub const FN_LOCK_OFFSET: u16 = 0;
+pub const FN_UNLOCK_OFFSET: u16 = FN_LOCK_OFFSET + 4 + 4 + 4 + 3 + 3 + 1 + 1;
+pub(crate) const FN_UNLOCK_END_OFFSET: u16 = FN_UNLOCK_OFFSET + 4 + 4 + 4 + 3 + 3 + 1;
+
+/// Token lock, kept as [`OS_LOCK`] owned state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct TokenLock {
+    /// Amount of locked assets.
+    pub locked_amount: Amount,
+    /// Block height starting from which the assets can be unlocked.
+    pub unlock_height: BlockHeight,
+    /// Application-defined purpose of the lock, like vesting, escrow or
+    /// staking.
+    pub lock_purpose: u8,
+}
+impl StrictSerialize for TokenLock {}
+impl StrictDeserialize for TokenLock {}
+
+impl TokenLock {
+    /// Checks whether the lock may be spent by an unlock mined at the given
+    /// `height`.
+    pub fn is_unlocked(&self, height: BlockHeight) -> bool { height >= self.unlock_height }
+}
+
+/// AluVM library validating [`TS_LOCK`] and [`TS_UNLOCK`] transitions.
+pub fn locking_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Lock validation
+        // Checking that the spent assets match the locked amount.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        // Read locked amount into a64[0]
+        lds     OS_LOCK,a16[0],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Unlock validation
+        // Checking that the spent lock is converted back into the same amount of assets.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        // Read locked amount into a64[0]
+        ldp     OS_LOCK,a16[0],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_ASSET;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong token locking script")
+}
+
+/// State transitions adding token locking to a schema with [`OS_ASSET`]
+/// fungible state.
+pub struct TokenLocking;
+
+impl TokenLocking {
+    fn transition_schemata() -> (TransitionSchema, TransitionSchema) {
+        let alu_lib = locking_lib();
+        let code = alu_lib.code.as_ref();
+        let state_arg =
+            |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+        assert_eq!(code[FN_LOCK_OFFSET as usize], INSTR_PUTA);
+        assert_eq!(code[FN_LOCK_OFFSET as usize + 8], INSTR_LDS);
+        assert_eq!(state_arg(FN_LOCK_OFFSET + 8), OS_LOCK.to_inner());
+        assert_eq!(code[FN_LOCK_OFFSET as usize + 12], INSTR_EXTR);
+        assert_eq!(code[FN_LOCK_OFFSET as usize + 15], INSTR_PCPS);
+        assert_eq!(state_arg(FN_LOCK_OFFSET + 15), OS_ASSET.to_inner());
+        assert_eq!(code[FN_UNLOCK_OFFSET as usize], INSTR_PUTA);
+        assert_eq!(code[FN_UNLOCK_OFFSET as usize + 8], INSTR_LDP);
+        assert_eq!(state_arg(FN_UNLOCK_OFFSET + 8), OS_LOCK.to_inner());
+        assert_eq!(code[FN_UNLOCK_OFFSET as usize + 15], INSTR_PCAS);
+        assert_eq!(state_arg(FN_UNLOCK_OFFSET + 15), OS_ASSET.to_inner());
+        assert_eq!(code[FN_UNLOCK_END_OFFSET as usize], INSTR_RET);
+        assert_eq!(code.len(), FN_UNLOCK_END_OFFSET as usize + 1);
+
+        let lock = TransitionSchema {
+            metadata: none!(),
+            globals: none!(),
+            inputs: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            assignments: tiny_bmap! {
+                OS_LOCK => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_LOCK_OFFSET, alu_lib.id())),
+        };
+        let unlock = TransitionSchema {
+            metadata: none!(),
+            globals: none!(),
+            inputs: tiny_bmap! {
+                OS_LOCK => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_UNLOCK_OFFSET, alu_lib.id())),
+        };
+        (lock, unlock)
+    }
+
+    /// Constructs the schema of [`TS_LOCK`] state transition.
+    pub fn lock_schema() -> TransitionSchema { Self::transition_schemata().0 }
+
+    /// Constructs the schema of [`TS_UNLOCK`] state transition.
+    pub fn unlock_schema() -> TransitionSchema { Self::transition_schemata().1 }
+
+    /// Adds [`TS_LOCK`] and [`TS_UNLOCK`] state transitions to the `schema`.
+    pub fn apply(schema: &mut Schema) -> Result<(), NiaSchemaError> {
+        schema
+            .owned_types
+            .insert(OS_LOCK, OwnedStateSchema::Structured(nia_type("RGBSchemata.TokenLock")?))
+            .expect("too many owned types");
+        let (lock, unlock) = Self::transition_schemata();
+        schema
+            .transitions
+            .extend([(TS_LOCK, lock), (TS_UNLOCK, unlock)])
+            .expect("too many transitions");
+        Ok(())
+    }
+
+    /// Adds locking state names to the interface implementation of an extended
+    /// schema.
+    pub fn apply_names(iimpl: &mut IfaceImpl) {
+        iimpl
+            .assignments
+            .push(NamedField::with(OS_LOCK, fname!("lockedAsset")))
+            .expect("too many assignments");
+        iimpl
+            .transitions
+            .extend([
+                NamedField::with(TS_LOCK, fname!("lock")),
+                NamedField::with(TS_UNLOCK, fname!("unlock")),
+            ])
+            .expect("too many transitions");
+    }
+
+    /// Validation scripts of a NIA schema extended with token locking.
+    pub fn scripts() -> Scripts {
+        let base = nia_base_lib().expect("invalid NIA validation library");
+        let nia = nia_lib().expect("invalid NIA validation library");
+        let lib = locking_lib();
+        confined_bmap! { base.id() => base, nia.id() => nia, lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ifaces::{IssuerWrapper, Rgb20};
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::{ContractBuilder, IfaceClass, TransitionBuilder};
+    use rgbstd::Identity;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, script_errno, seal, spec, spend_genesis_with,
+        validate_transition,
+    };
+    use crate::{nia_iface_impl, nia_schema, NonInflatableAsset};
+
+    fn lockable_schema() -> Schema {
+        let mut schema = nia_schema().unwrap();
+        TokenLocking::apply(&mut schema).unwrap();
+        schema
+    }
+
+    fn lockable_iimpl(schema: &Schema) -> IfaceImpl {
+        let mut iimpl = nia_iface_impl().unwrap();
+        iimpl.schema_id = schema.schema_id();
+        TokenLocking::apply_names(&mut iimpl);
+        iimpl
+    }
+
+    fn token_lock(amount: u64) -> TokenLock {
+        TokenLock {
+            locked_amount: Amount::from(amount),
+            unlock_height: BlockHeight::from(800_000),
+            lock_purpose: 0,
+        }
+    }
+
+    /// Issues 1000 units of a lockable NIA, optionally assigning the `lock` as
+    /// well.
+    ///
+    /// The genesis can't assign locks normally, thus the genesis schema is
+    /// patched to allow it; transition validation against the original
+    /// schema doesn't check the genesis.
+    fn contract(lock: Option<TokenLock>) -> Contract {
+        let mut schema = lockable_schema();
+        schema
+            .genesis
+            .assignments
+            .insert(OS_LOCK, Occurrences::NoneOrOnce)
+            .unwrap();
+        let iimpl = lockable_iimpl(&schema);
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            Rgb20::iface(NonInflatableAsset::FEATURES),
+            schema,
+            iimpl,
+            NonInflatableAsset::types(),
+            TokenLocking::scripts(),
+        );
+        let mut builder = fungible_genesis(builder, spec("TEST", "Test"), 1000)
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap();
+        if let Some(lock) = lock {
+            builder = builder.add_data("lockedAsset", seal(1), lock).unwrap();
+        }
+        builder.issue_contract().unwrap().into_consignment()
+    }
+
+    /// Constructs the transition named `name` of the lockable NIA, which spends
+    /// all genesis assignments of the `contract` of the types taken by the
+    /// transition.
+    fn spend_genesis(contract: &Contract, name: &'static str) -> TransitionBuilder {
+        let schema = lockable_schema();
+        let builder = TransitionBuilder::named_transition(
+            contract.contract_id(),
+            Rgb20::iface(NonInflatableAsset::FEATURES),
+            schema.clone(),
+            lockable_iimpl(&schema),
+            name,
+            NonInflatableAsset::types(),
+        )
+        .unwrap();
+        spend_genesis_with(contract, &schema, builder)
+    }
+
+    #[test]
+    fn lockable_nia() {
+        let schema = lockable_schema();
+        let lock = &schema.transitions[&TS_LOCK];
+        assert_eq!(lock.inputs.get(&OS_ASSET), Some(&Occurrences::OnceOrMore));
+        assert_eq!(lock.assignments.get(&OS_LOCK), Some(&Occurrences::Once));
+        let unlock = &schema.transitions[&TS_UNLOCK];
+        assert_eq!(unlock.inputs.get(&OS_LOCK), Some(&Occurrences::Once));
+        assert_eq!(unlock.assignments.get(&OS_ASSET), Some(&Occurrences::OnceOrMore));
+        assert_eq!(TokenLocking::scripts().len(), 3);
+
+        let iimpl = lockable_iimpl(&schema);
+        assert!(iimpl
+            .check(&Rgb20::iface(NonInflatableAsset::FEATURES), &schema)
+            .is_ok());
+    }
+
+    #[test]
+    fn unlock_height() {
+        let lock = token_lock(100);
+        assert!(!lock.is_unlocked(BlockHeight::from(799_999)));
+        assert!(lock.is_unlocked(BlockHeight::from(800_000)));
+        assert!(lock.is_unlocked(BlockHeight::from(800_001)));
+    }
+
+    #[test]
+    fn lock() {
+        let contract = contract(None);
+        let lock = |amount: u64| {
+            let transition = spend_genesis(&contract, "lock")
+                .add_data("lockedAsset", graph_seal(0), token_lock(amount))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &lockable_schema(), &transition)
+        };
+
+        let status = lock(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(lock(999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(lock(1001)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn unlock() {
+        let contract = contract(Some(token_lock(100)));
+        let unlock = |amounts: &[u64]| {
+            let mut builder = spend_genesis(&contract, "unlock");
+            for (vout, amount) in (0u32..).zip(amounts) {
+                builder = builder
+                    .add_fungible_state("assetOwner", graph_seal(vout), *amount)
+                    .unwrap();
+            }
+            let transition = builder.complete_transition().unwrap();
+            validate_transition(&contract, &lockable_schema(), &transition)
+        };
+
+        let status = unlock(&[100]);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = unlock(&[60, 40]);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(unlock(&[101])), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(unlock(&[60, 30])), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+}
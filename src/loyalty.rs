@@ -0,0 +1,403 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loyalty point schema implementing RGB20 fungible assets interface for
+//! non-transferable points controlled by the issuer.
+//!
+//! All operations with loyalty points, including transfers, require the issuer
+//! authority right ([`OS_ISSUER_AUTHORITY`]) held by the program issuer; thus
+//! holders can't transfer points peer-to-peer. The issuer awards new points
+//! and, together with the holder, redeems them, emitting a redemption receipt
+//! ([`RedemptionReceipt`]) for the burned points.
+//!
+//! RGB valencies can't gate state transitions, so the issuer authority is
+//! implemented as an owned right which must be spent and re-assigned by each
+//! privileged operation.
+
+use aluvm::isa::opcodes::INSTR_PUTA;
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, Amount, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PRECISION,
+    GS_TERMS, TS_TRANSFER,
+};
+
+/// Number of points required for a single reward unit.
+pub const GS_REDEMPTION_RATE: GlobalStateType = GlobalStateType::with(2800);
+/// Name of the loyalty program.
+pub const GS_PROGRAM_NAME: GlobalStateType = GlobalStateType::with(2801);
+/// Amount of points awarded by an operation.
+pub const GS_AWARDED_POINTS: GlobalStateType = GlobalStateType::with(2802);
+
+/// Fungible allocation of loyalty points.
+pub const OS_LOYALTY_POINTS: AssignmentType = AssignmentType::with(4800);
+/// Right of the issuer to authorize operations with loyalty points.
+pub const OS_ISSUER_AUTHORITY: AssignmentType = AssignmentType::with(4801);
+/// Receipt for redeemed loyalty points ([`RedemptionReceipt`]).
+pub const OS_REDEMPTION_RECEIPT: AssignmentType = AssignmentType::with(4802);
+
+pub const TS_AWARD_POINTS: TransitionType = TransitionType::with(10800);
+pub const TS_REDEEM_POINTS: TransitionType = TransitionType::with(10801);
+
+pub(crate) const FN_LOYALTY_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_LOYALTY_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+pub const FN_AWARD_POINTS_OFFSET: u16 = FN_LOYALTY_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 2;
+pub const FN_REDEEM_POINTS_OFFSET: u16 = FN_AWARD_POINTS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 2;
+
+/// Receipt for redeemed loyalty points, kept as [`OS_REDEMPTION_RECEIPT`] owned
+/// state.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct RedemptionReceipt {
+    /// Amount of burned points.
+    pub points: Amount,
+    /// Number of reward units received for the points at
+    /// [`GS_REDEMPTION_RATE`].
+    pub reward_units: u64,
+}
+impl StrictSerialize for RedemptionReceipt {}
+impl StrictDeserialize for RedemptionReceipt {}
+
+/// Assembles AluVM library with validation scripts used by the loyalty point
+/// schema.
+pub fn loyalty_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_LOYALTY_POINTS;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the issued supply matches the sum of allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_LOYALTY_POINTS;
+        test;
+        ret;
+
+        // SUBROUTINE Award validation
+        // Checking that the awarded points match the sum of the new allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_AWARDED_POINTS,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_LOYALTY_POINTS;
+        test;
+        ret;
+
+        // SUBROUTINE Redemption validation
+        // Checking that the receipt covers all redeemed points.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        lds     OS_REDEMPTION_RECEIPT,a16[0],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_LOYALTY_POINTS;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong loyalty point script")
+}
+
+fn loyalty_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = loyalty_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_LOYALTY_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_LOYALTY_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_LOYALTY_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_LOYALTY_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_AWARD_POINTS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_AWARD_POINTS_OFFSET + 12), GS_AWARDED_POINTS.to_inner());
+    assert_eq!(code[FN_REDEEM_POINTS_OFFSET as usize + 8], INSTR_LDS);
+    assert_eq!(state_arg(FN_REDEEM_POINTS_OFFSET + 8), OS_REDEMPTION_RECEIPT.to_inner());
+    assert_eq!(code[FN_REDEEM_POINTS_OFFSET as usize + 15], INSTR_PCPS);
+    assert_eq!(state_arg(FN_REDEEM_POINTS_OFFSET + 15), OS_LOYALTY_POINTS.to_inner());
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("LoyaltyPointSchema"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_REDEMPTION_RATE => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_PROGRAM_NAME => GlobalStateSchema::once(types.get("RGBContract.Name")),
+            GS_AWARDED_POINTS => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_LOYALTY_POINTS => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_ISSUER_AUTHORITY => OwnedStateSchema::Declarative,
+            OS_REDEMPTION_RECEIPT =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.RedemptionReceipt")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_REDEMPTION_RATE => Occurrences::Once,
+                GS_PROGRAM_NAME => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_LOYALTY_POINTS => Occurrences::OnceOrMore,
+                OS_ISSUER_AUTHORITY => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_LOYALTY_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_LOYALTY_POINTS => Occurrences::OnceOrMore,
+                    OS_ISSUER_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_LOYALTY_POINTS => Occurrences::OnceOrMore,
+                    OS_ISSUER_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_LOYALTY_TRANSFER_OFFSET, alu_id))
+            },
+            TS_AWARD_POINTS => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_AWARDED_POINTS => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_ISSUER_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_LOYALTY_POINTS => Occurrences::OnceOrMore,
+                    OS_ISSUER_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_AWARD_POINTS_OFFSET, alu_id))
+            },
+            TS_REDEEM_POINTS => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_LOYALTY_POINTS => Occurrences::OnceOrMore,
+                    OS_ISSUER_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_ISSUER_AUTHORITY => Occurrences::Once,
+                    OS_REDEMPTION_RECEIPT => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_REDEEM_POINTS_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn loyalty_rgb20() -> IfaceImpl {
+    let schema = loyalty_schema();
+    let iface = Rgb20::iface(LoyaltyPoints::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_REDEMPTION_RATE, fname!("redemptionRate")),
+            NamedField::with(GS_PROGRAM_NAME, fname!("programName")),
+            NamedField::with(GS_AWARDED_POINTS, fname!("awardedPoints")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_LOYALTY_POINTS, fname!("assetOwner")),
+            NamedField::with(OS_ISSUER_AUTHORITY, fname!("issuerAuthority")),
+            NamedField::with(OS_REDEMPTION_RECEIPT, fname!("redemptionReceipt")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_AWARD_POINTS, fname!("awardPoints")),
+            NamedField::with(TS_REDEEM_POINTS, fname!("redeemPoints")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+pub struct LoyaltyPoints;
+
+impl IssuerWrapper for LoyaltyPoints {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { loyalty_schema() }
+    fn issue_impl() -> IfaceImpl { loyalty_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = loyalty_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::stl::Name;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(LoyaltyPoints::FEATURES);
+        if let Err(err) = loyalty_rgb20().check(&iface, &loyalty_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid loyalty point RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn no_peer_transfers() {
+        let schema = loyalty_schema();
+        for transition in schema.transitions.values() {
+            assert_eq!(transition.inputs.get(&OS_ISSUER_AUTHORITY), Some(&Occurrences::Once));
+        }
+    }
+
+    /// Issues 500 loyalty points together with the issuer authority.
+    fn contract(issued: u64) -> Option<Contract> {
+        let builder = issuer::<LoyaltyPoints>();
+        let contract = fungible_genesis(builder, spec("PTS", "Loyalty points"), issued)
+            .add_global_state("redemptionRate", Amount::from(100u64))
+            .unwrap()
+            .add_global_state("programName", Name::from("Coffee club"))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 500u64)
+            .unwrap()
+            .add_rights("issuerAuthority", seal(1))
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn loyalty_genesis() {
+        assert!(contract(500).is_some());
+        assert!(contract(5000).is_none());
+    }
+
+    #[test]
+    fn award_points() {
+        let contract = contract(500).unwrap();
+        let award = |awarded: u64, allocated: u64| {
+            let transition = spend_genesis::<LoyaltyPoints>(&contract, "awardPoints")
+                .add_global_state("awardedPoints", Amount::from(awarded))
+                .unwrap()
+                .add_fungible_state("assetOwner", graph_seal(0), allocated)
+                .unwrap()
+                .add_rights("issuerAuthority", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &LoyaltyPoints::schema(), &transition)
+        };
+
+        let status = award(100, 100);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(award(100, 200)), Some(ERRNO_ISSUED_MISMATCH));
+    }
+
+    #[test]
+    fn redeem_points() {
+        let contract = contract(500).unwrap();
+        let redeem = |points: u64| {
+            let receipt = RedemptionReceipt {
+                points: Amount::from(points),
+                reward_units: points / 100,
+            };
+            let transition = spend_genesis::<LoyaltyPoints>(&contract, "redeemPoints")
+                .add_rights("issuerAuthority", graph_seal(0))
+                .unwrap()
+                .add_data("redemptionReceipt", graph_seal(1), receipt)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &LoyaltyPoints::schema(), &transition)
+        };
+
+        let status = redeem(500);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(redeem(400)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+}
@@ -22,82 +22,94 @@
 use std::io;
 use std::io::stdout;
 
-use ifaces::rgb21::Rgb21;
-use ifaces::{rgb20, rgb21, rgb25, IssuerWrapper, Rgb20, Rgb25};
+use ifaces::IssuerWrapper;
 use rgbstd::containers::{FileContent, Kit};
-use rgbstd::interface::IfaceClass;
+use rgbstd::interface::{Iface, IfaceClass, IfaceImpl};
+use rgbstd::validation::Scripts;
 use rgbstd::vm::RgbIsa;
-use schemata::{CollectibleFungibleAsset, NonInflatableAsset, UniqueDigitalAsset};
+use rgbstd::Schema;
+use schemata::{
+    BasketTokenIssuer, BondIssuer, BurnableNonInflatableAsset, CarbonCredit, CertificateSchema,
+    CollectibleFungibleAsset, ContractInflatableAsset, DividendToken, DualAsset, GameItem,
+    GovernanceToken, IdentityIssuer, LicensingSchema, LoyaltyPoints, MultiSigSchema, MusicRights,
+    NominalAsset, NonInflatableAsset, PartiallyBlindedSchema, PredictionMarketSchema,
+    PrivacyPreservingNonInflatableAsset, PropertyRightsToken, ReputationToken,
+    SyntheticAssetSchema, TokenBridgeSchema, TrancheAsset, UniqueDigitalAsset, VestingAsset,
+};
+use strict_types::TypeSystem;
+
+/// Exports kit of a schema into `schemata` directory under the given file name.
+type Export = fn(&str) -> io::Result<()>;
+
+/// Kit files exported into `schemata` directory, named after the issuers they
+/// are produced by.
+const KITS: &[(&str, Export)] = &[
+    ("NonInflatableAssets", export::<NonInflatableAsset>),
+    ("BurnableNonInflatableAssets", export::<BurnableNonInflatableAsset>),
+    ("UniqueDigitalAsset", export::<UniqueDigitalAsset>),
+    ("CollectibleFungibleAsset", export::<CollectibleFungibleAsset>),
+    ("ContractInflatableAsset", export::<ContractInflatableAsset>),
+    ("VestingAsset", export::<VestingAsset>),
+    ("GovernanceToken", export::<GovernanceToken>),
+    ("BondSchema", export::<BondIssuer>),
+    ("LoyaltyPointSchema", export::<LoyaltyPoints>),
+    ("CarbonCreditSchema", export::<CarbonCredit>),
+    ("MusicRightsSchema", export::<MusicRights>),
+    ("GameItem", export::<GameItem>),
+    ("DualAssetSchema", export::<DualAsset>),
+    ("PredictionMarket", export::<PredictionMarketSchema>),
+    ("SyntheticAsset", export::<SyntheticAssetSchema>),
+    ("BasketToken", export::<BasketTokenIssuer>),
+    ("DecentralizedIdentity", export::<IdentityIssuer>),
+    ("Certificate", export::<CertificateSchema>),
+    ("MultiSigAsset", export::<MultiSigSchema>),
+    ("TokenBridge", export::<TokenBridgeSchema>),
+    ("ReputationToken", export::<ReputationToken>),
+    ("TrancheAsset", export::<TrancheAsset>),
+    ("PrivacyPreservingNIA", export::<PrivacyPreservingNonInflatableAsset>),
+    ("PartiallyBlindedAsset", export::<PartiallyBlindedSchema>),
+    ("DividendTokenSchema", export::<DividendToken>),
+    ("NominalAsset", export::<NominalAsset>),
+    ("PropertyRightsToken", export::<PropertyRightsToken>),
+    ("Licensing", export::<LicensingSchema>),
+];
 
 fn main() -> io::Result<()> {
-    nia()?;
-    uda()?;
-    cfa()?;
+    for (name, export) in KITS {
+        export(name)?;
+    }
 
     Ok(())
 }
 
-fn nia() -> io::Result<()> {
-    let schema = NonInflatableAsset::schema();
-    let iimpl = NonInflatableAsset::issue_impl();
-    let lib = NonInflatableAsset::scripts();
-    let types = NonInflatableAsset::types();
-
-    let mut kit = Kit::default();
-    kit.schemata.push(schema).unwrap();
-    kit.ifaces
-        .push(Rgb20::iface(rgb20::Features::FIXED))
-        .unwrap();
-    kit.iimpls.push(iimpl).unwrap();
-    kit.scripts.extend(lib.into_values()).unwrap();
-    kit.types = types;
-
-    kit.save_file("schemata/NonInflatableAssets.rgb")?;
-    kit.save_armored("schemata/NonInflatableAssets.rgba")?;
-    print_lib(&kit);
-
-    Ok(())
+fn export<W: IssuerWrapper>(name: &str) -> io::Result<()> {
+    export_kit(
+        name,
+        W::schema(),
+        W::IssuingIface::iface(W::FEATURES),
+        [W::issue_impl()],
+        W::scripts(),
+        W::types(),
+    )
 }
 
-fn uda() -> io::Result<()> {
-    let schema = UniqueDigitalAsset::schema();
-    let iimpl = UniqueDigitalAsset::issue_impl();
-    let lib = UniqueDigitalAsset::scripts();
-    let types = UniqueDigitalAsset::types();
-
-    let mut kit = Kit::default();
-    kit.schemata.push(schema).unwrap();
-    kit.ifaces
-        .push(Rgb21::iface(rgb21::Features::NONE))
-        .unwrap();
-    kit.iimpls.push(iimpl).unwrap();
-    kit.scripts.extend(lib.into_values()).unwrap();
-    kit.types = types;
-
-    kit.save_file("schemata/UniqueDigitalAsset.rgb")?;
-    kit.save_armored("schemata/UniqueDigitalAsset.rgba")?;
-    print_lib(&kit);
-
-    Ok(())
-}
-
-fn cfa() -> io::Result<()> {
-    let schema = CollectibleFungibleAsset::schema();
-    let iimpl = CollectibleFungibleAsset::issue_impl();
-    let lib = CollectibleFungibleAsset::scripts();
-    let types = CollectibleFungibleAsset::types();
-
+fn export_kit(
+    name: &str,
+    schema: Schema,
+    iface: Iface,
+    iimpls: impl IntoIterator<Item = IfaceImpl>,
+    lib: Scripts,
+    types: TypeSystem,
+) -> io::Result<()> {
     let mut kit = Kit::default();
     kit.schemata.push(schema).unwrap();
-    kit.ifaces
-        .push(Rgb25::iface(rgb25::Features::NONE))
-        .unwrap();
-    kit.iimpls.push(iimpl).unwrap();
+    kit.ifaces.push(iface).unwrap();
+    kit.iimpls.extend(iimpls).unwrap();
     kit.scripts.extend(lib.into_values()).unwrap();
     kit.types = types;
 
-    kit.save_file("schemata/CollectibleFungibleAsset.rgb")?;
-    kit.save_armored("schemata/CollectibleFungibleAsset.rgba")?;
+    kit.save_file(format!("schemata/{name}.rgb"))?;
+    kit.save_armored(format!("schemata/{name}.rgba"))?;
     print_lib(&kit);
 
     Ok(())
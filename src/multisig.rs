@@ -0,0 +1,540 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Multi-signature NIA schema requiring m-of-n threshold issuer authority for
+//! freezing and confiscating assets.
+//!
+//! The schema extends the freezable NIA ([`NiaFeatures::FREEZE`] and
+//! [`NiaFeatures::CONFISCATE`]) replacing the freeze and confiscation rights
+//! with a set of signer rights ([`OS_SIGNER_RIGHT`]), one per each of the
+//! signer keys listed in the genesis ([`GS_SIGNER_KEYS`]). Each of the signer
+//! rights records the number of the required signers ([`GS_REQUIRED_SIGS`]),
+//! and freezing, unfreezing or confiscating assets requires at least that
+//! number of the rights being spent and re-assigned unchanged.
+//!
+//! The threshold is enforced by the seals of the signer rights rather than by
+//! signatures (see [consensus limitations](crate#consensus-limitations)): each
+//! of them is controlled by a different signer, and spending `m` of them
+//! requires `m` signers to co-sign the witness transaction. Wallets must check
+//! that each signer right is assigned to a seal controlled by the key with the
+//! index it records.
+
+use aluvm::isa::opcodes::{INSTR_CALL, INSTR_JIF, INSTR_PUTA, INSTR_RET};
+use aluvm::isa::{ControlFlowOp, Instr, MoveOp};
+use aluvm::library::{Lib, LibSite};
+use aluvm::reg::{Reg32, RegA};
+use amplify::confinement::Confined;
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20};
+use rgbstd::interface::{IfaceImpl, NamedField};
+use rgbstd::schema::{GlobalStateSchema, Occurrences, OwnedStateSchema, Schema};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_CNP, INSTR_CNS, INSTR_LDG, INSTR_LDM, INSTR_LDP, INSTR_LDS};
+use rgbstd::vm::{ContractOp, RgbIsa};
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::{SemId, TypeSystem};
+
+use crate::nia::{nia_iface_impl_for, nia_type, FN_NIA_GENESIS_OFFSET};
+use crate::stl::LIB_NAME_RGB_SCHEMATA;
+use crate::{
+    nia_lib, nia_schema_with_features, NiaFeatures, NonInflatableAsset, ERRNO_NON_EQUAL_IN_OUT,
+    MS_FROZEN_AMOUNT, OS_ASSET, OS_CONFISCATE_RIGHT, OS_FREEZE_RIGHT, OS_FROZEN, TS_CONFISCATE,
+    TS_FREEZE, TS_UNFREEZE,
+};
+
+/// Number of the signers required to authorize an issuer operation
+/// ([`RequiredSigs`]).
+pub const GS_REQUIRED_SIGS: GlobalStateType = GlobalStateType::with(4100);
+/// Public keys of the signers ([`crate::IssuerKey`]), up to [`MAX_SIGNERS`].
+pub const GS_SIGNER_KEYS: GlobalStateType = GlobalStateType::with(4101);
+
+/// Right of a single signer to authorize issuer operations ([`SignerRight`]).
+pub const OS_SIGNER_RIGHT: AssignmentType = AssignmentType::with(6000);
+
+/// Error raised when the genesis defines an invalid threshold or invalid signer
+/// rights.
+pub const ERRNO_INVALID_SIGNERS: u8 = 53;
+/// Error raised when an issuer operation is authorized by less than the
+/// required number of signers, or doesn't re-assign the spent signer rights
+/// unchanged.
+pub const ERRNO_INSUFFICIENT_SIGNATURES: u8 = 54;
+
+/// Maximum number of the signers of a multi-signature asset.
+pub const MAX_SIGNERS: u16 = 15;
+
+pub const FN_MULTISIG_FREEZE_OFFSET: u16 = 0;
+pub const FN_MULTISIG_UNFREEZE_OFFSET: u16 =
+    FN_MULTISIG_FREEZE_OFFSET + 4 + 4 + 4 + 3 + 3 + 3 + 1 + 3;
+/// Offset of the shared multi-signature verification, which is the tail of all
+/// issuer operation validators.
+pub const FN_MULTISIG_OFFSET: u16 = FN_MULTISIG_UNFREEZE_OFFSET + 4 + 4 + 4 + 3 + 3 + 3 + 1 + 3;
+pub(crate) const FN_MULTISIG_LOOP_OFFSET: u16 =
+    FN_MULTISIG_OFFSET + 4 + 4 + 4 + 3 + 1 + 4 + 4 + 4 + 3 + 3 + 3 + 1 + 1;
+pub(crate) const FN_MULTISIG_END_OFFSET: u16 =
+    FN_MULTISIG_LOOP_OFFSET + 3 + 3 + 4 + 4 + 2 + 1 + 3 + 3;
+pub const FN_MULTISIG_GENESIS_OFFSET: u16 = FN_MULTISIG_END_OFFSET + 1;
+pub(crate) const FN_MULTISIG_GENESIS_RIGHTS_OFFSET: u16 =
+    FN_MULTISIG_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 2 + 1 + 1 + 4 + 3 + 1 + 1;
+pub(crate) const FN_MULTISIG_GENESIS_LOOP_OFFSET: u16 =
+    FN_MULTISIG_GENESIS_RIGHTS_OFFSET + 4 + 3 + 3 + 1 + 1 + 4 + 4;
+pub(crate) const FN_MULTISIG_GENESIS_END_OFFSET: u16 =
+    FN_MULTISIG_GENESIS_LOOP_OFFSET + 3 + 3 + 4 + 3 + 3 + 1 + 3 + 3 + 1 + 3 + 3;
+
+/// Number of the signers required to authorize an issuer operation.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct RequiredSigs(u8);
+impl StrictSerialize for RequiredSigs {}
+impl StrictDeserialize for RequiredSigs {}
+
+/// Right of a single signer, kept as [`OS_SIGNER_RIGHT`] owned state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct SignerRight {
+    /// Index of the signer key in [`GS_SIGNER_KEYS`].
+    pub signer: u8,
+    /// Number of the signers required to authorize an issuer operation,
+    /// repeating [`GS_REQUIRED_SIGS`].
+    pub required_sigs: RequiredSigs,
+}
+impl StrictSerialize for SignerRight {}
+impl StrictDeserialize for SignerRight {}
+
+/// Assembles AluVM library with the multi-signature verification used by the
+/// multi-signature schema.
+///
+/// The genesis validation calls into the NIA library for the issued supply
+/// checks, thus the library must be used together with [`nia_lib`].
+// `sub` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn multisig_lib() -> Lib {
+    let nia_id = nia_lib().expect("invalid NIA validation library").id();
+
+    let mut code = rgbasm! {
+        // SUBROUTINE Freeze validation
+        // Same as NIA freeze validation followed by the multi-signature verification.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        ldm     MS_FROZEN_AMOUNT,s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_ASSET;
+        pcas    OS_FROZEN;
+        test;
+        jmp     FN_MULTISIG_OFFSET;
+
+        // SUBROUTINE Unfreeze and confiscation validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        ldm     MS_FROZEN_AMOUNT,s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_FROZEN;
+        pcas    OS_ASSET;
+        test;
+        jmp     FN_MULTISIG_OFFSET;
+
+        // SUBROUTINE Multi-signature verification
+        // Checking that all spent signer rights are re-assigned.
+        put     a8[0],ERRNO_INSUFFICIENT_SIGNATURES;
+    };
+    // NB: `rgbasm!` doesn't know `cnp` and `cns` opcodes, thus the instructions are
+    // constructed directly
+    code.extend([
+        Instr::ExtensionCodes(RgbIsa::Contract(ContractOp::CnP(OS_SIGNER_RIGHT, Reg32::Reg1))),
+        Instr::ExtensionCodes(RgbIsa::Contract(ContractOp::CnS(OS_SIGNER_RIGHT, Reg32::Reg2))),
+    ]);
+    code.extend(rgbasm! {
+        eq.n    a16[1],a16[2];
+        test;
+        // Read the required number of signers from the first spent right into a8[1]
+        put     a16[0],0;
+        put     a16[3],1;
+        ldp     OS_SIGNER_RIGHT,a16[0],s16[0];
+        extr    s16[0],a8[1],a16[3];
+    });
+    // NB: `rgbasm!` can't copy between registers of different size, thus the
+    // instruction is constructed directly
+    code.push(Instr::Move(MoveOp::CpyA(RegA::A8, Reg32::Reg1, RegA::A16, Reg32::Reg4)));
+    code.extend(rgbasm! {
+        // Checking that the number of spent rights is not less than the required one
+        lt.u    a16[1],a16[4];
+        inv     st0;
+        test;
+        // Checking that each of the spent rights is re-assigned unchanged, so the signers and the
+        // threshold are kept.
+        eq.n    a16[0],a16[1];
+        jif     FN_MULTISIG_END_OFFSET;
+        ldp     OS_SIGNER_RIGHT,a16[0],s16[0];
+        lds     OS_SIGNER_RIGHT,a16[0],s16[1];
+        eq      s16[0],s16[1];
+        test;
+        add     a16[0],1;
+        jmp     FN_MULTISIG_LOOP_OFFSET;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        put     a8[0],ERRNO_INVALID_SIGNERS;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read the required number of signers into a8[2]
+        ldg     GS_REQUIRED_SIGS,a8[1],s16[0];
+        extr    s16[0],a8[2],a16[0];
+        // Checking that the threshold is non-zero and doesn't exceed the number of signer keys
+        ifz     a8[2];
+        inv     st0;
+        test;
+        cng     GS_SIGNER_KEYS,a8[3];
+        gt.u    a8[2],a8[3];
+        inv     st0;
+        test;
+        // Checking that there are enough signer rights to reach the threshold
+    });
+    code.push(Instr::ExtensionCodes(RgbIsa::Contract(ContractOp::CnS(
+        OS_SIGNER_RIGHT,
+        Reg32::Reg1,
+    ))));
+    code.push(Instr::Move(MoveOp::CpyA(RegA::A8, Reg32::Reg2, RegA::A16, Reg32::Reg4)));
+    code.extend(rgbasm! {
+        lt.u    a16[1],a16[4];
+        inv     st0;
+        test;
+        put     a16[2],1;
+        put     a16[3],0;
+        // Checking that each of the signer rights refers to a known key and records the threshold
+        eq.n    a16[0],a16[1];
+        jif     FN_MULTISIG_GENESIS_END_OFFSET;
+        lds     OS_SIGNER_RIGHT,a16[0],s16[1];
+        extr    s16[1],a8[4],a16[3];
+        lt.u    a8[4],a8[3];
+        test;
+        extr    s16[1],a8[5],a16[2];
+        eq.n    a8[2],a8[5];
+        test;
+        add     a16[0],1;
+        jmp     FN_MULTISIG_GENESIS_LOOP_OFFSET;
+    });
+    // NB: AluVM returns from a call into the beginning of the called routine
+    // instead of the instruction following the call, thus the NIA genesis
+    // validation is called as the concluding step
+    let nia_genesis = LibSite::with(FN_NIA_GENESIS_OFFSET, nia_id);
+    code.push(Instr::ControlFlow(ControlFlowOp::Call(nia_genesis)));
+    code.extend(rgbasm! {
+        ret;
+    });
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong multi-signature script")
+}
+
+fn multisig_schema() -> Schema {
+    let mut schema =
+        nia_schema_with_features(MultiSigSchema::NIA_FEATURES).expect("invalid NIA schema");
+
+    let alu_lib = multisig_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    assert_eq!(code[FN_MULTISIG_FREEZE_OFFSET as usize + 8], INSTR_LDM);
+    assert_eq!(code[FN_MULTISIG_UNFREEZE_OFFSET as usize + 8], INSTR_LDM);
+    assert_eq!(code[FN_MULTISIG_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_MULTISIG_OFFSET as usize + 4], INSTR_CNP);
+    assert_eq!(code[FN_MULTISIG_LOOP_OFFSET as usize + 3], INSTR_JIF);
+    assert_eq!(code[FN_MULTISIG_LOOP_OFFSET as usize + 6], INSTR_LDP);
+    assert_eq!(code[FN_MULTISIG_END_OFFSET as usize], INSTR_RET);
+    assert_eq!(code[FN_MULTISIG_GENESIS_RIGHTS_OFFSET as usize], INSTR_CNS);
+    assert_eq!(code[FN_MULTISIG_GENESIS_LOOP_OFFSET as usize + 3], INSTR_JIF);
+    assert_eq!(code[FN_MULTISIG_GENESIS_LOOP_OFFSET as usize + 6], INSTR_LDS);
+    assert_eq!(code[FN_MULTISIG_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(code[FN_MULTISIG_GENESIS_END_OFFSET as usize], INSTR_CALL);
+
+    schema.name = tn!("MultiSigAsset");
+    for ty in [OS_FREEZE_RIGHT, OS_CONFISCATE_RIGHT] {
+        schema.owned_types.remove(&ty).expect("NIA owned types");
+        schema
+            .genesis
+            .assignments
+            .remove(&ty)
+            .expect("NIA genesis assignments");
+    }
+    schema
+        .owned_types
+        .insert(
+            OS_SIGNER_RIGHT,
+            OwnedStateSchema::Structured(multisig_type("RGBSchemata.SignerRight")),
+        )
+        .expect("too many owned types");
+    schema
+        .global_types
+        .extend([
+            (GS_REQUIRED_SIGS, GlobalStateSchema::once(multisig_type("RGBSchemata.RequiredSigs"))),
+            (GS_SIGNER_KEYS, GlobalStateSchema::many(multisig_type("RGBSchemata.IssuerKey"))),
+        ])
+        .expect("too many global types");
+    schema
+        .genesis
+        .globals
+        .extend([
+            (GS_REQUIRED_SIGS, Occurrences::Once),
+            (GS_SIGNER_KEYS, Occurrences::OnceOrUpTo(MAX_SIGNERS)),
+        ])
+        .expect("too many genesis globals");
+    schema
+        .genesis
+        .assignments
+        .insert(OS_SIGNER_RIGHT, Occurrences::OnceOrUpTo(MAX_SIGNERS))
+        .expect("too many genesis assignments");
+    schema.genesis.validator = Some(LibSite::with(FN_MULTISIG_GENESIS_OFFSET, alu_id));
+
+    for (ty, offset) in [
+        (TS_FREEZE, FN_MULTISIG_FREEZE_OFFSET),
+        (TS_UNFREEZE, FN_MULTISIG_UNFREEZE_OFFSET),
+        (TS_CONFISCATE, FN_MULTISIG_UNFREEZE_OFFSET),
+    ] {
+        let transition = schema
+            .transitions
+            .get_mut(&ty)
+            .expect("freezable NIA schema");
+        for state in [&mut transition.inputs, &mut transition.assignments] {
+            state
+                .remove(&OS_FREEZE_RIGHT)
+                .expect("NIA transition state");
+            state
+                .remove(&OS_CONFISCATE_RIGHT)
+                .expect("NIA transition state");
+            state
+                .insert(OS_SIGNER_RIGHT, Occurrences::OnceOrUpTo(MAX_SIGNERS))
+                .expect("too many transition state types");
+        }
+        transition.validator = Some(LibSite::with(offset, alu_id));
+    }
+    schema
+}
+
+fn multisig_type(name: &'static str) -> SemId {
+    nia_type(name).expect("multi-signature types are part of NIA type system")
+}
+
+fn multisig_rgb20() -> IfaceImpl {
+    let schema = multisig_schema();
+    let mut iimpl =
+        nia_iface_impl_for(&schema, MultiSigSchema::NIA_FEATURES).expect("invalid NIA schema");
+
+    let rights = [OS_FREEZE_RIGHT, OS_CONFISCATE_RIGHT];
+    iimpl.assignments = Confined::try_from_iter(
+        iimpl
+            .assignments
+            .into_iter()
+            .filter(|field| !rights.contains(&field.id))
+            .chain([NamedField::with(OS_SIGNER_RIGHT, fname!("signerRight"))]),
+    )
+    .expect("too many assignments");
+    iimpl
+        .global_state
+        .extend([
+            NamedField::with(GS_REQUIRED_SIGS, fname!("requiredSigs")),
+            NamedField::with(GS_SIGNER_KEYS, fname!("signerKeys")),
+        ])
+        .expect("too many global state fields");
+    iimpl
+}
+
+/// Non-inflatable asset which can be frozen and confiscated only by a threshold
+/// of signers.
+pub struct MultiSigSchema;
+
+impl MultiSigSchema {
+    /// Optional NIA features present in the multi-signature schema.
+    pub const NIA_FEATURES: NiaFeatures = NiaFeatures::FREEZE
+        .union(NiaFeatures::CONFISCATE)
+        .union(NiaFeatures::EXTENDED);
+}
+
+impl IssuerWrapper for MultiSigSchema {
+    const FEATURES: rgb20::Features = Self::NIA_FEATURES.to_rgb20();
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { multisig_schema() }
+    fn issue_impl() -> IfaceImpl { multisig_rgb20() }
+
+    fn types() -> TypeSystem { NonInflatableAsset::types() }
+
+    fn scripts() -> Scripts {
+        let nia = nia_lib().expect("invalid NIA validation library");
+        let lib = multisig_lib();
+        confined_bmap! { nia.id() => nia, lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::IfaceClass;
+    use rgbstd::stl::Ticker;
+    use rgbstd::{validation, Amount, Input, Operation, Opout};
+
+    use super::*;
+    use crate::test_helpers::{
+        add_metadata, fungible_genesis, graph_seal, issuer_with_assignments, script_errno, seal,
+        spec, spend_genesis, validate_transition,
+    };
+    use crate::{AssetTicker, IssuerKey};
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(MultiSigSchema::FEATURES);
+        if let Err(err) = multisig_rgb20().check(&iface, &multisig_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid multi-signature RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn threshold_gated_operations() {
+        let schema = multisig_schema();
+        let lib_id = multisig_lib().id();
+        assert!(!schema.owned_types.contains_key(&OS_FREEZE_RIGHT));
+        assert!(!schema.owned_types.contains_key(&OS_CONFISCATE_RIGHT));
+        for ty in [TS_FREEZE, TS_UNFREEZE, TS_CONFISCATE] {
+            let transition = &schema.transitions[&ty];
+            assert!(transition.inputs.contains_key(&OS_SIGNER_RIGHT));
+            assert!(transition.assignments.contains_key(&OS_SIGNER_RIGHT));
+            assert_eq!(transition.validator.map(|site| site.lib), Some(lib_id));
+        }
+    }
+
+    /// Issues 1000 assets and 400 frozen assets, with `required` of the `keys`
+    /// signers holding the signer `rights` made of the signer index and the
+    /// recorded threshold.
+    fn contract(supply: u64, required: u8, keys: u8, rights: &[(u8, u8)]) -> Option<Contract> {
+        let builder = issuer_with_assignments::<MultiSigSchema>(&[OS_FROZEN]);
+        let mut builder = fungible_genesis(builder, spec("MSIG", "Multisig"), supply)
+            .add_global_state("ticker", AssetTicker::from(Ticker::from("MSIG")))
+            .unwrap()
+            .add_global_state("requiredSigs", RequiredSigs::from(required))
+            .unwrap();
+        for key in 0..keys {
+            builder = builder
+                .add_global_state("signerKeys", IssuerKey::from([key + 2; 33]))
+                .unwrap();
+        }
+        for (vout, (signer, required_sigs)) in (2u32..).zip(rights) {
+            builder = builder
+                .add_data("signerRight", seal(vout), SignerRight {
+                    signer: *signer,
+                    required_sigs: RequiredSigs::from(*required_sigs),
+                })
+                .unwrap();
+        }
+        let contract = builder
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_fungible_state("frozenAsset", seal(1), 400u64)
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn multisig_genesis() {
+        let rights = [(0, 2), (1, 2), (2, 2)];
+        assert!(contract(1000, 2, 3, &rights).is_some());
+        assert!(contract(1000, 2, 3, &rights[..2]).is_some());
+        // Issued supply is still checked by NIA genesis validation
+        assert!(contract(999, 2, 3, &rights).is_none());
+        assert!(contract(1000, 0, 3, &[(0, 0)]).is_none());
+        assert!(contract(1000, 4, 3, &rights).is_none());
+        assert!(contract(1000, 2, 3, &rights[..1]).is_none());
+        assert!(contract(1000, 2, 3, &[(0, 2), (1, 3)]).is_none());
+        assert!(contract(1000, 2, 3, &[(0, 2), (3, 2)]).is_none());
+    }
+
+    /// Validates the issuer operation `name` of a 2-of-3 multi-signature
+    /// contract, which spends the first `signers` of the signer rights,
+    /// re-assigns the `reassigned` ones and allocates `amount` of the
+    /// `owner` state, reporting `reported` amount in its metadata.
+    fn issuer_operation(
+        name: &'static str,
+        signers: u16,
+        reassigned: &[u8],
+        owner: &'static str,
+        amount: u64,
+        reported: u64,
+    ) -> validation::Status {
+        let contract = contract(1000, 2, 3, &[(0, 2), (1, 2), (2, 2)]).unwrap();
+        let mut builder = spend_genesis::<MultiSigSchema>(&contract, name)
+            .add_fungible_state(owner, graph_seal(0), amount)
+            .unwrap();
+        for (vout, signer) in (1u32..).zip(reassigned) {
+            builder = builder
+                .add_data("signerRight", graph_seal(vout), SignerRight {
+                    signer: *signer,
+                    required_sigs: RequiredSigs::from(2),
+                })
+                .unwrap();
+        }
+        let mut transition = builder.complete_transition().unwrap();
+        for no in signers..3 {
+            let right = Opout::new(contract.genesis.id(), OS_SIGNER_RIGHT, no);
+            transition.inputs.remove(&Input::with(right)).unwrap();
+        }
+        add_metadata(&mut transition, MS_FROZEN_AMOUNT, Amount::from(reported));
+        validate_transition(&contract, &MultiSigSchema::schema(), &transition)
+    }
+
+    #[test]
+    fn freeze() {
+        let freeze = |signers, reassigned: &[u8], frozen, reported| {
+            issuer_operation("freeze", signers, reassigned, "frozenAsset", frozen, reported)
+        };
+
+        let status = freeze(3, &[0, 1, 2], 1000, 1000);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = freeze(2, &[0, 1], 1000, 1000);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = freeze(1, &[0], 1000, 1000);
+        assert_eq!(script_errno(status), Some(ERRNO_INSUFFICIENT_SIGNATURES));
+        let status = freeze(2, &[0], 1000, 1000);
+        assert_eq!(script_errno(status), Some(ERRNO_INSUFFICIENT_SIGNATURES));
+        let status = freeze(2, &[0, 2], 1000, 1000);
+        assert_eq!(script_errno(status), Some(ERRNO_INSUFFICIENT_SIGNATURES));
+        let status = freeze(2, &[0, 1], 1000, 900);
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn unfreeze_and_confiscate() {
+        for name in ["unfreeze", "confiscate"] {
+            let release = |signers, reassigned: &[u8], amount, reported| {
+                issuer_operation(name, signers, reassigned, "assetOwner", amount, reported)
+            };
+
+            let status = release(2, &[0, 1], 400, 400);
+            assert!(status.failures.is_empty(), "{status}");
+            let status = release(1, &[0], 400, 400);
+            assert_eq!(script_errno(status), Some(ERRNO_INSUFFICIENT_SIGNATURES));
+            let status = release(2, &[0, 1], 500, 500);
+            assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+        }
+    }
+}
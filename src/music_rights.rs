@@ -0,0 +1,484 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Music rights schema implementing RGB20 fungible assets interface for
+//! tokenizing fractional ownership of the rights to a recording.
+//!
+//! The recording is identified by its ISRC ([`GS_ISRC`]) and the composer
+//! identity ([`GS_COMPOSER_IDENTITY`]); the number of rights units issued in
+//! genesis defines the total royalty shares ([`GS_TOTAL_ROYALTY_SHARES`]). When
+//! a royalty payment is distributed, each holder collects its share with
+//! [`TS_COLLECT_ROYALTY`], which keeps the holder's rights units and produces a
+//! royalty claim ([`RoyaltyClaim`]). The script verifies that the claimed
+//! payout is proportional to the units spent by the holder using integer
+//! arithmetic, rounding down.
+//!
+//! The total royalty shares are repeated in each claim, and matching them with
+//! [`GS_TOTAL_ROYALTY_SHARES`] is left to the payer (see [consensus
+//! limitations](crate#consensus-limitations)). Multiplication overflowing 64
+//! bits fails the validation.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, Amount, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PRECISION,
+    GS_TERMS, TS_TRANSFER,
+};
+
+/// International Standard Recording Code of the recording ([`Isrc`]).
+pub const GS_ISRC: GlobalStateType = GlobalStateType::with(3200);
+/// Identity of the composer.
+pub const GS_COMPOSER_IDENTITY: GlobalStateType = GlobalStateType::with(3201);
+/// Type of the tokenized rights ([`RightsType`]).
+pub const GS_RIGHTS_TYPE: GlobalStateType = GlobalStateType::with(3202);
+/// Total number of royalty shares, equal to the issued rights units.
+pub const GS_TOTAL_ROYALTY_SHARES: GlobalStateType = GlobalStateType::with(3203);
+
+/// Fungible allocation of fractional rights units.
+pub const OS_RIGHTS_UNIT: AssignmentType = AssignmentType::with(5100);
+/// Claim of a royalty payout ([`RoyaltyClaim`]).
+pub const OS_ROYALTY_CLAIM: AssignmentType = AssignmentType::with(5101);
+
+pub const TS_COLLECT_ROYALTY: TransitionType = TransitionType::with(11100);
+
+/// Error raised when the claimed royalty payout is not proportional to the
+/// spent rights units.
+pub const ERRNO_INVALID_ROYALTY: u8 = 43;
+
+pub(crate) const FN_MUSIC_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_MUSIC_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+pub const FN_COLLECT_ROYALTY_OFFSET: u16 =
+    FN_MUSIC_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1 + 4 + 3 + 3 + 1 + 1;
+
+/// International Standard Recording Code: 12 ASCII alphanumeric characters.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct Isrc([u8; 12]);
+impl StrictSerialize for Isrc {}
+impl StrictDeserialize for Isrc {}
+
+/// Error parsing [`Isrc`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display("invalid ISRC '{0}', which must consist of 12 ASCII alphanumeric characters")]
+pub struct InvalidIsrc(pub String);
+
+impl FromStr for Isrc {
+    type Err = InvalidIsrc;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = <[u8; 12]>::try_from(s.as_bytes()).map_err(|_| InvalidIsrc(s.to_owned()))?;
+        if !code.iter().all(u8::is_ascii_alphanumeric) {
+            return Err(InvalidIsrc(s.to_owned()));
+        }
+        Ok(Self(code.map(|c| c.to_ascii_uppercase())))
+    }
+}
+
+impl Isrc {
+    /// Returns ASCII characters of the code.
+    pub fn as_bytes(&self) -> &[u8; 12] { &self.0 }
+}
+
+impl Display for Isrc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.0))
+    }
+}
+
+/// Type of the tokenized music rights.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, tags = repr, into_u8, try_from_u8)]
+#[repr(u8)]
+pub enum RightsType {
+    /// Rights to the master recording.
+    #[strict_type(dumb)]
+    Master = 0,
+    /// Synchronization rights.
+    Sync = 1,
+    /// Public performance rights.
+    Performance = 2,
+}
+impl StrictSerialize for RightsType {}
+impl StrictDeserialize for RightsType {}
+
+/// Claim of a royalty payout, kept as [`OS_ROYALTY_CLAIM`] owned state.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct RoyaltyClaim {
+    /// Rights units held by the claimant.
+    pub units: Amount,
+    /// Total number of royalty shares ([`GS_TOTAL_ROYALTY_SHARES`]).
+    pub total_shares: Amount,
+    /// Total royalty payment being distributed.
+    pub payment: u64,
+    /// Part of the payment due to the claimant.
+    pub payout: u64,
+}
+impl StrictSerialize for RoyaltyClaim {}
+impl StrictDeserialize for RoyaltyClaim {}
+
+impl RoyaltyClaim {
+    /// Constructs claim with the payout proportional to the claimant units,
+    /// rounded down.
+    ///
+    /// Returns `None` if the total shares are zero or the amounts overflow.
+    pub fn new(units: Amount, total_shares: Amount, payment: u64) -> Option<Self> {
+        let payout = payment
+            .checked_mul(units.value())?
+            .checked_div(total_shares.value())?;
+        Some(RoyaltyClaim {
+            units,
+            total_shares,
+            payment,
+            payout,
+        })
+    }
+}
+
+/// Assembles AluVM library with validation scripts used by the music rights
+/// schema.
+// `mul` and `div` macro arms for float registers panic on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn music_rights_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_RIGHTS_UNIT;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the issued supply matches the sum of allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_RIGHTS_UNIT;
+        test;
+        // Checking that the total royalty shares match the issued supply.
+        ldg     GS_TOTAL_ROYALTY_SHARES,a8[1],s16[1];
+        extr    s16[1],a64[1],a16[0];
+        eq.n    a64[0],a64[1];
+        test;
+        ret;
+
+        // SUBROUTINE Royalty collection validation
+        // Checking that the holder keeps all rights units.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_RIGHTS_UNIT;
+        test;
+        // Checking that the claim is made for all spent rights units.
+        put     a16[0],0;
+        lds     OS_ROYALTY_CLAIM,a16[0],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_RIGHTS_UNIT;
+        test;
+        // Reading total shares, payment and payout from the claim.
+        put     a8[0],ERRNO_INVALID_ROYALTY;
+        put     a16[1],8;
+        extr    s16[0],a64[1],a16[1];
+        put     a16[2],16;
+        extr    s16[0],a64[2],a16[2];
+        put     a16[3],24;
+        extr    s16[0],a64[3],a16[3];
+        // Checking that payout == units * payment / total shares.
+        mul.uc  a64[0],a64[2];
+        div.uc  a64[2],a64[1];
+        eq.n    a64[1],a64[3];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong music rights script")
+}
+
+fn music_rights_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = music_rights_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_MUSIC_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_MUSIC_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_MUSIC_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_MUSIC_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_MUSIC_GENESIS_OFFSET as usize + 23], INSTR_LDG);
+    assert_eq!(state_arg(FN_MUSIC_GENESIS_OFFSET + 23), GS_TOTAL_ROYALTY_SHARES.to_inner());
+    assert_eq!(code[FN_COLLECT_ROYALTY_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_COLLECT_ROYALTY_OFFSET as usize + 12], INSTR_LDS);
+    assert_eq!(state_arg(FN_COLLECT_ROYALTY_OFFSET + 12), OS_ROYALTY_CLAIM.to_inner());
+    assert_eq!(code[FN_COLLECT_ROYALTY_OFFSET as usize + 19], INSTR_PCPS);
+    assert_eq!(code[FN_COLLECT_ROYALTY_OFFSET as usize + 31], INSTR_EXTR);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("MusicRightsSchema"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_ISRC => GlobalStateSchema::once(types.get("RGBSchemata.Isrc")),
+            GS_COMPOSER_IDENTITY => GlobalStateSchema::once(types.get("RGBContract.Details")),
+            GS_RIGHTS_TYPE => GlobalStateSchema::once(types.get("RGBSchemata.RightsType")),
+            GS_TOTAL_ROYALTY_SHARES => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_RIGHTS_UNIT => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_ROYALTY_CLAIM =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.RoyaltyClaim")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_ISRC => Occurrences::Once,
+                GS_COMPOSER_IDENTITY => Occurrences::Once,
+                GS_RIGHTS_TYPE => Occurrences::Once,
+                GS_TOTAL_ROYALTY_SHARES => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_RIGHTS_UNIT => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_MUSIC_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_RIGHTS_UNIT => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_RIGHTS_UNIT => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_MUSIC_TRANSFER_OFFSET, alu_id))
+            },
+            TS_COLLECT_ROYALTY => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_RIGHTS_UNIT => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_RIGHTS_UNIT => Occurrences::OnceOrMore,
+                    OS_ROYALTY_CLAIM => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_COLLECT_ROYALTY_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn music_rights_rgb20() -> IfaceImpl {
+    let schema = music_rights_schema();
+    let iface = Rgb20::iface(MusicRights::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_ISRC, fname!("isrc")),
+            NamedField::with(GS_COMPOSER_IDENTITY, fname!("composerIdentity")),
+            NamedField::with(GS_RIGHTS_TYPE, fname!("rightsType")),
+            NamedField::with(GS_TOTAL_ROYALTY_SHARES, fname!("totalRoyaltyShares")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_RIGHTS_UNIT, fname!("assetOwner")),
+            NamedField::with(OS_ROYALTY_CLAIM, fname!("royaltyClaim")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_COLLECT_ROYALTY, fname!("collectRoyalty")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Fractional ownership of music rights to a recording.
+pub struct MusicRights;
+
+impl IssuerWrapper for MusicRights {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { music_rights_schema() }
+    fn issue_impl() -> IfaceImpl { music_rights_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = music_rights_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::stl::Details;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(MusicRights::FEATURES);
+        if let Err(err) = music_rights_rgb20().check(&iface, &music_rights_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid music rights RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn isrc() {
+        let isrc = Isrc::from_str("usrc17607839").unwrap();
+        assert_eq!(isrc.to_string(), "USRC17607839");
+        assert!(Isrc::from_str("US-RC1-76-07839").is_err());
+        assert!(Isrc::from_str("USRC1760783").is_err());
+    }
+
+    #[test]
+    fn claim_layout() {
+        // Royalty collection script reads units, total shares, payment and payout at
+        // offsets 0, 8, 16 and 24 of the claim
+        let claim = RoyaltyClaim::new(Amount::from(250u64), Amount::from(1000u64), 10_003).unwrap();
+        assert_eq!(claim.payout, 2500);
+        let data = claim.to_strict_serialized::<32>().unwrap();
+        assert_eq!(&data[..8], &250u64.to_le_bytes());
+        assert_eq!(&data[8..16], &1000u64.to_le_bytes());
+        assert_eq!(&data[16..24], &10_003u64.to_le_bytes());
+        assert_eq!(&data[24..], &2500u64.to_le_bytes());
+        assert_eq!(RoyaltyClaim::new(Amount::from(1u64), Amount::ZERO, 100), None);
+    }
+
+    /// Issues 1000 rights units with the given total royalty `shares`.
+    fn contract(shares: u64) -> Option<Contract> {
+        let contract = fungible_genesis(issuer::<MusicRights>(), spec("SONG", "Song rights"), 1000)
+            .add_global_state("isrc", Isrc::from_str("USRC17607839").unwrap())
+            .unwrap()
+            .add_global_state("composerIdentity", Details::from("Jane Doe"))
+            .unwrap()
+            .add_global_state("rightsType", RightsType::Performance)
+            .unwrap()
+            .add_global_state("totalRoyaltyShares", Amount::from(shares))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn music_rights_genesis() {
+        assert!(contract(1000).is_some());
+        assert!(contract(100).is_none());
+    }
+
+    #[test]
+    fn collect_royalty() {
+        let contract = contract(1000).unwrap();
+        let collect = |kept: u64, claim: RoyaltyClaim| {
+            let transition = spend_genesis::<MusicRights>(&contract, "collectRoyalty")
+                .add_fungible_state("assetOwner", graph_seal(0), kept)
+                .unwrap()
+                .add_data("royaltyClaim", graph_seal(1), claim)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &MusicRights::schema(), &transition)
+        };
+        let claim = |units: u64, total_shares: u64| {
+            RoyaltyClaim::new(Amount::from(units), Amount::from(total_shares), 10_003).unwrap()
+        };
+
+        let status = collect(1000, claim(1000, 1000));
+        assert!(status.failures.is_empty(), "{status}");
+        // Payout is rounded down
+        let status = collect(1000, claim(1000, 3000));
+        assert!(status.failures.is_empty(), "{status}");
+        let excessive = RoyaltyClaim {
+            payout: 3335,
+            ..claim(1000, 3000)
+        };
+        assert_eq!(script_errno(collect(1000, excessive)), Some(ERRNO_INVALID_ROYALTY));
+        assert_eq!(script_errno(collect(1000, claim(2000, 3000))), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(collect(900, claim(1000, 1000))), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+}
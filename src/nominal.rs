@@ -0,0 +1,646 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Nominal asset schema implementing RGB20 fungible assets interface with both
+//! secondary issuance and burning, like stablecoins minted and redeemed by
+//! their issuer.
+//!
+//! The net circulating supply, i.e. the cumulative issued supply less the
+//! cumulative burned supply, is kept within the maximum supply
+//! ([`GS_MAX_SUPPLY`]) in the same way as in CIA: the genesis assigns the
+//! inflation allowance ([`OS_INFLATION`]) covering exactly the difference
+//! between the maximum and the issued supply, each [`TS_ISSUE_MORE`] spends the
+//! allowance for the issued amount, and each [`TS_BURN`] returns the burned
+//! amount to the allowance. Thus, the sum of the circulating supply and the
+//! allowance always equals the maximum supply. Burning requires both the burn
+//! right and the inflation allowance, so only the issuer can burn.
+//!
+//! Each issuance and burn also declares the resulting net supply
+//! ([`GS_NET_SUPPLY`]). The scripts check these values only in genesis (see
+//! [consensus limitations](crate#consensus-limitations)); wallets are expected
+//! to replay them with [`compute_net_supply`].
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA, INSTR_RET};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDM, INSTR_PCAS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, Genesis, GlobalStateType, Identity, OpId, Operation, Transition};
+use strict_types::TypeSystem;
+
+use crate::cia::global_amount;
+use crate::stl::rgb_schemata_stl;
+use crate::{
+    ERRNO_INFLATION_EXCEEDS_ALLOWANCE, ERRNO_INFLATION_MISMATCH, ERRNO_ISSUED_MISMATCH,
+    ERRNO_NON_EQUAL_IN_OUT, GS_ADDL_SUPPLY, GS_BURNED_SUPPLY, GS_BURN_CONSIGNMENT_URL,
+    GS_ISSUED_SUPPLY, GS_MAX_SUPPLY, GS_NOMINAL, GS_PRECISION, GS_TERMS, MS_ALLOWED_INFLATION,
+    MS_BURN_PROOF, OS_ASSET, OS_BURN_RIGHT, OS_INFLATION, TS_BURN, TS_ISSUE_MORE, TS_TRANSFER,
+};
+
+/// Net circulating supply after the operation: the cumulative issued supply
+/// less the cumulative burned supply.
+pub const GS_NET_SUPPLY: GlobalStateType = GlobalStateType::with(5000);
+
+pub(crate) const FN_NOMINAL_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_NOMINAL_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+pub const FN_NOMINAL_ISSUE_OFFSET: u16 = FN_NOMINAL_GENESIS_OFFSET +
+    (4 + 4 + 4 + 4 + 3 + 3 + 1) +
+    (4 + 3 + 3 + 1) +
+    (4 + 4 + 3 + 3 + 1 + 3 + 2);
+pub const FN_NOMINAL_BURN_OFFSET: u16 = FN_NOMINAL_ISSUE_OFFSET +
+    (4 + 4 + 4 + 3 + 3 + 1 + 3) +
+    (4 + 4 + 4 + 3 + 3 + 1) +
+    (4 + 3 + 1 + 3 + 2);
+const FN_NOMINAL_BURN_END_OFFSET: u16 = FN_NOMINAL_BURN_OFFSET +
+    (4 + 4 + 4 + 3 + 3 + 1 + 3) +
+    (4 + 4 + 4 + 3 + 3 + 1) +
+    (4 + 3 + 1 + 3 + 1);
+
+/// Assembles AluVM library with validation scripts used by the nominal asset
+/// schema.
+// `sub` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn nominal_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the issued supply matches the sum of allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_ASSET;
+        test;
+        // Checking that the net supply equals the issued supply.
+        ldg     GS_NET_SUPPLY,a8[1],s16[2];
+        extr    s16[2],a64[2],a16[0];
+        eq.n    a64[0],a64[2];
+        test;
+        // Checking that the inflation allowance covers exactly the difference between the maximum
+        // and the issued supply.
+        put     a8[0],ERRNO_INFLATION_MISMATCH;
+        ldg     GS_MAX_SUPPLY,a8[1],s16[1];
+        extr    s16[1],a64[1],a16[0];
+        // a64[0] := a64[1] - a64[0]; fails if the issued supply exceeds the max supply
+        sub.uc  a64[1],a64[0];
+        test;
+        pcas    OS_INFLATION;
+        test;
+        ret;
+
+        // SUBROUTINE Issue-more validation
+        // Checking that the spent inflation allowance matches the value reported in the metadata.
+        put     a8[0],ERRNO_INFLATION_EXCEEDS_ALLOWANCE;
+        put     a16[0],0;
+        ldm     MS_ALLOWED_INFLATION,s16[1];
+        extr    s16[1],a64[0],a16[0];
+        pcps    OS_INFLATION;
+        test;
+        // Keep the allowance in a64[1]
+        extr    s16[1],a64[1],a16[0];
+        // Checking pedersen commitments against reported amount of additional supply
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        ldg     GS_ADDL_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_ASSET;
+        test;
+        // Checking that the remaining allowance is re-assigned
+        put     a8[0],ERRNO_INFLATION_EXCEEDS_ALLOWANCE;
+        // a64[0] := a64[1] - a64[0]; fails if more is issued than the spent allowance
+        sub.uc  a64[1],a64[0];
+        test;
+        pcas    OS_INFLATION;
+        test;
+        ret;
+
+        // SUBROUTINE Burn validation
+        // Checking that the spent inflation allowance matches the value reported in the metadata.
+        put     a8[0],ERRNO_INFLATION_MISMATCH;
+        put     a16[0],0;
+        ldm     MS_ALLOWED_INFLATION,s16[1];
+        extr    s16[1],a64[0],a16[0];
+        pcps    OS_INFLATION;
+        test;
+        // Keep the allowance in a64[1]
+        extr    s16[1],a64[1],a16[0];
+        // Checking pedersen commitments of the destroyed inputs against reported amount of burned
+        // assets.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a8[1],0;
+        ldg     GS_BURNED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_ASSET;
+        test;
+        // Checking that the burned amount is returned to the re-assigned allowance
+        put     a8[0],ERRNO_INFLATION_MISMATCH;
+        // a64[0] := a64[1] + a64[0]; fails on overflow
+        add.uc  a64[1],a64[0];
+        test;
+        pcas    OS_INFLATION;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong nominal asset script")
+}
+
+fn nominal_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = nominal_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_NOMINAL_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_NOMINAL_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_NOMINAL_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_NOMINAL_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_NOMINAL_GENESIS_OFFSET as usize + 23], INSTR_LDG);
+    assert_eq!(state_arg(FN_NOMINAL_GENESIS_OFFSET + 23), GS_NET_SUPPLY.to_inner());
+    assert_eq!(code[FN_NOMINAL_GENESIS_OFFSET as usize + 38], INSTR_LDG);
+    assert_eq!(state_arg(FN_NOMINAL_GENESIS_OFFSET + 38), GS_MAX_SUPPLY.to_inner());
+    assert_eq!(code[FN_NOMINAL_ISSUE_OFFSET as usize - 1], INSTR_RET);
+    assert_eq!(code[FN_NOMINAL_ISSUE_OFFSET as usize + 8], INSTR_LDM);
+    assert_eq!(code[FN_NOMINAL_ISSUE_OFFSET as usize + 15], INSTR_PCPS);
+    assert_eq!(state_arg(FN_NOMINAL_ISSUE_OFFSET + 15), OS_INFLATION.to_inner());
+    assert_eq!(code[FN_NOMINAL_ISSUE_OFFSET as usize + 30], INSTR_LDG);
+    assert_eq!(state_arg(FN_NOMINAL_ISSUE_OFFSET + 30), GS_ADDL_SUPPLY.to_inner());
+    assert_eq!(code[FN_NOMINAL_BURN_OFFSET as usize - 1], INSTR_RET);
+    assert_eq!(code[FN_NOMINAL_BURN_OFFSET as usize + 8], INSTR_LDM);
+    assert_eq!(code[FN_NOMINAL_BURN_OFFSET as usize + 12], INSTR_EXTR);
+    assert_eq!(code[FN_NOMINAL_BURN_OFFSET as usize + 30], INSTR_LDG);
+    assert_eq!(state_arg(FN_NOMINAL_BURN_OFFSET + 30), GS_BURNED_SUPPLY.to_inner());
+    assert_eq!(code[FN_NOMINAL_BURN_OFFSET as usize + 37], INSTR_PCPS);
+    assert_eq!(state_arg(FN_NOMINAL_BURN_OFFSET + 37), OS_ASSET.to_inner());
+    assert_eq!(code[FN_NOMINAL_BURN_OFFSET as usize + 49], INSTR_PCAS);
+    assert_eq!(state_arg(FN_NOMINAL_BURN_OFFSET + 49), OS_INFLATION.to_inner());
+    assert_eq!(code[FN_NOMINAL_BURN_END_OFFSET as usize], INSTR_RET);
+    assert_eq!(code.len(), FN_NOMINAL_BURN_END_OFFSET as usize + 1);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("NominalAsset"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: tiny_bmap! {
+            MS_ALLOWED_INFLATION => types.get("RGBContract.Amount"),
+            MS_BURN_PROOF => types.get("RGBContract.BurnMeta"),
+        },
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_MAX_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_ADDL_SUPPLY => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+            GS_BURNED_SUPPLY => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+            GS_BURN_CONSIGNMENT_URL => GlobalStateSchema::many(types.get("RGBContract.Details")),
+            GS_NET_SUPPLY => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_INFLATION => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_BURN_RIGHT => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_MAX_SUPPLY => Occurrences::Once,
+                GS_NET_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::NoneOrMore,
+                OS_INFLATION => Occurrences::OnceOrMore,
+                OS_BURN_RIGHT => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_NOMINAL_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_NOMINAL_TRANSFER_OFFSET, alu_id))
+            },
+            TS_ISSUE_MORE => TransitionSchema {
+                metadata: tiny_bset![MS_ALLOWED_INFLATION],
+                globals: tiny_bmap! {
+                    GS_ADDL_SUPPLY => Occurrences::Once,
+                    GS_NET_SUPPLY => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_INFLATION => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_INFLATION => Occurrences::OnceOrMore,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_NOMINAL_ISSUE_OFFSET, alu_id))
+            },
+            TS_BURN => TransitionSchema {
+                metadata: tiny_bset![MS_ALLOWED_INFLATION, MS_BURN_PROOF],
+                globals: tiny_bmap! {
+                    GS_BURNED_SUPPLY => Occurrences::Once,
+                    GS_BURN_CONSIGNMENT_URL => Occurrences::NoneOrOnce,
+                    GS_NET_SUPPLY => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_INFLATION => Occurrences::OnceOrMore,
+                    OS_BURN_RIGHT => Occurrences::OnceOrMore,
+                },
+                assignments: tiny_bmap! {
+                    OS_INFLATION => Occurrences::OnceOrMore,
+                    OS_BURN_RIGHT => Occurrences::NoneOrMore,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_NOMINAL_BURN_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn nominal_rgb20() -> IfaceImpl {
+    let schema = nominal_schema();
+    let iface = Rgb20::iface(NominalAsset::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: tiny_bset! {
+            NamedField::with(MS_ALLOWED_INFLATION, fname!("allowedInflation")),
+            NamedField::with(MS_BURN_PROOF, fname!("burnProof")),
+        },
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_MAX_SUPPLY, fname!("maxSupply")),
+            NamedField::with(GS_ADDL_SUPPLY, fname!("additionalSupply")),
+            NamedField::with(GS_BURNED_SUPPLY, fname!("burnedSupply")),
+            NamedField::with(GS_BURN_CONSIGNMENT_URL, fname!("burnConsignmentUrl")),
+            NamedField::with(GS_NET_SUPPLY, fname!("netSupply")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+            NamedField::with(OS_INFLATION, fname!("inflationAllowance")),
+            NamedField::with(OS_BURN_RIGHT, fname!("burnRight")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_ISSUE_MORE, fname!("issue")),
+            NamedField::with(TS_BURN, fname!("burn")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+            NamedVariant::with(ERRNO_INFLATION_MISMATCH, vname!("inflationMismatch")),
+            NamedVariant::with(
+                ERRNO_INFLATION_EXCEEDS_ALLOWANCE,
+                vname!("inflationExceedsAllowance")
+            ),
+        ],
+    }
+}
+
+/// Errors replaying the net supply of a nominal asset contract with
+/// [`compute_net_supply`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum NetSupplyError {
+    /// genesis doesn't declare a valid issued, maximum or net supply.
+    InvalidGenesis,
+
+    /// operation {0} doesn't declare a valid supply change or net supply.
+    InvalidOperation(OpId),
+
+    /// operation {opid} declares net supply {declared}, while the actual net
+    /// supply is {actual}.
+    Mismatch {
+        opid: OpId,
+        declared: u64,
+        actual: u64,
+    },
+
+    /// net supply change of operation {0} overflows the maximum supply or
+    /// underflows zero.
+    OutOfBounds(OpId),
+}
+
+/// Replays the `transitions` of a nominal asset contract in their chronological
+/// order, checking the net supply ([`GS_NET_SUPPLY`]) declared by the `genesis`
+/// and each of its [`TS_ISSUE_MORE`] and [`TS_BURN`] transitions, and returning
+/// the final net supply.
+///
+/// Transitions of other types or of other contracts are skipped.
+pub fn compute_net_supply<'op>(
+    genesis: &Genesis,
+    transitions: impl Iterator<Item = &'op Transition>,
+) -> Result<u64, NetSupplyError> {
+    let issued = global_amount(&genesis.globals, GS_ISSUED_SUPPLY);
+    let max = global_amount(&genesis.globals, GS_MAX_SUPPLY);
+    let (Some(mut net), Some(max)) = (issued, max) else {
+        return Err(NetSupplyError::InvalidGenesis);
+    };
+    if global_amount(&genesis.globals, GS_NET_SUPPLY) != Some(net) || net > max {
+        return Err(NetSupplyError::InvalidGenesis);
+    }
+    let contract_id = genesis.contract_id();
+    for transition in transitions {
+        if transition.contract_id != contract_id {
+            continue;
+        }
+        let opid = transition.id();
+        let change = |ty| {
+            global_amount(&transition.globals, ty).ok_or(NetSupplyError::InvalidOperation(opid))
+        };
+        net = match transition.transition_type {
+            TS_ISSUE_MORE => net
+                .checked_add(change(GS_ADDL_SUPPLY)?)
+                .filter(|net| *net <= max),
+            TS_BURN => net.checked_sub(change(GS_BURNED_SUPPLY)?),
+            _ => continue,
+        }
+        .ok_or(NetSupplyError::OutOfBounds(opid))?;
+        let declared = change(GS_NET_SUPPLY)?;
+        if declared != net {
+            return Err(NetSupplyError::Mismatch {
+                opid,
+                declared,
+                actual: net,
+            });
+        }
+    }
+    Ok(net)
+}
+
+/// Fungible asset which can be both issued and burned by its issuer, keeping
+/// the net supply within the maximum supply.
+pub struct NominalAsset;
+
+impl IssuerWrapper for NominalAsset {
+    const FEATURES: rgb20::Features = rgb20::Features::INFLATABLE_BURNABLE;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { nominal_schema() }
+    fn issue_impl() -> IfaceImpl { nominal_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = nominal_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::U16;
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::ContractBuilder;
+    use rgbstd::invoice::Precision;
+    use rgbstd::stl::{AssetSpec, BurnMeta};
+    use rgbstd::{Amount, ContractId, DataState};
+    use strict_encoding::{StrictDumb, StrictSerialize};
+
+    use super::*;
+    use crate::test_helpers::{
+        add_metadata, fungible_genesis, graph_seal, issuer, script_errno, seal, spend_genesis,
+        validate_transition,
+    };
+
+    fn builder(issued: u64, max: u64, net: u64, allowance: u64) -> ContractBuilder {
+        let spec = AssetSpec::new("NUSD", "Nominal USD", Precision::CentiMicro);
+        fungible_genesis(issuer::<NominalAsset>(), spec, issued)
+            .add_global_state("maxSupply", Amount::from(max))
+            .unwrap()
+            .add_global_state("netSupply", Amount::from(net))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), issued)
+            .unwrap()
+            .add_fungible_state("inflationAllowance", seal(1), allowance)
+            .unwrap()
+            .add_rights("burnRight", seal(2))
+            .unwrap()
+    }
+
+    /// Issues 1000 out of maximal 10000 units.
+    fn contract() -> Contract {
+        builder(1_000, 10_000, 1_000, 9_000)
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(NominalAsset::FEATURES);
+        if let Err(err) = nominal_rgb20().check(&iface, &nominal_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid nominal asset RGB20 interface implementation");
+        }
+        let schema = nominal_schema();
+        let burn = &schema.transitions[&TS_BURN];
+        assert_eq!(burn.inputs.get(&OS_INFLATION), Some(&Occurrences::OnceOrMore));
+        assert_eq!(burn.assignments.get(&OS_INFLATION), Some(&Occurrences::OnceOrMore));
+        assert_eq!(burn.globals.get(&GS_NET_SUPPLY), Some(&Occurrences::Once));
+    }
+
+    #[test]
+    fn nominal_genesis() {
+        assert!(builder(1_000, 10_000, 1_000, 9_000)
+            .issue_contract()
+            .is_ok());
+        assert!(builder(1_000, 10_000, 1_000, 8_999)
+            .issue_contract()
+            .is_err());
+        assert!(builder(1_000, 10_000, 999, 9_000).issue_contract().is_err());
+    }
+
+    #[test]
+    fn net_supply() {
+        let contract = contract();
+        let genesis = &contract.genesis;
+        let transition = |ty, change_ty, change: u64, net: u64| {
+            let mut transition = Transition::strict_dumb();
+            transition.contract_id = genesis.contract_id();
+            transition.transition_type = ty;
+            for (ty, amount) in [(change_ty, change), (GS_NET_SUPPLY, net)] {
+                let data = Amount::from(amount).to_strict_serialized::<U16>().unwrap();
+                transition
+                    .globals
+                    .add_state(ty, DataState::from(data))
+                    .unwrap();
+            }
+            transition
+        };
+        let issue = transition(TS_ISSUE_MORE, GS_ADDL_SUPPLY, 9_000, 10_000);
+        let burn = transition(TS_BURN, GS_BURNED_SUPPLY, 4_000, 6_000);
+        let reissue = transition(TS_ISSUE_MORE, GS_ADDL_SUPPLY, 4_000, 10_000);
+        let transfer = transition(TS_TRANSFER, GS_ADDL_SUPPLY, 7_000, 0);
+        let mut foreign = transition(TS_BURN, GS_BURNED_SUPPLY, 1, 0);
+        foreign.contract_id = ContractId::strict_dumb();
+
+        assert_eq!(compute_net_supply(genesis, [].into_iter()), Ok(1_000));
+        assert_eq!(
+            compute_net_supply(genesis, [&issue, &transfer, &burn, &foreign, &reissue].into_iter()),
+            Ok(10_000)
+        );
+
+        let excess = transition(TS_ISSUE_MORE, GS_ADDL_SUPPLY, 1, 10_001);
+        assert_eq!(
+            compute_net_supply(genesis, [&issue, &excess].into_iter()),
+            Err(NetSupplyError::OutOfBounds(excess.id()))
+        );
+        let overburn = transition(TS_BURN, GS_BURNED_SUPPLY, 1_001, 0);
+        assert_eq!(
+            compute_net_supply(genesis, [&overburn].into_iter()),
+            Err(NetSupplyError::OutOfBounds(overburn.id()))
+        );
+        let wrong = transition(TS_BURN, GS_BURNED_SUPPLY, 500, 600);
+        assert_eq!(
+            compute_net_supply(genesis, [&wrong].into_iter()),
+            Err(NetSupplyError::Mismatch {
+                opid: wrong.id(),
+                declared: 600,
+                actual: 500
+            })
+        );
+        let mut invalid = transition(TS_BURN, GS_BURNED_SUPPLY, 1, 999);
+        invalid.globals = none!();
+        assert_eq!(
+            compute_net_supply(genesis, [&invalid].into_iter()),
+            Err(NetSupplyError::InvalidOperation(invalid.id()))
+        );
+    }
+
+    #[test]
+    fn transfer() {
+        let contract = contract();
+        let transfer = |amount: u64| {
+            let transition = spend_genesis::<NominalAsset>(&contract, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &NominalAsset::schema(), &transition)
+        };
+
+        let status = transfer(1_000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn issue_more() {
+        let contract = contract();
+        let issue = |allowed: u64, issued: u64, allocated: u64, remaining: u64| {
+            let mut transition = spend_genesis::<NominalAsset>(&contract, "issue")
+                .add_global_state("additionalSupply", Amount::from(issued))
+                .unwrap()
+                .add_global_state("netSupply", Amount::from(1_000 + issued))
+                .unwrap()
+                .add_fungible_state("assetOwner", graph_seal(0), allocated)
+                .unwrap()
+                .add_fungible_state("inflationAllowance", graph_seal(1), remaining)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            add_metadata(&mut transition, MS_ALLOWED_INFLATION, Amount::from(allowed));
+            validate_transition(&contract, &NominalAsset::schema(), &transition)
+        };
+
+        let status = issue(9_000, 2_000, 2_000, 7_000);
+        assert!(status.failures.is_empty(), "{status}");
+        let errno = ERRNO_INFLATION_EXCEEDS_ALLOWANCE;
+        assert_eq!(script_errno(issue(8_000, 2_000, 2_000, 7_000)), Some(errno));
+        assert_eq!(script_errno(issue(9_000, 2_000, 2_000, 6_000)), Some(errno));
+        assert_eq!(script_errno(issue(9_000, 9_001, 9_001, 0)), Some(errno));
+        let status = issue(9_000, 2_000, 2_500, 7_000);
+        assert_eq!(script_errno(status), Some(ERRNO_ISSUED_MISMATCH));
+    }
+
+    #[test]
+    fn burn() {
+        let contract = contract();
+        let burn = |allowed: u64, burned: u64, remaining: u64| {
+            let mut transition = spend_genesis::<NominalAsset>(&contract, "burn")
+                .add_global_state("burnedSupply", Amount::from(burned))
+                .unwrap()
+                .add_global_state("netSupply", Amount::from(1_000 - burned))
+                .unwrap()
+                .add_fungible_state("inflationAllowance", graph_seal(0), remaining)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            add_metadata(&mut transition, MS_ALLOWED_INFLATION, Amount::from(allowed));
+            add_metadata(&mut transition, MS_BURN_PROOF, BurnMeta::default());
+            validate_transition(&contract, &NominalAsset::schema(), &transition)
+        };
+
+        let status = burn(9_000, 1_000, 10_000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(burn(9_000, 999, 9_999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(burn(9_000, 1_000, 9_000)), Some(ERRNO_INFLATION_MISMATCH));
+        assert_eq!(script_errno(burn(8_000, 1_000, 9_000)), Some(ERRNO_INFLATION_MISMATCH));
+    }
+}
@@ -0,0 +1,321 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Oracle-validated conditional transfers, composable into the schemata with
+//! [`OS_ASSET`] fungible state, like DeFi tokens restricting transfers
+//! depending on the asset price.
+//!
+//! The genesis of a schema extended by [`OracleTransfer::apply`] may commit to
+//! the oracle key ([`GS_ORACLE_KEY`]). [`TS_ORACLE_TRANSFER`] moves assets in
+//! the same way as the normal transfer, carrying the oracle-signed price
+//! attestation ([`OracleAttestation`]) in its metadata. The script checks only
+//! that the sum of inputs equals the sum of outputs, while wallets must check
+//! the attestation against the oracle key of the contract with
+//! [`OracleAttestation::verify`] before accepting the transfer (see [consensus
+//! limitations](crate#consensus-limitations)).
+
+use aluvm::isa::opcodes::{INSTR_PUTA, INSTR_RET, INSTR_TEST};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use bp::secp256k1::{schnorr, Message, XOnlyPublicKey, SECP256K1};
+use rgbstd::interface::{IfaceImpl, NamedField};
+use rgbstd::schema::{GlobalStateSchema, Occurrences, Schema, TransitionSchema};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_PCVS;
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, MetaType, TransitionType};
+use sha2::{Digest, Sha256};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+use crate::nia::nia_type;
+use crate::stl::{BlockHeight, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    nia_base_lib, nia_lib, IssuerKey, NiaSchemaError, OraclePrice, ERRNO_NON_EQUAL_IN_OUT,
+    GS_ORACLE_KEY, OS_ASSET,
+};
+
+/// Oracle-signed price attestation ([`OracleAttestation`]), provided by the
+/// oracle transfer.
+pub const MS_ORACLE_ATTESTATION: MetaType = MetaType::with(4800);
+
+pub const TS_ORACLE_TRANSFER: TransitionType = TransitionType::with(12800);
+
+pub const FN_ORACLE_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_ORACLE_TRANSFER_END_OFFSET: u16 = FN_ORACLE_TRANSFER_OFFSET + 4 + 3 + 1;
+
+/// Oracle attestation of the asset price, authorizing a conditional transfer.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct OracleAttestation {
+    /// Price of the asset attested by the oracle.
+    pub price: OraclePrice,
+    /// Height of the block at which the price was observed.
+    pub height: BlockHeight,
+    /// BIP-340 signature of the oracle key over [`OracleAttestation::message`].
+    pub signature: [u8; 64],
+}
+impl StrictSerialize for OracleAttestation {}
+impl StrictDeserialize for OracleAttestation {}
+
+impl OracleAttestation {
+    fn dumb() -> Self {
+        Self {
+            price: OraclePrice::default(),
+            height: BlockHeight::default(),
+            signature: [0u8; 64],
+        }
+    }
+
+    /// Computes the message signed by the oracle, which is SHA256 hash of the
+    /// strict-encoded attestation without the signature.
+    pub fn message(&self) -> [u8; 32] {
+        let data = self
+            .to_strict_serialized::<76>()
+            .expect("fixed-size attestation");
+        Sha256::digest(&data[..data.len() - 64]).into()
+    }
+
+    /// Verifies the signature of the `oracle` key over the attestation.
+    pub fn verify(&self, oracle: &IssuerKey) -> bool {
+        let Ok(key) = XOnlyPublicKey::from_slice(&oracle[1..]) else {
+            return false;
+        };
+        let Ok(signature) = schnorr::Signature::from_slice(&self.signature) else {
+            return false;
+        };
+        let message = Message::from_digest(self.message());
+        SECP256K1.verify_schnorr(&signature, &message, &key).is_ok()
+    }
+}
+
+/// AluVM library validating [`TS_ORACLE_TRANSFER`] transitions.
+pub fn oracle_transfer_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Oracle transfer validation
+        // The oracle signature is verified by wallets, since AluVM can't check it.
+        // Checking that the sum of inputs is equal to the sum of outputs.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_ASSET;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong oracle transfer script")
+}
+
+/// State transition adding oracle-validated transfers to a schema with
+/// [`OS_ASSET`] fungible state.
+pub struct OracleTransfer;
+
+impl OracleTransfer {
+    /// Constructs the schema of [`TS_ORACLE_TRANSFER`] state transition.
+    pub fn transition_schema() -> TransitionSchema {
+        let alu_lib = oracle_transfer_lib();
+        let code = alu_lib.code.as_ref();
+        let state_arg =
+            |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+        assert_eq!(code[FN_ORACLE_TRANSFER_OFFSET as usize], INSTR_PUTA);
+        assert_eq!(code[FN_ORACLE_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+        assert_eq!(state_arg(FN_ORACLE_TRANSFER_OFFSET + 4), OS_ASSET.to_inner());
+        assert_eq!(code[FN_ORACLE_TRANSFER_OFFSET as usize + 7], INSTR_TEST);
+        assert_eq!(code[FN_ORACLE_TRANSFER_END_OFFSET as usize], INSTR_RET);
+        assert_eq!(code.len(), FN_ORACLE_TRANSFER_END_OFFSET as usize + 1);
+
+        TransitionSchema {
+            metadata: tiny_bset![MS_ORACLE_ATTESTATION],
+            globals: none!(),
+            inputs: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_ORACLE_TRANSFER_OFFSET, alu_lib.id())),
+        }
+    }
+
+    /// Adds [`TS_ORACLE_TRANSFER`] state transition to the `schema`, allowing
+    /// its genesis to commit to the oracle key.
+    pub fn apply(schema: &mut Schema) -> Result<(), NiaSchemaError> {
+        schema
+            .meta_types
+            .insert(MS_ORACLE_ATTESTATION, nia_type("RGBSchemata.OracleAttestation")?)
+            .expect("too many meta types");
+        schema
+            .global_types
+            .insert(GS_ORACLE_KEY, GlobalStateSchema::once(nia_type("RGBSchemata.IssuerKey")?))
+            .expect("too many global types");
+        schema
+            .genesis
+            .globals
+            .insert(GS_ORACLE_KEY, Occurrences::NoneOrOnce)
+            .expect("too many genesis globals");
+        schema
+            .transitions
+            .insert(TS_ORACLE_TRANSFER, Self::transition_schema())
+            .expect("too many transitions");
+        Ok(())
+    }
+
+    /// Adds oracle state names to the interface implementation of an extended
+    /// schema.
+    pub fn apply_names(iimpl: &mut IfaceImpl) {
+        iimpl
+            .metadata
+            .push(NamedField::with(MS_ORACLE_ATTESTATION, fname!("oracleAttestation")))
+            .expect("too many metadata fields");
+        iimpl
+            .global_state
+            .push(NamedField::with(GS_ORACLE_KEY, fname!("oracleKey")))
+            .expect("too many global state fields");
+        iimpl
+            .transitions
+            .push(NamedField::with(TS_ORACLE_TRANSFER, fname!("oracleTransfer")))
+            .expect("too many transitions");
+    }
+
+    /// Validation scripts of a NIA schema extended with oracle transfers.
+    pub fn scripts() -> Scripts {
+        let base = nia_base_lib().expect("invalid NIA validation library");
+        let nia = nia_lib().expect("invalid NIA validation library");
+        let lib = oracle_transfer_lib();
+        confined_bmap! { base.id() => base, nia.id() => nia, lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bp::secp256k1::Keypair;
+    use ifaces::{IssuerWrapper, Rgb20};
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::{ContractBuilder, IfaceClass, TransitionBuilder};
+    use rgbstd::Identity;
+
+    use super::*;
+    use crate::test_helpers::{
+        add_metadata, fungible_genesis, graph_seal, script_errno, seal, spec, spend_genesis_with,
+        validate_transition,
+    };
+    use crate::{nia_iface_impl, nia_schema, NonInflatableAsset};
+
+    fn oracle_schema() -> Schema {
+        let mut schema = nia_schema().unwrap();
+        OracleTransfer::apply(&mut schema).unwrap();
+        schema
+    }
+
+    fn oracle_iimpl() -> IfaceImpl {
+        let mut iimpl = nia_iface_impl().unwrap();
+        iimpl.schema_id = oracle_schema().schema_id();
+        OracleTransfer::apply_names(&mut iimpl);
+        iimpl
+    }
+
+    /// Issues 1000 units of NIA extended with oracle transfers.
+    fn contract() -> Contract {
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            Rgb20::iface(NonInflatableAsset::FEATURES),
+            oracle_schema(),
+            oracle_iimpl(),
+            NonInflatableAsset::types(),
+            OracleTransfer::scripts(),
+        );
+        fungible_genesis(builder, spec("TEST", "Test"), 1000)
+            .add_global_state("oracleKey", IssuerKey::from([2u8; 33]))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .issue_contract()
+            .expect("genesis may commit to the oracle key")
+            .into_consignment()
+    }
+
+    #[test]
+    fn oracle_nia() {
+        let schema = oracle_schema();
+        assert_eq!(schema.genesis.globals.get(&GS_ORACLE_KEY), Some(&Occurrences::NoneOrOnce));
+        let transfer = &schema.transitions[&TS_ORACLE_TRANSFER];
+        assert!(transfer.metadata.contains(&MS_ORACLE_ATTESTATION));
+        assert_eq!(transfer.inputs.get(&OS_ASSET), Some(&Occurrences::OnceOrMore));
+        assert_eq!(OracleTransfer::scripts().len(), 3);
+
+        let iface = Rgb20::iface(NonInflatableAsset::FEATURES);
+        assert!(oracle_iimpl().check(&iface, &schema).is_ok());
+        contract();
+    }
+
+    #[test]
+    fn attestation_signature() {
+        let keypair = Keypair::from_seckey_slice(SECP256K1, &[1u8; 32]).unwrap();
+        let oracle = IssuerKey::from(keypair.public_key().serialize());
+        let mut attestation = OracleAttestation {
+            price: OraclePrice::from(6_500_000_000_000),
+            height: BlockHeight::from(850_000),
+            signature: [0u8; 64],
+        };
+        assert!(!attestation.verify(&oracle));
+        let message = Message::from_digest(attestation.message());
+        attestation.signature = *SECP256K1
+            .sign_schnorr_no_aux_rand(&message, &keypair)
+            .as_ref();
+        assert!(attestation.verify(&oracle));
+
+        let other = Keypair::from_seckey_slice(SECP256K1, &[2u8; 32]).unwrap();
+        assert!(!attestation.verify(&IssuerKey::from(other.public_key().serialize())));
+        attestation.price = OraclePrice::from(1);
+        assert!(!attestation.verify(&oracle));
+    }
+
+    #[test]
+    fn oracle_transfer() {
+        let contract = contract();
+        let attestation = OracleAttestation {
+            price: OraclePrice::from(6_500_000_000_000),
+            height: BlockHeight::from(850_000),
+            signature: [0u8; 64],
+        };
+        let transfer = |amount: u64| {
+            let builder = TransitionBuilder::named_transition(
+                contract.contract_id(),
+                Rgb20::iface(NonInflatableAsset::FEATURES),
+                oracle_schema(),
+                oracle_iimpl(),
+                "oracleTransfer",
+                NonInflatableAsset::types(),
+            )
+            .unwrap();
+            let mut transition = spend_genesis_with(&contract, &oracle_schema(), builder)
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            add_metadata(&mut transition, MS_ORACLE_ATTESTATION, attestation);
+            validate_transition(&contract, &oracle_schema(), &transition)
+        };
+
+        let status = transfer(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+}
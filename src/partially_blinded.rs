@@ -0,0 +1,489 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Partially blinded asset schema implementing RGB20 fungible assets interface
+//! with selective disclosure of the confidential amounts.
+//!
+//! The assets are held as Pedersen-committed allocations
+//! ([`OS_BLINDED_ASSET`]); a holder may disclose the amount of an allocation to
+//! an auditor or a counterparty with [`TS_REVEAL`] transition, which spends the
+//! allocation and produces a revealed allocation ([`OS_REVEALED_ASSET`])
+//! keeping the amount together with the blinding factor of the commitment
+//! ([`RevealedAsset`]). When an auditor requests disclosure of a single output
+//! of a confidential transfer, [`TS_PARTIAL_REVEAL`] transition reveals that
+//! output while keeping the other outputs blinded.
+//!
+//! RGB consensus checks the sums of Pedersen commitments only against the
+//! amounts blinded with the reserved empty blinding factor, and AluVM has no
+//! instruction opening a commitment with a blinding factor taken from the
+//! state. Thus, the scripts check only that the revealed opening, provided in
+//! the transition metadata ([`MS_REVEAL_OPENING`]), hashes to the original
+//! commitment of the revealed allocation and matches its amount and blinding
+//! factor; the receivers of the disclosure must check that the revealed
+//! allocation opens the spent commitment with [`RevealedAsset::opens`].
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA, INSTR_RET, INSTR_SHA256};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use commit_verify::Conceal;
+use ifaces::{rgb20, IssuerWrapper, Rgb20};
+use rgbstd::interface::{IfaceImpl, NamedField};
+use rgbstd::schema::{Occurrences, Schema, TransitionSchema};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDM, INSTR_LDS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{
+    rgbasm, Amount, AssetTag, AssignmentType, BlindingFactor, MetaType, OwnedStateSchema,
+    PedersenCommitment, RevealedValue, TransitionType,
+};
+use sha2::{Digest, Sha256};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::nia::{nia_iface_impl_for, nia_type};
+use crate::stl::LIB_NAME_RGB_SCHEMATA;
+use crate::{
+    nia_lib, nia_schema_with_features, NiaFeatures, NiaSchemaError, NonInflatableAsset, OS_ASSET,
+};
+
+/// Confidential allocation of the asset, committing to the amount with a
+/// Pedersen commitment.
+pub const OS_BLINDED_ASSET: AssignmentType = OS_ASSET;
+/// Allocation disclosing its amount and blinding factor ([`RevealedAsset`]).
+pub const OS_REVEALED_ASSET: AssignmentType = AssignmentType::with(6700);
+
+/// Opening of the revealed commitment ([`RevealOpening`]), provided by the
+/// reveal transitions.
+pub const MS_REVEAL_OPENING: MetaType = MetaType::with(4801);
+
+pub const TS_REVEAL: TransitionType = TransitionType::with(12700);
+pub const TS_PARTIAL_REVEAL: TransitionType = TransitionType::with(12701);
+
+/// Revealed allocation doesn't match the opening of its original commitment.
+/// Not a part of RGB20 interface errors.
+pub const ERRNO_INVALID_REVEAL: u8 = 62;
+
+pub const FN_REVEAL_OFFSET: u16 = 0;
+const FN_REVEAL_END_OFFSET: u16 =
+    FN_REVEAL_OFFSET + 4 * 4 + 4 + 2 + 4 + (3 + 3 + 1) + (3 + 3 + 3 + 1) * 2;
+
+/// Disclosed opening of a [`OS_BLINDED_ASSET`] allocation, kept as
+/// [`OS_REVEALED_ASSET`] owned state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct RevealedAsset {
+    /// Blinding factor of the Pedersen commitment to the amount.
+    pub blinding_factor: [u8; 32],
+    /// Amount of the revealed allocation.
+    pub amount: Amount,
+    /// SHA-256 commitment to the opening, see [`RevealOpening::commitment`].
+    pub original_commitment: [u8; 32],
+}
+impl StrictSerialize for RevealedAsset {}
+impl StrictDeserialize for RevealedAsset {}
+
+impl RevealedAsset {
+    /// Constructs the disclosure for the revealed state of a blinded
+    /// allocation.
+    pub fn with(value: &RevealedValue) -> Self { RevealOpening::with(value).reveal() }
+
+    /// Returns the opening of the revealed commitment, to be put into the
+    /// reveal transition metadata.
+    pub fn opening(&self) -> RevealOpening {
+        RevealOpening {
+            blinding_factor: self.blinding_factor,
+            amount: self.amount,
+        }
+    }
+
+    /// Checks that the amount and the blinding factor open the `commitment` of
+    /// the spent allocation, using the asset `tag` of [`OS_BLINDED_ASSET`]
+    /// from the contract genesis.
+    pub fn opens(&self, commitment: PedersenCommitment, tag: AssetTag) -> bool {
+        let Ok(blinding) = BlindingFactor::try_from(self.blinding_factor) else {
+            return false;
+        };
+        RevealedValue::with_blinding(self.amount.value(), blinding, tag)
+            .conceal()
+            .commitment ==
+            commitment
+    }
+}
+
+/// Opening of a blinded allocation commitment, kept as [`MS_REVEAL_OPENING`]
+/// metadata.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct RevealOpening {
+    /// Blinding factor of the Pedersen commitment to the amount.
+    pub blinding_factor: [u8; 32],
+    /// Amount of the revealed allocation.
+    pub amount: Amount,
+}
+impl StrictSerialize for RevealOpening {}
+impl StrictDeserialize for RevealOpening {}
+
+impl RevealOpening {
+    /// Constructs the opening of the revealed state of a blinded allocation.
+    pub fn with(value: &RevealedValue) -> Self {
+        Self {
+            blinding_factor: *value.blinding,
+            amount: Amount::from(value.value.as_u64()),
+        }
+    }
+
+    /// Computes SHA-256 commitment to the blinding factor followed by the
+    /// little-endian amount.
+    pub fn commitment(&self) -> [u8; 32] {
+        let data = self
+            .to_strict_serialized::<40>()
+            .expect("fixed-size opening");
+        Sha256::digest(data.as_slice()).into()
+    }
+
+    /// Constructs the revealed allocation committing to this opening.
+    pub fn reveal(&self) -> RevealedAsset {
+        RevealedAsset {
+            blinding_factor: self.blinding_factor,
+            amount: self.amount,
+            original_commitment: self.commitment(),
+        }
+    }
+}
+
+/// AluVM library validating [`TS_REVEAL`] and [`TS_PARTIAL_REVEAL`]
+/// transitions.
+pub fn reveal_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Reveal validation
+        put     a8[0],ERRNO_INVALID_REVEAL;
+        // Offsets of the blinding factor, the amount and the original commitment
+        put     a16[0],0;
+        put     a16[1],32;
+        put     a16[2],40;
+        ldm     MS_REVEAL_OPENING,s16[0];
+        sha2    s16[0],r256[0];
+        lds     OS_REVEALED_ASSET,a16[0],s16[1];
+        // Checking that the opening hashes to the original commitment
+        extr    s16[1],r256[1],a16[2];
+        eq.n    r256[0],r256[1];
+        test;
+        // Checking that the revealed blinding factor and the amount match the opening
+        extr    s16[0],r256[0],a16[0];
+        extr    s16[1],r256[1],a16[0];
+        eq.n    r256[0],r256[1];
+        test;
+        extr    s16[0],a64[0],a16[1];
+        extr    s16[1],a64[1],a16[1];
+        eq.n    a64[0],a64[1];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong reveal script")
+}
+
+/// Constructs the schema of a reveal state transition, spending `inputs`
+/// blinded allocations and producing a single revealed allocation together with
+/// `blinded` ones.
+fn reveal_schema(inputs: Occurrences, blinded: Option<Occurrences>) -> TransitionSchema {
+    let alu_lib = reveal_lib();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_REVEAL_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_REVEAL_OFFSET as usize + 16], INSTR_LDM);
+    assert_eq!(state_arg(FN_REVEAL_OFFSET + 16), MS_REVEAL_OPENING.to_inner());
+    assert_eq!(code[FN_REVEAL_OFFSET as usize + 20], INSTR_SHA256);
+    assert_eq!(code[FN_REVEAL_OFFSET as usize + 22], INSTR_LDS);
+    assert_eq!(state_arg(FN_REVEAL_OFFSET + 22), OS_REVEALED_ASSET.to_inner());
+    assert_eq!(code[FN_REVEAL_OFFSET as usize + 26], INSTR_EXTR);
+    assert_eq!(code[FN_REVEAL_END_OFFSET as usize], INSTR_RET);
+    assert_eq!(code.len(), FN_REVEAL_END_OFFSET as usize + 1);
+
+    let mut assignments = tiny_bmap! {
+        OS_REVEALED_ASSET => Occurrences::Once,
+    };
+    if let Some(blinded) = blinded {
+        assignments
+            .insert(OS_BLINDED_ASSET, blinded)
+            .expect("too many assignments");
+    }
+    TransitionSchema {
+        metadata: tiny_bset![MS_REVEAL_OPENING],
+        globals: none!(),
+        inputs: tiny_bmap! {
+            OS_BLINDED_ASSET => inputs,
+        },
+        assignments,
+        valencies: none!(),
+        validator: Some(LibSite::with(FN_REVEAL_OFFSET, alu_lib.id())),
+    }
+}
+
+/// Constructs the partially blinded asset schema, extending NIA with
+/// [`TS_REVEAL`] and [`TS_PARTIAL_REVEAL`] transitions.
+pub fn partially_blinded_schema() -> Result<Schema, NiaSchemaError> {
+    let mut schema = nia_schema_with_features(PartiallyBlindedSchema::NIA_FEATURES)?;
+    schema.name = tn!("PartiallyBlindedAsset");
+    schema
+        .meta_types
+        .insert(MS_REVEAL_OPENING, nia_type("RGBSchemata.RevealOpening")?)
+        .expect("too many meta types");
+    schema
+        .owned_types
+        .insert(
+            OS_REVEALED_ASSET,
+            OwnedStateSchema::Structured(nia_type("RGBSchemata.RevealedAsset")?),
+        )
+        .expect("too many owned types");
+    schema
+        .transitions
+        .insert(TS_REVEAL, reveal_schema(Occurrences::Once, None))
+        .expect("too many transitions");
+    schema
+        .transitions
+        .insert(
+            TS_PARTIAL_REVEAL,
+            reveal_schema(Occurrences::OnceOrMore, Some(Occurrences::NoneOrMore)),
+        )
+        .expect("too many transitions");
+    Ok(schema)
+}
+
+/// Constructs RGB20 interface implementation for the
+/// [`partially_blinded_schema`].
+pub fn partially_blinded_iface_impl() -> Result<IfaceImpl, NiaSchemaError> {
+    let schema = partially_blinded_schema()?;
+    let mut iimpl = nia_iface_impl_for(&schema, PartiallyBlindedSchema::NIA_FEATURES)?;
+    iimpl
+        .metadata
+        .push(NamedField::with(MS_REVEAL_OPENING, fname!("revealOpening")))
+        .expect("too many metadata fields");
+    iimpl
+        .assignments
+        .push(NamedField::with(OS_REVEALED_ASSET, fname!("revealedAsset")))
+        .expect("too many assignments");
+    iimpl
+        .transitions
+        .push(NamedField::with(TS_REVEAL, fname!("reveal")))
+        .expect("too many transitions");
+    iimpl
+        .transitions
+        .push(NamedField::with(TS_PARTIAL_REVEAL, fname!("partialReveal")))
+        .expect("too many transitions");
+    Ok(iimpl)
+}
+
+/// Non-inflatable asset whose holders may disclose the amounts of their
+/// allocations, see [`partially_blinded_schema`].
+pub struct PartiallyBlindedSchema;
+
+impl PartiallyBlindedSchema {
+    /// Optional NIA features present in the partially blinded schema.
+    pub const NIA_FEATURES: NiaFeatures = NiaFeatures::EXTENDED;
+}
+
+impl IssuerWrapper for PartiallyBlindedSchema {
+    const FEATURES: rgb20::Features = Self::NIA_FEATURES.to_rgb20();
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { partially_blinded_schema().expect("invalid partially blinded schema") }
+    fn issue_impl() -> IfaceImpl {
+        partially_blinded_iface_impl().expect("invalid partially blinded schema")
+    }
+
+    fn types() -> TypeSystem { NonInflatableAsset::types() }
+
+    fn scripts() -> Scripts {
+        let nia = nia_lib().expect("invalid NIA validation library");
+        let lib = reveal_lib();
+        confined_bmap! { nia.id() => nia, lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::IfaceClass;
+    use rgbstd::stl::Ticker;
+
+    use super::*;
+    use crate::test_helpers::{
+        add_metadata, fungible_genesis, graph_seal, issuer, script_errno, seal, spec,
+        spend_genesis, validate_transition,
+    };
+    use crate::AssetTicker;
+
+    /// Issues 1000 blinded units of the asset.
+    fn contract() -> Contract {
+        fungible_genesis(issuer::<PartiallyBlindedSchema>(), spec("PBA", "Partially blinded"), 1000)
+            .add_global_state("ticker", AssetTicker::from(Ticker::from("PBA")))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    /// Returns the revealed state of the blinded allocation assigned by the
+    /// genesis.
+    fn genesis_value(contract: &Contract) -> RevealedValue {
+        let assigns = contract.genesis.assignments[&OS_BLINDED_ASSET].as_fungible();
+        *assigns[0].as_revealed_state().unwrap()
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(PartiallyBlindedSchema::FEATURES);
+        let schema = PartiallyBlindedSchema::schema();
+        if let Err(err) = PartiallyBlindedSchema::issue_impl().check(&iface, &schema) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid partially blinded RGB20 interface implementation");
+        }
+        let reveal = &schema.transitions[&TS_REVEAL];
+        assert_eq!(reveal.inputs.get(&OS_BLINDED_ASSET), Some(&Occurrences::Once));
+        assert_eq!(reveal.assignments.get(&OS_REVEALED_ASSET), Some(&Occurrences::Once));
+        let partial = &schema.transitions[&TS_PARTIAL_REVEAL];
+        assert!(partial.metadata.contains(&MS_REVEAL_OPENING));
+        assert_eq!(partial.inputs.get(&OS_BLINDED_ASSET), Some(&Occurrences::OnceOrMore));
+        assert_eq!(partial.assignments.get(&OS_BLINDED_ASSET), Some(&Occurrences::NoneOrMore));
+        assert_eq!(partial.validator, Some(LibSite::with(FN_REVEAL_OFFSET, reveal_lib().id())));
+    }
+
+    #[test]
+    fn reveal_commitment() {
+        let tag = AssetTag::new_random("blinded", OS_BLINDED_ASSET);
+        let value = RevealedValue::new_random_blinding(1000u64, tag);
+        let revealed = RevealedAsset::with(&value);
+        let opening = revealed.opening();
+        assert_eq!(opening, RevealOpening::with(&value));
+
+        // Reveal script hashes the whole opening and reads the revealed state at fixed
+        // offsets
+        let mut preimage = opening.blinding_factor.to_vec();
+        preimage.extend(1000u64.to_le_bytes());
+        let commitment: [u8; 32] = Sha256::digest(&preimage).into();
+        assert_eq!(opening.to_strict_serialized::<40>().unwrap().as_slice(), preimage);
+        assert_eq!(revealed.original_commitment, commitment);
+        let data = revealed.to_strict_serialized::<72>().unwrap();
+        assert_eq!(&data[..40], preimage);
+        assert_eq!(&data[40..], commitment);
+
+        let other = RevealOpening {
+            amount: Amount::from(999u64),
+            ..opening
+        };
+        assert_ne!(other.commitment(), revealed.original_commitment);
+    }
+
+    #[test]
+    fn selective_disclosure() {
+        let tag = AssetTag::new_random("blinded", OS_BLINDED_ASSET);
+        let value = RevealedValue::new_random_blinding(1000u64, tag);
+        let commitment = value.conceal().commitment;
+        let revealed = RevealedAsset::with(&value);
+        assert_eq!(revealed.amount, Amount::from(1000u64));
+        assert!(revealed.opens(commitment, tag));
+
+        let other_tag = AssetTag::new_random("other", OS_BLINDED_ASSET);
+        assert!(!revealed.opens(commitment, other_tag));
+        let wrong_amount = RevealedAsset {
+            amount: Amount::from(999u64),
+            ..revealed
+        };
+        assert!(!wrong_amount.opens(commitment, tag));
+        let wrong_blinding = RevealedAsset {
+            blinding_factor: *BlindingFactor::random(),
+            ..revealed
+        };
+        assert!(!wrong_blinding.opens(commitment, tag));
+        let invalid_blinding = RevealedAsset {
+            blinding_factor: [0xFF; 32],
+            ..revealed
+        };
+        assert!(!invalid_blinding.opens(commitment, tag));
+    }
+
+    #[test]
+    fn reveal() {
+        let contract = contract();
+        let revealed = RevealedAsset::with(&genesis_value(&contract));
+        let reveal = |revealed: RevealedAsset, opening: RevealOpening| {
+            let mut transition = spend_genesis::<PartiallyBlindedSchema>(&contract, "reveal")
+                .add_data("revealedAsset", graph_seal(0), revealed)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            add_metadata(&mut transition, MS_REVEAL_OPENING, opening);
+            validate_transition(&contract, &PartiallyBlindedSchema::schema(), &transition)
+        };
+
+        let status = reveal(revealed, revealed.opening());
+        assert!(status.failures.is_empty(), "{status}");
+        // Opening doesn't hash to the original commitment
+        let forged = RevealOpening {
+            amount: Amount::from(999u64),
+            ..revealed.opening()
+        };
+        assert_eq!(script_errno(reveal(revealed, forged)), Some(ERRNO_INVALID_REVEAL));
+        // Revealed amount doesn't match the opening
+        let misreported = RevealedAsset {
+            amount: Amount::from(999u64),
+            ..revealed
+        };
+        let status = reveal(misreported, revealed.opening());
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_REVEAL));
+    }
+
+    #[test]
+    fn partial_reveal() {
+        let contract = contract();
+        let opening = RevealOpening {
+            blinding_factor: *BlindingFactor::random(),
+            amount: Amount::from(400u64),
+        };
+        let reveal = |revealed: RevealedAsset| {
+            let mut transition =
+                spend_genesis::<PartiallyBlindedSchema>(&contract, "partialReveal")
+                    .add_data("revealedAsset", graph_seal(0), revealed)
+                    .unwrap()
+                    .add_fungible_state("assetOwner", graph_seal(1), 600u64)
+                    .unwrap()
+                    .complete_transition()
+                    .unwrap();
+            add_metadata(&mut transition, MS_REVEAL_OPENING, opening);
+            validate_transition(&contract, &PartiallyBlindedSchema::schema(), &transition)
+        };
+
+        let status = reveal(opening.reveal());
+        assert!(status.failures.is_empty(), "{status}");
+        let other = RevealOpening {
+            amount: Amount::from(500u64),
+            ..opening
+        };
+        assert_eq!(script_errno(reveal(other.reveal())), Some(ERRNO_INVALID_REVEAL));
+    }
+}
@@ -0,0 +1,544 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prediction market schema implementing RGB20 fungible assets interface for
+//! binary outcome tokens.
+//!
+//! Each market asks a single yes/no question, committed to by its hash
+//! ([`GS_QUESTION_HASH`]). The market maker holding the mint right
+//! ([`OS_MINT_RIGHT`]) issues pairs of equal amounts of YES ([`OS_YES_TOKEN`])
+//! and NO ([`OS_NO_TOKEN`]) tokens with [`TS_MINT_PAIR`] against the collateral
+//! it receives. After the resolution height ([`GS_RESOLUTION_HEIGHT`]) the
+//! oracle spends its right ([`OS_ORACLE_RIGHT`]) with [`TS_RESOLVE`],
+//! publishing the outcome, and holders of the winning tokens burn them with
+//! [`TS_REDEEM_WINNING`] for the collateral.
+//!
+//! The resolution is authorized by the oracle right, and wallets must check the
+//! signature of the oracle key ([`GS_ORACLE_KEY`]) over the outcome (see
+//! [consensus limitations](crate#consensus-limitations)). Payments are out of
+//! the contract scope: the collateral is kept and paid out by the market maker.
+//! Each redemption repeats the outcome in its global state, and the market
+//! maker pays out only redemptions matching the resolved outcome. The YES token
+//! is exposed through the standard RGB20 names.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_JMP, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_PCAS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_ORACLE_KEY, GS_TERMS, TS_TRANSFER,
+};
+
+/// Height after which the market is resolved ([`crate::BlockHeight`]).
+pub const GS_RESOLUTION_HEIGHT: GlobalStateType = GlobalStateType::with(3601);
+/// SHA256 hash of the market question text ([`QuestionHash`]).
+pub const GS_QUESTION_HASH: GlobalStateType = GlobalStateType::with(3602);
+/// Outcome of the market ([`MarketOutcome`]), published by the oracle and
+/// repeated by each redemption.
+pub const GS_OUTCOME: GlobalStateType = GlobalStateType::with(3603);
+/// Number of YES/NO token pairs issued by a mint operation.
+pub const GS_MINTED_PAIRS: GlobalStateType = GlobalStateType::with(3604);
+/// Amount of the winning tokens burned by a redemption.
+pub const GS_REDEEMED_AMOUNT: GlobalStateType = GlobalStateType::with(3605);
+
+/// Fungible allocation of the tokens winning if the outcome is
+/// [`MarketOutcome::Yes`] (RGB20 `assetOwner`).
+pub const OS_YES_TOKEN: AssignmentType = AssignmentType::with(5500);
+/// Fungible allocation of the tokens winning if the outcome is
+/// [`MarketOutcome::No`].
+pub const OS_NO_TOKEN: AssignmentType = AssignmentType::with(5501);
+/// Right of the market maker to mint token pairs.
+pub const OS_MINT_RIGHT: AssignmentType = AssignmentType::with(5502);
+/// Right of the oracle to resolve the market.
+pub const OS_ORACLE_RIGHT: AssignmentType = AssignmentType::with(5503);
+
+/// Transfer of the YES tokens (RGB20 `transfer`).
+pub const TS_TRANSFER_YES: TransitionType = TS_TRANSFER;
+pub const TS_TRANSFER_NO: TransitionType = TransitionType::with(11600);
+pub const TS_MINT_PAIR: TransitionType = TransitionType::with(11601);
+pub const TS_RESOLVE: TransitionType = TransitionType::with(11602);
+pub const TS_REDEEM_WINNING: TransitionType = TransitionType::with(11603);
+
+/// Error raised when YES and NO token amounts don't match the declared number
+/// of pairs.
+pub const ERRNO_INVALID_PAIR: u8 = 47;
+/// Error raised when the burned winning tokens don't match the redeemed amount.
+pub const ERRNO_INVALID_REDEMPTION: u8 = 48;
+
+pub(crate) const FN_YES_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_NO_TRANSFER_OFFSET: u16 = FN_YES_TRANSFER_OFFSET + 4 + 3 + 2;
+pub(crate) const FN_MARKET_GENESIS_OFFSET: u16 = FN_NO_TRANSFER_OFFSET + 4 + 3 + 2;
+pub const FN_MINT_PAIR_OFFSET: u16 = FN_MARKET_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3;
+pub(crate) const FN_PAIR_CHECK_OFFSET: u16 = FN_MINT_PAIR_OFFSET + 4 + 4 + 4 + 4;
+pub const FN_REDEEM_OFFSET: u16 = FN_PAIR_CHECK_OFFSET + 3 + 3 + 1 + 3 + 1 + 1;
+pub(crate) const FN_REDEEM_NO_OFFSET: u16 =
+    FN_REDEEM_OFFSET + 4 + 4 + 4 + 4 + 3 + 4 + 3 + 2 + 3 + 1 + 3 + 1 + 1;
+
+/// Outcome of a binary prediction market.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, tags = repr, into_u8, try_from_u8)]
+#[repr(u8)]
+pub enum MarketOutcome {
+    /// The event hasn't happened; NO tokens win.
+    #[strict_type(dumb)]
+    No = 0,
+    /// The event has happened; YES tokens win.
+    Yes = 1,
+}
+impl StrictSerialize for MarketOutcome {}
+impl StrictDeserialize for MarketOutcome {}
+
+/// SHA256 hash of the question asked by a prediction market.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct QuestionHash([u8; 32]);
+impl StrictSerialize for QuestionHash {}
+impl StrictDeserialize for QuestionHash {}
+
+impl QuestionHash {
+    /// Computes hash of the `question` text.
+    pub fn with_question(question: &str) -> Self {
+        use sha2::{Digest, Sha256};
+        Self(Sha256::digest(question).into())
+    }
+}
+
+/// Assembles AluVM library with validation scripts used by the prediction
+/// market schema.
+pub fn prediction_market_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE YES token transfer validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_YES_TOKEN;
+        test;
+        ret;
+
+        // SUBROUTINE NO token transfer validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_NO_TOKEN;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Reading the number of pairs issued in genesis and checking them in the pair check.
+        put     a8[0],ERRNO_INVALID_PAIR;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        jmp     FN_PAIR_CHECK_OFFSET;
+
+        // SUBROUTINE Mint pair validation
+        put     a8[0],ERRNO_INVALID_PAIR;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_MINTED_PAIRS,a8[1],s16[0];
+
+        // SUBROUTINE Pair check
+        // Checking that both YES and NO allocations sum up to the number of pairs.
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_YES_TOKEN;
+        test;
+        pcas    OS_NO_TOKEN;
+        test;
+        ret;
+
+        // SUBROUTINE Redemption validation
+        put     a8[0],ERRNO_INVALID_REDEMPTION;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read redeemed amount into a64[0]
+        ldg     GS_REDEEMED_AMOUNT,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        // Read the outcome into a8[2]
+        ldg     GS_OUTCOME,a8[1],s16[1];
+        extr    s16[1],a8[2],a16[0];
+        // Checking that the burned tokens of the winning side match the redeemed amount
+        ifz     a8[2];
+        jif     FN_REDEEM_NO_OFFSET;
+        // Resetting the failed outcome comparison
+        inv     st0;
+        pcps    OS_YES_TOKEN;
+        test;
+        ret;
+        // NO tokens redemption
+        pcps    OS_NO_TOKEN;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong prediction market script")
+}
+
+fn prediction_market_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = prediction_market_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_YES_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_YES_TRANSFER_OFFSET + 4), OS_YES_TOKEN.to_inner());
+    assert_eq!(code[FN_NO_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_NO_TRANSFER_OFFSET + 4), OS_NO_TOKEN.to_inner());
+    assert_eq!(code[FN_MARKET_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_MARKET_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_MARKET_GENESIS_OFFSET as usize + 16], INSTR_JMP);
+    assert_eq!(code[FN_MINT_PAIR_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_PAIR_CHECK_OFFSET as usize], INSTR_EXTR);
+    assert_eq!(code[FN_PAIR_CHECK_OFFSET as usize + 3], INSTR_PCAS);
+    assert_eq!(code[FN_REDEEM_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_REDEEM_OFFSET as usize + 19], INSTR_LDG);
+    assert_eq!(state_arg(FN_REDEEM_OFFSET + 19), GS_OUTCOME.to_inner());
+    assert_eq!(code[FN_REDEEM_OFFSET as usize + 32], INSTR_PCPS);
+    assert_eq!(state_arg(FN_REDEEM_OFFSET + 32), OS_YES_TOKEN.to_inner());
+    assert_eq!(code[FN_REDEEM_NO_OFFSET as usize], INSTR_PCPS);
+    assert_eq!(state_arg(FN_REDEEM_NO_OFFSET), OS_NO_TOKEN.to_inner());
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("PredictionMarket"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_ORACLE_KEY => GlobalStateSchema::once(types.get("RGBSchemata.IssuerKey")),
+            GS_RESOLUTION_HEIGHT => GlobalStateSchema::once(types.get("RGBSchemata.BlockHeight")),
+            GS_QUESTION_HASH => GlobalStateSchema::once(types.get("RGBSchemata.QuestionHash")),
+            GS_OUTCOME => GlobalStateSchema::many(types.get("RGBSchemata.MarketOutcome")),
+            GS_MINTED_PAIRS => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+            GS_REDEEMED_AMOUNT => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_YES_TOKEN => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_NO_TOKEN => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_MINT_RIGHT => OwnedStateSchema::Declarative,
+            OS_ORACLE_RIGHT => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_ORACLE_KEY => Occurrences::Once,
+                GS_RESOLUTION_HEIGHT => Occurrences::Once,
+                GS_QUESTION_HASH => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_YES_TOKEN => Occurrences::OnceOrMore,
+                OS_NO_TOKEN => Occurrences::OnceOrMore,
+                OS_MINT_RIGHT => Occurrences::Once,
+                OS_ORACLE_RIGHT => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_MARKET_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER_YES => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_YES_TOKEN => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_YES_TOKEN => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_YES_TRANSFER_OFFSET, alu_id))
+            },
+            TS_TRANSFER_NO => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_NO_TOKEN => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_NO_TOKEN => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_NO_TRANSFER_OFFSET, alu_id))
+            },
+            TS_MINT_PAIR => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_MINTED_PAIRS => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_MINT_RIGHT => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_YES_TOKEN => Occurrences::OnceOrMore,
+                    OS_NO_TOKEN => Occurrences::OnceOrMore,
+                    OS_MINT_RIGHT => Occurrences::NoneOrOnce,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_MINT_PAIR_OFFSET, alu_id))
+            },
+            TS_RESOLVE => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_OUTCOME => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_ORACLE_RIGHT => Occurrences::Once
+                },
+                assignments: none!(),
+                valencies: none!(),
+                validator: None
+            },
+            TS_REDEEM_WINNING => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_OUTCOME => Occurrences::Once,
+                    GS_REDEEMED_AMOUNT => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_YES_TOKEN => Occurrences::NoneOrMore,
+                    OS_NO_TOKEN => Occurrences::NoneOrMore,
+                },
+                assignments: none!(),
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_REDEEM_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn prediction_market_rgb20() -> IfaceImpl {
+    let schema = prediction_market_schema();
+    let iface = Rgb20::iface(PredictionMarketSchema::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_ORACLE_KEY, fname!("oracleKey")),
+            NamedField::with(GS_RESOLUTION_HEIGHT, fname!("resolutionHeight")),
+            NamedField::with(GS_QUESTION_HASH, fname!("questionHash")),
+            NamedField::with(GS_OUTCOME, fname!("outcome")),
+            NamedField::with(GS_MINTED_PAIRS, fname!("mintedPairs")),
+            NamedField::with(GS_REDEEMED_AMOUNT, fname!("redeemedAmount")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_YES_TOKEN, fname!("assetOwner")),
+            NamedField::with(OS_NO_TOKEN, fname!("noTokenOwner")),
+            NamedField::with(OS_MINT_RIGHT, fname!("mintRight")),
+            NamedField::with(OS_ORACLE_RIGHT, fname!("oracleRight")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER_YES, fname!("transfer")),
+            NamedField::with(TS_TRANSFER_NO, fname!("transferNo")),
+            NamedField::with(TS_MINT_PAIR, fname!("mintPair")),
+            NamedField::with(TS_RESOLVE, fname!("resolve")),
+            NamedField::with(TS_REDEEM_WINNING, fname!("redeemWinning")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),],
+    }
+}
+
+/// Binary prediction market issuing YES and NO outcome tokens.
+pub struct PredictionMarketSchema;
+
+impl IssuerWrapper for PredictionMarketSchema {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { prediction_market_schema() }
+    fn issue_impl() -> IfaceImpl { prediction_market_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = prediction_market_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::Amount;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+    use crate::{BlockHeight, IssuerKey};
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(PredictionMarketSchema::FEATURES);
+        if let Err(err) = prediction_market_rgb20().check(&iface, &prediction_market_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid prediction market RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn outcome_operations() {
+        let schema = prediction_market_schema();
+        let resolve = &schema.transitions[&TS_RESOLVE];
+        assert!(resolve.inputs.keys().eq([&OS_ORACLE_RIGHT]));
+        assert!(resolve.globals.keys().eq([&GS_OUTCOME]));
+        let redeem = &schema.transitions[&TS_REDEEM_WINNING];
+        assert!(redeem.assignments.is_empty());
+        assert_eq!(
+            QuestionHash::with_question("Will it rain tomorrow?"),
+            QuestionHash::with_question("Will it rain tomorrow?")
+        );
+        assert_ne!(
+            QuestionHash::with_question("Will it rain tomorrow?"),
+            QuestionHash::with_question("Will it snow tomorrow?")
+        );
+    }
+
+    /// Issues `yes` and `no` tokens of a market with 1000 issued pairs.
+    fn contract(yes: u64, no: u64) -> Option<Contract> {
+        let builder = issuer::<PredictionMarketSchema>();
+        let contract = fungible_genesis(builder, spec("RAIN", "Rain"), 1000)
+            .add_global_state("oracleKey", IssuerKey::from([2u8; 33]))
+            .unwrap()
+            .add_global_state("resolutionHeight", BlockHeight::from(850_000))
+            .unwrap()
+            .add_global_state("questionHash", QuestionHash::with_question("Will it rain?"))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), yes)
+            .unwrap()
+            .add_fungible_state("noTokenOwner", seal(1), no)
+            .unwrap()
+            .add_rights("mintRight", seal(2))
+            .unwrap()
+            .add_rights("oracleRight", seal(3))
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn market_genesis() {
+        assert!(contract(1000, 1000).is_some());
+        assert!(contract(1000, 999).is_none(), "NO tokens must match YES tokens");
+        assert!(contract(999, 1000).is_none(), "YES tokens must match the issued pairs");
+    }
+
+    #[test]
+    fn transfer() {
+        let contract = contract(1000, 1000).unwrap();
+        let transfer = |name: &'static str, owner: &'static str, amount: u64| {
+            let transition = spend_genesis::<PredictionMarketSchema>(&contract, name)
+                .add_fungible_state(owner, graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &PredictionMarketSchema::schema(), &transition)
+        };
+
+        let status = transfer("transfer", "assetOwner", 1000);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = transfer("transferNo", "noTokenOwner", 1000);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = transfer("transferNo", "noTokenOwner", 2000);
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn mint_pair() {
+        let contract = contract(1000, 1000).unwrap();
+        let mint = |pairs: u64, yes: u64, no: u64| {
+            let transition = spend_genesis::<PredictionMarketSchema>(&contract, "mintPair")
+                .add_global_state("mintedPairs", Amount::from(pairs))
+                .unwrap()
+                .add_fungible_state("assetOwner", graph_seal(0), yes)
+                .unwrap()
+                .add_fungible_state("noTokenOwner", graph_seal(1), no)
+                .unwrap()
+                .add_rights("mintRight", graph_seal(2))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &PredictionMarketSchema::schema(), &transition)
+        };
+
+        let status = mint(500, 500, 500);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(mint(500, 500, 400)), Some(ERRNO_INVALID_PAIR));
+        assert_eq!(script_errno(mint(500, 600, 600)), Some(ERRNO_INVALID_PAIR));
+    }
+
+    #[test]
+    fn redeem_winning() {
+        let contract = contract(1000, 1000).unwrap();
+        let redeem = |outcome: MarketOutcome, redeemed: u64| {
+            let transition = spend_genesis::<PredictionMarketSchema>(&contract, "redeemWinning")
+                .add_global_state("outcome", outcome)
+                .unwrap()
+                .add_global_state("redeemedAmount", Amount::from(redeemed))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &PredictionMarketSchema::schema(), &transition)
+        };
+
+        let status = redeem(MarketOutcome::Yes, 1000);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = redeem(MarketOutcome::No, 1000);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = redeem(MarketOutcome::Yes, 2000);
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_REDEMPTION));
+    }
+}
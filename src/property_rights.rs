@@ -0,0 +1,560 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Property rights token schema implementing RGB20 fungible assets interface
+//! for fractional ownership of real estate.
+//!
+//! The fungible part of the contract are the property shares
+//! ([`OS_PROPERTY_SHARE`]), while the genesis describes the property: its
+//! address ([`GS_PROPERTY_ADDRESS`]), cadastral title identifier
+//! ([`GS_PROPERTY_TITLE_ID`]), total value in cents ([`GS_TOTAL_VALUE`]) and
+//! the jurisdiction ([`GS_JURISDICTION`]). The issuer, holding the KYC
+//! authority right ([`OS_KYC_AUTHORITY`]), attests holders which passed KYC
+//! with [`TS_ATTEST_KYC`] transition, assigning them KYC attestations
+//! ([`KycAttestation`]).
+//!
+//! The transfer script can't read the KYC requirement ([`GS_KYC_REQUIRED`])
+//! from the genesis (see [consensus limitations](crate#consensus-limitations)).
+//! Instead, the schema comes in two variants produced by
+//! [`property_rights_schema`]: the KYC-gated one, which [`PropertyRightsToken`]
+//! issues, requires the sender to provide its KYC attestation as a co-input to
+//! each [`TS_TRANSFER`] and to re-assign it unmodified; the open one has no
+//! such restriction. The genesis script of each variant checks that
+//! [`GS_KYC_REQUIRED`] matches the variant, so the flag can't be set for a
+//! contract which transfers are not restricted and vice versa. Binding the
+//! attestation to the holder identity is left to the KYC provider and wallets.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_JMP, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDP, INSTR_LDS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PRECISION,
+    GS_TERMS, TS_TRANSFER,
+};
+
+/// Postal address of the property.
+pub const GS_PROPERTY_ADDRESS: GlobalStateType = GlobalStateType::with(5100);
+/// Cadastral identifier of the property title.
+pub const GS_PROPERTY_TITLE_ID: GlobalStateType = GlobalStateType::with(5101);
+/// Total value of the property in cents ([`PropertyValue`]).
+pub const GS_TOTAL_VALUE: GlobalStateType = GlobalStateType::with(5102);
+/// Jurisdiction the property and its title are registered in.
+pub const GS_JURISDICTION: GlobalStateType = GlobalStateType::with(5103);
+/// Whether transfers of the shares require KYC attestation of the sender
+/// ([`KycRequired`]).
+pub const GS_KYC_REQUIRED: GlobalStateType = GlobalStateType::with(5104);
+
+/// Fractional share of the property.
+pub const OS_PROPERTY_SHARE: AssignmentType = AssignmentType::with(6900);
+/// KYC attestation of a share holder ([`KycAttestation`]).
+pub const OS_KYC_ATTESTATION: AssignmentType = AssignmentType::with(6901);
+/// Right of the issuer to attest share holders.
+pub const OS_KYC_AUTHORITY: AssignmentType = AssignmentType::with(6902);
+
+pub const TS_ATTEST_KYC: TransitionType = TransitionType::with(13000);
+
+/// KYC requirement doesn't match the schema variant, or the transfer modifies
+/// the KYC attestation. Not a part of RGB20 interface errors.
+pub const ERRNO_KYC_MISMATCH: u8 = 63;
+
+pub(crate) const FN_PROPERTY_TRANSFER_OFFSET: u16 = 0;
+pub const FN_KYC_TRANSFER_OFFSET: u16 = 4 + 3 + 1 + 1;
+pub(crate) const FN_KYC_GENESIS_OFFSET: u16 = FN_KYC_TRANSFER_OFFSET +
+    (4 + 3 + 1) +
+    (4 + 4 + 4 + 4 + 4) +
+    (3 + 3 + 3 + 1) +
+    (3 + 3 + 3 + 1 + 1);
+pub(crate) const FN_OPEN_GENESIS_OFFSET: u16 = FN_KYC_GENESIS_OFFSET + 4 + 3;
+const FN_PROPERTY_GENESIS_OFFSET: u16 = FN_OPEN_GENESIS_OFFSET + 4;
+
+/// Total value of the property in cents, kept in [`GS_TOTAL_VALUE`] global
+/// state.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct PropertyValue(u64);
+impl StrictSerialize for PropertyValue {}
+impl StrictDeserialize for PropertyValue {}
+
+/// Flag kept in [`GS_KYC_REQUIRED`] global state.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct KycRequired(bool);
+impl StrictSerialize for KycRequired {}
+impl StrictDeserialize for KycRequired {}
+
+/// KYC attestation of a share holder, kept as [`OS_KYC_ATTESTATION`] owned
+/// state.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct KycAttestation {
+    /// Hash of the KYC provider identity.
+    pub provider_id: [u8; 32],
+    /// Hash of the off-chain KYC record of the holder.
+    pub record_hash: [u8; 32],
+}
+impl StrictSerialize for KycAttestation {}
+impl StrictDeserialize for KycAttestation {}
+
+/// Assembles AluVM library with validation scripts used by the property rights
+/// schema.
+pub fn property_rights_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Open transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_PROPERTY_SHARE;
+        test;
+        ret;
+
+        // SUBROUTINE KYC-gated transfer validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_PROPERTY_SHARE;
+        test;
+        // Checking that the KYC attestation co-input is re-assigned unmodified.
+        put     a8[0],ERRNO_KYC_MISMATCH;
+        put     a16[0],0;
+        put     a16[1],32;
+        ldp     OS_KYC_ATTESTATION,a16[0],s16[0];
+        lds     OS_KYC_ATTESTATION,a16[0],s16[1];
+        // Compare provider ids
+        extr    s16[0],r256[0],a16[0];
+        extr    s16[1],r256[1],a16[0];
+        eq.n    r256[0],r256[1];
+        test;
+        // Compare KYC record hashes
+        extr    s16[0],r256[0],a16[1];
+        extr    s16[1],r256[1],a16[1];
+        eq.n    r256[0],r256[1];
+        test;
+        ret;
+
+        // SUBROUTINE KYC-gated genesis validation
+        // Put the expected KYC requirement flag into a8[2]
+        put     a8[2],1;
+        jmp     FN_PROPERTY_GENESIS_OFFSET;
+
+        // SUBROUTINE Open genesis validation
+        put     a8[2],0;
+
+        // SUBROUTINE Shared genesis code
+        // Checking that the KYC requirement matches the schema variant.
+        put     a8[0],ERRNO_KYC_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_KYC_REQUIRED,a8[1],s16[0];
+        extr    s16[0],a8[3],a16[0];
+        eq.n    a8[2],a8[3];
+        test;
+        // Checking that the issued supply matches the sum of allocations.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_PROPERTY_SHARE;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong property rights token script")
+}
+
+/// Constructs property rights schema variant with KYC-gated transfers if
+/// `kyc_required` is set, or with unrestricted transfers otherwise.
+pub fn property_rights_schema(kyc_required: bool) -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = property_rights_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_PROPERTY_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_KYC_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_KYC_TRANSFER_OFFSET as usize + 20], INSTR_LDP);
+    assert_eq!(state_arg(FN_KYC_TRANSFER_OFFSET + 20), OS_KYC_ATTESTATION.to_inner());
+    assert_eq!(code[FN_KYC_TRANSFER_OFFSET as usize + 24], INSTR_LDS);
+    assert_eq!(state_arg(FN_KYC_TRANSFER_OFFSET + 24), OS_KYC_ATTESTATION.to_inner());
+    assert_eq!(code[FN_KYC_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_KYC_GENESIS_OFFSET as usize + 4], INSTR_JMP);
+    assert_eq!(code[FN_OPEN_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_PROPERTY_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_PROPERTY_GENESIS_OFFSET + 12), GS_KYC_REQUIRED.to_inner());
+    assert_eq!(code[FN_PROPERTY_GENESIS_OFFSET as usize + 16], INSTR_EXTR);
+    assert_eq!(code[FN_PROPERTY_GENESIS_OFFSET as usize + 27], INSTR_LDG);
+    assert_eq!(state_arg(FN_PROPERTY_GENESIS_OFFSET + 27), GS_ISSUED_SUPPLY.to_inner());
+
+    let (genesis_offset, transfer_offset, kyc_authority) = if kyc_required {
+        (FN_KYC_GENESIS_OFFSET, FN_KYC_TRANSFER_OFFSET, Occurrences::Once)
+    } else {
+        (FN_OPEN_GENESIS_OFFSET, FN_PROPERTY_TRANSFER_OFFSET, Occurrences::NoneOrOnce)
+    };
+    let mut transfer_inputs = tiny_bmap! {
+        OS_PROPERTY_SHARE => Occurrences::OnceOrMore
+    };
+    let mut transfer_assignments = transfer_inputs.clone();
+    if kyc_required {
+        transfer_inputs
+            .insert(OS_KYC_ATTESTATION, Occurrences::Once)
+            .expect("under the limit");
+        transfer_assignments
+            .insert(OS_KYC_ATTESTATION, Occurrences::Once)
+            .expect("under the limit");
+    }
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("PropertyRightsToken"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_PROPERTY_ADDRESS => GlobalStateSchema::once(types.get("RGBContract.Details")),
+            GS_PROPERTY_TITLE_ID => GlobalStateSchema::once(types.get("RGBContract.Details")),
+            GS_TOTAL_VALUE => GlobalStateSchema::once(types.get("RGBSchemata.PropertyValue")),
+            GS_JURISDICTION => GlobalStateSchema::once(types.get("RGBContract.Details")),
+            GS_KYC_REQUIRED => GlobalStateSchema::once(types.get("RGBSchemata.KycRequired")),
+        },
+        owned_types: tiny_bmap! {
+            OS_PROPERTY_SHARE => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_KYC_ATTESTATION =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.KycAttestation")),
+            OS_KYC_AUTHORITY => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_PROPERTY_ADDRESS => Occurrences::Once,
+                GS_PROPERTY_TITLE_ID => Occurrences::Once,
+                GS_TOTAL_VALUE => Occurrences::Once,
+                GS_JURISDICTION => Occurrences::Once,
+                GS_KYC_REQUIRED => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_PROPERTY_SHARE => Occurrences::OnceOrMore,
+                OS_KYC_ATTESTATION => Occurrences::NoneOrMore,
+                OS_KYC_AUTHORITY => kyc_authority,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(genesis_offset, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: transfer_inputs,
+                assignments: transfer_assignments,
+                valencies: none!(),
+                validator: Some(LibSite::with(transfer_offset, alu_id))
+            },
+            TS_ATTEST_KYC => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_KYC_AUTHORITY => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_KYC_AUTHORITY => Occurrences::Once,
+                    OS_KYC_ATTESTATION => Occurrences::OnceOrMore,
+                },
+                valencies: none!(),
+                validator: None
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+/// Constructs RGB20 interface implementation for the property rights schema
+/// variant.
+pub fn property_rights_rgb20(kyc_required: bool) -> IfaceImpl {
+    let schema = property_rights_schema(kyc_required);
+    let iface = Rgb20::iface(PropertyRightsToken::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_PROPERTY_ADDRESS, fname!("propertyAddress")),
+            NamedField::with(GS_PROPERTY_TITLE_ID, fname!("propertyTitleId")),
+            NamedField::with(GS_TOTAL_VALUE, fname!("totalValue")),
+            NamedField::with(GS_JURISDICTION, fname!("jurisdiction")),
+            NamedField::with(GS_KYC_REQUIRED, fname!("kycRequired")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_PROPERTY_SHARE, fname!("assetOwner")),
+            NamedField::with(OS_KYC_ATTESTATION, fname!("kycAttestation")),
+            NamedField::with(OS_KYC_AUTHORITY, fname!("kycAuthority")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_ATTEST_KYC, fname!("attestKyc")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Fractional shares of a real estate property with KYC-gated transfers.
+///
+/// Contracts with unrestricted transfers are issued with the schema returned by
+/// [`property_rights_schema`] for `false`.
+pub struct PropertyRightsToken;
+
+impl IssuerWrapper for PropertyRightsToken {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { property_rights_schema(true) }
+    fn issue_impl() -> IfaceImpl { property_rights_rgb20(true) }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = property_rights_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::{ContractBuilder, TransitionBuilder};
+    use rgbstd::stl::Details;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, script_errno, seal, spec, spend_genesis_with,
+        validate_transition,
+    };
+
+    fn attestation(record: u8) -> KycAttestation {
+        KycAttestation {
+            provider_id: [1; 32],
+            record_hash: [record; 32],
+        }
+    }
+
+    /// Issues 1000 property shares of the `kyc_schema` variant declaring the
+    /// `kyc_required` flag, together with a KYC attestation and the KYC
+    /// authority.
+    fn contract(kyc_schema: bool, kyc_required: bool) -> Option<Contract> {
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            Rgb20::iface(PropertyRightsToken::FEATURES),
+            property_rights_schema(kyc_schema),
+            property_rights_rgb20(kyc_schema),
+            PropertyRightsToken::types(),
+            PropertyRightsToken::scripts(),
+        );
+        let contract = fungible_genesis(builder, spec("PRT", "Property shares"), 1000)
+            .add_global_state("propertyAddress", Details::from("1 Main St, Springfield"))
+            .unwrap()
+            .add_global_state("propertyTitleId", Details::from("12-345-678"))
+            .unwrap()
+            .add_global_state("totalValue", PropertyValue::from(5_000_000_000u64))
+            .unwrap()
+            .add_global_state("jurisdiction", Details::from("US-IL"))
+            .unwrap()
+            .add_global_state("kycRequired", KycRequired::from(kyc_required))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_data("kycAttestation", seal(1), attestation(2))
+            .unwrap()
+            .add_rights("kycAuthority", seal(2))
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    /// Constructs the transition named `name` of the `kyc_schema` variant,
+    /// which spends all genesis assignments of the `contract` of the types
+    /// taken by the transition.
+    fn spend_genesis(
+        contract: &Contract,
+        kyc_schema: bool,
+        name: &'static str,
+    ) -> TransitionBuilder {
+        let schema = property_rights_schema(kyc_schema);
+        let builder = TransitionBuilder::named_transition(
+            contract.contract_id(),
+            Rgb20::iface(PropertyRightsToken::FEATURES),
+            schema.clone(),
+            property_rights_rgb20(kyc_schema),
+            name,
+            PropertyRightsToken::types(),
+        )
+        .unwrap();
+        spend_genesis_with(contract, &schema, builder)
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(PropertyRightsToken::FEATURES);
+        for kyc_required in [true, false] {
+            let schema = property_rights_schema(kyc_required);
+            if let Err(err) = property_rights_rgb20(kyc_required).check(&iface, &schema) {
+                for e in err {
+                    eprintln!("{e}");
+                }
+                panic!("invalid property rights token RGB20 interface implementation");
+            }
+            let transfer = &schema.transitions[&TS_TRANSFER];
+            let attestation = kyc_required.then_some(&Occurrences::Once);
+            assert_eq!(transfer.inputs.get(&OS_KYC_ATTESTATION), attestation);
+            assert_eq!(transfer.assignments.get(&OS_KYC_ATTESTATION), attestation);
+        }
+        let schema_id = |kyc_required| property_rights_schema(kyc_required).schema_id();
+        assert_ne!(schema_id(true), schema_id(false));
+    }
+
+    #[test]
+    fn state_layout() {
+        // Genesis script reads the flag from the first byte, transfer script compares
+        // the attestation by 32-byte halves
+        assert_eq!(
+            KycRequired::from(true)
+                .to_strict_serialized::<8>()
+                .unwrap()
+                .as_slice(),
+            &[1]
+        );
+        assert_eq!(
+            KycRequired::from(false)
+                .to_strict_serialized::<8>()
+                .unwrap()
+                .as_slice(),
+            &[0]
+        );
+        let attestation = KycAttestation {
+            provider_id: [1; 32],
+            record_hash: [2; 32],
+        };
+        assert_eq!(attestation.to_strict_serialized::<64>().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn kyc_requirement_genesis() {
+        assert!(contract(true, true).is_some());
+        assert!(contract(true, false).is_none());
+        assert!(contract(false, false).is_some());
+        assert!(contract(false, true).is_none());
+    }
+
+    #[test]
+    fn kyc_transfer() {
+        let contract = contract(true, true).unwrap();
+        let transfer = |amount: u64, attestation: Option<KycAttestation>| {
+            let mut builder = spend_genesis(&contract, true, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap();
+            if let Some(attestation) = attestation {
+                builder = builder
+                    .add_data("kycAttestation", graph_seal(1), attestation)
+                    .unwrap();
+            }
+            let transition = builder.complete_transition().unwrap();
+            validate_transition(&contract, &property_rights_schema(true), &transition)
+        };
+
+        let status = transfer(1000, Some(attestation(2)));
+        assert!(status.failures.is_empty(), "{status}");
+        let status = transfer(999, Some(attestation(2)));
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+        let status = transfer(1000, Some(attestation(3)));
+        assert_eq!(script_errno(status), Some(ERRNO_KYC_MISMATCH));
+        assert!(!transfer(1000, None).failures.is_empty());
+    }
+
+    #[test]
+    fn open_transfer() {
+        let contract = contract(false, false).unwrap();
+        let transfer = |amount: u64| {
+            let transition = spend_genesis(&contract, false, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &property_rights_schema(false), &transition)
+        };
+
+        let status = transfer(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn attest_kyc() {
+        let contract = contract(true, true).unwrap();
+        let transition = spend_genesis(&contract, true, "attestKyc")
+            .add_rights("kycAuthority", graph_seal(0))
+            .unwrap()
+            .add_data("kycAttestation", graph_seal(1), attestation(4))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let status = validate_transition(&contract, &property_rights_schema(true), &transition);
+        assert!(status.failures.is_empty(), "{status}");
+    }
+}
@@ -0,0 +1,122 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Registry of schemata allowing to dispatch contract handling by their schema
+//! id.
+
+use std::collections::BTreeMap;
+
+use ifaces::IssuerWrapper;
+use rgbstd::SchemaId;
+
+use crate::{
+    BasketTokenIssuer, BondIssuer, CarbonCredit, CertificateSchema, CollectibleFungibleAsset,
+    ContractInflatableAsset, DividendToken, DualAsset, ErasedIssuerWrapper, GameItem,
+    GovernanceToken, IdentityIssuer, LicensingSchema, LoyaltyPoints, MultiSigSchema, MusicRights,
+    NominalAsset, NonInflatableAsset, PartiallyBlindedSchema, PredictionMarketSchema,
+    PrivacyPreservingNonInflatableAsset, PropertyRightsToken, ReputationToken,
+    SyntheticAssetSchema, TokenBridgeSchema, TrancheAsset, UniqueDigitalAsset, VestingAsset,
+};
+
+/// Set of schemata indexed by their [`SchemaId`].
+#[derive(Default)]
+pub struct SchemaRegistry {
+    schemata: BTreeMap<SchemaId, Box<dyn ErasedIssuerWrapper>>,
+}
+
+impl SchemaRegistry {
+    /// Constructs an empty registry.
+    pub fn new() -> Self { Self::default() }
+
+    /// Constructs a registry containing all schemata provided by this crate.
+    pub fn with_builtin_schemata() -> Self {
+        let mut registry = Self::new();
+        registry.register(NonInflatableAsset);
+        registry.register(UniqueDigitalAsset);
+        registry.register(CollectibleFungibleAsset);
+        registry.register(ContractInflatableAsset);
+        registry.register(VestingAsset);
+        registry.register(GovernanceToken);
+        registry.register(BondIssuer);
+        registry.register(LoyaltyPoints);
+        registry.register(CarbonCredit);
+        registry.register(MusicRights);
+        registry.register(GameItem);
+        registry.register(DualAsset);
+        registry.register(PredictionMarketSchema);
+        registry.register(SyntheticAssetSchema);
+        registry.register(BasketTokenIssuer);
+        registry.register(IdentityIssuer);
+        registry.register(CertificateSchema);
+        registry.register(MultiSigSchema);
+        registry.register(TokenBridgeSchema);
+        registry.register(ReputationToken);
+        registry.register(TrancheAsset);
+        registry.register(PrivacyPreservingNonInflatableAsset);
+        registry.register(PartiallyBlindedSchema);
+        registry.register(DividendToken);
+        registry.register(NominalAsset);
+        registry.register(PropertyRightsToken);
+        registry.register(LicensingSchema);
+        registry
+    }
+
+    /// Adds schema to the registry, replacing previously registered schema with
+    /// the same id.
+    pub fn register<W: IssuerWrapper + 'static>(&mut self, wrapper: W) {
+        let id = wrapper.erased_schema_id();
+        self.schemata.insert(id, Box::new(wrapper));
+    }
+
+    /// Returns schema registered under the given id.
+    pub fn lookup(&self, id: SchemaId) -> Option<&dyn ErasedIssuerWrapper> {
+        self.schemata.get(&id).map(Box::as_ref)
+    }
+
+    /// Iterates over ids of all registered schemata.
+    pub fn schema_ids(&self) -> impl Iterator<Item = SchemaId> + '_ {
+        self.schemata.keys().copied()
+    }
+
+    pub fn len(&self) -> usize { self.schemata.len() }
+
+    pub fn is_empty(&self) -> bool { self.schemata.is_empty() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::NIA_SCHEMA_ID;
+
+    #[test]
+    fn builtin_lookup() {
+        let registry = SchemaRegistry::with_builtin_schemata();
+        assert_eq!(registry.len(), 27);
+        let nia = registry
+            .lookup(*NIA_SCHEMA_ID)
+            .expect("NIA must be registered");
+        assert_eq!(nia.erased_schema().name, NonInflatableAsset::schema().name);
+        for id in registry.schema_ids() {
+            assert_eq!(registry.lookup(id).unwrap().erased_schema_id(), id);
+        }
+        assert!(SchemaRegistry::new().lookup(*NIA_SCHEMA_ID).is_none());
+    }
+}
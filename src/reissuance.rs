@@ -0,0 +1,244 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reissuance state extension adding secondary issuance to NIA-based schemata.
+//!
+//! Instead of deploying a separate CIA contract, the issuer of an asset created
+//! with a schema extended by [`ReissuanceExtension::apply`] may issue more
+//! assets with [`ES_REISSUE`] state extension, redeeming the issuer authority
+//! valency ([`VA_ISSUER_AUTHORITY`]) declared by the genesis. Reissued assets
+//! are allocated into the same state type as the original ones, so both
+//! remain fungible.
+//!
+//! An extension can't be added to an already issued contract, since its schema
+//! is committed to by the contract id: the extension must be applied to the
+//! schema before the genesis is created.
+//!
+//! The script only checks that the declared reissued amount
+//! ([`GS_ADDL_SUPPLY`]) is non-zero and matches the reissued allocations. The
+//! issuer public key ([`GS_EXTENSION_AUTHORITY`]) is committed to by the
+//! genesis, and wallets must check the signature of that key over the extension
+//! before accepting reissued assets (see [consensus
+//! limitations](crate#consensus-limitations)).
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use rgbstd::interface::{IfaceImpl, NamedField};
+use rgbstd::schema::{ExtensionSchema, ExtensionType, GlobalStateSchema, Occurrences, Schema};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_PCAS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType};
+
+use crate::nia::nia_type;
+use crate::{
+    nia_base_lib, nia_lib, NiaSchemaError, GS_ADDL_SUPPLY, GS_ISSUER_KEY, OS_ASSET,
+    VA_ISSUER_AUTHORITY,
+};
+
+/// Public key of the issuer authorized to reissue assets
+/// ([`crate::IssuerKey`]).
+pub const GS_EXTENSION_AUTHORITY: GlobalStateType = GS_ISSUER_KEY;
+/// Reissued asset allocations. Defined as the NIA asset state, so reissued
+/// assets are fungible with the ones allocated in genesis.
+pub const OS_REISSUED_ASSET: AssignmentType = OS_ASSET;
+
+pub const ES_REISSUE: ExtensionType = ExtensionType::with(11500);
+
+pub const ERRNO_INVALID_REISSUANCE: u8 = 46;
+
+pub const FN_REISSUE_OFFSET: u16 = 0;
+
+/// AluVM library validating [`ES_REISSUE`] state extensions.
+pub fn reissuance_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Reissuance validation
+        put     a8[0],ERRNO_INVALID_REISSUANCE;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read declared reissued amount into a64[0]
+        ldg     GS_ADDL_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        // Checking that the amount is non-zero
+        ifz     a64[0];
+        inv     st0;
+        test;
+        // verify sum of pedersen commitments for reissued assets against a64[0] value
+        pcas    OS_REISSUED_ASSET;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong reissuance extension script")
+}
+
+/// State extension adding reissuance capability to a NIA-based schema.
+pub struct ReissuanceExtension;
+
+impl ReissuanceExtension {
+    /// Constructs the schema of [`ES_REISSUE`] state extension.
+    pub fn extension_schema() -> ExtensionSchema {
+        let alu_lib = reissuance_lib();
+        let code = alu_lib.code.as_ref();
+        assert_eq!(code[FN_REISSUE_OFFSET as usize], INSTR_PUTA);
+        assert_eq!(code[FN_REISSUE_OFFSET as usize + 12], INSTR_LDG);
+        assert_eq!(code[FN_REISSUE_OFFSET as usize + 16], INSTR_EXTR);
+        assert_eq!(code[FN_REISSUE_OFFSET as usize + 23], INSTR_PCAS);
+
+        ExtensionSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_ADDL_SUPPLY => Occurrences::Once,
+            },
+            redeems: tiny_bset![VA_ISSUER_AUTHORITY],
+            assignments: tiny_bmap! {
+                OS_REISSUED_ASSET => Occurrences::OnceOrMore,
+            },
+            valencies: tiny_bset![VA_ISSUER_AUTHORITY],
+            validator: Some(LibSite::with(FN_REISSUE_OFFSET, alu_lib.id())),
+        }
+    }
+
+    /// Adds [`ES_REISSUE`] state extension to the `schema`, requiring its
+    /// genesis to commit to the issuer public key and to declare the issuer
+    /// authority valency.
+    pub fn apply(schema: &mut Schema) -> Result<(), NiaSchemaError> {
+        schema
+            .global_types
+            .insert(
+                GS_EXTENSION_AUTHORITY,
+                GlobalStateSchema::once(nia_type("RGBSchemata.IssuerKey")?),
+            )
+            .expect("too many global types");
+        schema
+            .global_types
+            .insert(GS_ADDL_SUPPLY, GlobalStateSchema::many(nia_type("RGBContract.Amount")?))
+            .expect("too many global types");
+        schema
+            .valency_types
+            .push(VA_ISSUER_AUTHORITY)
+            .expect("too many valency types");
+        schema
+            .genesis
+            .globals
+            .insert(GS_EXTENSION_AUTHORITY, Occurrences::Once)
+            .expect("too many genesis globals");
+        schema
+            .genesis
+            .valencies
+            .push(VA_ISSUER_AUTHORITY)
+            .expect("too many genesis valencies");
+        schema
+            .extensions
+            .insert(ES_REISSUE, Self::extension_schema())
+            .expect("too many extensions");
+        Ok(())
+    }
+
+    /// Adds reissuance state names to the interface implementation of an
+    /// extended schema.
+    pub fn apply_names(iimpl: &mut IfaceImpl) {
+        iimpl
+            .global_state
+            .extend([
+                NamedField::with(GS_EXTENSION_AUTHORITY, fname!("extensionAuthority")),
+                NamedField::with(GS_ADDL_SUPPLY, fname!("reissuedSupply")),
+            ])
+            .expect("too many global state fields");
+        iimpl
+            .valencies
+            .push(NamedField::with(VA_ISSUER_AUTHORITY, fname!("issuerAuthority")))
+            .expect("too many valencies");
+        iimpl
+            .extensions
+            .push(NamedField::with(ES_REISSUE, fname!("reissue")))
+            .expect("too many extensions");
+    }
+
+    /// Validation scripts of a NIA schema extended with reissuance.
+    pub fn scripts() -> Scripts {
+        let base = nia_base_lib().expect("invalid NIA validation library");
+        let nia = nia_lib().expect("invalid NIA validation library");
+        let lib = reissuance_lib();
+        confined_bmap! { base.id() => base, nia.id() => nia, lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use bp::seals::txout::CloseMethod;
+    use bp::Txid;
+    use ifaces::{IssuerWrapper, Rgb20};
+    use rgbstd::containers::BuilderSeal;
+    use rgbstd::interface::{ContractBuilder, IfaceClass};
+    use rgbstd::invoice::Precision;
+    use rgbstd::stl::{AssetSpec, ContractTerms, RicardianContract};
+    use rgbstd::{Amount, GenesisSeal, Identity, XChain};
+
+    use super::*;
+    use crate::{nia_iface_impl, nia_schema, IssuerKey, NonInflatableAsset};
+
+    #[test]
+    fn reissuable_nia() {
+        let mut schema = nia_schema().unwrap();
+        ReissuanceExtension::apply(&mut schema).unwrap();
+        let extension = &schema.extensions[&ES_REISSUE];
+        assert!(extension.redeems.contains(&VA_ISSUER_AUTHORITY));
+        assert_eq!(extension.assignments.get(&OS_REISSUED_ASSET), Some(&Occurrences::OnceOrMore));
+        assert_eq!(schema.genesis.globals.get(&GS_EXTENSION_AUTHORITY), Some(&Occurrences::Once));
+
+        let mut iimpl = nia_iface_impl().unwrap();
+        iimpl.schema_id = schema.schema_id();
+        ReissuanceExtension::apply_names(&mut iimpl);
+        let iface = Rgb20::iface(NonInflatableAsset::FEATURES);
+        assert!(iimpl.check(&iface, &schema).is_ok());
+
+        let txid =
+            Txid::from_str("14295d5bb1a191cdb6286dc0944df938421e3dfcbf0811353ccac4100c2068c5")
+                .unwrap();
+        let seal = XChain::Bitcoin(GenesisSeal::new_random(CloseMethod::TapretFirst, txid, 0));
+        ContractBuilder::with(
+            Identity::default(),
+            iface,
+            schema,
+            iimpl,
+            NonInflatableAsset::types(),
+            ReissuanceExtension::scripts(),
+        )
+        .add_global_state("spec", AssetSpec::new("TEST", "Test", Precision::Indivisible))
+        .unwrap()
+        .add_global_state("terms", ContractTerms {
+            text: RicardianContract::default(),
+            media: None,
+        })
+        .unwrap()
+        .add_global_state("issuedSupply", Amount::from(1000u64))
+        .unwrap()
+        .add_global_state("extensionAuthority", IssuerKey::from([2u8; 33]))
+        .unwrap()
+        .add_fungible_state("assetOwner", BuilderSeal::from(seal), 1000u64)
+        .unwrap()
+        .issue_contract()
+        .expect("genesis of the reissuable asset must be valid");
+    }
+}
@@ -0,0 +1,384 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Issuer rekeying transition adding rotation of the issuer signing key to
+//! NIA-based schemata.
+//!
+//! The issuer of an asset created with a schema extended by
+//! [`IssuerRekey::apply`] may replace its current public key
+//! ([`GS_CURRENT_ISSUER_KEY`]) with [`TS_REKEY`] transition, which spends
+//! the rekey right ([`OS_REKEY_RIGHT`]) and assigns it to a seal controlled by
+//! the new key. The transition also declares a new issuer authority valency
+//! ([`VA_ISSUER_AUTHORITY`]), so state extensions authorized by the issuer may
+//! redeem the authority of the latest key.
+//!
+//! Valencies can't be redeemed by state transitions, thus the rekey is gated by
+//! the owned right instead. The script only checks that the [`RekeyProof`]
+//! certifies the key declared by the transition, while wallets must check the
+//! signature of the previous key of the contract over the proof with
+//! [`RekeyProof::verify`] (see [consensus
+//! limitations](crate#consensus-limitations)).
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA, INSTR_RET};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use bp::secp256k1::{schnorr, Message, XOnlyPublicKey, SECP256K1};
+use rgbstd::interface::{IfaceImpl, NamedField};
+use rgbstd::schema::{GlobalStateSchema, Occurrences, Schema, TransitionSchema};
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::INSTR_LDG;
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, OwnedStateSchema, TransitionType};
+use sha2::{Digest, Sha256};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+use crate::nia::nia_type;
+use crate::stl::LIB_NAME_RGB_SCHEMATA;
+use crate::{nia_base_lib, nia_lib, IssuerKey, NiaSchemaError, VA_ISSUER_AUTHORITY};
+
+/// Current public key of the issuer ([`IssuerKey`]), declared by genesis and by
+/// each rekey.
+pub const GS_CURRENT_ISSUER_KEY: GlobalStateType = GlobalStateType::with(4600);
+/// Signature of the previous issuer key over the new one ([`RekeyProof`]).
+pub const GS_REKEY_PROOF: GlobalStateType = GlobalStateType::with(4601);
+
+/// Right of the issuer to replace its key.
+pub const OS_REKEY_RIGHT: AssignmentType = AssignmentType::with(6500);
+
+pub const TS_REKEY: TransitionType = TransitionType::with(12500);
+
+/// Error raised when the rekey proof doesn't certify the new issuer key.
+pub const ERRNO_INVALID_REKEY: u8 = 60;
+
+pub const FN_REKEY_OFFSET: u16 = 0;
+pub(crate) const FN_REKEY_END_OFFSET: u16 =
+    FN_REKEY_OFFSET + 4 + 4 + 4 + 4 + 4 + 3 + 3 + 3 + 1 + 4 + 3 + 3 + 3 + 1;
+
+/// Certificate of a new issuer key, signed by the previous one.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct RekeyProof {
+    /// New issuer key, which must match the [`GS_CURRENT_ISSUER_KEY`] of the
+    /// rekey transition.
+    pub new_key: IssuerKey,
+    /// BIP-340 signature of the previous issuer key over
+    /// [`RekeyProof::message`].
+    pub signature: [u8; 64],
+}
+impl StrictSerialize for RekeyProof {}
+impl StrictDeserialize for RekeyProof {}
+
+impl RekeyProof {
+    fn dumb() -> Self {
+        Self {
+            new_key: IssuerKey::from([2u8; 33]),
+            signature: [0u8; 64],
+        }
+    }
+
+    /// Computes the message signed by the previous issuer key, which is SHA256
+    /// hash of the strict-encoded new key.
+    pub fn message(&self) -> [u8; 32] {
+        let data = self
+            .new_key
+            .to_strict_serialized::<33>()
+            .expect("fixed-size key");
+        Sha256::digest(data.as_slice()).into()
+    }
+
+    /// Verifies the signature of the `previous` issuer key over the new key.
+    pub fn verify(&self, previous: &IssuerKey) -> bool {
+        let Ok(key) = XOnlyPublicKey::from_slice(&previous[1..]) else {
+            return false;
+        };
+        let Ok(signature) = schnorr::Signature::from_slice(&self.signature) else {
+            return false;
+        };
+        let message = Message::from_digest(self.message());
+        SECP256K1.verify_schnorr(&signature, &message, &key).is_ok()
+    }
+}
+
+/// AluVM library validating [`TS_REKEY`] transitions.
+pub fn rekey_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Rekey validation
+        put     a8[0],ERRNO_INVALID_REKEY;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read the new key into s16[0] and the rekey proof into s16[1]
+        ldg     GS_CURRENT_ISSUER_KEY,a8[1],s16[0];
+        ldg     GS_REKEY_PROOF,a8[1],s16[1];
+        // Checking that the proof starts with the new key. The 33-byte key is compared as two
+        // overlapping 32-byte halves, starting at its first and second bytes.
+        extr    s16[0],r256[0],a16[0];
+        extr    s16[1],r256[1],a16[0];
+        eq.n    r256[0],r256[1];
+        test;
+        put     a16[1],1;
+        extr    s16[0],r256[0],a16[1];
+        extr    s16[1],r256[1],a16[1];
+        eq.n    r256[0],r256[1];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong issuer rekey script")
+}
+
+/// State transition adding issuer key rotation to a NIA-based schema.
+pub struct IssuerRekey;
+
+impl IssuerRekey {
+    /// Constructs the schema of [`TS_REKEY`] state transition.
+    pub fn transition_schema() -> TransitionSchema {
+        let alu_lib = rekey_lib();
+        let code = alu_lib.code.as_ref();
+        assert_eq!(code[FN_REKEY_OFFSET as usize], INSTR_PUTA);
+        assert_eq!(code[FN_REKEY_OFFSET as usize + 12], INSTR_LDG);
+        assert_eq!(code[FN_REKEY_OFFSET as usize + 16], INSTR_LDG);
+        assert_eq!(code[FN_REKEY_OFFSET as usize + 20], INSTR_EXTR);
+        assert_eq!(code[FN_REKEY_OFFSET as usize + 34], INSTR_EXTR);
+        assert_eq!(code[FN_REKEY_END_OFFSET as usize], INSTR_RET);
+        assert_eq!(code.len(), FN_REKEY_END_OFFSET as usize + 1);
+
+        TransitionSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_CURRENT_ISSUER_KEY => Occurrences::Once,
+                GS_REKEY_PROOF => Occurrences::Once,
+            },
+            inputs: tiny_bmap! {
+                OS_REKEY_RIGHT => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_REKEY_RIGHT => Occurrences::Once,
+            },
+            valencies: tiny_bset![VA_ISSUER_AUTHORITY],
+            validator: Some(LibSite::with(FN_REKEY_OFFSET, alu_lib.id())),
+        }
+    }
+
+    /// Adds [`TS_REKEY`] state transition to the `schema`, requiring its
+    /// genesis to commit to the issuer public key, to assign the rekey
+    /// right and to declare the issuer authority valency.
+    pub fn apply(schema: &mut Schema) -> Result<(), NiaSchemaError> {
+        schema
+            .global_types
+            .insert(
+                GS_CURRENT_ISSUER_KEY,
+                GlobalStateSchema::many(nia_type("RGBSchemata.IssuerKey")?),
+            )
+            .expect("too many global types");
+        schema
+            .global_types
+            .insert(GS_REKEY_PROOF, GlobalStateSchema::many(nia_type("RGBSchemata.RekeyProof")?))
+            .expect("too many global types");
+        schema
+            .owned_types
+            .insert(OS_REKEY_RIGHT, OwnedStateSchema::Declarative)
+            .expect("too many owned types");
+        schema
+            .valency_types
+            .push(VA_ISSUER_AUTHORITY)
+            .expect("too many valency types");
+        schema
+            .genesis
+            .globals
+            .insert(GS_CURRENT_ISSUER_KEY, Occurrences::Once)
+            .expect("too many genesis globals");
+        schema
+            .genesis
+            .assignments
+            .insert(OS_REKEY_RIGHT, Occurrences::Once)
+            .expect("too many genesis assignments");
+        schema
+            .genesis
+            .valencies
+            .push(VA_ISSUER_AUTHORITY)
+            .expect("too many genesis valencies");
+        schema
+            .transitions
+            .insert(TS_REKEY, Self::transition_schema())
+            .expect("too many transitions");
+        Ok(())
+    }
+
+    /// Adds rekey state names to the interface implementation of an extended
+    /// schema.
+    pub fn apply_names(iimpl: &mut IfaceImpl) {
+        iimpl
+            .global_state
+            .extend([
+                NamedField::with(GS_CURRENT_ISSUER_KEY, fname!("currentIssuerKey")),
+                NamedField::with(GS_REKEY_PROOF, fname!("rekeyProof")),
+            ])
+            .expect("too many global state fields");
+        iimpl
+            .assignments
+            .push(NamedField::with(OS_REKEY_RIGHT, fname!("rekeyRight")))
+            .expect("too many assignments");
+        iimpl
+            .valencies
+            .push(NamedField::with(VA_ISSUER_AUTHORITY, fname!("issuerAuthority")))
+            .expect("too many valencies");
+        iimpl
+            .transitions
+            .push(NamedField::with(TS_REKEY, fname!("rekey")))
+            .expect("too many transitions");
+    }
+
+    /// Validation scripts of a NIA schema extended with issuer rekeying.
+    pub fn scripts() -> Scripts {
+        let base = nia_base_lib().expect("invalid NIA validation library");
+        let nia = nia_lib().expect("invalid NIA validation library");
+        let lib = rekey_lib();
+        confined_bmap! { base.id() => base, nia.id() => nia, lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bp::secp256k1::Keypair;
+    use ifaces::{IssuerWrapper, Rgb20};
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::{ContractBuilder, IfaceClass, TransitionBuilder};
+    use rgbstd::Identity;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, script_errno, seal, spec, spend_genesis_with,
+        validate_transition,
+    };
+    use crate::{nia_iface_impl, nia_schema, NonInflatableAsset};
+
+    fn signed_proof(previous: &Keypair, next: &Keypair) -> RekeyProof {
+        let mut proof = RekeyProof {
+            new_key: IssuerKey::from(next.public_key().serialize()),
+            signature: [0u8; 64],
+        };
+        let message = Message::from_digest(proof.message());
+        proof.signature = *SECP256K1
+            .sign_schnorr_no_aux_rand(&message, previous)
+            .as_ref();
+        proof
+    }
+
+    fn keypair(secret: u8) -> Keypair {
+        Keypair::from_seckey_slice(SECP256K1, &[secret; 32]).unwrap()
+    }
+
+    fn rekeyable_schema() -> Schema {
+        let mut schema = nia_schema().unwrap();
+        IssuerRekey::apply(&mut schema).unwrap();
+        schema
+    }
+
+    fn rekeyable_iimpl() -> IfaceImpl {
+        let mut iimpl = nia_iface_impl().unwrap();
+        iimpl.schema_id = rekeyable_schema().schema_id();
+        IssuerRekey::apply_names(&mut iimpl);
+        iimpl
+    }
+
+    /// Issues rekeyable NIA with the issuer key of [`keypair`] `1`.
+    fn contract() -> Contract {
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            Rgb20::iface(NonInflatableAsset::FEATURES),
+            rekeyable_schema(),
+            rekeyable_iimpl(),
+            NonInflatableAsset::types(),
+            IssuerRekey::scripts(),
+        );
+        let key = IssuerKey::from(keypair(1).public_key().serialize());
+        fungible_genesis(builder, spec("TEST", "Test"), 1000)
+            .add_global_state("currentIssuerKey", key)
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_rights("rekeyRight", seal(1))
+            .unwrap()
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    #[test]
+    fn rekey_proof_signature() {
+        let (old, new) = (keypair(1), keypair(2));
+        let proof = signed_proof(&old, &new);
+        assert!(proof.verify(&IssuerKey::from(old.public_key().serialize())));
+        assert!(!proof.verify(&IssuerKey::from(new.public_key().serialize())));
+        let forged = signed_proof(&new, &new);
+        assert!(!forged.verify(&IssuerKey::from(old.public_key().serialize())));
+    }
+
+    #[test]
+    fn rekeyable_nia() {
+        let schema = rekeyable_schema();
+        let rekey = &schema.transitions[&TS_REKEY];
+        assert_eq!(rekey.inputs.get(&OS_REKEY_RIGHT), Some(&Occurrences::Once));
+        assert_eq!(rekey.assignments.get(&OS_REKEY_RIGHT), Some(&Occurrences::Once));
+        assert!(rekey.valencies.contains(&VA_ISSUER_AUTHORITY));
+        assert!(schema.genesis.valencies.contains(&VA_ISSUER_AUTHORITY));
+
+        let iimpl = rekeyable_iimpl();
+        assert!(iimpl
+            .check(&Rgb20::iface(NonInflatableAsset::FEATURES), &schema)
+            .is_ok());
+    }
+
+    #[test]
+    fn rekey() {
+        let contract = contract();
+        let rekey = |key: IssuerKey, proof: RekeyProof| {
+            let builder = TransitionBuilder::named_transition(
+                contract.contract_id(),
+                Rgb20::iface(NonInflatableAsset::FEATURES),
+                rekeyable_schema(),
+                rekeyable_iimpl(),
+                "rekey",
+                NonInflatableAsset::types(),
+            )
+            .unwrap();
+            let transition = spend_genesis_with(&contract, &rekeyable_schema(), builder)
+                .add_global_state("currentIssuerKey", key)
+                .unwrap()
+                .add_global_state("rekeyProof", proof)
+                .unwrap()
+                .add_rights("rekeyRight", graph_seal(0))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &rekeyable_schema(), &transition)
+        };
+
+        let proof = signed_proof(&keypair(1), &keypair(2));
+        let status = rekey(proof.new_key, proof);
+        assert!(status.failures.is_empty(), "{status}");
+        let old = IssuerKey::from(keypair(1).public_key().serialize());
+        assert_eq!(script_errno(rekey(old, proof)), Some(ERRNO_INVALID_REKEY));
+        let mut other = *proof.new_key;
+        other[32] ^= 0x01;
+        assert_eq!(script_errno(rekey(IssuerKey::from(other), proof)), Some(ERRNO_INVALID_REKEY));
+    }
+}
@@ -0,0 +1,716 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reputation token schema for soulbound, non-transferable reputation scores.
+//!
+//! The issuer, identified by its public key ([`GS_ISSUER_KEY`]), holds the
+//! reputation authority ([`OS_REPUTATION_AUTHORITY`]), which carries the
+//! maximal score ([`GS_SCORE_CAP`]) defined in genesis. Spending the authority,
+//! the issuer assigns reputation scores ([`OS_REPUTATION`]) to their holders
+//! with [`TS_ISSUE_REPUTATION`] and increments or decrements them by a declared
+//! delta ([`GS_SCORE_DELTA`]) with [`TS_INCREMENT_SCORE`] and
+//! [`TS_DECREMENT_SCORE`].
+//!
+//! The schema has no transfer: reputation stays bound to the holder, and score
+//! updates must keep the holder and the category of the reputation unchanged.
+//! Since updates spend the reputation, they require the holder to co-sign the
+//! witness transaction together with the issuer.
+//!
+//! The score cap is kept in the reputation authority state, since contract
+//! global state can't be accessed from transition scripts; each transition
+//! checks that the cap is passed unchanged.
+//!
+//! The schema has no standard interface, thus it implements its own
+//! [`ReputationIface`].
+
+use aluvm::isa::opcodes::{INSTR_JMP, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{IssuerWrapper, LNPBP_IDENTITY};
+use rgbstd::interface::{
+    AssignIface, ContractIface, DataAllocation, GenesisIface, GlobalIface, Iface, IfaceClass,
+    IfaceId, IfaceImpl, Modifier, NamedField, NamedVariant, OutpointFilter, OwnedIface, Req,
+    TransitionIface, VerNo,
+};
+use rgbstd::schema::{GenesisSchema, GlobalStateSchema, Occurrences, Schema, TransitionSchema};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDP, INSTR_LDS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, OwnedStateSchema, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::{TypeLib, TypeSystem};
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::GS_ISSUER_KEY;
+
+/// Name of the reputation interface.
+pub const LIB_NAME_REPUTATION: &str = "Reputation";
+
+pub const REPUTATION_IFACE_ID: IfaceId = IfaceId::from_array([
+    0x99, 0xed, 0x42, 0x8a, 0x62, 0x3c, 0x80, 0xeb, 0xee, 0xc3, 0xe6, 0x41, 0x51, 0x01, 0xb6, 0x23,
+    0x7b, 0x70, 0x6e, 0x64, 0x82, 0x50, 0xbb, 0xb5, 0xfc, 0x49, 0x9a, 0x2f, 0x44, 0x5b, 0x5b, 0xae,
+]);
+
+/// Maximal reputation score ([`ReputationScore`]).
+pub const GS_SCORE_CAP: GlobalStateType = GlobalStateType::with(4400);
+/// Amount by which a score update changes the reputation score
+/// ([`ReputationScore`]).
+pub const GS_SCORE_DELTA: GlobalStateType = GlobalStateType::with(4401);
+
+/// Soulbound reputation of a holder ([`Reputation`]).
+pub const OS_REPUTATION: AssignmentType = AssignmentType::with(6300);
+/// Right of the issuer to assign and update reputation scores, keeping the
+/// score cap ([`ReputationScore`]).
+pub const OS_REPUTATION_AUTHORITY: AssignmentType = AssignmentType::with(6301);
+
+pub const TS_ISSUE_REPUTATION: TransitionType = TransitionType::with(12300);
+pub const TS_INCREMENT_SCORE: TransitionType = TransitionType::with(12301);
+pub const TS_DECREMENT_SCORE: TransitionType = TransitionType::with(12302);
+
+/// Error raised when a reputation score exceeds the score cap.
+pub const ERRNO_SCORE_CAP_EXCEEDED: u8 = 56;
+/// Error raised when a score update changes the holder, the category or the
+/// score cap, or doesn't change the score by the declared non-zero delta.
+pub const ERRNO_INVALID_SCORE_UPDATE: u8 = 57;
+
+pub const FN_REPUTATION_GENESIS_OFFSET: u16 = 0;
+pub const FN_ISSUE_REPUTATION_OFFSET: u16 =
+    FN_REPUTATION_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 4 + 3 + 3 + 3 + 1 + 1;
+pub const FN_INCREMENT_SCORE_OFFSET: u16 =
+    FN_ISSUE_REPUTATION_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 3 + 1 + 4 + 4 + 4 + 3 + 3 + 1 + 1 + 1;
+pub const FN_DECREMENT_SCORE_OFFSET: u16 = FN_INCREMENT_SCORE_OFFSET + 4 + 3;
+pub(crate) const FN_SCORE_UPDATE_OFFSET: u16 = FN_DECREMENT_SCORE_OFFSET + 4;
+pub(crate) const FN_SCORE_UPDATE_DELTA_OFFSET: u16 = FN_SCORE_UPDATE_OFFSET +
+    4 +
+    4 +
+    4 +
+    4 +
+    3 +
+    3 +
+    3 +
+    1 +
+    4 +
+    4 +
+    3 +
+    3 +
+    3 +
+    1 +
+    4 +
+    3 +
+    3 +
+    3 +
+    1;
+pub(crate) const FN_SCORE_DECREMENT_TAIL_OFFSET: u16 = FN_SCORE_UPDATE_DELTA_OFFSET +
+    4 +
+    3 +
+    3 +
+    4 +
+    4 +
+    3 +
+    2 +
+    1 +
+    1 +
+    2 +
+    3 +
+    3 +
+    3 +
+    1 +
+    4 +
+    3 +
+    1 +
+    1 +
+    1;
+
+/// Reputation score, used both for the score cap and for score update deltas.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct ReputationScore(u32);
+impl StrictSerialize for ReputationScore {}
+impl StrictDeserialize for ReputationScore {}
+
+/// Soulbound reputation assigned to a holder.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct Reputation {
+    /// Hash of the seal definition of the holder the reputation is bound to.
+    pub holder_seal: [u8; 32],
+    /// Current reputation score, not exceeding the score cap.
+    pub score: u32,
+    /// Application-specific category of the reputation.
+    pub category: u8,
+}
+impl StrictSerialize for Reputation {}
+impl StrictDeserialize for Reputation {}
+
+/// Assembles AluVM library with validation scripts used by the reputation token
+/// schema.
+// `sub` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn reputation_lib() -> Lib {
+    let code = rgbasm! {
+        // SUBROUTINE Genesis validation
+        // Checking that the reputation authority carries the score cap
+        put     a8[0],ERRNO_INVALID_SCORE_UPDATE;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_SCORE_CAP,a8[1],s16[0];
+        lds     OS_REPUTATION_AUTHORITY,a16[0],s16[1];
+        extr    s16[0],a32[0],a16[0];
+        extr    s16[1],a32[1],a16[0];
+        eq.n    a32[0],a32[1];
+        test;
+        ret;
+
+        // SUBROUTINE Reputation issue validation
+        // Checking that the score cap is kept and the issued score doesn't exceed it
+        put     a8[0],ERRNO_INVALID_SCORE_UPDATE;
+        put     a16[0],0;
+        ldp     OS_REPUTATION_AUTHORITY,a16[0],s16[2];
+        lds     OS_REPUTATION_AUTHORITY,a16[0],s16[3];
+        extr    s16[2],a32[2],a16[0];
+        extr    s16[3],a32[3],a16[0];
+        eq.n    a32[2],a32[3];
+        test;
+        put     a8[0],ERRNO_SCORE_CAP_EXCEEDED;
+        lds     OS_REPUTATION,a16[0],s16[1];
+        put     a16[1],32;
+        extr    s16[1],a32[1],a16[1];
+        gt.u    a32[1],a32[2];
+        inv     st0;
+        test;
+        ret;
+
+        // SUBROUTINE Score increment validation
+        put     a8[4],1;
+        jmp     FN_SCORE_UPDATE_OFFSET;
+
+        // SUBROUTINE Score decrement validation
+        put     a8[4],0;
+
+        // SUBROUTINE Shared score update validation
+        // Checking that the score cap is kept
+        put     a8[0],ERRNO_INVALID_SCORE_UPDATE;
+        put     a16[0],0;
+        ldp     OS_REPUTATION_AUTHORITY,a16[0],s16[2];
+        lds     OS_REPUTATION_AUTHORITY,a16[0],s16[3];
+        extr    s16[2],a32[2],a16[0];
+        extr    s16[3],a32[3],a16[0];
+        eq.n    a32[2],a32[3];
+        test;
+        // Checking that the holder is kept
+        ldp     OS_REPUTATION,a16[0],s16[0];
+        lds     OS_REPUTATION,a16[0],s16[1];
+        extr    s16[0],r256[0],a16[0];
+        extr    s16[1],r256[1],a16[0];
+        eq.n    r256[0],r256[1];
+        test;
+        // Checking that the category is kept
+        put     a16[1],36;
+        extr    s16[0],a8[1],a16[1];
+        extr    s16[1],a8[2],a16[1];
+        eq.n    a8[1],a8[2];
+        test;
+        // Reading previous score into a32[0], new score into a32[1] and the delta into a32[4]
+        put     a16[1],32;
+        extr    s16[0],a32[0],a16[1];
+        extr    s16[1],a32[1],a16[1];
+        put     a8[3],0;
+        ldg     GS_SCORE_DELTA,a8[3],s16[4];
+        extr    s16[4],a32[4],a16[0];
+        ifz     a32[4];
+        inv     st0;
+        test;
+        ifz     a8[4];
+        jif     FN_SCORE_DECREMENT_TAIL_OFFSET;
+        // a32[4] := a32[0] + a32[4]; fails on overflow
+        add.uc  a32[0],a32[4];
+        eq.n    a32[4],a32[1];
+        test;
+        put     a8[0],ERRNO_SCORE_CAP_EXCEEDED;
+        gt.u    a32[1],a32[2];
+        inv     st0;
+        test;
+        ret;
+        // a32[4] := a32[0] - a32[4]; fails if the delta exceeds the score
+        sub.uc  a32[0],a32[4];
+        eq.n    a32[4],a32[1];
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong reputation token script")
+}
+
+fn reputation_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = reputation_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_REPUTATION_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_REPUTATION_GENESIS_OFFSET + 12), GS_SCORE_CAP.to_inner());
+    assert_eq!(code[FN_ISSUE_REPUTATION_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_ISSUE_REPUTATION_OFFSET as usize + 8], INSTR_LDP);
+    assert_eq!(state_arg(FN_ISSUE_REPUTATION_OFFSET + 8), OS_REPUTATION_AUTHORITY.to_inner());
+    assert_eq!(code[FN_INCREMENT_SCORE_OFFSET as usize + 4], INSTR_JMP);
+    assert_eq!(code[FN_DECREMENT_SCORE_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_SCORE_UPDATE_OFFSET as usize + 26], INSTR_LDP);
+    assert_eq!(state_arg(FN_SCORE_UPDATE_OFFSET + 26), OS_REPUTATION.to_inner());
+    assert_eq!(code[FN_SCORE_UPDATE_OFFSET as usize + 30], INSTR_LDS);
+    assert_eq!(code[FN_SCORE_UPDATE_DELTA_OFFSET as usize + 14], INSTR_LDG);
+    assert_eq!(state_arg(FN_SCORE_UPDATE_DELTA_OFFSET + 14), GS_SCORE_DELTA.to_inner());
+    assert_eq!(code.len(), FN_SCORE_DECREMENT_TAIL_OFFSET as usize + 3 + 3 + 1 + 1);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("ReputationToken"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_ISSUER_KEY => GlobalStateSchema::once(types.get("RGBSchemata.IssuerKey")),
+            GS_SCORE_CAP => GlobalStateSchema::once(types.get("RGBSchemata.ReputationScore")),
+            GS_SCORE_DELTA => GlobalStateSchema::once(types.get("RGBSchemata.ReputationScore")),
+        },
+        owned_types: tiny_bmap! {
+            OS_REPUTATION => OwnedStateSchema::Structured(types.get("RGBSchemata.Reputation")),
+            OS_REPUTATION_AUTHORITY =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.ReputationScore")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_ISSUER_KEY => Occurrences::Once,
+                GS_SCORE_CAP => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_REPUTATION_AUTHORITY => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_REPUTATION_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_ISSUE_REPUTATION => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_REPUTATION_AUTHORITY => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_REPUTATION => Occurrences::Once,
+                    OS_REPUTATION_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_ISSUE_REPUTATION_OFFSET, alu_id)),
+            },
+            TS_INCREMENT_SCORE => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_SCORE_DELTA => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_REPUTATION => Occurrences::Once,
+                    OS_REPUTATION_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_REPUTATION => Occurrences::Once,
+                    OS_REPUTATION_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_INCREMENT_SCORE_OFFSET, alu_id)),
+            },
+            TS_DECREMENT_SCORE => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_SCORE_DELTA => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_REPUTATION => Occurrences::Once,
+                    OS_REPUTATION_AUTHORITY => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_REPUTATION => Occurrences::Once,
+                    OS_REPUTATION_AUTHORITY => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_DECREMENT_SCORE_OFFSET, alu_id)),
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn score_update_iface() -> TransitionIface {
+    TransitionIface {
+        modifier: Modifier::Final,
+        optional: false,
+        metadata: none!(),
+        globals: tiny_bmap! {
+            fname!("scoreDelta") => Occurrences::Once,
+        },
+        inputs: tiny_bmap! {
+            fname!("reputation") => Occurrences::Once,
+            fname!("reputationAuthority") => Occurrences::Once,
+        },
+        assignments: tiny_bmap! {
+            fname!("reputation") => Occurrences::Once,
+            fname!("reputationAuthority") => Occurrences::Once,
+        },
+        valencies: none!(),
+        errors: tiny_bset! {
+            vname!("scoreCapExceeded"),
+            vname!("invalidScoreUpdate"),
+        },
+        default_assignment: Some(fname!("reputation")),
+    }
+}
+
+/// Interface of non-transferable reputation scores assigned by an issuer.
+pub fn reputation() -> Iface {
+    let types = StandardTypes::with(rgb_schemata_stl());
+    Iface {
+        version: VerNo::V1,
+        name: tn!("ReputationToken"),
+        inherits: none!(),
+        developer: Identity::from(LNPBP_IDENTITY),
+        timestamp: 1713343888,
+        metadata: none!(),
+        global_state: tiny_bmap! {
+            fname!("issuerKey") => GlobalIface::required(types.get("RGBSchemata.IssuerKey")),
+            fname!("scoreCap") => GlobalIface::required(types.get("RGBSchemata.ReputationScore")),
+            fname!("scoreDelta") =>
+                GlobalIface::none_or_many(types.get("RGBSchemata.ReputationScore")),
+        },
+        assignments: tiny_bmap! {
+            fname!("reputation") => AssignIface::private(
+                OwnedIface::Data(types.get("RGBSchemata.Reputation")),
+                Req::NoneOrMore,
+            ),
+            fname!("reputationAuthority") => AssignIface::public(
+                OwnedIface::Data(types.get("RGBSchemata.ReputationScore")),
+                Req::Required,
+            ),
+        },
+        valencies: none!(),
+        genesis: GenesisIface {
+            modifier: Modifier::Final,
+            metadata: none!(),
+            globals: tiny_bmap! {
+                fname!("issuerKey") => Occurrences::Once,
+                fname!("scoreCap") => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                fname!("reputationAuthority") => Occurrences::Once,
+            },
+            valencies: none!(),
+            errors: tiny_bset! {
+                vname!("invalidScoreUpdate")
+            },
+        },
+        transitions: tiny_bmap! {
+            fname!("issueReputation") => TransitionIface {
+                modifier: Modifier::Final,
+                optional: false,
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    fname!("reputationAuthority") => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    fname!("reputation") => Occurrences::Once,
+                    fname!("reputationAuthority") => Occurrences::Once,
+                },
+                valencies: none!(),
+                errors: tiny_bset! {
+                    vname!("scoreCapExceeded"),
+                    vname!("invalidScoreUpdate"),
+                },
+                default_assignment: Some(fname!("reputation")),
+            },
+            fname!("incrementScore") => score_update_iface(),
+            fname!("decrementScore") => score_update_iface(),
+        },
+        extensions: none!(),
+        errors: tiny_bmap! {
+            vname!("scoreCapExceeded")
+                => tiny_s!("reputation score exceeds the score cap"),
+            vname!("invalidScoreUpdate")
+                => tiny_s!("score update changes the holder, the category or the score cap, or \
+                            doesn't match the declared delta"),
+        },
+        default_operation: None,
+    }
+}
+
+/// Reputation interface class.
+#[derive(Wrapper, WrapperMut, Clone, Eq, PartialEq, Debug)]
+#[wrapper(Deref)]
+#[wrapper_mut(DerefMut)]
+pub struct ReputationIface(ContractIface);
+
+impl From<ContractIface> for ReputationIface {
+    fn from(iface: ContractIface) -> Self {
+        if !ReputationIface::IFACE_IDS.contains(&iface.iface.iface_id) {
+            panic!("the provided interface is not reputation interface");
+        }
+        Self(iface)
+    }
+}
+
+impl IfaceClass for ReputationIface {
+    const IFACE_NAME: &'static str = LIB_NAME_REPUTATION;
+    const IFACE_IDS: &'static [IfaceId] = &[REPUTATION_IFACE_ID];
+
+    type Features = ();
+    type Info = ();
+
+    fn iface(_features: Self::Features) -> Iface { reputation() }
+
+    fn iface_id(_features: Self::Features) -> IfaceId { REPUTATION_IFACE_ID }
+
+    fn stl() -> TypeLib { rgb_schemata_stl() }
+
+    fn info(&self) -> Self::Info {}
+}
+
+impl ReputationIface {
+    pub fn reputations<'c>(
+        &'c self,
+        filter: impl OutpointFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        self.0
+            .data("reputation", filter)
+            .expect("reputation interface requires `reputation` state")
+    }
+}
+
+fn reputation_iimpl() -> IfaceImpl {
+    let schema = reputation_schema();
+    let iface = reputation();
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_ISSUER_KEY, fname!("issuerKey")),
+            NamedField::with(GS_SCORE_CAP, fname!("scoreCap")),
+            NamedField::with(GS_SCORE_DELTA, fname!("scoreDelta")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_REPUTATION, fname!("reputation")),
+            NamedField::with(OS_REPUTATION_AUTHORITY, fname!("reputationAuthority")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_ISSUE_REPUTATION, fname!("issueReputation")),
+            NamedField::with(TS_INCREMENT_SCORE, fname!("incrementScore")),
+            NamedField::with(TS_DECREMENT_SCORE, fname!("decrementScore")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_SCORE_CAP_EXCEEDED, vname!("scoreCapExceeded")),
+            NamedVariant::with(ERRNO_INVALID_SCORE_UPDATE, vname!("invalidScoreUpdate")),
+        ],
+    }
+}
+
+/// Issuer of soulbound reputation scores.
+pub struct ReputationToken;
+
+impl IssuerWrapper for ReputationToken {
+    const FEATURES: () = ();
+    type IssuingIface = ReputationIface;
+
+    fn schema() -> Schema { reputation_schema() }
+    fn issue_impl() -> IfaceImpl { reputation_iimpl() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = reputation_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+
+    use super::*;
+    use crate::test_helpers::{
+        graph_seal, issuer_with_assignments, script_errno, seal, spend_genesis, validate_transition,
+    };
+    use crate::{IssuerKey, TS_TRANSFER};
+
+    fn held_reputation(holder: u8, score: u32, category: u8) -> Reputation {
+        Reputation {
+            holder_seal: [holder; 32],
+            score,
+            category,
+        }
+    }
+
+    #[test]
+    fn iface_id() {
+        let iface_id = reputation().iface_id();
+        assert_eq!(ReputationIface::IFACE_IDS[0], iface_id);
+    }
+
+    #[test]
+    fn iface_check() {
+        if let Err(err) = ReputationIface::iface(()).check() {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid reputation interface definition");
+        }
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = ReputationIface::iface(ReputationToken::FEATURES);
+        if let Err(err) = reputation_iimpl().check(&iface, &reputation_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid reputation interface implementation");
+        }
+    }
+
+    #[test]
+    fn soulbound() {
+        let schema = reputation_schema();
+        assert!(!schema.transitions.contains_key(&TS_TRANSFER));
+        for transition in schema.transitions.values() {
+            assert!(transition.inputs.contains_key(&OS_REPUTATION_AUTHORITY));
+            assert!(transition.validator.is_some());
+        }
+    }
+
+    /// Issues reputation token with the score cap of 100, keeping the
+    /// `authority_cap` in the reputation authority and optionally assigning
+    /// reputation with the `score` in category 1.
+    fn contract(authority_cap: u32, score: Option<u32>) -> Option<Contract> {
+        let mut builder = issuer_with_assignments::<ReputationToken>(&[OS_REPUTATION])
+            .add_global_state("issuerKey", IssuerKey::from([2u8; 33]))
+            .unwrap()
+            .add_global_state("scoreCap", ReputationScore::from(100))
+            .unwrap()
+            .add_data("reputationAuthority", seal(0), ReputationScore::from(authority_cap))
+            .unwrap();
+        if let Some(score) = score {
+            builder = builder
+                .add_data("reputation", seal(1), held_reputation(7, score, 1))
+                .unwrap();
+        }
+        let contract = builder.issue_contract().ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn reputation_layout() {
+        // Score update script reads the score at byte 32 and the category at byte 36
+        let reputation = Reputation {
+            holder_seal: [7u8; 32],
+            score: 0x01020304,
+            category: 5,
+        };
+        let data = reputation.to_strict_serialized::<64>().unwrap();
+        assert_eq!(data.len(), 37);
+        assert_eq!(&data[32..36], &0x01020304u32.to_le_bytes());
+        assert_eq!(data[36], 5);
+    }
+
+    #[test]
+    fn reputation_genesis() {
+        assert!(contract(100, None).is_some());
+        assert!(contract(1000, None).is_none());
+    }
+
+    #[test]
+    fn issue_reputation() {
+        let contract = contract(100, None).unwrap();
+        let issue = |cap: u32, score: u32| {
+            let transition = spend_genesis::<ReputationToken>(&contract, "issueReputation")
+                .add_data("reputation", graph_seal(0), held_reputation(7, score, 1))
+                .unwrap()
+                .add_data("reputationAuthority", graph_seal(1), ReputationScore::from(cap))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &ReputationToken::schema(), &transition)
+        };
+
+        let status = issue(100, 100);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(issue(100, 101)), Some(ERRNO_SCORE_CAP_EXCEEDED));
+        assert_eq!(script_errno(issue(1000, 50)), Some(ERRNO_INVALID_SCORE_UPDATE));
+    }
+
+    #[test]
+    fn update_score() {
+        let contract = contract(100, Some(40)).unwrap();
+        let update = |name: &'static str, delta: u32, reputation: Reputation| {
+            let transition = spend_genesis::<ReputationToken>(&contract, name)
+                .add_global_state("scoreDelta", ReputationScore::from(delta))
+                .unwrap()
+                .add_data("reputation", graph_seal(0), reputation)
+                .unwrap()
+                .add_data("reputationAuthority", graph_seal(1), ReputationScore::from(100))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &ReputationToken::schema(), &transition)
+        };
+        let increment = |delta, reputation| update("incrementScore", delta, reputation);
+        let decrement = |delta, reputation| update("decrementScore", delta, reputation);
+
+        let status = increment(60, held_reputation(7, 100, 1));
+        assert!(status.failures.is_empty(), "{status}");
+        let status = decrement(40, held_reputation(7, 0, 1));
+        assert!(status.failures.is_empty(), "{status}");
+
+        let status = increment(61, held_reputation(7, 101, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_SCORE_CAP_EXCEEDED));
+        let status = increment(10, held_reputation(7, 60, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_SCORE_UPDATE));
+        let status = increment(0, held_reputation(7, 40, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_SCORE_UPDATE));
+        let status = decrement(41, held_reputation(7, 0, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_SCORE_UPDATE));
+        let status = decrement(10, held_reputation(8, 30, 1));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_SCORE_UPDATE));
+        let status = decrement(10, held_reputation(7, 30, 2));
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_SCORE_UPDATE));
+    }
+}
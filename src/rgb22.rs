@@ -0,0 +1,285 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RGB22 decentralized identity interface.
+//!
+//! The interface is not yet provided by the RGB interfaces crate, thus it is
+//! defined here together with its data types, which are part of the
+//! [`crate::LIB_NAME_RGB_SCHEMATA`] type library. An identity registry holding
+//! the registry authority issues non-transferable identity claims and may
+//! revoke them, while claim holders may only update the hash of the claim
+//! metadata.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::ascii::AsciiString;
+use amplify::confinement::Confined;
+use ifaces::LNPBP_IDENTITY;
+use rgbstd::interface::{
+    AssignIface, ContractIface, DataAllocation, GenesisIface, GlobalIface, Iface, IfaceClass,
+    IfaceId, Modifier, OutpointFilter, OwnedIface, Req, TransitionIface, VerNo,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::{Identity, Occurrences};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeLib;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+
+/// Name of the RGB22 interface class.
+pub const LIB_NAME_RGB22: &str = "RGB22";
+
+pub const RGB22_IFACE_ID: IfaceId = IfaceId::from_array([
+    0x01, 0x83, 0xc3, 0x58, 0x8a, 0xb9, 0x65, 0x8e, 0x39, 0xe1, 0xab, 0xfe, 0x38, 0x8b, 0xef, 0xbb,
+    0x5f, 0x87, 0xda, 0x4a, 0xe8, 0x6d, 0xaf, 0xc7, 0xf0, 0x0e, 0xc6, 0x4b, 0x46, 0xb4, 0x80, 0xee,
+]);
+
+/// Decentralized identifier (DID) consisting of up to 255 ASCII characters and
+/// starting with the `did:` scheme.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct Did(Confined<AsciiString, 5, 255>);
+impl StrictSerialize for Did {}
+impl StrictDeserialize for Did {}
+
+impl Did {
+    fn dumb() -> Self { Self::from_str("did:rgb:example").expect("valid DID") }
+}
+
+/// Error parsing [`Did`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(
+    "invalid decentralized identifier '{0}', which must start with 'did:' and consist of up to \
+     255 ASCII characters"
+)]
+pub struct InvalidDid(pub String);
+
+impl FromStr for Did {
+    type Err = InvalidDid;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.starts_with("did:") {
+            return Err(InvalidDid(s.to_owned()));
+        }
+        let ascii = AsciiString::from_ascii(s).map_err(|_| InvalidDid(s.to_owned()))?;
+        Confined::try_from(ascii)
+            .map(Self)
+            .map_err(|_| InvalidDid(s.to_owned()))
+    }
+}
+
+impl Display for Did {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(self.0.as_str()) }
+}
+
+/// Identity claim issued by a registry to the owner of a decentralized
+/// identifier.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct IdentityClaim {
+    /// Identifier of the claimed identity.
+    pub did: Did,
+    /// Compressed secp256k1 public key controlling the identity.
+    pub public_key: [u8; 33],
+    /// SHA256 hash of the identity metadata document.
+    pub metadata_hash: [u8; 32],
+}
+impl StrictSerialize for IdentityClaim {}
+impl StrictDeserialize for IdentityClaim {}
+
+impl IdentityClaim {
+    fn dumb() -> Self {
+        Self {
+            did: Did::dumb(),
+            public_key: [2u8; 33],
+            metadata_hash: [0u8; 32],
+        }
+    }
+}
+
+pub fn identity() -> Iface {
+    let types = StandardTypes::with(Rgb22::stl());
+    Iface {
+        version: VerNo::V1,
+        name: tn!("DecentralizedIdentity"),
+        inherits: none!(),
+        developer: Identity::from(LNPBP_IDENTITY),
+        timestamp: 1713343888,
+        metadata: none!(),
+        global_state: tiny_bmap! {
+            fname!("registryId") => GlobalIface::required(types.get("RGBSchemata.Did")),
+            fname!("registryKey") => GlobalIface::required(types.get("RGBSchemata.IssuerKey")),
+        },
+        assignments: tiny_bmap! {
+            fname!("identityClaim") => AssignIface::private(
+                OwnedIface::Data(types.get("RGBSchemata.IdentityClaim")),
+                Req::NoneOrMore,
+            ),
+            fname!("registryAuthority") => AssignIface::public(OwnedIface::Rights, Req::Required),
+        },
+        valencies: none!(),
+        genesis: GenesisIface {
+            modifier: Modifier::Final,
+            metadata: none!(),
+            globals: tiny_bmap! {
+                fname!("registryId") => Occurrences::Once,
+                fname!("registryKey") => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                fname!("identityClaim") => Occurrences::NoneOrMore,
+                fname!("registryAuthority") => Occurrences::Once,
+            },
+            valencies: none!(),
+            errors: none!(),
+        },
+        transitions: tiny_bmap! {
+            fname!("issueIdentity") => TransitionIface {
+                modifier: Modifier::Final,
+                optional: false,
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    fname!("registryAuthority") => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    fname!("identityClaim") => Occurrences::OnceOrMore,
+                    fname!("registryAuthority") => Occurrences::Once,
+                },
+                valencies: none!(),
+                errors: none!(),
+                default_assignment: Some(fname!("identityClaim")),
+            },
+            fname!("revokeIdentity") => TransitionIface {
+                modifier: Modifier::Final,
+                optional: false,
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    fname!("identityClaim") => Occurrences::OnceOrMore,
+                    fname!("registryAuthority") => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    fname!("registryAuthority") => Occurrences::Once,
+                },
+                valencies: none!(),
+                errors: none!(),
+                default_assignment: None,
+            },
+            fname!("updateMetadata") => TransitionIface {
+                modifier: Modifier::Final,
+                optional: false,
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    fname!("identityClaim") => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    fname!("identityClaim") => Occurrences::Once,
+                },
+                valencies: none!(),
+                errors: tiny_bset! {
+                    vname!("immutableClaim")
+                },
+                default_assignment: Some(fname!("identityClaim")),
+            },
+        },
+        extensions: none!(),
+        errors: tiny_bmap! {
+            vname!("immutableClaim")
+                => tiny_s!("metadata update changes the identifier or the key of the claim"),
+        },
+        default_operation: None,
+    }
+}
+
+/// RGB22 decentralized identity interface class.
+#[derive(Wrapper, WrapperMut, Clone, Eq, PartialEq, Debug)]
+#[wrapper(Deref)]
+#[wrapper_mut(DerefMut)]
+pub struct Rgb22(ContractIface);
+
+impl From<ContractIface> for Rgb22 {
+    fn from(iface: ContractIface) -> Self {
+        if !Rgb22::IFACE_IDS.contains(&iface.iface.iface_id) {
+            panic!("the provided interface is not RGB22 interface");
+        }
+        Self(iface)
+    }
+}
+
+impl IfaceClass for Rgb22 {
+    const IFACE_NAME: &'static str = LIB_NAME_RGB22;
+    const IFACE_IDS: &'static [IfaceId] = &[RGB22_IFACE_ID];
+
+    type Features = ();
+    type Info = ();
+
+    fn iface(_features: Self::Features) -> Iface { identity() }
+
+    fn iface_id(_features: Self::Features) -> IfaceId { RGB22_IFACE_ID }
+
+    fn stl() -> TypeLib { rgb_schemata_stl() }
+
+    fn info(&self) -> Self::Info {}
+}
+
+impl Rgb22 {
+    pub fn identity_claims<'c>(
+        &'c self,
+        filter: impl OutpointFilter + 'c,
+    ) -> impl Iterator<Item = DataAllocation> + 'c {
+        self.0
+            .data("identityClaim", filter)
+            .expect("RGB22 interface requires `identityClaim` state")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn iface_id() {
+        let iface_id = identity().iface_id();
+        assert_eq!(Rgb22::IFACE_IDS[0], iface_id);
+    }
+
+    #[test]
+    fn iface_check() {
+        if let Err(err) = Rgb22::iface(()).check() {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid RGB22 interface definition");
+        }
+    }
+
+    #[test]
+    fn did_parsing() {
+        assert_eq!(Did::from_str("did:rgb:alice").unwrap().to_string(), "did:rgb:alice");
+        assert!(Did::from_str("rgb:alice").is_err());
+        assert!(Did::from_str("did:rgb:алиса").is_err());
+    }
+}
@@ -0,0 +1,243 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON-friendly representation of schemata.
+
+use std::collections::BTreeMap;
+
+use aluvm::library::LibSite;
+use amplify::Wrapper;
+use rgbstd::interface::IfaceImpl;
+use rgbstd::schema::{GlobalStateSchema, Occurrences, OwnedStateSchema, Schema};
+use rgbstd::{AssignmentType, GlobalStateType, MetaType, TransitionType, ValencyType};
+use serde::{Deserialize, Serialize};
+use strict_types::SemId;
+
+/// Representation of a [`Schema`] suitable for JSON serialization.
+///
+/// State and operation types are keyed by their interface names when the schema
+/// is converted with [`SchemaJson::from_with_impl`]; types which are not named
+/// by the interface implementation, as well as all types in schemata converted
+/// with [`From<Schema>`], are keyed by their numeric ids.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaJson {
+    pub schema_id: String,
+    pub name: String,
+    pub timestamp: i64,
+    pub developer: String,
+    pub meta_types: BTreeMap<String, SemId>,
+    pub global_types: BTreeMap<String, GlobalStateSchema>,
+    pub owned_types: BTreeMap<String, OwnedStateSchema>,
+    pub valency_types: Vec<String>,
+    pub genesis: OpSchemaJson,
+    pub extensions: BTreeMap<String, OpSchemaJson>,
+    pub transitions: BTreeMap<String, OpSchemaJson>,
+}
+
+/// Representation of a genesis, state extension or state transition schema.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpSchemaJson {
+    pub metadata: Vec<String>,
+    pub globals: BTreeMap<String, Occurrences>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub inputs: BTreeMap<String, Occurrences>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redeems: Vec<String>,
+    pub assignments: BTreeMap<String, Occurrences>,
+    pub valencies: Vec<String>,
+    /// Validation script entry point in `<offset>@<lib id>` form.
+    pub validator: Option<String>,
+}
+
+struct Names<'a>(Option<&'a IfaceImpl>);
+
+impl Names<'_> {
+    fn meta(&self, id: MetaType) -> String {
+        self.0
+            .and_then(|iimpl| iimpl.meta_name(id))
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| id.to_inner().to_string())
+    }
+
+    fn global(&self, id: GlobalStateType) -> String {
+        self.0
+            .and_then(|iimpl| iimpl.global_name(id))
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| id.to_inner().to_string())
+    }
+
+    fn assignment(&self, id: AssignmentType) -> String {
+        self.0
+            .and_then(|iimpl| iimpl.assignment_name(id))
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| id.to_inner().to_string())
+    }
+
+    fn valency(&self, id: ValencyType) -> String {
+        self.0
+            .and_then(|iimpl| iimpl.valency_name(id))
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| id.to_inner().to_string())
+    }
+
+    fn transition(&self, id: TransitionType) -> String {
+        self.0
+            .and_then(|iimpl| iimpl.transition_name(id))
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| id.to_inner().to_string())
+    }
+
+    fn op<'b>(
+        &self,
+        metadata: impl IntoIterator<Item = &'b MetaType>,
+        globals: impl IntoIterator<Item = (&'b GlobalStateType, &'b Occurrences)>,
+        assignments: impl IntoIterator<Item = (&'b AssignmentType, &'b Occurrences)>,
+        valencies: impl IntoIterator<Item = &'b ValencyType>,
+        validator: Option<&LibSite>,
+    ) -> OpSchemaJson {
+        OpSchemaJson {
+            metadata: metadata.into_iter().map(|id| self.meta(*id)).collect(),
+            globals: globals
+                .into_iter()
+                .map(|(id, occ)| (self.global(*id), occ.clone()))
+                .collect(),
+            inputs: none!(),
+            redeems: none!(),
+            assignments: assignments
+                .into_iter()
+                .map(|(id, occ)| (self.assignment(*id), occ.clone()))
+                .collect(),
+            valencies: valencies.into_iter().map(|id| self.valency(*id)).collect(),
+            validator: validator.map(|site| format!("{:#06X}@{}", site.pos, site.lib)),
+        }
+    }
+
+    fn schema(&self, schema: &Schema) -> SchemaJson {
+        let genesis = &schema.genesis;
+        SchemaJson {
+            schema_id: schema.schema_id().to_string(),
+            name: schema.name.to_string(),
+            timestamp: schema.timestamp,
+            developer: schema.developer.to_string(),
+            meta_types: schema
+                .meta_types
+                .iter()
+                .map(|(id, sem_id)| (self.meta(*id), *sem_id))
+                .collect(),
+            global_types: schema
+                .global_types
+                .iter()
+                .map(|(id, state)| (self.global(*id), state.clone()))
+                .collect(),
+            owned_types: schema
+                .owned_types
+                .iter()
+                .map(|(id, state)| (self.assignment(*id), *state))
+                .collect(),
+            valency_types: schema
+                .valency_types
+                .iter()
+                .map(|id| self.valency(*id))
+                .collect(),
+            genesis: self.op(
+                &genesis.metadata,
+                &genesis.globals,
+                &genesis.assignments,
+                &genesis.valencies,
+                genesis.validator.as_ref(),
+            ),
+            extensions: schema
+                .extensions
+                .iter()
+                .map(|(id, ext)| {
+                    let mut op = self.op(
+                        &ext.metadata,
+                        &ext.globals,
+                        &ext.assignments,
+                        &ext.valencies,
+                        ext.validator.as_ref(),
+                    );
+                    op.redeems = ext.redeems.iter().map(|id| self.valency(*id)).collect();
+                    (id.to_inner().to_string(), op)
+                })
+                .collect(),
+            transitions: schema
+                .transitions
+                .iter()
+                .map(|(id, ts)| {
+                    let mut op = self.op(
+                        &ts.metadata,
+                        &ts.globals,
+                        &ts.assignments,
+                        &ts.valencies,
+                        ts.validator.as_ref(),
+                    );
+                    op.inputs = ts
+                        .inputs
+                        .iter()
+                        .map(|(id, occ)| (self.assignment(*id), occ.clone()))
+                        .collect();
+                    (self.transition(*id), op)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Schema> for SchemaJson {
+    fn from(schema: Schema) -> Self { Names(None).schema(&schema) }
+}
+
+impl SchemaJson {
+    /// Converts schema resolving type names via the provided interface
+    /// implementation.
+    pub fn from_with_impl(schema: &Schema, iface_impl: &IfaceImpl) -> SchemaJson {
+        Names(Some(iface_impl)).schema(schema)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ifaces::IssuerWrapper;
+
+    use super::*;
+    use crate::NonInflatableAsset;
+
+    #[test]
+    fn named_json() {
+        let schema = NonInflatableAsset::schema();
+        let json = SchemaJson::from_with_impl(&schema, &NonInflatableAsset::issue_impl());
+        assert!(json.transitions.contains_key("transfer"));
+        assert!(json.owned_types.contains_key("assetOwner"));
+        assert_eq!(json.transitions["transfer"].inputs["assetOwner"], Occurrences::OnceOrMore);
+
+        let value = serde_json::to_string(&json).unwrap();
+        let restored: SchemaJson = serde_json::from_str(&value).unwrap();
+        assert_eq!(restored, json);
+
+        let unnamed = SchemaJson::from(schema);
+        assert!(unnamed
+            .transitions
+            .contains_key(&crate::TS_TRANSFER.to_inner().to_string()));
+    }
+}
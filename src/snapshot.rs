@@ -0,0 +1,186 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Snapshots of the type systems used by the schemata at their publication
+//! time.
+//!
+//! Schema ids commit to the semantic ids of the state types, which are derived
+//! from the strict type libraries of the dependencies. A dependency update
+//! changing these libraries would thus silently change the schema ids; type
+//! system snapshots allow detecting such drift.
+//!
+//! Snapshots are kept in the `stl` directory and must be re-captured with
+//! [`TypeSystemSnapshot::capture`] only when the schema is changed
+//! intentionally.
+
+use std::collections::BTreeSet;
+
+use amplify::confinement::{Confined, U24};
+use ifaces::IssuerWrapper;
+use rgbstd::schema::{OwnedStateSchema, Schema};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::{SemId, TypeSysId, TypeSystem};
+
+use crate::NonInflatableAsset;
+
+/// Type system of a schema frozen at its publication time, reduced to the types
+/// used by the schema state.
+#[derive(Copy, Clone, Debug)]
+pub struct TypeSystemSnapshot {
+    /// Name of the schema using the type system.
+    pub name: &'static str,
+    /// Strict-encoded type system, as produced by
+    /// [`TypeSystemSnapshot::capture`].
+    pub data: &'static [u8],
+    /// Constructor of the type system from the current strict type libraries.
+    pub current: fn() -> TypeSystem,
+}
+
+/// Type system used by [`NonInflatableAsset`] at its publication time.
+pub const NIA_TYPES_SNAPSHOT: TypeSystemSnapshot = TypeSystemSnapshot {
+    name: "NonInflatableAsset",
+    data: include_bytes!("../stl/NonInflatableAsset.sty"),
+    current: || schema_types(&NonInflatableAsset::schema(), &NonInflatableAsset::types()),
+};
+
+/// Drift of a type system from its [`TypeSystemSnapshot`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum DriftError {
+    /// type system snapshot of {name} can't be decoded. Details: {reason}
+    InvalidSnapshot { name: &'static str, reason: String },
+
+    /// type system has changed from {expected} to {found}, which changes the
+    /// schema id.
+    Drift {
+        expected: TypeSysId,
+        found: TypeSysId,
+        /// Types present in the snapshot and absent from the current type
+        /// system.
+        removed: BTreeSet<SemId>,
+        /// Types absent from the snapshot and present in the current type
+        /// system.
+        added: BTreeSet<SemId>,
+    },
+}
+
+/// Extracts types used by the `schema` state from `types`, including all their
+/// dependencies.
+///
+/// # Panics
+///
+/// If `types` doesn't contain some of the types used by the schema.
+pub fn schema_types(schema: &Schema, types: &TypeSystem) -> TypeSystem {
+    let ids = schema
+        .meta_types
+        .values()
+        .copied()
+        .chain(schema.global_types.values().map(|state| state.sem_id))
+        .chain(schema.owned_types.values().filter_map(|state| match state {
+            OwnedStateSchema::Structured(sem_id) => Some(*sem_id),
+            _ => None,
+        }));
+    types
+        .extract(ids)
+        .expect("type system doesn't contain types used by the schema")
+}
+
+impl TypeSystemSnapshot {
+    /// Serializes `types` into snapshot data.
+    pub fn capture(types: &TypeSystem) -> Vec<u8> {
+        types
+            .to_strict_serialized::<U24>()
+            .expect("type system exceeds maximum size")
+            .into_inner()
+    }
+
+    /// Decodes type system stored in the snapshot.
+    pub fn type_system(&self) -> Result<TypeSystem, DriftError> {
+        let data =
+            Confined::try_from(self.data.to_vec()).map_err(|_| DriftError::InvalidSnapshot {
+                name: self.name,
+                reason: s!("snapshot exceeds maximum size"),
+            })?;
+        TypeSystem::from_strict_serialized::<U24>(data).map_err(|err| DriftError::InvalidSnapshot {
+            name: self.name,
+            reason: err.to_string(),
+        })
+    }
+}
+
+/// Checks that the type system built from the current strict type libraries
+/// matches the `snapshot`.
+pub fn verify_against_current_stl(snapshot: &TypeSystemSnapshot) -> Result<(), DriftError> {
+    let frozen = snapshot.type_system()?;
+    let current = (snapshot.current)();
+    if frozen.id() == current.id() {
+        return Ok(());
+    }
+    let frozen_ids = frozen.keys().copied().collect::<BTreeSet<_>>();
+    let current_ids = current.keys().copied().collect::<BTreeSet<_>>();
+    Err(DriftError::Drift {
+        expected: frozen.id(),
+        found: current.id(),
+        removed: frozen_ids.difference(&current_ids).copied().collect(),
+        added: current_ids.difference(&frozen_ids).copied().collect(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::stl::StandardTypes;
+
+    use super::*;
+
+    #[test]
+    fn nia_snapshot() {
+        assert_eq!(verify_against_current_stl(&NIA_TYPES_SNAPSHOT), Ok(()));
+    }
+
+    #[test]
+    fn drift() {
+        let drifted = TypeSystemSnapshot {
+            current: || {
+                let schema = NonInflatableAsset::schema();
+                let mut types = schema_types(&schema, &NonInflatableAsset::types());
+                types.extend(StandardTypes::new().type_system()).unwrap();
+                types
+            },
+            ..NIA_TYPES_SNAPSHOT
+        };
+        match verify_against_current_stl(&drifted) {
+            Err(DriftError::Drift { removed, added, .. }) => {
+                assert!(removed.is_empty());
+                assert!(!added.is_empty());
+            }
+            res => panic!("drift not detected: {res:?}"),
+        }
+
+        let corrupted = TypeSystemSnapshot {
+            data: &[0xFF; 4],
+            ..NIA_TYPES_SNAPSHOT
+        };
+        assert!(matches!(
+            verify_against_current_stl(&corrupted),
+            Err(DriftError::InvalidSnapshot { .. })
+        ));
+    }
+}
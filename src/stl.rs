@@ -0,0 +1,552 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict type library with data types used by the structured state of the
+//! domain-specific schemata provided by this crate.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::ascii::AsciiString;
+use amplify::confinement::{self, Confined};
+use bp::CompressedPk;
+use rgbstd::stl::{bp_tx_stl, rgb_contract_stl, Ticker};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::stl::std_stl;
+use strict_types::{CompileError, LibBuilder, TypeLib};
+
+use crate::basket::{BasketAllocation, BasketComponent};
+use crate::bond::BondNote;
+use crate::bridge::{LockTxProof, RedemptionRequest, SourceChain};
+use crate::carbon::{RetirementReceipt, VintageYear};
+use crate::certificate::Certificate;
+use crate::cia::{ScheduleIndex, SupplySchedule};
+use crate::dividend::{ClaimReceipt, DividendCredit};
+use crate::gaming::{GameItemData, ItemLevel};
+use crate::governance::{Ballot, Proposal, VoteDelegation};
+use crate::licensing::License;
+use crate::locking::TokenLock;
+use crate::loyalty::RedemptionReceipt;
+use crate::multisig::{RequiredSigs, SignerRight};
+use crate::music_rights::{Isrc, RightsType, RoyaltyClaim};
+use crate::oracle::OracleAttestation;
+use crate::partially_blinded::{RevealOpening, RevealedAsset};
+use crate::prediction_market::{MarketOutcome, QuestionHash};
+use crate::property_rights::{KycAttestation, KycRequired, PropertyValue};
+use crate::rekey::RekeyProof;
+use crate::reputation::{Reputation, ReputationScore};
+use crate::rgb22::{Did, IdentityClaim};
+use crate::synthetic::{OraclePrice, PriceAttestation, UnderlyingAssetName};
+use crate::tranche::{TrancheIndex, TrancheRight, TrancheSizes};
+use crate::whitelist::{WhitelistProof, WhitelistRoot, WhitelistVersion};
+
+/// Name of the strict type library with the schemata data types.
+pub const LIB_NAME_RGB_SCHEMATA: &str = "RGBSchemata";
+
+/// Rate expressed in basis points (1/100 of a percent).
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct BasisPoints(u16);
+impl StrictSerialize for BasisPoints {}
+impl StrictDeserialize for BasisPoints {}
+
+/// Bitcoin block height.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct BlockHeight(u32);
+impl StrictSerialize for BlockHeight {}
+impl StrictDeserialize for BlockHeight {}
+
+/// Duration measured in a number of bitcoin blocks.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct BlockCount(u32);
+impl StrictSerialize for BlockCount {}
+impl StrictDeserialize for BlockCount {}
+
+/// Compressed secp256k1 public key of a contract issuer.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct IssuerKey([u8; 33]);
+impl StrictSerialize for IssuerKey {}
+impl StrictDeserialize for IssuerKey {}
+
+impl IssuerKey {
+    fn dumb() -> Self { Self([2u8; 33]) }
+}
+
+impl From<CompressedPk> for IssuerKey {
+    fn from(pk: CompressedPk) -> Self { Self(pk.to_byte_array()) }
+}
+
+/// Attestation of the reserves backing a custodial asset, published by an
+/// auditor.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct ReserveAttestation {
+    /// Identity of the auditor, like a hash of its public key.
+    pub auditor_identity: [u8; 32],
+    /// Commitment to the audited reserves.
+    pub reserve_commitment: [u8; 64],
+    /// Unix timestamp of the attestation.
+    pub timestamp: i64,
+}
+impl StrictSerialize for ReserveAttestation {}
+impl StrictDeserialize for ReserveAttestation {}
+
+impl ReserveAttestation {
+    fn dumb() -> Self {
+        Self {
+            auditor_identity: [0u8; 32],
+            reserve_commitment: [0u8; 64],
+            timestamp: 0,
+        }
+    }
+}
+
+/// URL of the legal agreement bound to a contract, like an HTTPS link,
+/// consisting of up to 256 ASCII characters.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct LegalTermsUrl(Confined<AsciiString, 1, 256>);
+impl StrictSerialize for LegalTermsUrl {}
+impl StrictDeserialize for LegalTermsUrl {}
+
+impl LegalTermsUrl {
+    fn dumb() -> Self { Self::from_str("https://example.com").expect("valid URL") }
+}
+
+/// Error parsing [`LegalTermsUrl`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display("invalid legal terms URL '{0}', which must consist of 1 to 256 ASCII characters")]
+pub struct InvalidLegalTermsUrl(pub String);
+
+impl FromStr for LegalTermsUrl {
+    type Err = InvalidLegalTermsUrl;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ascii = AsciiString::from_ascii(s).map_err(|_| InvalidLegalTermsUrl(s.to_owned()))?;
+        Confined::try_from(ascii)
+            .map(Self)
+            .map_err(|_| InvalidLegalTermsUrl(s.to_owned()))
+    }
+}
+
+impl Display for LegalTermsUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(self.0.as_str()) }
+}
+
+/// SHA256 hash of the legal agreement document bound to a contract.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct LegalTermsHash([u8; 32]);
+impl StrictSerialize for LegalTermsHash {}
+impl StrictDeserialize for LegalTermsHash {}
+
+impl LegalTermsHash {
+    /// Computes hash of the legal agreement `document`.
+    pub fn with_document(document: impl AsRef<[u8]>) -> Self {
+        use sha2::{Digest, Sha256};
+        Self(Sha256::digest(document).into())
+    }
+}
+
+/// Plain-language description of an asset, consisting of up to 1024 ASCII
+/// characters.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct AssetDescription(Confined<AsciiString, 1, 1024>);
+impl StrictSerialize for AssetDescription {}
+impl StrictDeserialize for AssetDescription {}
+
+impl AssetDescription {
+    fn dumb() -> Self { Self::from_str("Asset").expect("valid description") }
+}
+
+/// Error parsing [`AssetDescription`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display("invalid asset description, which must consist of 1 to 1024 ASCII characters")]
+pub struct InvalidAssetDescription;
+
+impl FromStr for AssetDescription {
+    type Err = InvalidAssetDescription;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ascii = AsciiString::from_ascii(s).map_err(|_| InvalidAssetDescription)?;
+        Confined::try_from(ascii)
+            .map(Self)
+            .map_err(|_| InvalidAssetDescription)
+    }
+}
+
+impl Display for AssetDescription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(self.0.as_str()) }
+}
+
+/// Checks whether `s` is an HTTP or HTTPS URL which can be committed to a
+/// contract as [`WebsiteUrl`]. AluVM doesn't validate URLs, thus the check must
+/// be performed by the callers constructing genesis data.
+pub fn validate_url(s: &str) -> bool {
+    let rest = s
+        .strip_prefix("https://")
+        .or_else(|| s.strip_prefix("http://"));
+    matches!(rest, Some(rest) if !rest.is_empty()) && s.len() <= 256 && s.is_ascii()
+}
+
+/// HTTP or HTTPS URL of the issuer website, consisting of up to 256 ASCII
+/// characters.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct WebsiteUrl(Confined<AsciiString, 1, 256>);
+impl StrictSerialize for WebsiteUrl {}
+impl StrictDeserialize for WebsiteUrl {}
+
+impl WebsiteUrl {
+    fn dumb() -> Self { Self::from_str("https://example.com").expect("valid URL") }
+}
+
+/// Error parsing [`WebsiteUrl`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display("invalid website URL '{0}', which must be HTTP or HTTPS URL of up to 256 characters")]
+pub struct InvalidWebsiteUrl(pub String);
+
+impl FromStr for WebsiteUrl {
+    type Err = InvalidWebsiteUrl;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !validate_url(s) {
+            return Err(InvalidWebsiteUrl(s.to_owned()));
+        }
+        let ascii = AsciiString::from_ascii(s).map_err(|_| InvalidWebsiteUrl(s.to_owned()))?;
+        Confined::try_from(ascii)
+            .map(Self)
+            .map_err(|_| InvalidWebsiteUrl(s.to_owned()))
+    }
+}
+
+impl Display for WebsiteUrl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(self.0.as_str()) }
+}
+
+/// MIME type of an asset icon, like `image/svg+xml`, consisting of up to 32
+/// ASCII characters.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct IconMime(Confined<AsciiString, 1, 32>);
+impl StrictSerialize for IconMime {}
+impl StrictDeserialize for IconMime {}
+
+impl IconMime {
+    fn dumb() -> Self { Self::from_str("image/png").expect("valid MIME type") }
+}
+
+/// Error parsing [`IconMime`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display("invalid icon MIME type '{0}', which must consist of 1 to 32 ASCII characters")]
+pub struct InvalidIconMime(pub String);
+
+impl FromStr for IconMime {
+    type Err = InvalidIconMime;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ascii = AsciiString::from_ascii(s).map_err(|_| InvalidIconMime(s.to_owned()))?;
+        Confined::try_from(ascii)
+            .map(Self)
+            .map_err(|_| InvalidIconMime(s.to_owned()))
+    }
+}
+
+impl Display for IconMime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(self.0.as_str()) }
+}
+
+/// Maximum size of [`IconData`]. Global state data is limited to 65535 bytes,
+/// which must also hold the 16-bit length prefix of the icon.
+pub const MAX_ICON_DATA_LEN: usize = u16::MAX as usize - 2;
+
+/// Raw data of an asset icon, stored on-chain to let wallets display it without
+/// external calls.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self(confined_vec![0]))]
+pub struct IconData(Confined<Vec<u8>, 1, MAX_ICON_DATA_LEN>);
+impl StrictSerialize for IconData {}
+impl StrictDeserialize for IconData {}
+
+impl TryFrom<Vec<u8>> for IconData {
+    type Error = confinement::Error;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> { Confined::try_from(data).map(Self) }
+}
+
+/// Compliance requirements of a security token, declared by its issuer.
+///
+/// The flags are informational: they are not enforced by the contract
+/// validation, and wallets must enforce them off-chain.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Hash, Debug, Default, From)]
+#[wrapper(BitOps)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct ComplianceFlags(u8);
+impl StrictSerialize for ComplianceFlags {}
+impl StrictDeserialize for ComplianceFlags {}
+
+impl ComplianceFlags {
+    /// No compliance requirements.
+    pub const NONE: Self = ComplianceFlags(0);
+    /// Asset may be held only by accredited investors.
+    pub const ACCREDITED_INVESTOR_ONLY: Self = ComplianceFlags(0x01);
+    /// Holders must pass know-your-customer checks.
+    pub const KYC_REQUIRED: Self = ComplianceFlags(0x02);
+    /// Transfers are subject to anti-money laundering checks.
+    pub const AML_REQUIRED: Self = ComplianceFlags(0x04);
+    /// Asset can't be held by residents of restricted jurisdictions.
+    pub const RESTRICTED_JURISDICTIONS: Self = ComplianceFlags(0x08);
+
+    /// Checks whether all flags from `other` are set.
+    pub const fn contains(self, other: Self) -> bool { self.0 & other.0 == other.0 }
+}
+
+/// Asset ticker kept in [`GS_TICKER`](crate::GS_TICKER) global state.
+///
+/// `RGBContract.Ticker` has no strict serialization, thus it can't be added to
+/// a contract directly. The wrapper is serialized exactly as the wrapped
+/// ticker.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct AssetTicker(Ticker);
+impl StrictSerialize for AssetTicker {}
+impl StrictDeserialize for AssetTicker {}
+
+/// Entry of the burn log ([`GS_BURN_LOG`](crate::GS_BURN_LOG)), appended by
+/// each burn operation.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct BurnLogEntry {
+    /// Burned amount, which must match the burned supply reported by the
+    /// operation.
+    pub amount: u64,
+    /// Hash identifying the burn, like SHA256 of the burned input outpoints.
+    /// The id of the burn operation itself can't be used, since it commits
+    /// to the entry.
+    pub transition_id_hash: [u8; 32],
+    /// Unix timestamp of the burn, which is not verified by the contract
+    /// validation.
+    pub timestamp: i64,
+}
+impl StrictSerialize for BurnLogEntry {}
+impl StrictDeserialize for BurnLogEntry {}
+
+/// Entry of the proof of burn log
+/// ([`GS_PROOF_OF_BURN`](crate::GS_PROOF_OF_BURN)), referencing the Bitcoin
+/// transaction output provably destroying the burned tokens.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct ProofOfBurnEntry {
+    /// Burned amount, which must match the burned supply reported by the
+    /// operation.
+    pub burned_amount: u64,
+    /// Id of the transaction anchoring the burn.
+    pub anchor_txid: [u8; 32],
+    /// Number of the unspendable `OP_RETURN` output of the anchor transaction.
+    pub vout: u32,
+    /// Hash of the `OP_RETURN` output data.
+    pub opreturn_hash: [u8; 32],
+}
+impl StrictSerialize for ProofOfBurnEntry {}
+impl StrictDeserialize for ProofOfBurnEntry {}
+
+// `CompileError` is defined upstream
+#[allow(clippy::result_large_err)]
+fn _rgb_schemata_stl() -> Result<TypeLib, CompileError> {
+    LibBuilder::new(libname!(LIB_NAME_RGB_SCHEMATA), tiny_bset! {
+        std_stl().to_dependency(),
+        bp_tx_stl().to_dependency(),
+        rgb_contract_stl().to_dependency()
+    })
+    .transpile::<BasisPoints>()
+    .transpile::<BlockHeight>()
+    .transpile::<BlockCount>()
+    .transpile::<IssuerKey>()
+    .transpile::<ReserveAttestation>()
+    .transpile::<LegalTermsUrl>()
+    .transpile::<LegalTermsHash>()
+    .transpile::<ComplianceFlags>()
+    .transpile::<AssetDescription>()
+    .transpile::<WebsiteUrl>()
+    .transpile::<IconMime>()
+    .transpile::<IconData>()
+    .transpile::<BurnLogEntry>()
+    .transpile::<ProofOfBurnEntry>()
+    .transpile::<VoteDelegation>()
+    .transpile::<Proposal>()
+    .transpile::<Ballot>()
+    .transpile::<BondNote>()
+    .transpile::<RedemptionReceipt>()
+    .transpile::<VintageYear>()
+    .transpile::<RetirementReceipt>()
+    .transpile::<Isrc>()
+    .transpile::<RightsType>()
+    .transpile::<RoyaltyClaim>()
+    .transpile::<ItemLevel>()
+    .transpile::<GameItemData>()
+    .transpile::<MarketOutcome>()
+    .transpile::<QuestionHash>()
+    .transpile::<UnderlyingAssetName>()
+    .transpile::<OraclePrice>()
+    .transpile::<PriceAttestation>()
+    .transpile::<BasketComponent>()
+    .transpile::<BasketAllocation>()
+    .transpile::<Did>()
+    .transpile::<IdentityClaim>()
+    .transpile::<Certificate>()
+    .transpile::<RequiredSigs>()
+    .transpile::<SignerRight>()
+    .transpile::<SourceChain>()
+    .transpile::<LockTxProof>()
+    .transpile::<RedemptionRequest>()
+    .transpile::<ReputationScore>()
+    .transpile::<Reputation>()
+    .transpile::<TrancheIndex>()
+    .transpile::<TrancheSizes>()
+    .transpile::<TrancheRight>()
+    .transpile::<RekeyProof>()
+    .transpile::<SupplySchedule>()
+    .transpile::<ScheduleIndex>()
+    .transpile::<WhitelistRoot>()
+    .transpile::<WhitelistVersion>()
+    .transpile::<WhitelistProof>()
+    .transpile::<OracleAttestation>()
+    .transpile::<TokenLock>()
+    .transpile::<RevealedAsset>()
+    .transpile::<RevealOpening>()
+    .transpile::<DividendCredit>()
+    .transpile::<ClaimReceipt>()
+    .transpile::<PropertyValue>()
+    .transpile::<KycRequired>()
+    .transpile::<KycAttestation>()
+    .transpile::<License>()
+    .compile()
+}
+
+/// Compiles strict type library with the schemata data types.
+pub fn rgb_schemata_stl() -> TypeLib {
+    _rgb_schemata_stl().expect("invalid strict type RGBSchemata library")
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::stl::StandardTypes;
+
+    use super::*;
+
+    #[test]
+    fn type_system() {
+        let types = StandardTypes::with(rgb_schemata_stl());
+        types.get("RGBSchemata.BasisPoints");
+        types.get("RGBSchemata.BlockHeight");
+        types.get("RGBSchemata.BlockCount");
+        types.get("RGBSchemata.IssuerKey");
+        types.get("RGBSchemata.ReserveAttestation");
+        types.get("RGBSchemata.LegalTermsUrl");
+        types.get("RGBSchemata.LegalTermsHash");
+        types.get("RGBSchemata.ComplianceFlags");
+        types.get("RGBSchemata.AssetDescription");
+        types.get("RGBSchemata.WebsiteUrl");
+        types.get("RGBSchemata.IconMime");
+        types.get("RGBSchemata.IconData");
+        types.get("RGBSchemata.BurnLogEntry");
+        types.get("RGBSchemata.ProofOfBurnEntry");
+        types.get("RGBSchemata.VoteDelegation");
+        types.get("RGBSchemata.Proposal");
+        types.get("RGBSchemata.Ballot");
+        types.get("RGBSchemata.BondNote");
+        types.get("RGBSchemata.RedemptionReceipt");
+        types.get("RGBSchemata.VintageYear");
+        types.get("RGBSchemata.RetirementReceipt");
+        types.get("RGBSchemata.Isrc");
+        types.get("RGBSchemata.RightsType");
+        types.get("RGBSchemata.RoyaltyClaim");
+        types.get("RGBSchemata.ItemLevel");
+        types.get("RGBSchemata.GameItemData");
+        types.get("RGBSchemata.MarketOutcome");
+        types.get("RGBSchemata.QuestionHash");
+        types.get("RGBSchemata.UnderlyingAssetName");
+        types.get("RGBSchemata.OraclePrice");
+        types.get("RGBSchemata.PriceAttestation");
+        types.get("RGBSchemata.BasketComponent");
+        types.get("RGBSchemata.BasketAllocation");
+        types.get("RGBSchemata.Did");
+        types.get("RGBSchemata.IdentityClaim");
+        types.get("RGBSchemata.Certificate");
+        types.get("RGBSchemata.RequiredSigs");
+        types.get("RGBSchemata.SignerRight");
+        types.get("RGBSchemata.SourceChain");
+        types.get("RGBSchemata.LockTxProof");
+        types.get("RGBSchemata.RedemptionRequest");
+        types.get("RGBSchemata.ReputationScore");
+        types.get("RGBSchemata.Reputation");
+        types.get("RGBSchemata.TrancheIndex");
+        types.get("RGBSchemata.TrancheSizes");
+        types.get("RGBSchemata.TrancheRight");
+        types.get("RGBSchemata.RekeyProof");
+        types.get("RGBSchemata.ScheduleEntry");
+        types.get("RGBSchemata.SupplySchedule");
+        types.get("RGBSchemata.ScheduleIndex");
+        types.get("RGBSchemata.WhitelistRoot");
+        types.get("RGBSchemata.WhitelistVersion");
+        types.get("RGBSchemata.WhitelistProof");
+        types.get("RGBSchemata.OracleAttestation");
+        types.get("RGBSchemata.TokenLock");
+        types.get("RGBSchemata.RevealedAsset");
+        types.get("RGBSchemata.RevealOpening");
+        types.get("RGBSchemata.DividendCredit");
+        types.get("RGBSchemata.ClaimReceipt");
+        types.get("RGBSchemata.PropertyValue");
+        types.get("RGBSchemata.KycRequired");
+        types.get("RGBSchemata.KycAttestation");
+        types.get("RGBSchemata.License");
+    }
+}
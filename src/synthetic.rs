@@ -0,0 +1,565 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Synthetic asset schema implementing RGB20 fungible assets interface for
+//! assets tracking the price of an external underlying asset, like tokenized
+//! gold or synthetic bitcoin.
+//!
+//! The genesis commits to the name of the underlying price feed
+//! ([`GS_UNDERLYING_ASSET_NAME`]), the public key of the price oracle
+//! ([`GS_ORACLE_KEY`]), the initial price ([`GS_LAST_KNOWN_PRICE`]) and the
+//! maximum price move allowed by a single rebalance
+//! ([`GS_REBALANCE_TOLERANCE`]). The issuer holding the rebalance right
+//! ([`OS_REBALANCE_RIGHT`]) adjusts the supply of the synthetic asset with
+//! [`TS_REBALANCE`], which carries the oracle attestation of the new price
+//! ([`PriceAttestation`]) in its metadata.
+//!
+//! The script checks that the attested price is the new last known price and
+//! that it doesn't move from the previous attested price by more than the
+//! tolerance. Each rebalance repeats the tolerance in its global state, and
+//! wallets must check the oracle signature with [`PriceAttestation::verify`],
+//! as well as that the previous price of the attestation and the
+//! repeated tolerance match the contract state (see [consensus
+//! limitations](crate#consensus-limitations)).
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_JMP, INSTR_PUTA};
+use aluvm::isa::{Instr, MoveOp};
+use aluvm::library::{Lib, LibSite};
+use aluvm::reg::{Reg32, RegA};
+use amplify::ascii::AsciiString;
+use amplify::confinement::Confined;
+use amplify::Wrapper;
+use bp::secp256k1::{schnorr, Message, XOnlyPublicKey, SECP256K1};
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDM, INSTR_PCAS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, MetaType, TransitionType};
+use sha2::{Digest, Sha256};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, BlockHeight, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    IssuerKey, ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL,
+    GS_ORACLE_KEY, GS_TERMS, TS_TRANSFER,
+};
+
+/// Name of the price feed of the underlying asset, like "XAU/USD"
+/// ([`UnderlyingAssetName`]).
+pub const GS_UNDERLYING_ASSET_NAME: GlobalStateType = GlobalStateType::with(3700);
+/// Last price of the underlying asset attested by the oracle ([`OraclePrice`]).
+pub const GS_LAST_KNOWN_PRICE: GlobalStateType = GlobalStateType::with(3701);
+/// Maximum price move allowed by a single rebalance ([`crate::BasisPoints`]).
+pub const GS_REBALANCE_TOLERANCE: GlobalStateType = GlobalStateType::with(3702);
+
+/// Oracle attestation of the new price ([`PriceAttestation`]), provided by the
+/// rebalance.
+pub const MS_PRICE_ATTESTATION: MetaType = MetaType::with(3710);
+
+/// Fungible allocation of the synthetic asset (RGB20 `assetOwner`).
+pub const OS_SYNTHETIC: AssignmentType = AssignmentType::with(5600);
+/// Right of the issuer to rebalance the supply.
+pub const OS_REBALANCE_RIGHT: AssignmentType = AssignmentType::with(5601);
+
+pub const TS_REBALANCE: TransitionType = TransitionType::with(11700);
+
+/// Error raised when the attested price doesn't match the last known price, or
+/// moves by more than the rebalance tolerance.
+pub const ERRNO_INVALID_REBALANCE: u8 = 49;
+
+pub(crate) const FN_SYNTHETIC_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_SYNTHETIC_GENESIS_OFFSET: u16 = FN_SYNTHETIC_TRANSFER_OFFSET + 4 + 3 + 1 + 1;
+pub const FN_REBALANCE_OFFSET: u16 = FN_SYNTHETIC_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1 + 1;
+pub(crate) const FN_REBALANCE_UP_OFFSET: u16 =
+    FN_REBALANCE_OFFSET + 4 + 4 + 4 + 4 + 4 + 3 + 3 + 4 + 3 + 3 + 1 + 3 + 3 + 3 + 3;
+pub(crate) const FN_REBALANCE_BOUND_OFFSET: u16 = FN_REBALANCE_UP_OFFSET + 3 + 3;
+
+/// Name of the price feed tracked by a synthetic asset, consisting of 1 to 32
+/// ASCII characters.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct UnderlyingAssetName(Confined<AsciiString, 1, 32>);
+impl StrictSerialize for UnderlyingAssetName {}
+impl StrictDeserialize for UnderlyingAssetName {}
+
+impl UnderlyingAssetName {
+    fn dumb() -> Self { Self::from_str("XAU/USD").expect("valid name") }
+}
+
+/// Error parsing [`UnderlyingAssetName`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display("invalid underlying asset name '{0}', which must consist of 1 to 32 ASCII characters")]
+pub struct InvalidUnderlyingAssetName(pub String);
+
+impl FromStr for UnderlyingAssetName {
+    type Err = InvalidUnderlyingAssetName;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ascii =
+            AsciiString::from_ascii(s).map_err(|_| InvalidUnderlyingAssetName(s.to_owned()))?;
+        Confined::try_from(ascii)
+            .map(Self)
+            .map_err(|_| InvalidUnderlyingAssetName(s.to_owned()))
+    }
+}
+
+impl Display for UnderlyingAssetName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(self.0.as_str()) }
+}
+
+/// Price of the underlying asset as a fixed-point number with
+/// [`OraclePrice::DECIMALS`] decimal places.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct OraclePrice(u64);
+impl StrictSerialize for OraclePrice {}
+impl StrictDeserialize for OraclePrice {}
+
+impl OraclePrice {
+    /// Number of decimal places of the price.
+    pub const DECIMALS: u8 = 8;
+}
+
+/// Oracle attestation of the price move of the underlying asset.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct PriceAttestation {
+    /// Price attested by the previous rebalance or the genesis.
+    pub previous_price: OraclePrice,
+    /// New price of the underlying asset.
+    pub price: OraclePrice,
+    /// Height of the block at which the price was observed.
+    pub height: BlockHeight,
+    /// BIP-340 signature of the oracle key over [`PriceAttestation::message`].
+    pub signature: [u8; 64],
+}
+impl StrictSerialize for PriceAttestation {}
+impl StrictDeserialize for PriceAttestation {}
+
+impl PriceAttestation {
+    fn dumb() -> Self {
+        Self {
+            previous_price: OraclePrice::default(),
+            price: OraclePrice::default(),
+            height: BlockHeight::default(),
+            signature: [0u8; 64],
+        }
+    }
+
+    /// Computes the message signed by the oracle, which is SHA256 hash of the
+    /// strict-encoded attestation without the signature.
+    pub fn message(&self) -> [u8; 32] {
+        let data = self
+            .to_strict_serialized::<84>()
+            .expect("fixed-size attestation");
+        Sha256::digest(&data[..data.len() - 64]).into()
+    }
+
+    /// Verifies the signature of the `oracle` key over the attestation.
+    pub fn verify(&self, oracle: &IssuerKey) -> bool {
+        let Ok(key) = XOnlyPublicKey::from_slice(&oracle[1..]) else {
+            return false;
+        };
+        let Ok(signature) = schnorr::Signature::from_slice(&self.signature) else {
+            return false;
+        };
+        let message = Message::from_digest(self.message());
+        SECP256K1.verify_schnorr(&signature, &message, &key).is_ok()
+    }
+}
+
+/// Assembles AluVM library with validation scripts used by the synthetic asset
+/// schema.
+// `sub`, `mul` and `div` macro arms for float registers panic on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn synthetic_lib() -> Lib {
+    let mut code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_SYNTHETIC;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_SYNTHETIC;
+        test;
+        ret;
+
+        // SUBROUTINE Rebalance validation
+        put     a8[0],ERRNO_INVALID_REBALANCE;
+        put     a8[1],0;
+        put     a16[0],0;
+        put     a16[1],8;
+        // Read previous price into a64[0] and the new price into a64[1]
+        ldm     MS_PRICE_ATTESTATION,s16[0];
+        extr    s16[0],a64[0],a16[0];
+        extr    s16[0],a64[1],a16[1];
+        // Checking that the attested price is the new last known price
+        ldg     GS_LAST_KNOWN_PRICE,a8[1],s16[1];
+        extr    s16[1],a64[2],a16[0];
+        eq.n    a64[1],a64[2];
+        test;
+        // Computing the price move into a64[2]
+        gt.u    a64[1],a64[0];
+        jif     FN_REBALANCE_UP_OFFSET;
+        // a64[2] := a64[0] - a64[2]
+        sub.uc  a64[0],a64[2];
+        jmp     FN_REBALANCE_BOUND_OFFSET;
+        // Price goes up: a64[2] := a64[1] - previous price
+        extr    s16[0],a64[2],a16[0];
+        sub.uc  a64[1],a64[2];
+        // Reading the tolerance in basis points into a16[2]
+        ldg     GS_REBALANCE_TOLERANCE,a8[1],s16[2];
+        extr    s16[2],a16[2],a16[0];
+    };
+    // NB: `rgbasm!` can't copy between registers of different size, thus the
+    // instruction is constructed directly
+    code.push(Instr::Move(MoveOp::CpyA(RegA::A16, Reg32::Reg2, RegA::A64, Reg32::Reg4)));
+    code.extend(rgbasm! {
+        // Checking that the move doesn't exceed previous price / 10000 * tolerance
+        put     a64[5],10000;
+        div.uc  a64[0],a64[5];
+        mul.uc  a64[4],a64[5];
+        gt.u    a64[2],a64[5];
+        inv     st0;
+        test;
+        ret;
+    });
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong synthetic asset script")
+}
+
+fn synthetic_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = synthetic_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_SYNTHETIC_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_SYNTHETIC_TRANSFER_OFFSET + 4), OS_SYNTHETIC.to_inner());
+    assert_eq!(code[FN_SYNTHETIC_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_SYNTHETIC_GENESIS_OFFSET + 12), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_SYNTHETIC_GENESIS_OFFSET as usize + 19], INSTR_PCAS);
+    assert_eq!(code[FN_REBALANCE_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_REBALANCE_OFFSET as usize + 16], INSTR_LDM);
+    assert_eq!(state_arg(FN_REBALANCE_OFFSET + 16), MS_PRICE_ATTESTATION.to_inner());
+    assert_eq!(code[FN_REBALANCE_OFFSET as usize + 26], INSTR_LDG);
+    assert_eq!(state_arg(FN_REBALANCE_OFFSET + 26), GS_LAST_KNOWN_PRICE.to_inner());
+    assert_eq!(code[FN_REBALANCE_UP_OFFSET as usize - 3], INSTR_JMP);
+    assert_eq!(code[FN_REBALANCE_UP_OFFSET as usize], INSTR_EXTR);
+    assert_eq!(code[FN_REBALANCE_BOUND_OFFSET as usize], INSTR_LDG);
+    assert_eq!(state_arg(FN_REBALANCE_BOUND_OFFSET), GS_REBALANCE_TOLERANCE.to_inner());
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("SyntheticAsset"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: tiny_bmap! {
+            MS_PRICE_ATTESTATION => types.get("RGBSchemata.PriceAttestation"),
+        },
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_UNDERLYING_ASSET_NAME =>
+                GlobalStateSchema::once(types.get("RGBSchemata.UnderlyingAssetName")),
+            GS_ORACLE_KEY => GlobalStateSchema::once(types.get("RGBSchemata.IssuerKey")),
+            GS_LAST_KNOWN_PRICE => GlobalStateSchema::many(types.get("RGBSchemata.OraclePrice")),
+            GS_REBALANCE_TOLERANCE =>
+                GlobalStateSchema::many(types.get("RGBSchemata.BasisPoints")),
+        },
+        owned_types: tiny_bmap! {
+            OS_SYNTHETIC => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_REBALANCE_RIGHT => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_UNDERLYING_ASSET_NAME => Occurrences::Once,
+                GS_ORACLE_KEY => Occurrences::Once,
+                GS_LAST_KNOWN_PRICE => Occurrences::Once,
+                GS_REBALANCE_TOLERANCE => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_SYNTHETIC => Occurrences::OnceOrMore,
+                OS_REBALANCE_RIGHT => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_SYNTHETIC_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_SYNTHETIC => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_SYNTHETIC => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_SYNTHETIC_TRANSFER_OFFSET, alu_id))
+            },
+            TS_REBALANCE => TransitionSchema {
+                metadata: tiny_bset![MS_PRICE_ATTESTATION],
+                globals: tiny_bmap! {
+                    GS_LAST_KNOWN_PRICE => Occurrences::Once,
+                    GS_REBALANCE_TOLERANCE => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_SYNTHETIC => Occurrences::NoneOrMore,
+                    OS_REBALANCE_RIGHT => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_SYNTHETIC => Occurrences::NoneOrMore,
+                    OS_REBALANCE_RIGHT => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_REBALANCE_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn synthetic_rgb20() -> IfaceImpl {
+    let schema = synthetic_schema();
+    let iface = Rgb20::iface(SyntheticAssetSchema::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: tiny_bset! {
+            NamedField::with(MS_PRICE_ATTESTATION, fname!("priceAttestation")),
+        },
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_UNDERLYING_ASSET_NAME, fname!("underlyingAssetName")),
+            NamedField::with(GS_ORACLE_KEY, fname!("oracleKey")),
+            NamedField::with(GS_LAST_KNOWN_PRICE, fname!("lastKnownPrice")),
+            NamedField::with(GS_REBALANCE_TOLERANCE, fname!("rebalanceTolerance")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_SYNTHETIC, fname!("assetOwner")),
+            NamedField::with(OS_REBALANCE_RIGHT, fname!("rebalanceRight")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_REBALANCE, fname!("rebalance")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Synthetic asset tracking the price of an external underlying asset.
+pub struct SyntheticAssetSchema;
+
+impl IssuerWrapper for SyntheticAssetSchema {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { synthetic_schema() }
+    fn issue_impl() -> IfaceImpl { synthetic_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = synthetic_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bp::secp256k1::Keypair;
+    use rgbstd::containers::Contract;
+
+    use super::*;
+    use crate::test_helpers::{
+        add_metadata, fungible_genesis, graph_seal, issuer, script_errno, seal, spec,
+        spend_genesis, validate_transition,
+    };
+    use crate::BasisPoints;
+
+    fn attestation(previous_price: u64, price: u64) -> PriceAttestation {
+        PriceAttestation {
+            previous_price: OraclePrice::from(previous_price),
+            price: OraclePrice::from(price),
+            height: BlockHeight::from(850_000),
+            signature: [0u8; 64],
+        }
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(SyntheticAssetSchema::FEATURES);
+        if let Err(err) = synthetic_rgb20().check(&iface, &synthetic_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid synthetic asset RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn attestation_signature() {
+        let keypair = Keypair::from_seckey_slice(SECP256K1, &[1u8; 32]).unwrap();
+        let oracle = IssuerKey::from(keypair.public_key().serialize());
+        let mut attestation = attestation(100_000, 101_000);
+        let message = Message::from_digest(attestation.message());
+        attestation.signature = *SECP256K1
+            .sign_schnorr_no_aux_rand(&message, &keypair)
+            .as_ref();
+        assert!(attestation.verify(&oracle));
+
+        let other = Keypair::from_seckey_slice(SECP256K1, &[2u8; 32]).unwrap();
+        assert!(!attestation.verify(&IssuerKey::from(other.public_key().serialize())));
+        attestation.price = OraclePrice::from(102_000);
+        assert!(!attestation.verify(&oracle));
+    }
+
+    #[test]
+    fn attestation_layout() {
+        // Rebalance script reads the previous price at offset 0 and the new price at
+        // offset 8
+        let data = attestation(100_000, 101_000)
+            .to_strict_serialized::<84>()
+            .unwrap();
+        assert_eq!(data.len(), 84);
+        assert_eq!(&data[..8], &100_000u64.to_le_bytes());
+        assert_eq!(&data[8..16], &101_000u64.to_le_bytes());
+    }
+
+    /// Issues 1000 synthetic gold tokens at the price of 2400 USD, allowing
+    /// price moves of 1%.
+    fn contract(allocated: u64) -> Option<Contract> {
+        let name = UnderlyingAssetName::from_str("XAU/USD").unwrap();
+        let builder = issuer::<SyntheticAssetSchema>();
+        let contract = fungible_genesis(builder, spec("SXAU", "Gold"), 1000)
+            .add_global_state("underlyingAssetName", name)
+            .unwrap()
+            .add_global_state("oracleKey", IssuerKey::from([2u8; 33]))
+            .unwrap()
+            .add_global_state("lastKnownPrice", OraclePrice::from(240_000_000_000))
+            .unwrap()
+            .add_global_state("rebalanceTolerance", BasisPoints::from(100))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), allocated)
+            .unwrap()
+            .add_rights("rebalanceRight", seal(1))
+            .unwrap()
+            .issue_contract()
+            .ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn synthetic_genesis() {
+        assert!(contract(1000).is_some());
+        assert!(contract(999).is_none());
+    }
+
+    #[test]
+    fn transfer() {
+        let contract = contract(1000).unwrap();
+        let transfer = |amount: u64| {
+            let transition = spend_genesis::<SyntheticAssetSchema>(&contract, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &SyntheticAssetSchema::schema(), &transition)
+        };
+
+        let status = transfer(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(1001)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn rebalance() {
+        let contract = contract(1000).unwrap();
+        let rebalance = |price: u64, attested: u64| {
+            let mut transition = spend_genesis::<SyntheticAssetSchema>(&contract, "rebalance")
+                .add_global_state("lastKnownPrice", OraclePrice::from(price))
+                .unwrap()
+                .add_global_state("rebalanceTolerance", BasisPoints::from(100))
+                .unwrap()
+                .add_fungible_state("assetOwner", graph_seal(0), 1010u64)
+                .unwrap()
+                .add_rights("rebalanceRight", graph_seal(1))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            let attestation = attestation(240_000_000_000, attested);
+            add_metadata(&mut transition, MS_PRICE_ATTESTATION, attestation);
+            validate_transition(&contract, &SyntheticAssetSchema::schema(), &transition)
+        };
+
+        let status = rebalance(242_400_000_000, 242_400_000_000);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = rebalance(237_600_000_000, 237_600_000_000);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = rebalance(242_400_000_000, 242_000_000_000);
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_REBALANCE));
+        let status = rebalance(242_500_000_000, 242_500_000_000);
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_REBALANCE));
+        let status = rebalance(237_500_000_000, 237_500_000_000);
+        assert_eq!(script_errno(status), Some(ERRNO_INVALID_REBALANCE));
+    }
+}
@@ -0,0 +1,293 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixtures shared by the unit tests issuing contracts and validating their
+//! state transitions.
+
+use std::str::FromStr;
+
+use amplify::confinement::{Confined, U16};
+use bp::seals::txout::CloseMethod;
+use bp::Txid;
+use ifaces::IssuerWrapper;
+use rgbstd::containers::{BuilderSeal, Contract, IndexedConsignment};
+use rgbstd::interface::{ContractBuilder, IfaceClass, TransitionBuilder};
+use rgbstd::invoice::Precision;
+use rgbstd::persistence::PersistedState;
+use rgbstd::schema::Occurrences;
+use rgbstd::stl::{AssetSpec, ContractTerms, RicardianContract};
+use rgbstd::validation::{self, CheckedConsignment};
+use rgbstd::{
+    Amount, Assign, AssignmentType, BlindingFactor, DataState, Extension, ExtensionType,
+    GenesisSeal, GlobalStateType, GraphSeal, Identity, MetaType, MetaValue, OpRef, Operation,
+    Opout, Redeemed, RevealedValue, Schema, Transition, TypedAssigns, Valencies, ValencyType,
+    XChain,
+};
+use strict_encoding::{StrictDumb, StrictSerialize};
+
+pub(crate) fn txid() -> Txid {
+    Txid::from_str("14295d5bb1a191cdb6286dc0944df938421e3dfcbf0811353ccac4100c2068c5").unwrap()
+}
+
+/// Genesis seal closed by the output `vout` of the [`txid`] transaction.
+pub(crate) fn seal(vout: u32) -> BuilderSeal<GenesisSeal> {
+    BuilderSeal::from(XChain::Bitcoin(GenesisSeal::new_random(
+        CloseMethod::TapretFirst,
+        txid(),
+        vout,
+    )))
+}
+
+/// Seal of a transition output closed by the output `vout` of the witness
+/// transaction.
+pub(crate) fn graph_seal(vout: u32) -> XChain<GraphSeal> {
+    XChain::Bitcoin(GraphSeal::new_random(CloseMethod::TapretFirst, Txid::strict_dumb(), vout))
+}
+
+pub(crate) fn terms() -> ContractTerms {
+    ContractTerms {
+        text: RicardianContract::default(),
+        media: None,
+    }
+}
+
+/// Specification of an indivisible asset.
+pub(crate) fn spec(ticker: &'static str, name: &'static str) -> AssetSpec {
+    AssetSpec::new(ticker, name, Precision::Indivisible)
+}
+
+/// Adds the asset specification `spec` and the default contract terms to the
+/// genesis `builder`.
+pub(crate) fn asset_genesis(builder: ContractBuilder, spec: AssetSpec) -> ContractBuilder {
+    builder
+        .add_global_state("spec", spec)
+        .unwrap()
+        .add_global_state("terms", terms())
+        .unwrap()
+}
+
+/// Adds the asset specification `spec`, the default contract terms and the
+/// `issued` supply to the genesis `builder` of a fungible asset.
+pub(crate) fn fungible_genesis(
+    builder: ContractBuilder,
+    spec: AssetSpec,
+    issued: u64,
+) -> ContractBuilder {
+    asset_genesis(builder, spec)
+        .add_global_state("issuedSupply", Amount::from(issued))
+        .unwrap()
+}
+
+/// Constructs genesis builder of the `W` issuer.
+pub(crate) fn issuer<W: IssuerWrapper>() -> ContractBuilder { issuer_with_assignments::<W>(&[]) }
+
+/// Constructs genesis builder of the `W` issuer whose schema allows the genesis
+/// to assign the `extra` state types, allowing tests to issue the state spent
+/// by the transitions which the genesis doesn't assign normally.
+///
+/// Transition validation against the original schema doesn't check the genesis,
+/// thus the transitions spending such a genesis remain valid.
+pub(crate) fn issuer_with_assignments<W: IssuerWrapper>(
+    extra: &[AssignmentType],
+) -> ContractBuilder {
+    let mut schema = W::schema();
+    for ty in extra {
+        schema
+            .genesis
+            .assignments
+            .insert(*ty, Occurrences::NoneOrMore)
+            .unwrap();
+    }
+    let mut iimpl = W::issue_impl();
+    iimpl.schema_id = schema.schema_id();
+    ContractBuilder::with(
+        Identity::default(),
+        W::IssuingIface::iface(W::FEATURES),
+        schema,
+        iimpl,
+        W::types(),
+        W::scripts(),
+    )
+}
+
+/// Constructs the transition named `name` of the `W` issuer, which spends all
+/// genesis assignments of the `contract` of the types taken by the transition.
+pub(crate) fn spend_genesis<W: IssuerWrapper>(
+    contract: &Contract,
+    name: &'static str,
+) -> TransitionBuilder {
+    let builder = TransitionBuilder::named_transition(
+        contract.contract_id(),
+        W::IssuingIface::iface(W::FEATURES),
+        W::schema(),
+        W::issue_impl(),
+        name,
+        W::types(),
+    )
+    .unwrap();
+    spend_genesis_with(contract, &W::schema(), builder)
+}
+
+/// Adds the asset tags and all genesis assignments of the `contract` of the
+/// types taken by the transition of the `schema` to the transition `builder`.
+pub(crate) fn spend_genesis_with(
+    contract: &Contract,
+    schema: &Schema,
+    mut builder: TransitionBuilder,
+) -> TransitionBuilder {
+    for (ty, tag) in contract.genesis.asset_tags.iter() {
+        builder = builder.add_asset_tag_raw(*ty, *tag).unwrap();
+    }
+    let genesis_id = contract.genesis.id();
+    let inputs = &schema.transitions[&builder.transition_type()].inputs;
+    for (ty, assigns) in contract.genesis.assignments.iter() {
+        if !inputs.contains_key(ty) {
+            continue;
+        }
+        let states = match assigns {
+            TypedAssigns::Fungible(assigns) => assigns
+                .iter()
+                .map(|assign| {
+                    let value = assign.as_revealed_state().unwrap();
+                    PersistedState::Amount(value.value.into(), value.blinding, value.tag)
+                })
+                .collect::<Vec<_>>(),
+            TypedAssigns::Declarative(assigns) => {
+                assigns.iter().map(|_| PersistedState::Void).collect()
+            }
+            TypedAssigns::Structured(assigns) => assigns
+                .iter()
+                .map(|assign| {
+                    let data = assign.as_revealed_state().unwrap();
+                    PersistedState::Data(data.value.clone(), data.salt)
+                })
+                .collect(),
+            _ => panic!("unsupported genesis state {ty}"),
+        };
+        for (no, state) in (0u16..).zip(states) {
+            builder = builder
+                .add_input(Opout::new(genesis_id, *ty, no), state)
+                .unwrap();
+        }
+    }
+    builder
+}
+
+/// Adds metadata `value` of the type `ty` to the `transition`, since the
+/// transition builder doesn't put the metadata into the completed transition.
+pub(crate) fn add_metadata(transition: &mut Transition, ty: MetaType, value: impl StrictSerialize) {
+    let value = value.to_strict_serialized::<U16>().unwrap();
+    transition
+        .metadata
+        .add_value(ty, MetaValue::from(value))
+        .unwrap();
+}
+
+/// Constructs the state extension of the type `ty` of the `contract`, redeeming
+/// the `valency` declared by its genesis and declaring it again.
+pub(crate) fn redeem_genesis(
+    contract: &Contract,
+    ty: ExtensionType,
+    valency: ValencyType,
+) -> Extension {
+    Extension {
+        ffv: default!(),
+        contract_id: contract.contract_id(),
+        extension_type: ty,
+        metadata: none!(),
+        globals: none!(),
+        assignments: none!(),
+        redeemed: Redeemed::from(tiny_bmap! { valency => contract.genesis.id() }),
+        valencies: Valencies::from(tiny_bset![valency]),
+        validator: default!(),
+        witness: default!(),
+    }
+}
+
+/// Adds global state `value` of the type `ty` to the `extension`.
+pub(crate) fn add_global_state(
+    extension: &mut Extension,
+    ty: GlobalStateType,
+    value: impl StrictSerialize,
+) {
+    let value = value.to_strict_serialized::<U16>().unwrap();
+    extension
+        .globals
+        .add_state(ty, DataState::from(value))
+        .unwrap();
+}
+
+/// Adds revealed fungible `amount` of the type `ty` to the `extension` of the
+/// `contract`, assigned to the seal closed by the output `vout` of the [`txid`]
+/// transaction.
+///
+/// The amount is committed with zero blinding factor, so a single allocation
+/// balances the amount checked by the extension validation script.
+pub(crate) fn add_fungible_state(
+    extension: &mut Extension,
+    contract: &Contract,
+    ty: AssignmentType,
+    vout: u32,
+    amount: u64,
+) {
+    let seal = XChain::Bitcoin(GenesisSeal::new_random(CloseMethod::TapretFirst, txid(), vout));
+    let tag = contract.genesis.asset_tags[&ty];
+    let state = RevealedValue::with_blinding(amount, BlindingFactor::EMPTY, tag);
+    let assign = Assign::revealed(seal, state);
+    match extension.assignments.get_mut(&ty) {
+        Some(TypedAssigns::Fungible(assigns)) => assigns.push(assign).unwrap(),
+        _ => {
+            let assigns = TypedAssigns::Fungible(Confined::try_from(vec![assign]).unwrap());
+            extension.assignments.insert(ty, assigns).unwrap();
+        }
+    }
+}
+
+/// Validates the `transition` against the `schema`, resolving its inputs to the
+/// genesis of the `contract` and running the transition validation script.
+pub(crate) fn validate_transition(
+    contract: &Contract,
+    schema: &Schema,
+    transition: &Transition,
+) -> validation::Status {
+    let consignment = IndexedConsignment::new(contract);
+    schema.validate_state(&CheckedConsignment::new(&consignment), OpRef::Transition(transition))
+}
+
+/// Validates the `extension` against the `schema`, running the extension
+/// validation script.
+pub(crate) fn validate_extension(
+    contract: &Contract,
+    schema: &Schema,
+    extension: &Extension,
+) -> validation::Status {
+    let consignment = IndexedConsignment::new(contract);
+    schema.validate_state(&CheckedConsignment::new(&consignment), OpRef::Extension(extension))
+}
+
+/// Extracts the error code of the single failure reported by the validation
+/// script.
+pub(crate) fn script_errno(status: validation::Status) -> Option<u8> {
+    match status.failures.as_slice() {
+        [validation::Failure::ScriptFailure(_, errno, _)] => *errno,
+        failures => panic!("unexpected failures {failures:?}"),
+    }
+}
@@ -0,0 +1,591 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tranche asset schema implementing RGB20 fungible assets interface for bonds
+//! and equity rounds issued in a sequence of tranches.
+//!
+//! The genesis declares the number of tranches ([`GS_TRANCHE_COUNT`]) and the
+//! maximal size of each of them ([`GS_TRANCHE_SIZES`]), and opens the first
+//! tranche ([`GS_CURRENT_TRANCHE`]), issuing up to its size as the issued
+//! supply. The issuer holding the tranche right ([`OS_TRANCHE_RIGHT`]) opens
+//! the following tranches one by one with [`TS_OPEN_TRANCHE`], each issuing up
+//! to the declared size of the tranche ([`GS_TRANCHE_ISSUED`]).
+//!
+//! The scripts can't read the contract global state (see [consensus
+//! limitations](crate#consensus-limitations)), thus the tranche right carries a
+//! copy of the tranche sizes together with the index of the next tranche
+//! ([`TrancheRight`]). The genesis script checks that the copy matches the
+//! declared sizes, and each tranche opening checks that the copy is
+//! passed unchanged while the index is incremented. Once the right is not
+//! re-assigned, no more tranches can be opened.
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_JIF, INSTR_PUTA, INSTR_RET};
+use aluvm::isa::{Instr, MoveOp};
+use aluvm::library::{Lib, LibSite};
+use aluvm::reg::{Reg32, RegA};
+use amplify::confinement::Confined;
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_CNS, INSTR_LDG, INSTR_LDP, INSTR_LDS, INSTR_PCAS, INSTR_PCVS};
+use rgbstd::vm::{ContractOp, RgbIsa};
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, TransitionType};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use strict_types::TypeSystem;
+
+use crate::stl::{rgb_schemata_stl, LIB_NAME_RGB_SCHEMATA};
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_TERMS,
+    TS_TRANSFER,
+};
+
+/// Number of tranches of the issuance ([`TrancheIndex`]).
+pub const GS_TRANCHE_COUNT: GlobalStateType = GlobalStateType::with(4500);
+/// Maximal size of each of the tranches ([`TrancheSizes`]).
+pub const GS_TRANCHE_SIZES: GlobalStateType = GlobalStateType::with(4501);
+/// Index of the tranche opened by the genesis or a tranche opening
+/// ([`TrancheIndex`]).
+pub const GS_CURRENT_TRANCHE: GlobalStateType = GlobalStateType::with(4502);
+/// Amount issued by a tranche opening (`RGBContract.Amount`).
+pub const GS_TRANCHE_ISSUED: GlobalStateType = GlobalStateType::with(4503);
+
+/// Fungible allocation of the tranche asset (RGB20 `assetOwner`).
+pub const OS_TRANCHE_TOKEN: AssignmentType = AssignmentType::with(6400);
+/// Right of the issuer to open the next tranche ([`TrancheRight`]).
+pub const OS_TRANCHE_RIGHT: AssignmentType = AssignmentType::with(6401);
+
+pub const TS_OPEN_TRANCHE: TransitionType = TransitionType::with(12400);
+
+/// Error raised when tranches are opened out of order or the tranche right
+/// doesn't match the declared tranches.
+pub const ERRNO_INVALID_TRANCHE: u8 = 58;
+/// Error raised when a tranche issues more than its declared size.
+pub const ERRNO_TRANCHE_OVERSIZED: u8 = 59;
+
+/// Maximal number of tranches of an issuance.
+pub const MAX_TRANCHES: usize = 0xFF;
+
+pub(crate) const FN_TRANCHE_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_TRANCHE_GENESIS_OFFSET: u16 = FN_TRANCHE_TRANSFER_OFFSET + 4 + 3 + 1 + 1;
+pub const FN_OPEN_TRANCHE_OFFSET: u16 =
+    FN_TRANCHE_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 2 + 1 + 4 + 4 + 3 + 3 + 3 + 1 + 3 + 4 + 3 + 3;
+pub(crate) const FN_TRANCHE_ISSUE_OFFSET: u16 =
+    FN_OPEN_TRANCHE_OFFSET + 4 + 4 + 4 + 4 + 3 + 4 + 3 + 4 + 3 + 3 + 3 + 1 + 4 + 3;
+pub(crate) const FN_TRANCHE_RIGHT_OFFSET: u16 =
+    FN_TRANCHE_ISSUE_OFFSET + 3 + 4 + 3 + 4 + 3 + 3 + 1 + 4 + 3 + 1 + 1 + 3 + 1;
+pub(crate) const FN_TRANCHE_LOOP_OFFSET: u16 =
+    FN_TRANCHE_RIGHT_OFFSET + 4 + 4 + 2 + 3 + 4 + 3 + 4 + 3 + 3 + 1 + 3 + 4 + 3 + 3 + 1 + 4;
+pub(crate) const FN_TRANCHE_END_OFFSET: u16 =
+    FN_TRANCHE_LOOP_OFFSET + 3 + 3 + 3 + 3 + 3 + 1 + 3 + 3;
+
+/// Index or number of tranches.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct TrancheIndex(u8);
+impl StrictSerialize for TrancheIndex {}
+impl StrictDeserialize for TrancheIndex {}
+
+/// Maximal sizes of the tranches, in the order of their opening.
+#[derive(Wrapper, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct TrancheSizes(Confined<Vec<u64>, 1, MAX_TRANCHES>);
+impl StrictSerialize for TrancheSizes {}
+impl StrictDeserialize for TrancheSizes {}
+
+impl TrancheSizes {
+    fn dumb() -> Self { Self(Confined::with(0)) }
+
+    /// Constructs tranche sizes, returning `None` if there are no sizes or more
+    /// than [`MAX_TRANCHES`] of them.
+    pub fn with(sizes: impl IntoIterator<Item = u64>) -> Option<Self> {
+        Confined::try_from_iter(sizes).ok().map(Self)
+    }
+
+    /// Number of the tranches.
+    pub fn count(&self) -> TrancheIndex { TrancheIndex(self.0.len() as u8) }
+}
+
+/// State of the issuer right to open tranches.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA, dumb = Self::dumb())]
+pub struct TrancheRight {
+    /// Copy of the declared tranche sizes ([`GS_TRANCHE_SIZES`]).
+    pub sizes: TrancheSizes,
+    /// Index of the tranche to be opened next.
+    pub next_tranche: TrancheIndex,
+}
+impl StrictSerialize for TrancheRight {}
+impl StrictDeserialize for TrancheRight {}
+
+impl TrancheRight {
+    fn dumb() -> Self {
+        Self {
+            sizes: TrancheSizes::dumb(),
+            next_tranche: TrancheIndex(1),
+        }
+    }
+}
+
+/// Assembles AluVM library with validation scripts used by the tranche asset
+/// schema.
+// `sub` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+pub fn tranche_lib() -> Lib {
+    let mut code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        pcvs    OS_TRANCHE_TOKEN;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the genesis opens the first tranche and declares sizes of all tranches
+        put     a8[0],ERRNO_INVALID_TRANCHE;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_CURRENT_TRANCHE,a8[1],s16[2];
+        extr    s16[2],a8[2],a16[0];
+        ifz     a8[2];
+        test;
+        ldg     GS_TRANCHE_SIZES,a8[1],s16[0];
+        ldg     GS_TRANCHE_COUNT,a8[1],s16[3];
+        extr    s16[0],a8[3],a16[0];
+        extr    s16[3],a8[4],a16[0];
+        eq.n    a8[3],a8[4];
+        test;
+        // The next tranche right must hold a copy of all the bytes of the sizes
+        len     s16[0],a16[1];
+        // Read the issued amount into a64[0]
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[3];
+        extr    s16[3],a64[0],a16[0];
+        jmp     FN_TRANCHE_ISSUE_OFFSET;
+
+        // SUBROUTINE Tranche opening validation
+        // Checking that the opened tranche is the next one from the spent tranche right
+        put     a8[0],ERRNO_INVALID_TRANCHE;
+        put     a8[1],0;
+        put     a16[0],0;
+        ldg     GS_CURRENT_TRANCHE,a8[1],s16[2];
+        extr    s16[2],a8[2],a16[0];
+        ldp     OS_TRANCHE_RIGHT,a16[0],s16[0];
+        // The next tranche index is the last byte of the right, preceded by the sizes
+        len     s16[0],a16[3];
+        put     a16[1],1;
+        sub.uc  a16[3],a16[1];
+        extr    s16[0],a8[3],a16[1];
+        eq.n    a8[2],a8[3];
+        test;
+        // Read the issued amount into a64[0]
+        ldg     GS_TRANCHE_ISSUED,a8[1],s16[3];
+        extr    s16[3],a64[0],a16[0];
+
+        // SUBROUTINE Shared tranche issue validation
+        // Reads the size of the tranche a8[2] from the sizes starting s16[0]
+    };
+    // NB: `rgbasm!` can't copy between registers of different size, thus the
+    // instruction is constructed directly
+    code.push(Instr::Move(MoveOp::CpyA(RegA::A8, Reg32::Reg2, RegA::A16, Reg32::Reg2)));
+    code.extend(rgbasm! {
+        // a16[2] := 1 + 8 * a16[2], skipping the length of the sizes
+        put     a16[4],8;
+        mul.uc  a16[4],a16[2];
+        put     a16[4],1;
+        add.uc  a16[4],a16[2];
+        // Fails if there is no such tranche
+        extr    s16[0],a64[1],a16[2];
+        test;
+        // Checking that the tranche doesn't issue more than its size
+        put     a8[0],ERRNO_TRANCHE_OVERSIZED;
+        gt.u    a64[0],a64[1];
+        inv     st0;
+        test;
+        pcas    OS_TRANCHE_TOKEN;
+        test;
+
+        // Checking the re-assigned tranche right, if any
+        put     a8[0],ERRNO_INVALID_TRANCHE;
+    });
+    // NB: `rgbasm!` doesn't know `cns` opcode, thus the instruction is constructed
+    // directly
+    code.push(Instr::ExtensionCodes(RgbIsa::Contract(ContractOp::CnS(
+        OS_TRANCHE_RIGHT,
+        Reg32::Reg5,
+    ))));
+    code.extend(rgbasm! {
+        // `cns` leaves the register empty if there is no assigned right
+        ifn     a16[5];
+        jif     FN_TRANCHE_END_OFFSET;
+        lds     OS_TRANCHE_RIGHT,a16[0],s16[1];
+        // The right must allow opening the tranche following the current one
+        extr    s16[1],a8[4],a16[1];
+        put     a8[5],1;
+        add.uc  a8[2],a8[5];
+        eq.n    a8[4],a8[5];
+        test;
+        // and keep the copy of the sizes, checked byte by byte since AluVM can't split strings
+        len     s16[1],a16[3];
+        put     a16[4],1;
+        add.uc  a16[1],a16[4];
+        eq.n    a16[3],a16[4];
+        test;
+        put     a16[6],0;
+        eq.n    a16[6],a16[1];
+        jif     FN_TRANCHE_END_OFFSET;
+        extr    s16[0],a8[6],a16[6];
+        extr    s16[1],a8[7],a16[6];
+        eq.n    a8[6],a8[7];
+        test;
+        add     a16[6],1;
+        jmp     FN_TRANCHE_LOOP_OFFSET;
+        ret;
+    });
+    Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong tranche asset script")
+}
+
+fn tranche_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = tranche_lib();
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_TRANCHE_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(state_arg(FN_TRANCHE_TRANSFER_OFFSET + 4), OS_TRANCHE_TOKEN.to_inner());
+    assert_eq!(code[FN_TRANCHE_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_TRANCHE_GENESIS_OFFSET + 12), GS_CURRENT_TRANCHE.to_inner());
+    assert_eq!(code[FN_OPEN_TRANCHE_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_OPEN_TRANCHE_OFFSET as usize + 19], INSTR_LDP);
+    assert_eq!(state_arg(FN_OPEN_TRANCHE_OFFSET + 19), OS_TRANCHE_RIGHT.to_inner());
+    assert_eq!(code[FN_OPEN_TRANCHE_OFFSET as usize + 40], INSTR_LDG);
+    assert_eq!(state_arg(FN_OPEN_TRANCHE_OFFSET + 40), GS_TRANCHE_ISSUED.to_inner());
+    assert_eq!(code[FN_TRANCHE_ISSUE_OFFSET as usize + 17], INSTR_EXTR);
+    assert_eq!(code[FN_TRANCHE_ISSUE_OFFSET as usize + 30], INSTR_PCAS);
+    assert_eq!(code[FN_TRANCHE_RIGHT_OFFSET as usize + 4], INSTR_CNS);
+    assert_eq!(code[FN_TRANCHE_RIGHT_OFFSET as usize + 13], INSTR_LDS);
+    assert_eq!(state_arg(FN_TRANCHE_RIGHT_OFFSET + 13), OS_TRANCHE_RIGHT.to_inner());
+    assert_eq!(code[FN_TRANCHE_LOOP_OFFSET as usize + 3], INSTR_JIF);
+    assert_eq!(code[FN_TRANCHE_END_OFFSET as usize], INSTR_RET);
+    assert_eq!(code.len(), FN_TRANCHE_END_OFFSET as usize + 1);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("TrancheAsset"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: none!(),
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_TRANCHE_COUNT => GlobalStateSchema::once(types.get("RGBSchemata.TrancheIndex")),
+            GS_TRANCHE_SIZES => GlobalStateSchema::once(types.get("RGBSchemata.TrancheSizes")),
+            GS_CURRENT_TRANCHE => GlobalStateSchema::many(types.get("RGBSchemata.TrancheIndex")),
+            GS_TRANCHE_ISSUED => GlobalStateSchema::many(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_TRANCHE_TOKEN => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_TRANCHE_RIGHT =>
+                OwnedStateSchema::Structured(types.get("RGBSchemata.TrancheRight")),
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_TRANCHE_COUNT => Occurrences::Once,
+                GS_TRANCHE_SIZES => Occurrences::Once,
+                GS_CURRENT_TRANCHE => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_TRANCHE_TOKEN => Occurrences::OnceOrMore,
+                OS_TRANCHE_RIGHT => Occurrences::NoneOrOnce,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_TRANCHE_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_TRANCHE_TOKEN => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_TRANCHE_TOKEN => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_TRANCHE_TRANSFER_OFFSET, alu_id))
+            },
+            TS_OPEN_TRANCHE => TransitionSchema {
+                metadata: none!(),
+                globals: tiny_bmap! {
+                    GS_CURRENT_TRANCHE => Occurrences::Once,
+                    GS_TRANCHE_ISSUED => Occurrences::Once,
+                },
+                inputs: tiny_bmap! {
+                    OS_TRANCHE_RIGHT => Occurrences::Once
+                },
+                assignments: tiny_bmap! {
+                    OS_TRANCHE_TOKEN => Occurrences::OnceOrMore,
+                    OS_TRANCHE_RIGHT => Occurrences::NoneOrOnce,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_OPEN_TRANCHE_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn tranche_rgb20() -> IfaceImpl {
+    let schema = tranche_schema();
+    let iface = Rgb20::iface(TrancheAsset::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: none!(),
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_TRANCHE_COUNT, fname!("trancheCount")),
+            NamedField::with(GS_TRANCHE_SIZES, fname!("trancheSizes")),
+            NamedField::with(GS_CURRENT_TRANCHE, fname!("currentTranche")),
+            NamedField::with(GS_TRANCHE_ISSUED, fname!("trancheIssued")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_TRANCHE_TOKEN, fname!("assetOwner")),
+            NamedField::with(OS_TRANCHE_RIGHT, fname!("trancheRight")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_OPEN_TRANCHE, fname!("openTranche")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+/// Bond or equity issuance opened in a sequence of tranches of declared sizes.
+pub struct TrancheAsset;
+
+impl IssuerWrapper for TrancheAsset {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { tranche_schema() }
+    fn issue_impl() -> IfaceImpl { tranche_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = tranche_lib();
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::Amount;
+
+    use super::*;
+    use crate::test_helpers::{
+        fungible_genesis, graph_seal, issuer, script_errno, seal, spec, spend_genesis,
+        validate_transition,
+    };
+
+    fn sizes() -> TrancheSizes { TrancheSizes::with([1000, 2000, 3000]).unwrap() }
+
+    fn right(next: u8) -> TrancheRight {
+        TrancheRight {
+            sizes: sizes(),
+            next_tranche: TrancheIndex::from(next),
+        }
+    }
+
+    /// Issues the `current` tranche out of `count` tranches sized 1000, 2000
+    /// and 3000, assigning the `issued` amount and optionally the tranche
+    /// `right`.
+    fn contract(
+        count: u8,
+        current: u8,
+        issued: u64,
+        right: Option<TrancheRight>,
+    ) -> Option<Contract> {
+        let builder = issuer::<TrancheAsset>();
+        let mut builder = fungible_genesis(builder, spec("TRCH", "Tranches"), issued)
+            .add_global_state("trancheCount", TrancheIndex::from(count))
+            .unwrap()
+            .add_global_state("trancheSizes", sizes())
+            .unwrap()
+            .add_global_state("currentTranche", TrancheIndex::from(current))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), issued)
+            .unwrap();
+        if let Some(right) = right {
+            builder = builder.add_data("trancheRight", seal(1), right).unwrap();
+        }
+        let contract = builder.issue_contract().ok()?;
+        Some(contract.into_consignment())
+    }
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(TrancheAsset::FEATURES);
+        if let Err(err) = tranche_rgb20().check(&iface, &tranche_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid tranche asset RGB20 interface implementation");
+        }
+    }
+
+    #[test]
+    fn tranche_layout() {
+        // Scripts read the size of tranche `i` at byte `1 + 8 * i` and the next tranche
+        // index from the last byte of the right
+        let sizes = TrancheSizes::with([100, 200]).unwrap();
+        let data = sizes.to_strict_serialized::<0xFFFF>().unwrap();
+        assert_eq!(data.len(), 1 + 16);
+        assert_eq!(data[0], 2);
+        assert_eq!(&data[9..17], &200u64.to_le_bytes());
+        let right = TrancheRight {
+            sizes,
+            next_tranche: TrancheIndex::from(1),
+        };
+        let right_data = right.to_strict_serialized::<0xFFFF>().unwrap();
+        assert_eq!(&right_data[..17], &data[..]);
+        assert_eq!(right_data[17], 1);
+        assert!(TrancheSizes::with([]).is_none());
+    }
+
+    #[test]
+    fn tranche_genesis() {
+        assert!(contract(3, 0, 1000, Some(right(1))).is_some());
+        assert!(contract(3, 0, 500, Some(right(1))).is_some());
+        assert!(contract(3, 0, 1000, None).is_some());
+        // First tranche is oversized
+        assert!(contract(3, 0, 1001, Some(right(1))).is_none());
+        // Tranches are opened out of order
+        assert!(contract(3, 1, 1000, Some(right(2))).is_none());
+        assert!(contract(3, 0, 1000, Some(right(2))).is_none());
+        // Tranche count doesn't match the sizes
+        assert!(contract(2, 0, 1000, Some(right(1))).is_none());
+        // Tranche right doesn't copy the declared sizes
+        assert!(contract(
+            3,
+            0,
+            1000,
+            Some(TrancheRight {
+                sizes: TrancheSizes::with([1000, 2000, 3001]).unwrap(),
+                next_tranche: TrancheIndex::from(1),
+            })
+        )
+        .is_none());
+        assert!(contract(
+            3,
+            0,
+            1000,
+            Some(TrancheRight {
+                sizes: TrancheSizes::with([1000, 2000]).unwrap(),
+                next_tranche: TrancheIndex::from(1),
+            })
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn transfer() {
+        let contract = contract(3, 0, 1000, Some(right(1))).unwrap();
+        let transfer = |amount: u64| {
+            let transition = spend_genesis::<TrancheAsset>(&contract, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &TrancheAsset::schema(), &transition)
+        };
+
+        let status = transfer(1000);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(999)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn open_tranche() {
+        let contract = contract(3, 0, 1000, Some(right(1))).unwrap();
+        let open = |current: u8, issued: u64, right: Option<TrancheRight>| {
+            let mut builder = spend_genesis::<TrancheAsset>(&contract, "openTranche")
+                .add_global_state("currentTranche", TrancheIndex::from(current))
+                .unwrap()
+                .add_global_state("trancheIssued", Amount::from(issued))
+                .unwrap()
+                .add_fungible_state("assetOwner", graph_seal(0), issued)
+                .unwrap();
+            if let Some(right) = right {
+                builder = builder
+                    .add_data("trancheRight", graph_seal(1), right)
+                    .unwrap();
+            }
+            let transition = builder.complete_transition().unwrap();
+            validate_transition(&contract, &TrancheAsset::schema(), &transition)
+        };
+
+        let status = open(1, 2000, Some(right(2)));
+        assert!(status.failures.is_empty(), "{status}");
+        let status = open(1, 1500, None);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(open(1, 2001, Some(right(2)))), Some(ERRNO_TRANCHE_OVERSIZED));
+        // Tranches are opened out of order
+        assert_eq!(script_errno(open(2, 3000, Some(right(3)))), Some(ERRNO_INVALID_TRANCHE));
+        assert_eq!(script_errno(open(1, 2000, Some(right(1)))), Some(ERRNO_INVALID_TRANCHE));
+        // Tranche right doesn't keep the copy of the sizes
+        let forged = TrancheRight {
+            sizes: TrancheSizes::with([1000, 2000, 9000]).unwrap(),
+            next_tranche: TrancheIndex::from(2),
+        };
+        assert_eq!(script_errno(open(1, 2000, Some(forged))), Some(ERRNO_INVALID_TRANCHE));
+    }
+}
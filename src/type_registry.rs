@@ -0,0 +1,126 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Registries of human-readable names of assignment and transition types.
+
+use std::collections::BTreeMap;
+
+use rgbstd::{AssignmentType, TransitionType};
+
+use crate::{
+    register_cia_assignment_types, register_cia_transition_types, register_nia_assignment_types,
+    register_nia_transition_types, register_uda_assignment_types, register_uda_transition_types,
+};
+
+/// Human-readable names of [`AssignmentType`]s, used in debugging and reporting
+/// output.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct AssignmentTypeRegistry(BTreeMap<AssignmentType, &'static str>);
+
+impl AssignmentTypeRegistry {
+    /// Constructs an empty registry.
+    pub fn new() -> Self { Self::default() }
+
+    /// Constructs a registry with the assignment types of NIA, CIA and UDA
+    /// schemata.
+    pub fn with_builtin_types() -> Self {
+        let mut registry = Self::new();
+        register_nia_assignment_types(&mut registry);
+        register_cia_assignment_types(&mut registry);
+        register_uda_assignment_types(&mut registry);
+        registry
+    }
+
+    /// Names the assignment type, replacing previously registered name.
+    pub fn register(&mut self, ty: AssignmentType, name: &'static str) { self.0.insert(ty, name); }
+
+    /// Returns name of the assignment type, if it is known to the registry.
+    pub fn name(&self, ty: AssignmentType) -> Option<&'static str> { self.0.get(&ty).copied() }
+
+    /// Iterates over all registered assignment types and their names.
+    pub fn iter(&self) -> impl Iterator<Item = (AssignmentType, &'static str)> + '_ {
+        self.0.iter().map(|(ty, name)| (*ty, *name))
+    }
+}
+
+/// Human-readable names of [`TransitionType`]s, used in debugging and reporting
+/// output.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TransitionTypeRegistry(BTreeMap<TransitionType, &'static str>);
+
+impl TransitionTypeRegistry {
+    /// Constructs an empty registry.
+    pub fn new() -> Self { Self::default() }
+
+    /// Constructs a registry with the transition types of NIA, CIA and UDA
+    /// schemata.
+    pub fn with_builtin_types() -> Self {
+        let mut registry = Self::new();
+        register_nia_transition_types(&mut registry);
+        register_cia_transition_types(&mut registry);
+        register_uda_transition_types(&mut registry);
+        registry
+    }
+
+    /// Names the transition type, replacing previously registered name.
+    pub fn register(&mut self, ty: TransitionType, name: &'static str) { self.0.insert(ty, name); }
+
+    /// Returns name of the transition type, if it is known to the registry.
+    pub fn name(&self, ty: TransitionType) -> Option<&'static str> { self.0.get(&ty).copied() }
+
+    /// Iterates over all registered transition types and their names.
+    pub fn iter(&self) -> impl Iterator<Item = (TransitionType, &'static str)> + '_ {
+        self.0.iter().map(|(ty, name)| (*ty, *name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ifaces::IssuerWrapper;
+
+    use super::*;
+    use crate::{
+        ContractInflatableAsset, NonInflatableAsset, SchemaRegistry, UniqueDigitalAsset, OS_ASSET,
+        OS_FROZEN, TS_BURN,
+    };
+
+    #[test]
+    fn type_registries() {
+        let registry = SchemaRegistry::with_builtin_schemata();
+        let assignments = AssignmentTypeRegistry::with_builtin_types();
+        let transitions = TransitionTypeRegistry::with_builtin_types();
+        for wrapper in [NonInflatableAsset::issue_impl(), ContractInflatableAsset::issue_impl()]
+            .into_iter()
+            .chain([UniqueDigitalAsset::issue_impl()])
+        {
+            let schema = registry.lookup(wrapper.schema_id).unwrap().erased_schema();
+            for ty in schema.owned_types.keys() {
+                assert_eq!(assignments.name(*ty), wrapper.assignment_name(*ty).map(|n| n.as_str()));
+            }
+            for ty in schema.transitions.keys() {
+                assert_eq!(transitions.name(*ty), wrapper.transition_name(*ty).map(|n| n.as_str()));
+            }
+        }
+        assert_eq!(assignments.name(OS_FROZEN), Some("frozenAsset"));
+        assert_eq!(transitions.name(TS_BURN), Some("burn"));
+        assert_eq!(AssignmentTypeRegistry::new().name(OS_ASSET), None);
+    }
+}
@@ -35,15 +35,15 @@ use rgbstd::{rgbasm, Identity, OwnedStateSchema};
 use strict_types::TypeSystem;
 
 use crate::{
-    ERRNO_NON_EQUAL_IN_OUT, ERRNO_NON_FRACTIONAL, GS_ATTACH, GS_NOMINAL, GS_TERMS, GS_TOKENS,
-    OS_ASSET, TS_TRANSFER,
+    AssignmentTypeRegistry, TransitionTypeRegistry, ERRNO_NON_EQUAL_IN_OUT, ERRNO_NON_FRACTIONAL,
+    GS_ATTACH, GS_NOMINAL, GS_TERMS, GS_TOKENS, OS_ASSET, TS_TRANSFER,
 };
 
 pub const FN_GENESIS_OFFSET: u16 = 4 + 4 + 3;
 pub const FN_TRANSFER_OFFSET: u16 = 0;
 pub const FN_SHARED_OFFSET: u16 = FN_GENESIS_OFFSET + 4 + 4 + 4;
 
-fn uda_lib() -> Lib {
+pub(crate) fn uda_lib() -> Lib {
     let code = rgbasm! {
         // SUBROUTINE 2: Transfer validation
         // Put 0 to a16[0]
@@ -92,7 +92,7 @@ fn uda_lib() -> Lib {
     Lib::assemble::<Instr<RgbIsa>>(&code).expect("wrong unique digital asset script")
 }
 
-fn uda_schema() -> Schema {
+pub(crate) fn uda_schema() -> Schema {
     let types = StandardTypes::with(Rgb21::stl());
 
     let alu_lib = uda_lib();
@@ -154,7 +154,7 @@ fn uda_schema() -> Schema {
     }
 }
 
-fn uda_rgb21() -> IfaceImpl {
+pub(crate) fn uda_rgb21() -> IfaceImpl {
     let schema = uda_schema();
     let iface = Rgb21::iface(rgb21::Features::NONE);
 
@@ -186,6 +186,16 @@ fn uda_rgb21() -> IfaceImpl {
     }
 }
 
+/// Registers names of the assignment types used by UDA schema.
+pub fn register_uda_assignment_types(r: &mut AssignmentTypeRegistry) {
+    r.register(OS_ASSET, "assetOwner");
+}
+
+/// Registers names of the transition types used by UDA schema.
+pub fn register_uda_transition_types(r: &mut TransitionTypeRegistry) {
+    r.register(TS_TRANSFER, "transfer");
+}
+
 pub struct UniqueDigitalAsset;
 
 impl IssuerWrapper for UniqueDigitalAsset {
@@ -217,4 +227,13 @@ mod test {
             panic!("invalid UDA RGB21 interface implementation");
         }
     }
+
+    #[test]
+    fn single_token_allocation() {
+        let schema = uda_schema();
+        assert_eq!(schema.genesis.assignments.get(&OS_ASSET), Some(&Occurrences::Once));
+        let transfer = schema.transitions.get(&TS_TRANSFER).unwrap();
+        assert_eq!(transfer.inputs.get(&OS_ASSET), Some(&Occurrences::Once));
+        assert_eq!(transfer.assignments.get(&OS_ASSET), Some(&Occurrences::Once));
+    }
 }
@@ -0,0 +1,75 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Consistency checks between schemata and their validation scripts.
+
+use aluvm::library::LibId;
+use rgbstd::validation::Scripts;
+use rgbstd::Schema;
+
+/// Errors detected by [`validate_scripts`].
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum ScriptValidationError {
+    /// schema references AluVM library {0} which is not present in the scripts.
+    MissingLibrary(LibId),
+}
+
+/// Checks that all validation scripts referenced by the schema genesis, state
+/// extensions and state transitions are present in `scripts`.
+pub fn validate_scripts(schema: &Schema, scripts: &Scripts) -> Result<(), ScriptValidationError> {
+    let validators = schema
+        .extensions
+        .values()
+        .map(|ext| &ext.validator)
+        .chain(schema.transitions.values().map(|ts| &ts.validator))
+        .chain([&schema.genesis.validator]);
+    for site in validators.flatten() {
+        if !scripts.contains_key(&site.lib) {
+            return Err(ScriptValidationError::MissingLibrary(site.lib));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use ifaces::IssuerWrapper;
+
+    use super::*;
+    use crate::{nia_base_lib, NonInflatableAsset, SchemaRegistry, UniqueDigitalAsset};
+
+    #[test]
+    fn scripts_validation() {
+        let schema = NonInflatableAsset::schema();
+        assert_eq!(validate_scripts(&schema, &NonInflatableAsset::scripts()), Ok(()));
+        assert_eq!(
+            validate_scripts(&schema, &UniqueDigitalAsset::scripts()),
+            Err(ScriptValidationError::MissingLibrary(nia_base_lib().unwrap().id()))
+        );
+        let registry = SchemaRegistry::with_builtin_schemata();
+        for id in registry.schema_ids() {
+            let wrapper = registry.lookup(id).unwrap();
+            let schema = wrapper.erased_schema();
+            assert_eq!(validate_scripts(&schema, &wrapper.erased_scripts()), Ok(()));
+        }
+    }
+}
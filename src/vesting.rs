@@ -0,0 +1,532 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Vesting asset schema implementing RGB20 fungible assets interface with part
+//! of the supply locked under a vesting schedule.
+//!
+//! The schedule (start height, cliff and total vesting duration) is committed
+//! to the genesis global state for information only, since the scripts can't
+//! check it against block heights (see [consensus
+//! limitations](crate#consensus-limitations)). Instead, releases with
+//! [`TS_CLIFF_RELEASE`] and [`TS_LINEAR_RELEASE`] must spend the release right
+//! ([`OS_RELEASE_RIGHT`]) held by the vesting trustee, who is trusted to follow
+//! the schedule. The release scripts guarantee that the released and remaining
+//! locked amounts match the locked allocations being spent.
+
+use std::sync::OnceLock;
+
+use aluvm::isa::opcodes::{INSTR_EXTR, INSTR_PUTA};
+use aluvm::isa::Instr;
+use aluvm::library::{Lib, LibSite};
+use amplify::Wrapper;
+use ifaces::{rgb20, IssuerWrapper, Rgb20, LNPBP_IDENTITY};
+use rgbstd::interface::{IfaceClass, IfaceImpl, NamedField, NamedVariant, VerNo};
+use rgbstd::schema::{
+    FungibleType, GenesisSchema, GlobalStateSchema, Occurrences, OwnedStateSchema, Schema,
+    TransitionSchema,
+};
+use rgbstd::stl::StandardTypes;
+use rgbstd::validation::Scripts;
+use rgbstd::vm::opcodes::{INSTR_LDG, INSTR_LDM, INSTR_PCAS, INSTR_PCPS, INSTR_PCVS};
+use rgbstd::vm::RgbIsa;
+use rgbstd::{rgbasm, AssignmentType, GlobalStateType, Identity, MetaType, TransitionType};
+use strict_types::TypeSystem;
+
+use crate::stl::rgb_schemata_stl;
+use crate::{
+    ERRNO_ISSUED_MISMATCH, ERRNO_NON_EQUAL_IN_OUT, GS_ISSUED_SUPPLY, GS_NOMINAL, GS_PRECISION,
+    GS_TERMS, OS_ASSET, TS_TRANSFER,
+};
+
+/// Block height at which vesting starts ([`crate::BlockHeight`]).
+pub const GS_VESTING_START: GlobalStateType = GlobalStateType::with(2300);
+/// Number of blocks after [`GS_VESTING_START`] before which nothing can be
+/// released ([`crate::BlockCount`]).
+pub const GS_CLIFF_DURATION: GlobalStateType = GlobalStateType::with(2301);
+/// Number of blocks after [`GS_VESTING_START`] at which the whole allocation is
+/// vested ([`crate::BlockCount`]).
+pub const GS_TOTAL_VESTING_DURATION: GlobalStateType = GlobalStateType::with(2302);
+/// Part of the issued supply locked under the vesting schedule at genesis.
+pub const GS_LOCKED_SUPPLY: GlobalStateType = GlobalStateType::with(2303);
+
+/// Amount of assets released from the locked allocations by an operation.
+pub const MS_RELEASED_AMOUNT: MetaType = MetaType::with(2310);
+/// Amount of assets which remain locked after an operation.
+pub const MS_LOCKED_AMOUNT: MetaType = MetaType::with(2311);
+
+/// Locked fungible allocation subject to the vesting schedule.
+pub const OS_VESTING_ALLOCATION: AssignmentType = AssignmentType::with(4300);
+/// Right of the vesting trustee to release the locked allocations.
+pub const OS_RELEASE_RIGHT: AssignmentType = AssignmentType::with(4301);
+
+pub const TS_CLIFF_RELEASE: TransitionType = TransitionType::with(10300);
+pub const TS_LINEAR_RELEASE: TransitionType = TransitionType::with(10301);
+
+pub(crate) const FN_VESTING_TRANSFER_OFFSET: u16 = 0;
+pub(crate) const FN_VESTING_GENESIS_OFFSET: u16 = 4 + 3 + 2;
+pub const FN_CLIFF_OFFSET: u16 =
+    FN_VESTING_GENESIS_OFFSET + 4 + 4 + 4 + 4 + 3 + 3 + 1 + 4 + 3 + 3 + 3 + 1 + 1;
+pub const FN_LINEAR_OFFSET: u16 = FN_CLIFF_OFFSET + 4 + 4 + 4 + 3 + 3 + 1 + 3 + 1 + 1;
+
+/// Errors assembling the vesting validation library.
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum VestingSchemaError {
+    /// vesting validation script can't be assembled. Details: {reason}
+    ScriptAssemblyFailed { reason: String },
+}
+
+/// Assembles AluVM library with validation scripts used by the vesting schema.
+pub fn vesting_lib() -> Result<Lib, VestingSchemaError> {
+    static LIB: OnceLock<Result<Lib, VestingSchemaError>> = OnceLock::new();
+    LIB.get_or_init(assemble_vesting_lib).clone()
+}
+
+// `sub` macro arm for float registers panics on integer flags
+#[allow(clippy::diverging_sub_expression)]
+fn assemble_vesting_lib() -> Result<Lib, VestingSchemaError> {
+    let code = rgbasm! {
+        // SUBROUTINE Transfer validation
+        // Set errno
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        // Checking that the sum of pedersen commitments in inputs is equal to the sum in outputs.
+        pcvs    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Genesis validation
+        // Checking that the locked allocations match the locked supply reported in the global
+        // state, and the unlocked allocations cover the rest of the issued supply.
+        put     a8[0],ERRNO_ISSUED_MISMATCH;
+        put     a8[1],0;
+        put     a16[0],0;
+        // Read locked supply into a64[0]
+        ldg     GS_LOCKED_SUPPLY,a8[1],s16[1];
+        extr    s16[1],a64[0],a16[0];
+        pcas    OS_VESTING_ALLOCATION;
+        test;
+        // Read issued supply into a64[1]
+        ldg     GS_ISSUED_SUPPLY,a8[1],s16[0];
+        extr    s16[0],a64[1],a16[0];
+        // a64[0] := a64[1] - a64[0]; fails if the locked amount exceeds the issued supply
+        sub.uc  a64[1],a64[0];
+        pcas    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Cliff release validation
+        // Checking that all spent locked allocations are released as unlocked assets.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        ldm     MS_RELEASED_AMOUNT,s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcps    OS_VESTING_ALLOCATION;
+        test;
+        pcas    OS_ASSET;
+        test;
+        ret;
+
+        // SUBROUTINE Linear release validation
+        // Checking that the spent locked allocations are split into the released assets and the
+        // allocations remaining locked.
+        put     a8[0],ERRNO_NON_EQUAL_IN_OUT;
+        put     a16[0],0;
+        ldm     MS_RELEASED_AMOUNT,s16[0];
+        extr    s16[0],a64[0],a16[0];
+        pcas    OS_ASSET;
+        test;
+        // Read remaining locked amount into a64[1]
+        ldm     MS_LOCKED_AMOUNT,s16[1];
+        extr    s16[1],a64[1],a16[0];
+        // a64[0] := a64[0] + a64[1]; fails on overflow
+        add.uc  a64[1],a64[0];
+        pcps    OS_VESTING_ALLOCATION;
+        test;
+        extr    s16[1],a64[0],a16[0];
+        pcas    OS_VESTING_ALLOCATION;
+        test;
+        ret;
+    };
+    Lib::assemble::<Instr<RgbIsa>>(&code).map_err(|err| VestingSchemaError::ScriptAssemblyFailed {
+        reason: err.to_string(),
+    })
+}
+
+fn vesting_schema() -> Schema {
+    let types = StandardTypes::with(rgb_schemata_stl());
+
+    let alu_lib = vesting_lib().expect("invalid vesting validation library");
+    let alu_id = alu_lib.id();
+    let code = alu_lib.code.as_ref();
+    let state_arg = |pos: u16| u16::from_le_bytes([code[pos as usize + 1], code[pos as usize + 2]]);
+    assert_eq!(code[FN_VESTING_TRANSFER_OFFSET as usize + 4], INSTR_PCVS);
+    assert_eq!(code[FN_VESTING_GENESIS_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_VESTING_GENESIS_OFFSET as usize + 12], INSTR_LDG);
+    assert_eq!(state_arg(FN_VESTING_GENESIS_OFFSET + 12), GS_LOCKED_SUPPLY.to_inner());
+    assert_eq!(code[FN_VESTING_GENESIS_OFFSET as usize + 23], INSTR_LDG);
+    assert_eq!(state_arg(FN_VESTING_GENESIS_OFFSET + 23), GS_ISSUED_SUPPLY.to_inner());
+    assert_eq!(code[FN_CLIFF_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_CLIFF_OFFSET as usize + 8], INSTR_LDM);
+    assert_eq!(state_arg(FN_CLIFF_OFFSET + 8), MS_RELEASED_AMOUNT.to_inner());
+    assert_eq!(code[FN_CLIFF_OFFSET as usize + 15], INSTR_PCPS);
+    assert_eq!(state_arg(FN_CLIFF_OFFSET + 15), OS_VESTING_ALLOCATION.to_inner());
+    assert_eq!(code[FN_LINEAR_OFFSET as usize], INSTR_PUTA);
+    assert_eq!(code[FN_LINEAR_OFFSET as usize + 8], INSTR_LDM);
+    assert_eq!(state_arg(FN_LINEAR_OFFSET + 8), MS_RELEASED_AMOUNT.to_inner());
+    assert_eq!(code[FN_LINEAR_OFFSET as usize + 15], INSTR_PCAS);
+    assert_eq!(code[FN_LINEAR_OFFSET as usize + 19], INSTR_LDM);
+    assert_eq!(state_arg(FN_LINEAR_OFFSET + 19), MS_LOCKED_AMOUNT.to_inner());
+    assert_eq!(code[FN_LINEAR_OFFSET as usize + 23], INSTR_EXTR);
+
+    Schema {
+        ffv: zero!(),
+        flags: none!(),
+        name: tn!("VestingAsset"),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        meta_types: tiny_bmap! {
+            MS_RELEASED_AMOUNT => types.get("RGBContract.Amount"),
+            MS_LOCKED_AMOUNT => types.get("RGBContract.Amount"),
+        },
+        global_types: tiny_bmap! {
+            GS_NOMINAL => GlobalStateSchema::once(types.get("RGBContract.AssetSpec")),
+            GS_TERMS => GlobalStateSchema::once(types.get("RGBContract.ContractTerms")),
+            GS_PRECISION => GlobalStateSchema::once(types.get("RGBContract.Precision")),
+            GS_ISSUED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+            GS_VESTING_START => GlobalStateSchema::once(types.get("RGBSchemata.BlockHeight")),
+            GS_CLIFF_DURATION => GlobalStateSchema::once(types.get("RGBSchemata.BlockCount")),
+            GS_TOTAL_VESTING_DURATION => {
+                GlobalStateSchema::once(types.get("RGBSchemata.BlockCount"))
+            },
+            GS_LOCKED_SUPPLY => GlobalStateSchema::once(types.get("RGBContract.Amount")),
+        },
+        owned_types: tiny_bmap! {
+            OS_ASSET => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_VESTING_ALLOCATION => OwnedStateSchema::Fungible(FungibleType::Unsigned64Bit),
+            OS_RELEASE_RIGHT => OwnedStateSchema::Declarative,
+        },
+        valency_types: none!(),
+        genesis: GenesisSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_NOMINAL => Occurrences::Once,
+                GS_TERMS => Occurrences::Once,
+                GS_PRECISION => Occurrences::NoneOrOnce,
+                GS_ISSUED_SUPPLY => Occurrences::Once,
+                GS_VESTING_START => Occurrences::Once,
+                GS_CLIFF_DURATION => Occurrences::Once,
+                GS_TOTAL_VESTING_DURATION => Occurrences::Once,
+                GS_LOCKED_SUPPLY => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_ASSET => Occurrences::OnceOrMore,
+                OS_VESTING_ALLOCATION => Occurrences::OnceOrMore,
+                OS_RELEASE_RIGHT => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: Some(LibSite::with(FN_VESTING_GENESIS_OFFSET, alu_id)),
+        },
+        extensions: none!(),
+        transitions: tiny_bmap! {
+            TS_TRANSFER => TransitionSchema {
+                metadata: none!(),
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_VESTING_TRANSFER_OFFSET, alu_id))
+            },
+            TS_CLIFF_RELEASE => TransitionSchema {
+                metadata: tiny_bset![MS_RELEASED_AMOUNT],
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_VESTING_ALLOCATION => Occurrences::OnceOrMore,
+                    OS_RELEASE_RIGHT => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_RELEASE_RIGHT => Occurrences::NoneOrOnce,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_CLIFF_OFFSET, alu_id))
+            },
+            TS_LINEAR_RELEASE => TransitionSchema {
+                metadata: tiny_bset![MS_RELEASED_AMOUNT, MS_LOCKED_AMOUNT],
+                globals: none!(),
+                inputs: tiny_bmap! {
+                    OS_VESTING_ALLOCATION => Occurrences::OnceOrMore,
+                    OS_RELEASE_RIGHT => Occurrences::Once,
+                },
+                assignments: tiny_bmap! {
+                    OS_ASSET => Occurrences::OnceOrMore,
+                    OS_VESTING_ALLOCATION => Occurrences::OnceOrMore,
+                    OS_RELEASE_RIGHT => Occurrences::Once,
+                },
+                valencies: none!(),
+                validator: Some(LibSite::with(FN_LINEAR_OFFSET, alu_id))
+            }
+        },
+        reserved: none!(),
+    }
+}
+
+fn vesting_rgb20() -> IfaceImpl {
+    let schema = vesting_schema();
+    let iface = Rgb20::iface(VestingAsset::FEATURES);
+
+    IfaceImpl {
+        version: VerNo::V1,
+        schema_id: schema.schema_id(),
+        iface_id: iface.iface_id(),
+        timestamp: 1713343888,
+        developer: Identity::from(LNPBP_IDENTITY),
+        metadata: tiny_bset! {
+            NamedField::with(MS_RELEASED_AMOUNT, fname!("releasedAmount")),
+            NamedField::with(MS_LOCKED_AMOUNT, fname!("lockedAmount")),
+        },
+        global_state: tiny_bset! {
+            NamedField::with(GS_NOMINAL, fname!("spec")),
+            NamedField::with(GS_TERMS, fname!("terms")),
+            NamedField::with(GS_PRECISION, fname!("precision")),
+            NamedField::with(GS_ISSUED_SUPPLY, fname!("issuedSupply")),
+            NamedField::with(GS_VESTING_START, fname!("vestingStart")),
+            NamedField::with(GS_CLIFF_DURATION, fname!("cliffDuration")),
+            NamedField::with(GS_TOTAL_VESTING_DURATION, fname!("totalVestingDuration")),
+            NamedField::with(GS_LOCKED_SUPPLY, fname!("lockedSupply")),
+        },
+        assignments: tiny_bset! {
+            NamedField::with(OS_ASSET, fname!("assetOwner")),
+            NamedField::with(OS_VESTING_ALLOCATION, fname!("vestingAllocation")),
+            NamedField::with(OS_RELEASE_RIGHT, fname!("releaseRight")),
+        },
+        valencies: none!(),
+        transitions: tiny_bset! {
+            NamedField::with(TS_TRANSFER, fname!("transfer")),
+            NamedField::with(TS_CLIFF_RELEASE, fname!("cliffRelease")),
+            NamedField::with(TS_LINEAR_RELEASE, fname!("linearRelease")),
+        },
+        extensions: none!(),
+        errors: tiny_bset![
+            NamedVariant::with(ERRNO_ISSUED_MISMATCH, vname!("issuedMismatch")),
+            NamedVariant::with(ERRNO_NON_EQUAL_IN_OUT, vname!("nonEqualAmounts")),
+        ],
+    }
+}
+
+pub struct VestingAsset;
+
+impl IssuerWrapper for VestingAsset {
+    const FEATURES: rgb20::Features = rgb20::Features::FIXED;
+    type IssuingIface = Rgb20;
+
+    fn schema() -> Schema { vesting_schema() }
+    fn issue_impl() -> IfaceImpl { vesting_rgb20() }
+
+    fn types() -> TypeSystem { StandardTypes::with(rgb_schemata_stl()).type_system() }
+
+    fn scripts() -> Scripts {
+        let lib = vesting_lib().expect("invalid vesting validation library");
+        confined_bmap! { lib.id() => lib }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::ContractBuilder;
+    use rgbstd::Amount;
+
+    use super::*;
+    use crate::stl::{BlockCount, BlockHeight};
+    use crate::test_helpers::{
+        add_metadata, fungible_genesis, graph_seal, issuer, script_errno, seal, spec,
+        spend_genesis, validate_transition,
+    };
+
+    #[test]
+    fn iimpl_check() {
+        let iface = Rgb20::iface(VestingAsset::FEATURES);
+        if let Err(err) = vesting_rgb20().check(&iface, &vesting_schema()) {
+            for e in err {
+                eprintln!("{e}");
+            }
+            panic!("invalid vesting RGB20 interface implementation");
+        }
+    }
+
+    fn genesis(unlocked: u64, locked: u64, issued: u64) -> ContractBuilder {
+        genesis_with_supply(unlocked, locked, locked, issued)
+    }
+
+    /// Genesis reporting the `locked_supply`, which may differ from the
+    /// `locked` allocation.
+    fn genesis_with_supply(
+        unlocked: u64,
+        locked_supply: u64,
+        locked: u64,
+        issued: u64,
+    ) -> ContractBuilder {
+        fungible_genesis(issuer::<VestingAsset>(), spec("VEST", "Vesting asset"), issued)
+            .add_global_state("vestingStart", BlockHeight::from(840_000))
+            .unwrap()
+            .add_global_state("cliffDuration", BlockCount::from(52_560))
+            .unwrap()
+            .add_global_state("totalVestingDuration", BlockCount::from(210_240))
+            .unwrap()
+            .add_global_state("lockedSupply", Amount::from(locked_supply))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), unlocked)
+            .unwrap()
+            .add_fungible_state("vestingAllocation", seal(1), locked)
+            .unwrap()
+            .add_rights("releaseRight", seal(2))
+            .unwrap()
+    }
+
+    fn contract() -> Contract {
+        genesis(250, 750, 1000)
+            .issue_contract()
+            .unwrap()
+            .into_consignment()
+    }
+
+    #[test]
+    fn vesting_genesis() {
+        genesis(250, 750, 1000)
+            .issue_contract()
+            .expect("genesis must pass vesting validation");
+        assert!(genesis(250, 750, 999).issue_contract().is_err());
+        assert!(genesis(250, 750, 1001).issue_contract().is_err());
+    }
+
+    #[test]
+    fn locked_allocation_mismatch() {
+        // Only the locked allocation differs from the reported locked supply, while the
+        // unlocked allocation matches the rest of the issued supply
+        assert!(genesis_with_supply(250, 700, 750, 950)
+            .issue_contract()
+            .is_err());
+        assert!(genesis_with_supply(250, 750, 700, 1000)
+            .issue_contract()
+            .is_err());
+    }
+
+    #[test]
+    fn trustee_gated_releases() {
+        let schema = vesting_schema();
+        for ty in [TS_CLIFF_RELEASE, TS_LINEAR_RELEASE] {
+            let transition = &schema.transitions[&ty];
+            assert_eq!(transition.inputs.get(&OS_RELEASE_RIGHT), Some(&Occurrences::Once));
+        }
+        let transfer = &schema.transitions[&TS_TRANSFER];
+        assert!(!transfer.inputs.contains_key(&OS_VESTING_ALLOCATION));
+    }
+
+    #[test]
+    fn transfer() {
+        let contract = contract();
+        let transfer = |amount: u64| {
+            let transition = spend_genesis::<VestingAsset>(&contract, "transfer")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            validate_transition(&contract, &VestingAsset::schema(), &transition)
+        };
+
+        let status = transfer(250);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(transfer(251)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn cliff_release() {
+        let contract = contract();
+        let release = |released: u64, amount: u64| {
+            let mut transition = spend_genesis::<VestingAsset>(&contract, "cliffRelease")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            add_metadata(&mut transition, MS_RELEASED_AMOUNT, Amount::from(released));
+            validate_transition(&contract, &VestingAsset::schema(), &transition)
+        };
+
+        let status = release(750, 750);
+        assert!(status.failures.is_empty(), "{status}");
+        assert_eq!(script_errno(release(700, 700)), Some(ERRNO_NON_EQUAL_IN_OUT));
+        assert_eq!(script_errno(release(750, 700)), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn linear_release() {
+        let contract = contract();
+        let release = |released: u64, amount: u64, locked: u64, remaining: u64| {
+            let mut transition = spend_genesis::<VestingAsset>(&contract, "linearRelease")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .add_fungible_state("vestingAllocation", graph_seal(1), remaining)
+                .unwrap()
+                .add_rights("releaseRight", graph_seal(2))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            add_metadata(&mut transition, MS_RELEASED_AMOUNT, Amount::from(released));
+            add_metadata(&mut transition, MS_LOCKED_AMOUNT, Amount::from(locked));
+            validate_transition(&contract, &VestingAsset::schema(), &transition)
+        };
+
+        let status = release(300, 300, 450, 450);
+        assert!(status.failures.is_empty(), "{status}");
+        let status = release(300, 300, 400, 400);
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+        let status = release(300, 350, 450, 450);
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+        let status = release(300, 300, 450, 400);
+        assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+    }
+
+    #[test]
+    fn linear_release_amount_mismatch() {
+        let contract = contract();
+        // Only the released assets differ from the released amount, while the spent
+        // allocation matches the released and the remaining locked amounts
+        for amount in [299u64, 301] {
+            let mut transition = spend_genesis::<VestingAsset>(&contract, "linearRelease")
+                .add_fungible_state("assetOwner", graph_seal(0), amount)
+                .unwrap()
+                .add_fungible_state("vestingAllocation", graph_seal(1), 450u64)
+                .unwrap()
+                .add_rights("releaseRight", graph_seal(2))
+                .unwrap()
+                .complete_transition()
+                .unwrap();
+            add_metadata(&mut transition, MS_RELEASED_AMOUNT, Amount::from(300u64));
+            add_metadata(&mut transition, MS_LOCKED_AMOUNT, Amount::from(450u64));
+            let status = validate_transition(&contract, &VestingAsset::schema(), &transition);
+            assert_eq!(script_errno(status), Some(ERRNO_NON_EQUAL_IN_OUT));
+        }
+    }
+}
@@ -0,0 +1,456 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transfer whitelist restricting the recipients of a NIA-based asset, like
+//! security tokens transferable to accredited investors only.
+//!
+//! The genesis of a schema extended by [`TransferWhitelist::apply`] commits to
+//! the Merkle root of the hashes of the allowed recipient seals
+//! ([`GS_WHITELIST_COMMITMENT`]) together with the whitelist version
+//! ([`GS_WHITELIST_VERSION`]). The issuer may publish a new whitelist with
+//! [`TS_UPDATE_WHITELIST`] transition, which spends the whitelist right
+//! ([`OS_WHITELIST_RIGHT`]). Each transfer carries the inclusion proof of its
+//! recipient in the metadata ([`MS_WHITELIST_PROOF`]); since RGB consensus
+//! requires the metadata declared by the transition schema to be present, the
+//! whitelist root is mandatory for the extended schema.
+//!
+//! The leaf hash of the tree is SHA256 of the seal hash. Inner nodes are SHA256
+//! hashes of the strict-encoded pair of their children ([`WhitelistNode`]), and
+//! a node without a sibling is paired with itself. The transfer script can't
+//! check the proof against the latest whitelist root and the recipient seal
+//! (see [consensus limitations](crate#consensus-limitations)); instead
+//! wallets must check the received transfers with [`WhitelistProof::verify`]
+//! before accepting them.
+
+use amplify::confinement::Confined;
+use amplify::Wrapper;
+use rgbstd::interface::{IfaceImpl, NamedField};
+use rgbstd::schema::{GlobalStateSchema, Occurrences, Schema, TransitionSchema};
+use rgbstd::{AssignmentType, GlobalStateType, MetaType, OwnedStateSchema, TransitionType};
+use sha2::{Digest, Sha256};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+use crate::nia::nia_type;
+use crate::stl::LIB_NAME_RGB_SCHEMATA;
+use crate::{NiaSchemaError, TS_TRANSFER};
+
+/// Merkle root of the allowed recipient seals ([`WhitelistRoot`]), declared by
+/// genesis and by each whitelist update.
+pub const GS_WHITELIST_COMMITMENT: GlobalStateType = GlobalStateType::with(4700);
+/// Version of the whitelist ([`WhitelistVersion`]), declared by genesis and by
+/// each update.
+pub const GS_WHITELIST_VERSION: GlobalStateType = GlobalStateType::with(4701);
+
+/// Inclusion proof of the transfer recipient into the whitelist
+/// ([`WhitelistProof`]).
+pub const MS_WHITELIST_PROOF: MetaType = MetaType::with(4700);
+
+/// Right of the issuer to update the whitelist.
+pub const OS_WHITELIST_RIGHT: AssignmentType = AssignmentType::with(6600);
+
+pub const TS_UPDATE_WHITELIST: TransitionType = TransitionType::with(12600);
+
+/// Maximal depth of the whitelist Merkle tree.
+pub const MAX_WHITELIST_DEPTH: usize = 32;
+
+/// Root of the whitelist Merkle tree.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct WhitelistRoot([u8; 32]);
+impl StrictSerialize for WhitelistRoot {}
+impl StrictDeserialize for WhitelistRoot {}
+
+/// Version of the whitelist, increased by the issuer with each update.
+#[derive(Wrapper, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, From)]
+#[wrapper(Deref, Display)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct WhitelistVersion(u32);
+impl StrictSerialize for WhitelistVersion {}
+impl StrictDeserialize for WhitelistVersion {}
+
+/// Preimage of an inner node of the whitelist Merkle tree.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct WhitelistNode {
+    pub left: [u8; 32],
+    pub right: [u8; 32],
+}
+impl StrictSerialize for WhitelistNode {}
+impl StrictDeserialize for WhitelistNode {}
+
+impl WhitelistNode {
+    /// Computes hash of the node.
+    pub fn node_hash(&self) -> [u8; 32] {
+        let data = self.to_strict_serialized::<64>().expect("fixed-size node");
+        Sha256::digest(data.as_slice()).into()
+    }
+}
+
+/// Inclusion proof of a recipient seal into the whitelist Merkle tree.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_RGB_SCHEMATA)]
+pub struct WhitelistProof {
+    /// Hash of the recipient seal, like a concealed seal.
+    pub seal_hash: [u8; 32],
+    /// Preimages of the nodes on the path from the leaf to the root, bottom-up.
+    pub path: Confined<Vec<WhitelistNode>, 0, MAX_WHITELIST_DEPTH>,
+}
+impl StrictSerialize for WhitelistProof {}
+impl StrictDeserialize for WhitelistProof {}
+
+impl WhitelistProof {
+    /// Verifies the proof against the latest whitelist `root` of the contract.
+    /// Wallets must also check that the proven
+    /// [`WhitelistProof::seal_hash`] matches the transfer recipient.
+    pub fn verify(&self, root: WhitelistRoot) -> bool {
+        merkle_verify(leaf_hash(&self.seal_hash), &self.path, root.into_inner())
+    }
+}
+
+fn leaf_hash(seal_hash: &[u8; 32]) -> [u8; 32] { Sha256::digest(seal_hash).into() }
+
+/// Builds the levels of a Merkle tree over the `leaves` hashes, bottom-up.
+fn merkle_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while levels[levels.len() - 1].len() > 1 {
+        let level = levels[levels.len() - 1]
+            .chunks(2)
+            .map(|pair| merkle_node(pair).node_hash())
+            .collect();
+        levels.push(level);
+    }
+    levels
+}
+
+fn merkle_node(pair: &[[u8; 32]]) -> WhitelistNode {
+    WhitelistNode {
+        left: pair[0],
+        right: *pair.last().expect("non-empty chunk"),
+    }
+}
+
+/// Collects preimages of the nodes on the path from the leaf with the given
+/// `index` to the root.
+fn merkle_path(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<WhitelistNode> {
+    let mut path = Vec::with_capacity(levels.len() - 1);
+    for level in &levels[..levels.len() - 1] {
+        let start = index - index % 2;
+        path.push(merkle_node(&level[start..level.len().min(start + 2)]));
+        index /= 2;
+    }
+    path
+}
+
+/// Checks that the `path` leads from the `leaf` hash to the `root`.
+fn merkle_verify(leaf: [u8; 32], path: &[WhitelistNode], root: [u8; 32]) -> bool {
+    let mut hash = leaf;
+    for node in path {
+        if node.left != hash && node.right != hash {
+            return false;
+        }
+        hash = node.node_hash();
+    }
+    hash == root
+}
+
+/// Merkle tree of the allowed recipient seals.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct WhitelistTree {
+    seals: Vec<[u8; 32]>,
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl WhitelistTree {
+    /// Constructs the tree, returning `None` if no `seals` are given or if the
+    /// tree is deeper than [`MAX_WHITELIST_DEPTH`].
+    pub fn with(seals: impl IntoIterator<Item = [u8; 32]>) -> Option<Self> {
+        let seals = seals.into_iter().collect::<Vec<_>>();
+        if seals.is_empty() {
+            return None;
+        }
+        let levels = merkle_levels(seals.iter().map(leaf_hash).collect());
+        if levels.len() - 1 > MAX_WHITELIST_DEPTH {
+            return None;
+        }
+        Some(Self { seals, levels })
+    }
+
+    /// Returns the root to be committed to the genesis or to a whitelist
+    /// update.
+    pub fn root(&self) -> WhitelistRoot { WhitelistRoot(self.levels[self.levels.len() - 1][0]) }
+
+    /// Constructs inclusion proof for the seal with the given `index`.
+    pub fn proof(&self, index: usize) -> Option<WhitelistProof> {
+        let seal_hash = *self.seals.get(index)?;
+        let path = Confined::try_from(merkle_path(&self.levels, index))
+            .expect("tree depth is checked on construction");
+        Some(WhitelistProof { seal_hash, path })
+    }
+}
+
+/// Transfer whitelist added to a NIA-based schema.
+pub struct TransferWhitelist;
+
+impl TransferWhitelist {
+    /// Constructs the schema of [`TS_UPDATE_WHITELIST`] state transition.
+    pub fn transition_schema() -> TransitionSchema {
+        TransitionSchema {
+            metadata: none!(),
+            globals: tiny_bmap! {
+                GS_WHITELIST_COMMITMENT => Occurrences::Once,
+                GS_WHITELIST_VERSION => Occurrences::Once,
+            },
+            inputs: tiny_bmap! {
+                OS_WHITELIST_RIGHT => Occurrences::Once,
+            },
+            assignments: tiny_bmap! {
+                OS_WHITELIST_RIGHT => Occurrences::Once,
+            },
+            valencies: none!(),
+            validator: None,
+        }
+    }
+
+    /// Adds [`TS_UPDATE_WHITELIST`] state transition to the `schema`, requiring
+    /// its genesis to commit to the whitelist and to assign the whitelist
+    /// right, and its transfers to carry the whitelist inclusion proof of
+    /// the recipient.
+    pub fn apply(schema: &mut Schema) -> Result<(), NiaSchemaError> {
+        schema
+            .meta_types
+            .insert(MS_WHITELIST_PROOF, nia_type("RGBSchemata.WhitelistProof")?)
+            .expect("too many meta types");
+        schema
+            .global_types
+            .insert(
+                GS_WHITELIST_COMMITMENT,
+                GlobalStateSchema::many(nia_type("RGBSchemata.WhitelistRoot")?),
+            )
+            .expect("too many global types");
+        schema
+            .global_types
+            .insert(
+                GS_WHITELIST_VERSION,
+                GlobalStateSchema::many(nia_type("RGBSchemata.WhitelistVersion")?),
+            )
+            .expect("too many global types");
+        schema
+            .owned_types
+            .insert(OS_WHITELIST_RIGHT, OwnedStateSchema::Declarative)
+            .expect("too many owned types");
+        schema
+            .genesis
+            .globals
+            .extend([
+                (GS_WHITELIST_COMMITMENT, Occurrences::Once),
+                (GS_WHITELIST_VERSION, Occurrences::Once),
+            ])
+            .expect("too many genesis globals");
+        schema
+            .genesis
+            .assignments
+            .insert(OS_WHITELIST_RIGHT, Occurrences::Once)
+            .expect("too many genesis assignments");
+        schema
+            .transitions
+            .get_mut(&TS_TRANSFER)
+            .expect("NIA transfer transition")
+            .metadata
+            .push(MS_WHITELIST_PROOF)
+            .expect("too many transfer metadata");
+        schema
+            .transitions
+            .insert(TS_UPDATE_WHITELIST, Self::transition_schema())
+            .expect("too many transitions");
+        Ok(())
+    }
+
+    /// Adds whitelist state names to the interface implementation of an
+    /// extended schema.
+    pub fn apply_names(iimpl: &mut IfaceImpl) {
+        iimpl
+            .metadata
+            .push(NamedField::with(MS_WHITELIST_PROOF, fname!("whitelistProof")))
+            .expect("too many metadata fields");
+        iimpl
+            .global_state
+            .extend([
+                NamedField::with(GS_WHITELIST_COMMITMENT, fname!("whitelistCommitment")),
+                NamedField::with(GS_WHITELIST_VERSION, fname!("whitelistVersion")),
+            ])
+            .expect("too many global state fields");
+        iimpl
+            .assignments
+            .push(NamedField::with(OS_WHITELIST_RIGHT, fname!("whitelistRight")))
+            .expect("too many assignments");
+        iimpl
+            .transitions
+            .push(NamedField::with(TS_UPDATE_WHITELIST, fname!("updateWhitelist")))
+            .expect("too many transitions");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ifaces::{IssuerWrapper, Rgb20};
+    use rgbstd::containers::Contract;
+    use rgbstd::interface::{ContractBuilder, IfaceClass, TransitionBuilder};
+    use rgbstd::validation::Failure;
+    use rgbstd::Identity;
+
+    use super::*;
+    use crate::test_helpers::{
+        add_metadata, fungible_genesis, graph_seal, seal, spec, spend_genesis_with,
+        validate_transition,
+    };
+    use crate::{nia_iface_impl, nia_schema, NonInflatableAsset};
+
+    fn whitelisted_schema() -> Schema {
+        let mut schema = nia_schema().unwrap();
+        TransferWhitelist::apply(&mut schema).unwrap();
+        schema
+    }
+
+    fn whitelisted_iimpl() -> IfaceImpl {
+        let mut iimpl = nia_iface_impl().unwrap();
+        iimpl.schema_id = whitelisted_schema().schema_id();
+        TransferWhitelist::apply_names(&mut iimpl);
+        iimpl
+    }
+
+    fn tree() -> WhitelistTree { WhitelistTree::with((0..3).map(|no| [no; 32])).unwrap() }
+
+    /// Issues whitelisted NIA committing to the [`tree`] root.
+    fn contract() -> Contract {
+        let builder = ContractBuilder::with(
+            Identity::default(),
+            Rgb20::iface(NonInflatableAsset::FEATURES),
+            whitelisted_schema(),
+            whitelisted_iimpl(),
+            NonInflatableAsset::types(),
+            NonInflatableAsset::scripts(),
+        );
+        fungible_genesis(builder, spec("TEST", "Test"), 1000)
+            .add_global_state("whitelistCommitment", tree().root())
+            .unwrap()
+            .add_global_state("whitelistVersion", WhitelistVersion::from(1))
+            .unwrap()
+            .add_fungible_state("assetOwner", seal(0), 1000u64)
+            .unwrap()
+            .add_rights("whitelistRight", seal(1))
+            .unwrap()
+            .issue_contract()
+            .expect("genesis must commit to the whitelist")
+            .into_consignment()
+    }
+
+    /// Constructs the transition named `name` of the whitelisted NIA, which
+    /// spends all genesis assignments of the `contract` of the types taken
+    /// by the transition.
+    fn spend_genesis(contract: &Contract, name: &'static str) -> TransitionBuilder {
+        let builder = TransitionBuilder::named_transition(
+            contract.contract_id(),
+            Rgb20::iface(NonInflatableAsset::FEATURES),
+            whitelisted_schema(),
+            whitelisted_iimpl(),
+            name,
+            NonInflatableAsset::types(),
+        )
+        .unwrap();
+        spend_genesis_with(contract, &whitelisted_schema(), builder)
+    }
+
+    #[test]
+    fn proofs() {
+        let tree = WhitelistTree::with((0..5).map(|no| [no; 32])).unwrap();
+        for index in 0..5 {
+            let proof = tree.proof(index).unwrap();
+            assert_eq!(proof.path.len(), 3);
+            assert!(proof.verify(tree.root()));
+        }
+        assert_eq!(tree.proof(5), None);
+        assert_eq!(WhitelistTree::with([]), None);
+
+        let single = WhitelistTree::with([[1u8; 32]]).unwrap();
+        assert!(single.proof(0).unwrap().path.is_empty());
+        assert!(single.proof(0).unwrap().verify(single.root()));
+
+        let mut proof = tree.proof(1).unwrap();
+        proof.seal_hash = [9u8; 32];
+        assert!(!proof.verify(tree.root()));
+        assert!(!tree.proof(1).unwrap().verify(single.root()));
+    }
+
+    #[test]
+    fn whitelisted_nia() {
+        let schema = whitelisted_schema();
+        let transfer = &schema.transitions[&TS_TRANSFER];
+        assert!(transfer.metadata.contains(&MS_WHITELIST_PROOF));
+        let update = &schema.transitions[&TS_UPDATE_WHITELIST];
+        assert_eq!(update.inputs.get(&OS_WHITELIST_RIGHT), Some(&Occurrences::Once));
+        assert_eq!(update.globals.get(&GS_WHITELIST_COMMITMENT), Some(&Occurrences::Once));
+
+        let iface = Rgb20::iface(NonInflatableAsset::FEATURES);
+        assert!(whitelisted_iimpl().check(&iface, &schema).is_ok());
+    }
+
+    #[test]
+    fn whitelisted_genesis() { contract(); }
+
+    #[test]
+    fn transfer() {
+        let contract = contract();
+        let mut transition = spend_genesis(&contract, "transfer")
+            .add_fungible_state("assetOwner", graph_seal(0), 1000u64)
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let status = validate_transition(&contract, &whitelisted_schema(), &transition);
+        let failures = status.failures.as_slice();
+        assert!(matches!(failures, [Failure::SchemaNoMetadata(_, MS_WHITELIST_PROOF)]), "{status}");
+
+        add_metadata(&mut transition, MS_WHITELIST_PROOF, tree().proof(1).unwrap());
+        let status = validate_transition(&contract, &whitelisted_schema(), &transition);
+        assert!(status.failures.is_empty(), "{status}");
+    }
+
+    #[test]
+    fn update_whitelist() {
+        let contract = contract();
+        let tree = WhitelistTree::with((3..7).map(|no| [no; 32])).unwrap();
+        let transition = spend_genesis(&contract, "updateWhitelist")
+            .add_global_state("whitelistCommitment", tree.root())
+            .unwrap()
+            .add_global_state("whitelistVersion", WhitelistVersion::from(2))
+            .unwrap()
+            .add_rights("whitelistRight", graph_seal(0))
+            .unwrap()
+            .complete_transition()
+            .unwrap();
+        let status = validate_transition(&contract, &whitelisted_schema(), &transition);
+        assert!(status.failures.is_empty(), "{status}");
+    }
+}
@@ -0,0 +1,53 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use amplify::num::u24;
+use rgbstd::Schema;
+use schemata::{nia_schema, nia_schema_with_features, NiaFeatures};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+const MAX_SCHEMA_LEN: usize = u24::MAX.into_usize();
+
+fn roundtrip(schema: Schema) {
+    let data = schema
+        .to_strict_serialized::<MAX_SCHEMA_LEN>()
+        .expect("schema exceeds the maximum size");
+    let decoded = Schema::from_strict_serialized::<MAX_SCHEMA_LEN>(data).unwrap();
+
+    assert_eq!(decoded.schema_id(), schema.schema_id());
+    assert_eq!(decoded.meta_types.len(), schema.meta_types.len());
+    assert_eq!(decoded.global_types.len(), schema.global_types.len());
+    assert_eq!(decoded.owned_types.len(), schema.owned_types.len());
+    assert_eq!(decoded.valency_types.len(), schema.valency_types.len());
+    assert_eq!(decoded.genesis.globals.len(), schema.genesis.globals.len());
+    assert_eq!(decoded.genesis.assignments.len(), schema.genesis.assignments.len());
+    assert_eq!(decoded.extensions.len(), schema.extensions.len());
+    assert_eq!(decoded.transitions.len(), schema.transitions.len());
+    assert_eq!(decoded, schema);
+}
+
+#[test]
+fn nia_schema_roundtrip() { roundtrip(nia_schema().unwrap()); }
+
+#[test]
+fn nia_schema_features_roundtrip() {
+    roundtrip(nia_schema_with_features(NiaFeatures::ALL).unwrap());
+}
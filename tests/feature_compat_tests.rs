@@ -0,0 +1,102 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compatibility matrix of NIA and CIA schemata against all `rgb20::Features`
+//! combinations.
+//!
+//! NIA implements only fixed-supply RGB20 contracts, optionally burnable and
+//! renamable when built with the matching [`NiaFeatures`]; implementations
+//! with extra features still satisfy the interfaces requiring fewer features.
+//! CIA issues more assets with a state extension redeeming the issuer authority
+//! valency, which doesn't implement the RGB20 inflation interface, thus it
+//! satisfies only the fixed-supply one.
+
+use ifaces::{rgb20, IssuerWrapper, Rgb20};
+use rgbstd::interface::{IfaceClass, IfaceImpl};
+use rgbstd::Schema;
+use schemata::{
+    nia_iface_impl_with_features, nia_schema_with_features, ContractInflatableAsset, NiaFeatures,
+};
+
+/// Columns of the matrix.
+const NIA_FEATURES: [NiaFeatures; 5] = [
+    NiaFeatures::NONE,
+    NiaFeatures::BURN,
+    NiaFeatures::RENAME,
+    NiaFeatures::BURN.union(NiaFeatures::RENAME),
+    NiaFeatures::ALL,
+];
+
+/// Rows of the matrix: interface features followed by whether they are
+/// satisfied by NIA with each of [`NIA_FEATURES`] and by CIA.
+const MATRIX: [(rgb20::Features, [bool; 5], bool); 10] = [
+    (rgb20::Features::FIXED, [true, true, true, true, true], true),
+    (rgb20::Features::RENAMABLE, [false, false, true, true, true], false),
+    (rgb20::Features::INFLATABLE, [false; 5], false),
+    (rgb20::Features::BURNABLE, [false, true, false, true, true], false),
+    (rgb20::Features::INFLATABLE_BURNABLE, [false; 5], false),
+    (rgb20::Features::REPLACEABLE, [false; 5], false),
+    (rgb20::Features::INFLATABLE_RENAMABLE, [false; 5], false),
+    (rgb20::Features::BURNABLE_RENAMABLE, [false, false, false, true, true], false),
+    (rgb20::Features::INFLATABLE_BURNABLE_RENAMABLE, [false; 5], false),
+    (rgb20::Features::ALL, [false; 5], false),
+];
+
+fn satisfies(features: rgb20::Features, iimpl: &IfaceImpl, schema: &Schema) -> bool {
+    iimpl.check(&Rgb20::iface(features), schema).is_ok()
+}
+
+#[test]
+fn matrix_covers_all_features() {
+    let rows = MATRIX
+        .iter()
+        .map(|(features, ..)| *features)
+        .collect::<Vec<_>>();
+    assert_eq!(rows, rgb20::Features::ENUMERATE);
+}
+
+#[test]
+fn nia_feature_compatibility() {
+    for (features, expected, _) in MATRIX {
+        for (nia_features, expected) in NIA_FEATURES.into_iter().zip(expected) {
+            let iimpl = nia_iface_impl_with_features(nia_features).unwrap();
+            let schema = nia_schema_with_features(nia_features).unwrap();
+            assert_eq!(
+                satisfies(features, &iimpl, &schema),
+                expected,
+                "NIA with {nia_features:?} against RGB20 {features:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn cia_feature_compatibility() {
+    let iimpl = ContractInflatableAsset::issue_impl();
+    let schema = ContractInflatableAsset::schema();
+    for (features, _, expected) in MATRIX {
+        assert_eq!(
+            satisfies(features, &iimpl, &schema),
+            expected,
+            "CIA against RGB20 {features:?}"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks interface implementations of the schemata against the current
+//! interface definitions, catching breaking changes in the `rgb-interfaces`
+//! crate.
+
+use ifaces::{rgb20, IssuerWrapper, Rgb20};
+use rgbstd::interface::{Iface, IfaceClass, IfaceImpl};
+use rgbstd::Schema;
+use schemata::{
+    nia_iface_impl, nia_iface_impl_with_features, nia_schema, nia_schema_with_features,
+    NiaFeatures, NonInflatableAsset,
+};
+
+fn verify_impl(iface: Iface, iimpl: &IfaceImpl, schema: &Schema) {
+    if let Err(errs) = iimpl.check(&iface, schema) {
+        for err in &errs {
+            eprintln!("{err}");
+        }
+        panic!("implementation {} doesn't match interface {}", iimpl.impl_id(), iface.name);
+    }
+}
+
+#[test]
+fn nia_iface_impl_verification() {
+    verify_impl(
+        Rgb20::iface(rgb20::Features::FIXED),
+        &nia_iface_impl().unwrap(),
+        &nia_schema().unwrap(),
+    );
+    assert_eq!(NonInflatableAsset::FEATURES, rgb20::Features::FIXED);
+}
+
+#[test]
+fn nia_features_iface_impl_verification() {
+    for features in [NiaFeatures::BURN, NiaFeatures::RENAME, NiaFeatures::ALL] {
+        verify_impl(
+            Rgb20::iface(features.to_rgb20()),
+            &nia_iface_impl_with_features(features).unwrap(),
+            &nia_schema_with_features(features).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Property-based tests of the NIA genesis and transfer validation.
+
+use std::str::FromStr;
+
+use amplify::Wrapper;
+use bp::dbc::Method;
+use bp::Txid;
+use commit_verify::CommitVerify;
+use proptest::prelude::*;
+use rgbstd::containers::{BuilderSeal, Contract, ValidContract};
+use rgbstd::invoice::Precision;
+use rgbstd::stl::{AssetSpec, ContractTerms, RicardianContract};
+use rgbstd::validation::Failure;
+use rgbstd::{
+    Amount, AssetTag, BlindingFactor, GenesisSeal, Identity, PedersenCommitment, RevealedValue,
+    XChain,
+};
+use schemata::dumb::DumbResolver;
+use schemata::{NiaIssuanceParams, OS_ASSET};
+use secp256k1_zkp::SECP256K1;
+use strict_encoding::StrictDumb;
+
+const MAX_AMOUNT: u64 = u64::MAX / 16;
+
+fn issue(amounts: &[u64], issued_supply: u64) -> Option<ValidContract> {
+    let txid =
+        Txid::from_str("14295d5bb1a191cdb6286dc0944df938421e3dfcbf0811353ccac4100c2068c5").unwrap();
+    let mut builder = NiaIssuanceParams {
+        spec: AssetSpec::new("TEST", "Test asset", Precision::Indivisible),
+        terms: ContractTerms {
+            text: RicardianContract::default(),
+            media: None,
+        },
+        issued_supply: Amount::from(issued_supply),
+        max_supply: None,
+        website: None,
+        description: None,
+    }
+    .contract_builder(Identity::default())
+    .unwrap();
+    for (vout, amount) in amounts.iter().enumerate() {
+        let seal = GenesisSeal::new_random(Method::TapretFirst, txid, vout as u32);
+        builder = builder
+            .add_fungible_state("assetOwner", BuilderSeal::from(XChain::Bitcoin(seal)), *amount)
+            .unwrap();
+    }
+    builder.issue_contract().ok()
+}
+
+fn has_script_failure(contract: Contract) -> bool {
+    let testnet = contract.genesis.testnet;
+    match contract.validate(&mut DumbResolver, testnet) {
+        Ok(_) => false,
+        Err((status, _)) => status
+            .failures
+            .iter()
+            .any(|failure| matches!(failure, Failure::ScriptFailure(..))),
+    }
+}
+
+// Mirrors `pcvs` check performed by the NIA transfer validation script
+fn commitments_balance(inputs: &[u64], outputs: &[u64]) -> bool {
+    let tag = AssetTag::strict_dumb();
+    let input_blindings = inputs
+        .iter()
+        .map(|_| BlindingFactor::random())
+        .collect::<Vec<_>>();
+    let mut output_blindings = outputs
+        .iter()
+        .map(|_| BlindingFactor::random())
+        .collect::<Vec<_>>();
+    output_blindings.pop();
+    let last = BlindingFactor::zero_balanced(input_blindings.clone(), output_blindings.clone())
+        .expect("invalid blinding factors");
+    output_blindings.push(last);
+
+    let commit = |values: &[u64], blindings: Vec<BlindingFactor>| {
+        values
+            .iter()
+            .zip(blindings)
+            .map(|(value, blinding)| {
+                PedersenCommitment::commit(&RevealedValue::with_blinding(*value, blinding, tag))
+                    .into_inner()
+            })
+            .collect::<Vec<_>>()
+    };
+    secp256k1_zkp::verify_commitments_sum_to_equal(
+        SECP256K1,
+        &commit(inputs, input_blindings),
+        &commit(outputs, output_blindings),
+    )
+}
+
+fn allocations() -> impl Strategy<Value = Vec<u64>> { prop::collection::vec(1..MAX_AMOUNT, 1..8) }
+
+/// Splits the sum of `amounts` into a random number of output amounts.
+fn split(amounts: Vec<u64>) -> impl Strategy<Value = (Vec<u64>, Vec<u64>)> {
+    let total: u64 = amounts.iter().sum();
+    prop::collection::vec(0..=total, 0..6).prop_map(move |mut cuts| {
+        cuts.push(0);
+        cuts.push(total);
+        cuts.sort_unstable();
+        let outputs = cuts.windows(2).map(|w| w[1] - w[0]).collect();
+        (amounts.clone(), outputs)
+    })
+}
+
+proptest! {
+    // Each case issues and validates a contract, so the number of cases is kept low
+    #![proptest_config(ProptestConfig {
+        cases: 16,
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn genesis_accepts_matching_supply(amounts in allocations()) {
+        let issued_supply = amounts.iter().sum();
+        prop_assert!(issue(&amounts, issued_supply).is_some());
+    }
+
+    #[test]
+    fn genesis_rejects_wrong_total(amounts in allocations(), delta in 1..MAX_AMOUNT, up: bool) {
+        let sum: u64 = amounts.iter().sum();
+        let issued_supply = if up || delta > sum { sum + delta } else { sum - delta };
+        prop_assert!(issue(&amounts, issued_supply).is_none());
+    }
+
+    // All-zero allocations can't add up to a non-zero issued supply
+    #[test]
+    fn genesis_rejects_zero_allocations(count in 1..8usize, issued_supply in 1..MAX_AMOUNT) {
+        prop_assert!(issue(&vec![0; count], issued_supply).is_none());
+    }
+
+    #[test]
+    fn genesis_rejects_unbalanced_commitments(
+        amounts in allocations(),
+        index: prop::sample::Index,
+    ) {
+        let issued_supply = amounts.iter().sum();
+        let mut contract = issue(&amounts, issued_supply).unwrap().into_consignment();
+        let assignments = contract.genesis.assignments.get_mut(&OS_ASSET).unwrap();
+        let allocations = assignments.as_fungible_mut().unwrap();
+        let allocation = &mut allocations[index.index(amounts.len())];
+        allocation.as_revealed_state_mut().unwrap().blinding = BlindingFactor::random();
+        prop_assert!(has_script_failure(contract));
+    }
+
+    #[test]
+    fn transfer_accepts_balanced_commitments(
+        (inputs, outputs) in allocations().prop_flat_map(split),
+    ) {
+        prop_assert!(commitments_balance(&inputs, &outputs));
+    }
+
+    #[test]
+    fn transfer_rejects_mismatched_commitments(
+        (inputs, mut outputs) in allocations().prop_flat_map(split),
+        delta in 1..MAX_AMOUNT,
+        index: prop::sample::Index,
+    ) {
+        let index = index.index(outputs.len());
+        let output = &mut outputs[index];
+        *output = output.checked_sub(delta).unwrap_or(*output + delta);
+        prop_assert!(!commitments_balance(&inputs, &outputs));
+    }
+}
@@ -0,0 +1,52 @@
+// RGB schemata by LNP/BP Standards Association
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-2024 by
+//     Dr Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2023-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use ifaces::IssuerWrapper;
+use rgbstd::SchemaId;
+use schemata::{NonInflatableAsset, NIA_SCHEMA_ID};
+
+/// Id of the NIA schema published in `schemata/NonInflatableAssets.rgba` with
+/// the initial stable release. Must never be changed: new NIA functionality
+/// goes into opt-in `NiaFeatures`, keeping the default schema intact.
+const EXPECTED_NIA_SCHEMA_ID: &str =
+    "rgb:sch:KzMZV9bO7gFhox97!klj0FonG2ZKnjuOIg2tFChu$YA#lucas-episode-silicon";
+
+#[test]
+fn nia_schema_id() {
+    let expected = SchemaId::from_str(EXPECTED_NIA_SCHEMA_ID).unwrap();
+    let actual = NonInflatableAsset::schema().schema_id();
+    assert_eq!(
+        actual, expected,
+        "NIA schema id has changed from {expected} to {actual}.\nAll existing NIA contracts \
+         reference the old id and will not be recognized anymore.\nIf the schema itself was not \
+         modified, the change comes from a dependency altering strict types or script encoding; \
+         compare versions of rgb-std, rgb-core, rgb-interfaces, strict_types and aluvm in \
+         Cargo.lock against the previous release and decide whether a schema migration is needed."
+    );
+}
+
+#[test]
+fn nia_schema_id_cache() {
+    let expected = SchemaId::from_str(EXPECTED_NIA_SCHEMA_ID).unwrap();
+    assert_eq!(*NIA_SCHEMA_ID, expected);
+}